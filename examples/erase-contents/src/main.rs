@@ -48,7 +48,7 @@ fn main() -> io::Result<()> {
             );
         }
 
-        erase_rect(&mut engine, layer, 10, 5, 20, 10);
+        erase_rect(&mut engine, layer, (10, 5), (20, 10));
 
         end_frame(&mut engine)?;
     }
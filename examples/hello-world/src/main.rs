@@ -1,9 +1,10 @@
 use germterm::{
     crossterm::event::{Event, KeyCode, KeyEvent},
-    draw::{draw_fps_counter, draw_text},
+    draw::{draw_fps_counter, draw_sprite, draw_text},
     engine::{Engine, end_frame, exit_cleanup, init, start_frame},
     input::poll_input,
     layer::create_layer,
+    sprite::{ResizeFilter, Sprite},
 };
 use std::io;
 
@@ -11,6 +12,11 @@ fn main() -> io::Result<()> {
     let mut engine = Engine::new(40, 20);
     let layer = create_layer(&mut engine, 0);
 
+    // Scale the logo down to 8x4 cells, twoxels fit 2 pixels in each cell vertically
+    let logo: Sprite = Sprite::from_image_bytes(include_bytes!("../../../assets/logo-middle.png"))
+        .expect("logo should be a valid PNG")
+        .resized(8, 8, ResizeFilter::Box);
+
     // Initialize engine and layers
     init(&mut engine)?;
 
@@ -32,6 +38,7 @@ fn main() -> io::Result<()> {
         // Draw contents
         draw_text(&mut engine, layer, 14, 9, "Hello, Ferris!");
         draw_fps_counter(&mut engine, layer, 0, 0);
+        draw_sprite(&mut engine, layer, 32.0, 16.0, &logo);
 
         // End the frame
         end_frame(&mut engine)?;
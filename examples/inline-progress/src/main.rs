@@ -0,0 +1,98 @@
+use germterm::{
+    color::Color,
+    crossterm::event::{Event, KeyCode, KeyEvent},
+    draw::{draw_rect, draw_text},
+    engine::{Engine, end_frame, exit_cleanup, init, start_frame},
+    inline::{InlineMode, print_above_inline},
+    input::poll_input,
+    layer::create_layer,
+};
+use std::io;
+
+const BAR_WIDTH: i16 = 30;
+const FILES: [&str; 6] = [
+    "assets/fonts.tar.gz",
+    "assets/sprites.tar.gz",
+    "assets/sounds.tar.gz",
+    "levels/overworld.dat",
+    "levels/dungeon.dat",
+    "config.toml",
+];
+
+fn main() -> io::Result<()> {
+    println!("Fetching {} files, 'q' to abort", FILES.len());
+
+    let mut engine = Engine::new(50, 2)
+        .inline(Some(InlineMode {
+            leave_last_frame: true,
+            scroll_region: false,
+        }))
+        .enable_mouse_capture(false)
+        .limit_fps(30);
+    let layer = create_layer(&mut engine, 0);
+
+    // Initialize engine and layers
+    init(&mut engine)?;
+
+    let mut file_index: usize = 0;
+    let mut file_progress: f32 = 0.0;
+
+    'update_loop: while file_index < FILES.len() {
+        start_frame(&mut engine);
+
+        // 'q' to abort the download
+        for event in poll_input() {
+            if let Event::Key(KeyEvent {
+                code: KeyCode::Char('q'),
+                ..
+            }) = event
+            {
+                break 'update_loop;
+            }
+        }
+
+        // Log lines are printed above the progress bar, into the regular scrollback
+        file_progress += engine.delta_time * 1.5;
+        if file_progress >= 1.0 {
+            print_above_inline(&mut engine, &format!("Fetched {}", FILES[file_index]))?;
+            file_progress = 0.0;
+            file_index += 1;
+            if file_index == FILES.len() {
+                break;
+            }
+        }
+
+        let total_progress: f32 = (file_index as f32 + file_progress) / FILES.len() as f32;
+        let filled_width: i16 = (total_progress * BAR_WIDTH as f32).round() as i16;
+
+        draw_text(&mut engine, layer, 0, 0, FILES[file_index]);
+        draw_rect(
+            &mut engine,
+            layer,
+            (0, 1),
+            (BAR_WIDTH, 1),
+            Color::new(60, 60, 70, 255),
+        );
+        draw_rect(
+            &mut engine,
+            layer,
+            (0, 1),
+            (filled_width, 1),
+            Color::new(120, 200, 120, 255),
+        );
+        draw_text(
+            &mut engine,
+            layer,
+            BAR_WIDTH + 1,
+            1,
+            format!("{:>3}%", (total_progress * 100.0) as u32),
+        );
+
+        end_frame(&mut engine)?;
+    }
+
+    // The last frame stays visible, and the shell prompt continues below it
+    exit_cleanup(&mut engine)?;
+    println!("Done!");
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use germterm::{
+    color::Color,
+    crossterm::event::{Event, KeyCode, KeyEvent},
+    draw::{draw_mini_text, draw_text, fill_screen, measure_mini_text},
+    engine::{Engine, end_frame, exit_cleanup, init, start_frame},
+    input::poll_input,
+    layer::create_layer,
+};
+use std::io;
+
+pub const TERM_COLS: u16 = 60;
+pub const TERM_ROWS: u16 = 24;
+
+fn main() -> io::Result<()> {
+    let mut engine: Engine = Engine::new(TERM_COLS, TERM_ROWS)
+        .title("mini-text")
+        .limit_fps(60);
+
+    let layer = create_layer(&mut engine, 0);
+
+    let charset: String = (' '..='~').collect();
+    let (charset_lines, _) = charset.split_at(48);
+
+    init(&mut engine)?;
+
+    'game_loop: loop {
+        start_frame(&mut engine);
+
+        for event in poll_input() {
+            if let Event::Key(KeyEvent {
+                code: KeyCode::Char('q'),
+                ..
+            }) = event
+            {
+                break 'game_loop;
+            }
+        }
+
+        fill_screen(&mut engine, layer, Color::BLACK);
+
+        // Full charset, split into lines
+        let charset_text: String = format!("{}\n{}", charset_lines, &charset[48..]);
+        draw_mini_text(&mut engine, layer, (1.0, 1.0), &charset_text, Color::WHITE);
+
+        // The same text at every sub-cell offset, to check it stays readable
+        for (i, (dx, dy)) in [(0.0, 0.0), (0.5, 0.0), (0.0, 0.25), (0.5, 0.75)]
+            .iter()
+            .enumerate()
+        {
+            let y: f32 = 6.0 + i as f32 * 2.0;
+            draw_text(
+                &mut engine,
+                layer,
+                1,
+                y as i16,
+                format!("+{dx:.2} +{dy:.2}"),
+            );
+            draw_mini_text(
+                &mut engine,
+                layer,
+                (14.0 + dx, y + dy),
+                "To AV Wavy jumps, LT.",
+                Color::CYAN,
+            );
+        }
+
+        // Centered label, pulsing along with the game time
+        let label: &str = "Mini text at octad resolution";
+        let (label_width, _) = measure_mini_text(label);
+        let pulse: f32 = (engine.game_time * 2.0).sin() * 0.5 + 0.5;
+        draw_mini_text(
+            &mut engine,
+            layer,
+            ((TERM_COLS as f32 - label_width) / 2.0, 18.0),
+            label,
+            Color::from_f32(1.0, 0.6 + pulse * 0.4, 0.2, 1.0),
+        );
+
+        end_frame(&mut engine)?;
+    }
+
+    exit_cleanup(&mut engine)?;
+    Ok(())
+}
@@ -1,10 +1,10 @@
 use germterm::{
     color::Color,
     crossterm::event::{Event, KeyCode, KeyEvent},
-    draw::{draw_octad, fill_screen},
+    draw::{draw_octad, draw_text, fill_screen},
     engine::{Engine, end_frame, exit_cleanup, init, start_frame},
     input::poll_input,
-    layer::create_layer,
+    layer::{OctadColorMode, create_layer, set_octad_color_mode},
 };
 
 use std::io;
@@ -18,6 +18,10 @@ fn main() -> io::Result<()> {
         .limit_fps(240);
 
     let layer = create_layer(&mut engine, 0);
+    let dominant_layer = create_layer(&mut engine, 1);
+    let average_layer = create_layer(&mut engine, 2);
+    set_octad_color_mode(&mut engine, dominant_layer, OctadColorMode::Dominant);
+    set_octad_color_mode(&mut engine, average_layer, OctadColorMode::Average);
 
     init(&mut engine)?;
 
@@ -42,6 +46,46 @@ fn main() -> io::Result<()> {
         draw_octad(&mut engine, layer, 0.9, 0.0, Color::BLUE);
         draw_octad(&mut engine, layer, 0.9, 0.25, Color::GREEN);
 
+        // Two data series sharing cells, drawn with each of the octad color modes
+        // LastWins: every cell turns the color of the series drawn last into it
+        // Dominant: the series with more dots in a cell takes the fg, the other one is turned off and shows as the bg
+        // Average: the colors mix, leaning towards the series with more dots in the cell
+        for (row, layer, label) in [
+            (2.0, layer, "LastWins"),
+            (4.0, dominant_layer, "Dominant"),
+            (6.0, average_layer, "Average"),
+        ] {
+            // 3 RED dots drawn before a single CYAN one
+            draw_octad(&mut engine, layer, 0.0, row, Color::RED);
+            draw_octad(&mut engine, layer, 0.0, row + 0.25, Color::RED);
+            draw_octad(&mut engine, layer, 0.0, row + 0.5, Color::RED);
+            draw_octad(&mut engine, layer, 0.5, row + 0.75, Color::CYAN);
+
+            // A single RED dot drawn before 3 CYAN ones
+            draw_octad(&mut engine, layer, 2.0, row, Color::RED);
+            draw_octad(&mut engine, layer, 2.5, row, Color::CYAN);
+            draw_octad(&mut engine, layer, 2.5, row + 0.25, Color::CYAN);
+            draw_octad(&mut engine, layer, 2.5, row + 0.5, Color::CYAN);
+
+            // Plotted a point at a time, taking turns: 2 RED dots and 3 CYAN ones
+            for (i, color) in [
+                Color::RED,
+                Color::CYAN,
+                Color::RED,
+                Color::CYAN,
+                Color::CYAN,
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                let x: f32 = 4.0 + (i % 2) as f32 * 0.5;
+                let y: f32 = row + (i / 2) as f32 * 0.25;
+                draw_octad(&mut engine, layer, x, y, color);
+            }
+
+            draw_text(&mut engine, layer, 7, row as i16, label);
+        }
+
         end_frame(&mut engine)?;
     }
 
@@ -3,10 +3,11 @@ use germterm::{
     crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind},
     draw::{draw_fps_counter, draw_text},
     engine::{Engine, end_frame, exit_cleanup, init, start_frame},
-    input::poll_input,
-    layer::create_layer,
+    input::{is_key_held, mouse_input, poll_tracked_input},
+    layer::{LayerIndex, create_layer},
     particle::{
-        ParticleColor, ParticleEmitter, ParticleEmitterShape, ParticleSpec, spawn_particles,
+        EmitterConfig, EmitterHandle, ParticleColor, ParticleEmitter, ParticleEmitterShape,
+        ParticleSpec, create_emitter, destroy_emitter, set_emitter_position, spawn_particles,
     },
     rich_text::{Attributes, RichText},
 };
@@ -24,11 +25,19 @@ fn main() -> io::Result<()> {
     let main_layer = create_layer(&mut engine, 0);
     let text_top_layer = create_layer(&mut engine, 1);
 
+    let mut mouse_pos: (f32, f32) = (TERM_COLS as f32 / 2.0, TERM_ROWS as f32 / 2.0);
+    let mut torch: Option<EmitterHandle> = None;
+
     init(&mut engine)?;
     'game_loop: loop {
         start_frame(&mut engine);
 
-        for event in poll_input() {
+        let events: Vec<Event> = poll_tracked_input(&mut engine).collect();
+        for event in events {
+            if let Some(mouse) = mouse_input(&event) {
+                mouse_pos = (mouse.x as f32 + 0.5, mouse.y as f32 + 0.5);
+            }
+
             if let Event::Key(KeyEvent {
                 code: KeyCode::Char('q'),
                 ..
@@ -54,6 +63,7 @@ fn main() -> io::Result<()> {
                         GradientStop::new(0.13, random_bright_color(&mut rng).with_alpha(255)),
                         GradientStop::new(1.0, random_bright_color(&mut rng).with_alpha(0)),
                     ])),
+                    ..Default::default()
                 };
                 let emitter: ParticleEmitter = ParticleEmitter {
                     shape: ParticleEmitterShape::Circle,
@@ -76,6 +86,19 @@ fn main() -> io::Result<()> {
             }
         }
 
+        // Holding E drags a continuous emitter along with the mouse
+        match (is_key_held(&engine, KeyCode::Char('e')), torch) {
+            (true, Some(handle)) => {
+                set_emitter_position(&mut engine, handle, mouse_pos);
+            }
+            (true, None) => torch = Some(create_torch(&mut engine, main_layer, mouse_pos)),
+            (false, Some(handle)) => {
+                destroy_emitter(&mut engine, handle);
+                torch = None;
+            }
+            (false, None) => {}
+        }
+
         draw_text(
             &mut engine,
             text_top_layer,
@@ -85,6 +108,14 @@ fn main() -> io::Result<()> {
                 .with_fg(Color::WHITE.with_alpha(100))
                 .with_attributes(Attributes::BOLD),
         );
+        draw_text(
+            &mut engine,
+            text_top_layer,
+            22,
+            (TERM_ROWS / 2 + 1) as i16,
+            RichText::new("Hold E to drag a flame with the mouse")
+                .with_fg(Color::WHITE.with_alpha(100)),
+        );
 
         draw_fps_counter(&mut engine, text_top_layer, 0, 0);
 
@@ -95,6 +126,32 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
+fn create_torch(engine: &mut Engine, layer: LayerIndex, pos: (f32, f32)) -> EmitterHandle {
+    let spec: ParticleSpec = ParticleSpec {
+        gravity_scale: -0.05,
+        speed: 2.0..=12.0,
+        lifetime_sec: 0.8,
+        color: ParticleColor::Gradient(ColorGradient::new(vec![
+            GradientStop::new(0.0, Color::YELLOW),
+            GradientStop::new(0.4, Color::ORANGE),
+            GradientStop::new(1.0, Color::RED.with_alpha(0)),
+        ])),
+        ..Default::default()
+    };
+    let config: EmitterConfig = EmitterConfig {
+        shape: ParticleEmitterShape::Cone {
+            direction_deg: -90.0,
+            width_deg: 60.0,
+        },
+        rate_per_sec: 120.0,
+        burst: Some(20),
+        duration: None,
+        seed: None,
+    };
+
+    create_emitter(engine, layer, pos, spec, config)
+}
+
 fn random_bright_color(rng: &mut impl rand::Rng) -> Color {
     let h = rng.random::<f32>() * std::f32::consts::TAU;
 
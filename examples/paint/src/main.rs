@@ -0,0 +1,104 @@
+use germterm::{
+    color::Color,
+    crossterm::event::{Event, KeyCode, KeyEvent, MouseButton, MouseEventKind},
+    draw::{draw_rect, draw_text, flood_fill},
+    engine::{Engine, end_frame, exit_cleanup, init, start_frame},
+    input::{mouse_input, poll_input},
+    layer::create_layer,
+};
+use std::io;
+
+const WIDTH: u16 = 60;
+const HEIGHT: u16 = 24;
+const WALL_COLOR: Color = Color::LIGHT_GRAY;
+const PALETTE: [Color; 5] = [
+    Color::RED,
+    Color::ORANGE,
+    Color::TEAL,
+    Color::VIOLET,
+    Color::PINK,
+];
+
+fn main() -> io::Result<()> {
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    let layer = create_layer(&mut engine, 0);
+
+    // Initialize engine and layers
+    init(&mut engine)?;
+
+    // The drawing is kept as walls plus the fills applied on top, and redrawn every frame
+    let mut walls: Vec<bool> = vec![false; WIDTH as usize * HEIGHT as usize];
+    let mut fills: Vec<(i16, i16, Color)> = Vec::new();
+
+    'update_loop: loop {
+        start_frame(&mut engine);
+
+        // Left mouse button draws walls, right mouse button fills, 'c' clears, 'q' exits
+        for event in poll_input() {
+            if let Event::Key(KeyEvent { code, .. }) = event {
+                match code {
+                    KeyCode::Char('q') => break 'update_loop,
+                    KeyCode::Char('c') => {
+                        walls.fill(false);
+                        fills.clear();
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            let Some(mouse) = mouse_input(&event) else {
+                continue;
+            };
+            let is_on_canvas: bool =
+                (0..WIDTH as i16).contains(&mouse.x) && (1..HEIGHT as i16).contains(&mouse.y);
+            if !is_on_canvas {
+                continue;
+            }
+
+            match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left)
+                | MouseEventKind::Drag(MouseButton::Left) => {
+                    walls[mouse.y as usize * WIDTH as usize + mouse.x as usize] = true;
+                }
+                MouseEventKind::Down(MouseButton::Right) => {
+                    let color: Color = PALETTE[fills.len() % PALETTE.len()];
+                    fills.push((mouse.x, mouse.y, color));
+                }
+                _ => {}
+            }
+        }
+
+        for (index, _) in walls.iter().enumerate().filter(|(_, is_wall)| **is_wall) {
+            let x: i16 = (index % WIDTH as usize) as i16;
+            let y: i16 = (index / WIDTH as usize) as i16;
+            draw_rect(&mut engine, layer, (x, y), (1, 1), WALL_COLOR);
+        }
+
+        // Each fill sees the result of the fills before it
+        for &(x, y, color) in &fills {
+            flood_fill(&mut engine, layer, x, y, color);
+        }
+
+        draw_rect(
+            &mut engine,
+            layer,
+            (0, 0),
+            (WIDTH as i16, 1),
+            Color::new(30, 30, 40, 255),
+        );
+        draw_text(
+            &mut engine,
+            layer,
+            1,
+            0,
+            "LMB: draw walls  RMB: fill  c: clear  q: quit",
+        );
+
+        end_frame(&mut engine)?;
+    }
+
+    // Restore terminal before exiting
+    exit_cleanup(&mut engine)?;
+    Ok(())
+}
@@ -3,11 +3,15 @@ use std::io;
 use germterm::{
     color::Color,
     crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind},
-    draw::draw_fps_counter,
+    draw::{draw_fps_counter, draw_text},
     engine::{Engine, end_frame, exit_cleanup, init, start_frame},
     input::poll_input,
     layer::create_layer,
-    particle::{ParticleColor, ParticleEmitter, ParticleSpec, spawn_particles},
+    particle::{
+        ParticleBounds, ParticleColor, ParticleEmitter, ParticleSpec, particle_count,
+        spawn_particles,
+    },
+    rich_text::RichText,
 };
 
 pub const TERM_COLS: u16 = 40;
@@ -20,6 +24,7 @@ fn main() -> io::Result<()> {
         .limit_fps(240);
 
     let layer = create_layer(&mut engine, 0);
+    let mut bounds_behavior: ParticleBounds = ParticleBounds::None;
 
     init(&mut engine)?;
 
@@ -32,6 +37,17 @@ fn main() -> io::Result<()> {
                     code: KeyCode::Char('q'),
                     ..
                 }) => break 'update_loop,
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('k'),
+                    kind: KeyEventKind::Press,
+                    ..
+                }) => {
+                    // Killing off-screen particles saves update time on particles that can't be seen
+                    bounds_behavior = match bounds_behavior {
+                        ParticleBounds::Kill => ParticleBounds::None,
+                        _ => ParticleBounds::Kill,
+                    };
+                }
                 Event::Key(KeyEvent {
                     code: KeyCode::Char('w'),
                     kind: KeyEventKind::Press,
@@ -47,6 +63,8 @@ fn main() -> io::Result<()> {
                             lifetime_sec: 4.0,
                             speed: 0.5..=35.0,
                             gravity_scale: 0.01,
+                            bounds_behavior,
+                            ..Default::default()
                         },
                         &ParticleEmitter {
                             count: PARTICLE_COUNT,
@@ -59,6 +77,12 @@ fn main() -> io::Result<()> {
         }
 
         draw_fps_counter(&mut engine, layer, 0, 1);
+        let stats_text: String = format!(
+            "particles: {} | [k] kill: {}",
+            particle_count(&engine),
+            bounds_behavior == ParticleBounds::Kill
+        );
+        draw_text(&mut engine, layer, 0, 2, RichText::new(stats_text));
 
         end_frame(&mut engine)?;
     }
@@ -0,0 +1,112 @@
+use germterm::{
+    color::{Color, ColorGradient, GradientStop},
+    crossterm::event::{Event, KeyCode, KeyEvent},
+    draw::{draw_fps_counter, draw_octad, draw_text},
+    engine::{Engine, end_frame, exit_cleanup, init, start_frame},
+    input::poll_input,
+    layer::create_layer,
+    particle::{
+        ParticleColor, ParticleEmitter, ParticleSpec, for_each_particle_mut, spawn_particles,
+    },
+    rich_text::RichText,
+};
+use std::io;
+
+pub const TERM_COLS: u16 = 60;
+pub const TERM_ROWS: u16 = 24;
+
+fn main() -> io::Result<()> {
+    let mut engine: Engine = Engine::new(TERM_COLS, TERM_ROWS)
+        .title("particle-orbit")
+        .limit_fps(60);
+
+    let particle_layer = create_layer(&mut engine, 0);
+    let text_layer = create_layer(&mut engine, 1);
+
+    let attraction: f32 = 400.0;
+    let swirl: f32 = 250.0;
+    let mut spawn_timer: f32 = 0.0;
+    let spec: ParticleSpec = ParticleSpec {
+        color: ParticleColor::Gradient(ColorGradient::new(vec![
+            GradientStop::new(0.0, Color::WHITE),
+            GradientStop::new(0.1, Color::CYAN),
+            GradientStop::new(1.0, Color::VIOLET.with_alpha(0)),
+        ])),
+        speed: 10.0..=25.0,
+        lifetime_sec: 4.0,
+        gravity_scale: 0.0,
+        ..Default::default()
+    };
+    let emitter: ParticleEmitter = ParticleEmitter {
+        count: 8,
+        ..Default::default()
+    };
+
+    init(&mut engine)?;
+
+    'update_loop: loop {
+        start_frame(&mut engine);
+
+        for event in poll_input() {
+            if let Event::Key(KeyEvent {
+                code: KeyCode::Char('q'),
+                ..
+            }) = event
+            {
+                break 'update_loop;
+            }
+        }
+
+        // --- Moving the attractor along a figure-eight ---
+        let t: f32 = engine.game_time * 0.6;
+        let target: (f32, f32) = (
+            TERM_COLS as f32 * 0.5 + t.sin() * TERM_COLS as f32 * 0.3,
+            TERM_ROWS as f32 * 0.5 + (t * 2.0).sin() * TERM_ROWS as f32 * 0.25,
+        );
+
+        spawn_timer += engine.delta_time;
+        while spawn_timer >= 0.05 {
+            spawn_timer -= 0.05;
+            spawn_particles(
+                &mut engine,
+                particle_layer,
+                target.0,
+                target.1,
+                &spec,
+                &emitter,
+            );
+        }
+
+        // --- Pulling particles into an orbit around the attractor ---
+        let delta_time: f32 = engine.delta_time;
+        for_each_particle_mut(&mut engine, particle_layer, |particle| {
+            let (x, y) = particle.pos();
+            // Vertical distances are doubled, as terminal cells usually have a 1:2 width to height ratio
+            let offset: (f32, f32) = (target.0 - x, (target.1 - y) * 2.0);
+            let distance: f32 = offset.0.hypot(offset.1).max(1.0);
+            let direction: (f32, f32) = (offset.0 / distance, offset.1 / distance);
+            let tangent: (f32, f32) = (-direction.1, direction.0);
+
+            let (vx, vy) = particle.velocity();
+            particle.set_velocity((
+                vx + (direction.0 * attraction + tangent.0 * swirl) * delta_time,
+                vy + (direction.1 * attraction + tangent.1 * swirl) * delta_time,
+            ));
+        });
+
+        draw_octad(&mut engine, text_layer, target.0, target.1, Color::WHITE);
+        draw_fps_counter(&mut engine, text_layer, 0, 0);
+        draw_text(
+            &mut engine,
+            text_layer,
+            0,
+            1,
+            RichText::new("Press Q to quit").with_fg(Color::WHITE.with_alpha(100)),
+        );
+
+        end_frame(&mut engine)?;
+    }
+
+    exit_cleanup(&mut engine)?;
+    Ok(())
+}
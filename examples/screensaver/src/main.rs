@@ -0,0 +1,97 @@
+use germterm::{
+    crossterm::event::{Event, KeyCode, KeyEvent},
+    draw::draw_fps_counter,
+    effects::screensaver::{
+        Fire, FireConfig, MatrixRain, MatrixRainConfig, Plasma, draw_fire, draw_matrix_rain,
+        draw_plasma, update_fire, update_matrix_rain,
+    },
+    engine::{Engine, end_frame, exit_cleanup, init, start_frame},
+    input::poll_input,
+    layer::create_layer,
+};
+use std::io;
+
+pub const TERM_COLS: u16 = 80;
+pub const TERM_ROWS: u16 = 24;
+
+const SEED: u32 = 1337;
+
+enum Effect {
+    Plasma,
+    MatrixRain,
+    Fire,
+}
+
+fn main() -> io::Result<()> {
+    let mut engine: Engine = Engine::new(TERM_COLS, TERM_ROWS)
+        .title("screensaver")
+        .limit_fps(60);
+
+    let effect_layer = create_layer(&mut engine, 0);
+    let ui_layer = create_layer(&mut engine, 1);
+
+    let plasma: Plasma = Plasma {
+        seed: SEED,
+        ..Default::default()
+    };
+    let mut rain: MatrixRain = MatrixRain::new(
+        TERM_COLS,
+        TERM_ROWS,
+        MatrixRainConfig {
+            seed: SEED,
+            ..Default::default()
+        },
+    );
+    let mut fire: Fire = Fire::new(
+        TERM_COLS,
+        TERM_ROWS,
+        FireConfig {
+            seed: SEED,
+            ..Default::default()
+        },
+    );
+    let mut effect: Effect = Effect::Plasma;
+
+    init(&mut engine)?;
+
+    'update_loop: loop {
+        start_frame(&mut engine);
+
+        for event in poll_input() {
+            if let Event::Key(KeyEvent { code, .. }) = event {
+                match code {
+                    KeyCode::Char('q') => break 'update_loop,
+                    KeyCode::Char('1') => effect = Effect::Plasma,
+                    KeyCode::Char('2') => effect = Effect::MatrixRain,
+                    KeyCode::Char('3') => effect = Effect::Fire,
+                    _ => {}
+                }
+            }
+        }
+
+        // Simulations keep running in the background, so switching back doesn't restart them
+        update_matrix_rain(&mut rain, engine.delta_time);
+        update_fire(&mut fire, engine.delta_time);
+
+        let time: f32 = engine.game_time;
+        match effect {
+            Effect::Plasma => draw_plasma(
+                &mut engine,
+                effect_layer,
+                &plasma,
+                (0, 0),
+                (TERM_COLS, TERM_ROWS),
+                time,
+            ),
+            Effect::MatrixRain => draw_matrix_rain(&mut engine, effect_layer, &rain, (0, 0)),
+            Effect::Fire => draw_fire(&mut engine, effect_layer, &fire, (0, 0)),
+        }
+
+        draw_fps_counter(&mut engine, ui_layer, 0, 0);
+
+        end_frame(&mut engine)?;
+    }
+
+    exit_cleanup(&mut engine)?;
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use germterm::{
+    color::{Color, ColorGradient, GradientStop, sample_gradient},
+    crossterm::event::{Event, KeyCode, KeyEvent},
+    draw::{draw_blocktad, draw_fps_counter, draw_rect},
+    engine::{Engine, end_frame, exit_cleanup, init, start_frame},
+    input::poll_input,
+    layer::create_layer,
+    noise::{fbm, perlin1},
+};
+use std::io;
+
+pub const TERM_COLS: u16 = 80;
+pub const TERM_ROWS: u16 = 24;
+
+const SEED: u32 = 1337;
+const SEA_LEVEL: f32 = 0.5;
+
+fn main() -> io::Result<()> {
+    let mut engine: Engine = Engine::new(TERM_COLS, TERM_ROWS)
+        .title("terrain")
+        .limit_fps(30);
+
+    let water_layer = create_layer(&mut engine, 0);
+    let land_layer = create_layer(&mut engine, 1);
+
+    let water_gradient: ColorGradient = ColorGradient::new(vec![
+        GradientStop::new(0.0, Color::new(17, 17, 70, 255)),
+        GradientStop::new(1.0, Color::new(40, 90, 160, 255)),
+    ]);
+    let land_gradient: ColorGradient = ColorGradient::new(vec![
+        GradientStop::new(0.0, Color::new(220, 200, 140, 255)),
+        GradientStop::new(0.1, Color::new(90, 160, 70, 255)),
+        GradientStop::new(0.45, Color::new(40, 100, 50, 255)),
+        GradientStop::new(0.65, Color::new(110, 100, 90, 255)),
+        GradientStop::new(0.8, Color::WHITE),
+    ]);
+
+    init(&mut engine)?;
+
+    'update_loop: loop {
+        start_frame(&mut engine);
+
+        for event in poll_input() {
+            if let Event::Key(KeyEvent {
+                code: KeyCode::Char('q'),
+                ..
+            }) = event
+            {
+                break 'update_loop;
+            }
+        }
+
+        // Scrolling over the map, with a slight wobble for a drifting camera feel
+        let scroll_x: f32 = engine.game_time * 4.0;
+        let scroll_y: f32 = perlin1(engine.game_time * 0.2, SEED) * 6.0;
+
+        for cell_y in 0..TERM_ROWS {
+            for cell_x in 0..TERM_COLS {
+                // --- Water, shaded by depth at the center of the cell ---
+                let cell_height: f32 = height_at(
+                    cell_x as f32 + 0.5 + scroll_x,
+                    cell_y as f32 + 0.5 + scroll_y,
+                );
+                let depth: f32 = (cell_height / SEA_LEVEL).clamp(0.0, 1.0);
+                draw_rect(
+                    &mut engine,
+                    water_layer,
+                    (cell_x as i16, cell_y as i16),
+                    (1, 1),
+                    sample_gradient(&water_gradient, depth),
+                );
+
+                // --- Land, as a blocktad bitmap of everything above sea level ---
+                // Merged blocktads share a single color, so the whole cell uses its highest point
+                let mut highest: f32 = 0.0;
+                let mut land_blocktads: [(f32, f32); 8] = [(0.0, 0.0); 8];
+                let mut land_count: usize = 0;
+
+                for sub_y in 0..4 {
+                    for sub_x in 0..2 {
+                        let x: f32 = cell_x as f32 + sub_x as f32 * 0.5;
+                        let y: f32 = cell_y as f32 + sub_y as f32 * 0.25;
+                        let height: f32 = height_at(x + 0.25 + scroll_x, y + 0.125 + scroll_y);
+
+                        if height > SEA_LEVEL {
+                            highest = highest.max(height);
+                            land_blocktads[land_count] = (x, y);
+                            land_count += 1;
+                        }
+                    }
+                }
+
+                let land_t: f32 = (highest - SEA_LEVEL) / (1.0 - SEA_LEVEL);
+                let land_color: Color = sample_gradient(&land_gradient, land_t);
+                for &(x, y) in &land_blocktads[..land_count] {
+                    draw_blocktad(&mut engine, land_layer, x, y, land_color);
+                }
+            }
+        }
+
+        draw_fps_counter(&mut engine, land_layer, 0, 0);
+
+        end_frame(&mut engine)?;
+    }
+
+    exit_cleanup(&mut engine)?;
+    Ok(())
+}
+
+/// Heightmap in the range `0.0..1.0`, stretched horizontally to account for the 1:2 cell aspect ratio.
+fn height_at(x: f32, y: f32) -> f32 {
+    fbm(x * 0.04, y * 0.08, 5, 2.0, 0.5, SEED)
+}
@@ -0,0 +1,153 @@
+use germterm::{
+    color::Color,
+    crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind},
+    cursor::{CursorStyle, set_cursor},
+    draw::{draw_rect, draw_text, fill_screen},
+    engine::{Engine, end_frame, exit_cleanup, init, start_frame},
+    input::poll_input,
+    layer::create_layer,
+    rich_text::RichText,
+    theme::{Theme, set_theme, theme_color},
+};
+use std::io;
+
+const FIELD_X: i16 = 2;
+const FIELD_Y: i16 = 3;
+const FIELD_WIDTH: i16 = 36;
+
+struct TextField {
+    text: Vec<char>,
+    cursor: usize,
+}
+
+fn handle_key(field: &mut TextField, code: KeyCode) {
+    match code {
+        KeyCode::Char(ch) if field.text.len() < FIELD_WIDTH as usize - 1 => {
+            field.text.insert(field.cursor, ch);
+            field.cursor += 1;
+        }
+        KeyCode::Backspace if field.cursor > 0 => {
+            field.cursor -= 1;
+            field.text.remove(field.cursor);
+        }
+        KeyCode::Delete if field.cursor < field.text.len() => {
+            field.text.remove(field.cursor);
+        }
+        KeyCode::Left => field.cursor = field.cursor.saturating_sub(1),
+        KeyCode::Right => field.cursor = (field.cursor + 1).min(field.text.len()),
+        KeyCode::Home => field.cursor = 0,
+        KeyCode::End => field.cursor = field.text.len(),
+        _ => {}
+    }
+}
+
+fn main() -> io::Result<()> {
+    let mut engine = Engine::new(40, 8);
+    let layer = create_layer(&mut engine, 0);
+
+    // Initialize engine and layers
+    init(&mut engine)?;
+
+    let mut field = TextField {
+        text: Vec::new(),
+        cursor: 0,
+    };
+    let mut submitted: String = String::new();
+    let mut is_light_theme: bool = false;
+
+    'update_loop: loop {
+        start_frame(&mut engine);
+
+        // 'Esc' to exit the program, 'Enter' to submit, 'Tab' to switch themes
+        for event in poll_input() {
+            // Bracketed paste delivers pasted text at once
+            if let Event::Paste(text) = &event {
+                for ch in text.chars().filter(|ch| !ch.is_control()) {
+                    handle_key(&mut field, KeyCode::Char(ch));
+                }
+                continue;
+            }
+
+            let Event::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                ..
+            }) = event
+            else {
+                continue;
+            };
+
+            match code {
+                KeyCode::Esc => break 'update_loop,
+                KeyCode::Tab => {
+                    is_light_theme = !is_light_theme;
+                    let theme: Theme = if is_light_theme {
+                        Theme::light()
+                    } else {
+                        Theme::dark()
+                    };
+                    set_theme(&mut engine, theme);
+                }
+                KeyCode::Enter => {
+                    submitted = field.text.drain(..).collect();
+                    field.cursor = 0;
+                }
+                _ => handle_key(&mut field, code),
+            }
+        }
+
+        // Colors are looked up every frame, so switching themes shows up right away
+        let bg: Color = theme_color(&mut engine, "bg");
+        let surface: Color = theme_color(&mut engine, "bg.surface");
+        let fg: Color = theme_color(&mut engine, "fg");
+        let muted: Color = theme_color(&mut engine, "fg.muted");
+        let accent: Color = theme_color(&mut engine, "accent");
+
+        let text: String = field.text.iter().collect();
+        fill_screen(&mut engine, layer, bg);
+        draw_text(
+            &mut engine,
+            layer,
+            FIELD_X,
+            1,
+            RichText::new("What's your name?").with_fg(muted),
+        );
+        draw_rect(
+            &mut engine,
+            layer,
+            (FIELD_X, FIELD_Y),
+            (FIELD_WIDTH, 1),
+            surface,
+        );
+        draw_text(
+            &mut engine,
+            layer,
+            FIELD_X,
+            FIELD_Y,
+            RichText::new(text).with_fg(fg),
+        );
+        if !submitted.is_empty() {
+            draw_text(
+                &mut engine,
+                layer,
+                FIELD_X,
+                FIELD_Y + 2,
+                RichText::new(format!("Hello, {submitted}!")).with_fg(accent),
+            );
+        }
+
+        // The cursor is drawn on top of the frame at the edit position
+        set_cursor(
+            &mut engine,
+            FIELD_X + field.cursor as i16,
+            FIELD_Y,
+            CursorStyle::BlinkingBar,
+        );
+
+        end_frame(&mut engine)?;
+    }
+
+    // Restore terminal before exiting
+    exit_cleanup(&mut engine)?;
+    Ok(())
+}
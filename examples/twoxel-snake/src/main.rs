@@ -1,10 +1,11 @@
 use germterm::{
     color::{Color, ColorGradient, GradientStop, sample_gradient},
-    crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind},
+    crossterm::event::{KeyCode, MouseButton, MouseEventKind},
     draw::{draw_octad, draw_text, draw_twoxel},
     engine::{Engine, end_frame, exit_cleanup, init, start_frame},
+    floating_text::{FadeCurve, FloatDrift, FloatSpec, spawn_floating_text},
     fps_counter::get_fps,
-    input::poll_input,
+    input::{is_key_just_pressed, mouse_input, poll_tracked_input},
     layer::{LayerIndex, create_layer},
     particle::{ParticleColor, ParticleEmitter, ParticleSpec, spawn_particles},
     rich_text::{Attributes, RichText},
@@ -36,7 +37,7 @@ fn main() -> io::Result<()> {
 
     let bg_decoration_color: Color = Color(0x45475aff);
     let movement_speed: f32 = 20.0;
-    let mut segments: Vec<(i16, i16)> = vec![(20, 22), (20, 21), (20, 20), (20, 19)];
+    let mut segments: Vec<(i16, i16)> = starting_segments();
     let mut apple_pos: (i16, i16) = random_pos();
     let mut last_direction: (i16, i16) = DOWN;
     let mut direction: (i16, i16) = DOWN;
@@ -50,52 +51,37 @@ fn main() -> io::Result<()> {
     init(&mut engine)?;
 
     'game_loop: loop {
-        for event in poll_input() {
-            match event {
-                Event::Key(KeyEvent {
-                    code: KeyCode::Char('q'),
-                    ..
-                }) => break 'game_loop,
-                Event::Key(KeyEvent {
-                    code: KeyCode::Char('w'),
-                    kind: KeyEventKind::Press,
-                    ..
-                }) => {
-                    if last_direction != DOWN {
-                        direction = UP;
-                    }
-                }
-                Event::Key(KeyEvent {
-                    code: KeyCode::Char('a'),
-                    kind: KeyEventKind::Press,
-                    ..
-                }) => {
-                    if last_direction != RIGHT {
-                        direction = LEFT
-                    }
-                }
-                Event::Key(KeyEvent {
-                    code: KeyCode::Char('s'),
-                    kind: KeyEventKind::Press,
-                    ..
-                }) => {
-                    if last_direction != UP {
-                        direction = DOWN
-                    }
-                }
-                Event::Key(KeyEvent {
-                    code: KeyCode::Char('d'),
-                    kind: KeyEventKind::Press,
-                    ..
-                }) => {
-                    if last_direction != LEFT {
-                        direction = RIGHT
-                    }
-                }
-                _ => (),
+        for event in poll_tracked_input(&mut engine) {
+            // Click anywhere to restart after a game over
+            if let Some(mouse) = mouse_input(&event)
+                && mouse.kind == MouseEventKind::Down(MouseButton::Left)
+                && matches!(game_state, GameState::GameOver)
+            {
+                segments = starting_segments();
+                apple_pos = random_pos();
+                last_direction = DOWN;
+                direction = DOWN;
+                move_timer = 0.0;
+                game_state = GameState::Playing;
             }
         }
 
+        if is_key_just_pressed(&engine, KeyCode::Char('q')) {
+            break 'game_loop;
+        }
+        if is_key_just_pressed(&engine, KeyCode::Char('w')) && last_direction != DOWN {
+            direction = UP;
+        }
+        if is_key_just_pressed(&engine, KeyCode::Char('a')) && last_direction != RIGHT {
+            direction = LEFT;
+        }
+        if is_key_just_pressed(&engine, KeyCode::Char('s')) && last_direction != UP {
+            direction = DOWN;
+        }
+        if is_key_just_pressed(&engine, KeyCode::Char('d')) && last_direction != LEFT {
+            direction = RIGHT;
+        }
+
         start_frame(&mut engine);
         if matches!(game_state, GameState::Playing) {
             move_timer += engine.delta_time;
@@ -129,6 +115,22 @@ fn main() -> io::Result<()> {
                         apple_pos.0 as f32 + 0.5,
                         (apple_pos.1 as f32 + 0.5) * 0.5,
                     );
+                    spawn_floating_text(
+                        &mut engine,
+                        layer_1,
+                        (apple_pos.0 as f32, apple_pos.1 as f32 * 0.5 - 1.0),
+                        RichText::new("+1").with_fg(Color(0xa6e3a1ff)),
+                        FloatSpec {
+                            velocity: (0.0, -3.0),
+                            lifetime_sec: 0.8,
+                            fade: FadeCurve::EaseIn,
+                            drift: Some(FloatDrift {
+                                amplitude: 1.0,
+                                frequency: 2.0,
+                                seed: segments.len() as u32,
+                            }),
+                        },
+                    );
                     apple_pos = random_pos();
                     spawn_apple_create_particles(
                         &mut engine,
@@ -214,6 +216,13 @@ fn main() -> io::Result<()> {
                     .with_fg(Color::RED)
                     .with_attributes(Attributes::BOLD),
             );
+            draw_text(
+                &mut engine,
+                layer_2,
+                (TERM_COLS / 2 - 9) as i16,
+                (TERM_ROWS / 2) as i16,
+                RichText::new("click to restart").with_fg(Color::LIGHT_GRAY),
+            );
         }
 
         end_frame(&mut engine)?;
@@ -223,6 +232,10 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
+fn starting_segments() -> Vec<(i16, i16)> {
+    vec![(20, 22), (20, 21), (20, 20), (20, 19)]
+}
+
 fn random_pos() -> (i16, i16) {
     let mut rng: ThreadRng = rand::rng();
     (
@@ -246,6 +259,7 @@ fn spawn_explosion(engine: &mut Engine, layer: LayerIndex, x: f32, y: f32) {
                 GradientStop::new(0.05, Color::RED),
                 GradientStop::new(1.0, Color::VIOLET.with_alpha(0)),
             ])),
+            ..Default::default()
         },
         &ParticleEmitter {
             count: 30,
@@ -268,6 +282,7 @@ fn spawn_apple_create_particles(engine: &mut Engine, layer: LayerIndex, x: f32,
                 GradientStop::new(0.0, Color::RED.with_alpha(100)),
                 GradientStop::new(1.0, Color::RED.with_alpha(0)),
             ])),
+            ..Default::default()
         },
         &ParticleEmitter {
             count: 70,
@@ -291,6 +306,7 @@ fn spawn_death_explosion(engine: &mut Engine, layer: LayerIndex, x: f32, y: f32)
                 GradientStop::new(0.05, Color::RED),
                 GradientStop::new(1.0, Color::YELLOW.with_alpha(0)),
             ])),
+            ..Default::default()
         },
         &ParticleEmitter {
             count: 500,
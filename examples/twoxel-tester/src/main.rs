@@ -34,13 +34,17 @@ fn main() -> io::Result<()> {
         draw_rect(
             &mut engine,
             layer,
-            0,
-            9,
-            TERM_COLS as i16,
-            9,
+            (0, 9),
+            (TERM_COLS as i16, 9),
             Color::BLACK.with_alpha(127),
         );
-        draw_rect(&mut engine, layer, 0, 18, TERM_COLS as i16, 9, Color::BLACK);
+        draw_rect(
+            &mut engine,
+            layer,
+            (0, 18),
+            (TERM_COLS as i16, 9),
+            Color::BLACK,
+        );
 
         draw_test_case(&mut engine, layer, 15.0, 1.0);
         draw_test_case(&mut engine, layer, 15.0, 10.0);
@@ -0,0 +1,77 @@
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use germterm::{
+    effects::screensaver::{
+        Fire, FireConfig, MatrixRain, MatrixRainConfig, Plasma, draw_fire, draw_matrix_rain,
+        draw_plasma, update_fire, update_matrix_rain,
+    },
+    engine::{Engine, end_frame, init},
+    layer::{LayerIndex, create_layer},
+};
+
+const DELTA_TIME: f32 = 1.0 / 60.0;
+
+fn offscreen_engine(cols: u16, rows: u16) -> (Engine, LayerIndex) {
+    let mut engine = Engine::offscreen(cols, rows);
+    let layer = create_layer(&mut engine, 0);
+    init(&mut engine).unwrap();
+    (engine, layer)
+}
+
+fn bench_screensaver(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Screensaver");
+
+    // Dimensions to test
+    let dimensions = vec![
+        (80, 24),  // Standard terminal
+        (120, 40), // Large terminal
+    ];
+
+    for (width, height) in dimensions {
+        group.bench_with_input(
+            BenchmarkId::new("Plasma", format!("{}x{}", width, height)),
+            &(width, height),
+            |b, &(w, h)| {
+                let (mut engine, layer) = offscreen_engine(w, h);
+                let plasma = Plasma::default();
+                b.iter(|| {
+                    let time = engine.game_time;
+                    draw_plasma(&mut engine, layer, &plasma, (0, 0), (w, h), time);
+                    black_box(end_frame(&mut engine)).unwrap();
+                })
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("Matrix Rain", format!("{}x{}", width, height)),
+            &(width, height),
+            |b, &(w, h)| {
+                let (mut engine, layer) = offscreen_engine(w, h);
+                let mut rain = MatrixRain::new(w, h, MatrixRainConfig::default());
+                b.iter(|| {
+                    update_matrix_rain(&mut rain, DELTA_TIME);
+                    draw_matrix_rain(&mut engine, layer, &rain, (0, 0));
+                    black_box(end_frame(&mut engine)).unwrap();
+                })
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("Fire", format!("{}x{}", width, height)),
+            &(width, height),
+            |b, &(w, h)| {
+                let (mut engine, layer) = offscreen_engine(w, h);
+                let mut fire = Fire::new(w, h, FireConfig::default());
+                b.iter(|| {
+                    update_fire(&mut fire, DELTA_TIME);
+                    draw_fire(&mut engine, layer, &fire, (0, 0));
+                    black_box(end_frame(&mut engine)).unwrap();
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_screensaver);
+criterion_main!(benches);
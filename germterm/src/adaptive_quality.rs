@@ -0,0 +1,223 @@
+//! Adaptive quality scaling under sustained frame overruns.
+//!
+//! Once enabled using [`enable_adaptive_quality`], the engine keeps track of how long each frame takes to update and draw,
+//! and lowers a quality scalar when frames keep taking longer than the target frame time.
+//! When there's enough headroom again the quality slowly recovers.
+//!
+//! The scalar can be read with [`quality`] to scale custom effects.
+//! Particle spawn counts (and optionally lifetimes) are scaled automatically, nothing else is affected.
+
+use std::time::Instant;
+
+use crate::engine::Engine;
+
+/// The lowest quality the controller can ever go down to.
+pub const MIN_QUALITY: f32 = 0.25;
+
+/// How quickly the quality drops per second of sustained overruns.
+const DECREASE_PER_SEC: f32 = 0.5;
+/// How quickly the quality recovers per second of sustained headroom, slower than the decrease to avoid oscillation.
+const RECOVERY_PER_SEC: f32 = 0.1;
+/// The frame time EMA has to fall below this fraction of the target before the quality recovers.
+///
+/// Between this and the target frame time the quality is held as-is.
+const HEADROOM_THRESHOLD: f32 = 0.75;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdaptiveConfig {
+    /// The frame time budget in seconds, usually `1.0 / target_fps`.
+    pub target_frame_time: f32,
+    /// The amount of frames the frame time EMA roughly averages over.
+    pub window: u32,
+    /// The quality never drops below this value, clamped to [`MIN_QUALITY`]`..=1.0`.
+    pub min_quality: f32,
+    /// Whether newly spawned particles also get their lifetime scaled by the quality.
+    pub scale_particle_lifetime: bool,
+}
+
+impl Default for AdaptiveConfig {
+    fn default() -> Self {
+        Self {
+            target_frame_time: 1.0 / 60.0,
+            window: 30,
+            min_quality: MIN_QUALITY,
+            scale_particle_lifetime: false,
+        }
+    }
+}
+
+pub(crate) struct AdaptiveQuality {
+    config: AdaptiveConfig,
+    frame_time_ema: f32,
+    quality: f32,
+    frame_start: Option<Instant>,
+}
+
+impl AdaptiveQuality {
+    pub fn new(config: AdaptiveConfig) -> Self {
+        Self {
+            config,
+            frame_time_ema: 0.0,
+            quality: 1.0,
+            frame_start: None,
+        }
+    }
+}
+
+/// Enables adaptive quality scaling, or replaces the config and resets the quality if it's already enabled.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{adaptive_quality::{AdaptiveConfig, enable_adaptive_quality}, engine::Engine};
+/// let mut engine = Engine::new(40, 20).limit_fps(60);
+/// enable_adaptive_quality(
+///     &mut engine,
+///     AdaptiveConfig {
+///         target_frame_time: 1.0 / 60.0,
+///         min_quality: 0.5,
+///         ..Default::default()
+///     },
+/// );
+/// ```
+pub fn enable_adaptive_quality(engine: &mut Engine, config: AdaptiveConfig) {
+    engine.adaptive_quality = Some(AdaptiveQuality::new(config));
+}
+
+pub fn disable_adaptive_quality(engine: &mut Engine) {
+    engine.adaptive_quality = None;
+}
+
+/// The current quality scalar in the range [`MIN_QUALITY`]`..=1.0`.
+///
+/// Always `1.0` when adaptive quality is disabled.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{adaptive_quality::quality, engine::Engine};
+/// let engine = Engine::new(40, 20);
+/// let star_count = (200.0 * quality(&engine)) as usize;
+/// ```
+pub fn quality(engine: &Engine) -> f32 {
+    engine
+        .adaptive_quality
+        .as_ref()
+        .map_or(1.0, |adaptive_quality| adaptive_quality.quality)
+}
+
+/// Scales a particle spawn count by the current quality, never scaling a non-zero count down to zero.
+pub(crate) fn scale_particle_count(engine: &Engine, count: usize) -> usize {
+    if count == 0 {
+        return 0;
+    }
+    ((count as f32 * quality(engine)).round() as usize).max(1)
+}
+
+pub(crate) fn scale_particle_lifetime(engine: &Engine, lifetime_sec: f32) -> f32 {
+    match &engine.adaptive_quality {
+        Some(adaptive_quality) if adaptive_quality.config.scale_particle_lifetime => {
+            lifetime_sec * adaptive_quality.quality
+        }
+        _ => lifetime_sec,
+    }
+}
+
+/// Marks the start of the measured part of the frame, right after waiting for the FPS limiter.
+pub(crate) fn start_frame_timing(adaptive_quality: &mut AdaptiveQuality) {
+    adaptive_quality.frame_start = Some(Instant::now());
+}
+
+/// Measures the time spent since [`start_frame_timing`] and feeds it into the controller.
+pub(crate) fn end_frame_timing(adaptive_quality: &mut AdaptiveQuality, delta_time: f32) {
+    if let Some(frame_start) = adaptive_quality.frame_start.take() {
+        let frame_time: f32 = frame_start.elapsed().as_secs_f32();
+        update_adaptive_quality(adaptive_quality, frame_time, delta_time);
+    }
+}
+
+/// Advances the controller by one frame which took `frame_time` seconds to update and draw.
+///
+/// The frame time excludes time spent waiting for the FPS limiter, as that time is headroom.
+pub(crate) fn update_adaptive_quality(
+    adaptive_quality: &mut AdaptiveQuality,
+    frame_time: f32,
+    delta_time: f32,
+) {
+    let config: AdaptiveConfig = adaptive_quality.config;
+    let smoothing_factor: f32 = 2.0 / (config.window.max(1) as f32 + 1.0);
+
+    if adaptive_quality.frame_time_ema <= 0.0 {
+        adaptive_quality.frame_time_ema = frame_time;
+    } else {
+        adaptive_quality.frame_time_ema +=
+            (frame_time - adaptive_quality.frame_time_ema) * smoothing_factor;
+    }
+
+    let min_quality: f32 = config.min_quality.clamp(MIN_QUALITY, 1.0);
+    if adaptive_quality.frame_time_ema > config.target_frame_time {
+        adaptive_quality.quality -= DECREASE_PER_SEC * delta_time;
+    } else if adaptive_quality.frame_time_ema < config.target_frame_time * HEADROOM_THRESHOLD {
+        adaptive_quality.quality += RECOVERY_PER_SEC * delta_time;
+    }
+    adaptive_quality.quality = adaptive_quality.quality.clamp(min_quality, 1.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AdaptiveConfig, AdaptiveQuality, DECREASE_PER_SEC, MIN_QUALITY, update_adaptive_quality,
+    };
+
+    const TARGET_FRAME_TIME: f32 = 1.0 / 60.0;
+
+    fn controller(min_quality: f32) -> AdaptiveQuality {
+        AdaptiveQuality::new(AdaptiveConfig {
+            target_frame_time: TARGET_FRAME_TIME,
+            min_quality,
+            ..Default::default()
+        })
+    }
+
+    /// Feeds `seconds` worth of frames taking `frame_time` each into the controller.
+    fn run_for(adaptive_quality: &mut AdaptiveQuality, seconds: f32, frame_time: f32) {
+        for _ in 0..(seconds / TARGET_FRAME_TIME).round() as usize {
+            update_adaptive_quality(adaptive_quality, frame_time, TARGET_FRAME_TIME);
+        }
+    }
+
+    #[test]
+    fn sustained_overruns_lower_the_quality_down_to_the_minimum() {
+        let mut adaptive_quality: AdaptiveQuality = controller(0.5);
+
+        run_for(&mut adaptive_quality, 0.5, TARGET_FRAME_TIME * 2.0);
+        assert!(adaptive_quality.quality < 1.0 && adaptive_quality.quality > 0.5);
+
+        run_for(&mut adaptive_quality, 10.0, TARGET_FRAME_TIME * 2.0);
+        assert_eq!(adaptive_quality.quality, 0.5);
+    }
+
+    #[test]
+    fn min_quality_is_clamped() {
+        let mut adaptive_quality: AdaptiveQuality = controller(0.0);
+        run_for(&mut adaptive_quality, 10.0, TARGET_FRAME_TIME * 2.0);
+        assert_eq!(adaptive_quality.quality, MIN_QUALITY);
+    }
+
+    #[test]
+    fn quality_recovers_slower_than_it_drops_and_holds_in_between() {
+        let mut adaptive_quality: AdaptiveQuality = controller(MIN_QUALITY);
+        run_for(&mut adaptive_quality, 1.0, TARGET_FRAME_TIME * 2.0);
+
+        // Just under the target, but without enough headroom to recover, once the average caught up
+        run_for(&mut adaptive_quality, 1.0, TARGET_FRAME_TIME * 0.9);
+        let lowered: f32 = adaptive_quality.quality;
+        run_for(&mut adaptive_quality, 2.0, TARGET_FRAME_TIME * 0.9);
+        assert_eq!(adaptive_quality.quality, lowered);
+
+        run_for(&mut adaptive_quality, 1.0, TARGET_FRAME_TIME * 0.1);
+        let recovered: f32 = adaptive_quality.quality - lowered;
+        assert!(recovered > 0.0 && recovered < DECREASE_PER_SEC);
+
+        run_for(&mut adaptive_quality, 20.0, TARGET_FRAME_TIME * 0.1);
+        assert_eq!(adaptive_quality.quality, 1.0);
+    }
+}
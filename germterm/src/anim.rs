@@ -0,0 +1,282 @@
+//! Tweening values over time, for pulsing, sliding and fading effects.
+//!
+//! A [`Tween`] interpolates between two values over a duration, shaped by an [`Easing`] curve.
+//! A [`Timeline`] plays several tweens one after another, with optional delays in between.
+//!
+//! Both are plain data, they can either be sampled at any point in time using `sample`,
+//! or keep track of their own time through `advance`.
+//! [`update_tween`] and [`update_timeline`] advance them by the delta time of the engine,
+//! for use in the update loop.
+//!
+//! Any type implementing [`Tweenable`] can be tweened, which includes `f32`, [`Color`],
+//! and `(f32, f32)` and `(i16, i16)` positions.
+
+use crate::{
+    color::{Color, Easing, ease, lerp},
+    engine::Engine,
+};
+
+/// A value which can be interpolated by a [`Tween`].
+pub trait Tweenable: Copy {
+    /// Returns the value at `t` between `from` and `to`, where `t` is usually within `0.0..=1.0`,
+    /// but may leave the range for overshooting [`Easing`] curves.
+    fn interpolate(from: Self, to: Self, t: f32) -> Self;
+}
+
+impl Tweenable for f32 {
+    #[inline]
+    fn interpolate(from: Self, to: Self, t: f32) -> Self {
+        from + (to - from) * t
+    }
+}
+
+/// Interpolated with [`lerp`], just like [`crate::color::sample_gradient`] does in the default color space.
+impl Tweenable for Color {
+    #[inline]
+    fn interpolate(from: Self, to: Self, t: f32) -> Self {
+        lerp(from, to, t)
+    }
+}
+
+impl Tweenable for (f32, f32) {
+    #[inline]
+    fn interpolate(from: Self, to: Self, t: f32) -> Self {
+        (
+            f32::interpolate(from.0, to.0, t),
+            f32::interpolate(from.1, to.1, t),
+        )
+    }
+}
+
+/// Cell positions are rounded to the nearest cell.
+impl Tweenable for (i16, i16) {
+    #[inline]
+    fn interpolate(from: Self, to: Self, t: f32) -> Self {
+        let axis = |from: i16, to: i16| f32::interpolate(from as f32, to as f32, t).round() as i16;
+        (axis(from.0, to.0), axis(from.1, to.1))
+    }
+}
+
+/// What a [`Tween`] does after reaching its end.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Repeat {
+    /// Stays at the end value.
+    #[default]
+    Once,
+    /// Jumps back to the start value and plays again.
+    Loop,
+    /// Plays backwards to the start value, then forwards again.
+    PingPong,
+}
+
+/// Interpolates between two values over a duration.
+///
+/// # Example
+/// ```rust
+/// # use germterm::{anim::{Repeat, Tween}, color::Easing};
+/// let pulse = Tween::new(0.0, 1.0, 0.5)
+///     .with_easing(Easing::Smoothstep)
+///     .with_repeat(Repeat::PingPong);
+///
+/// assert_eq!(pulse.sample(0.0), 0.0);
+/// assert_eq!(pulse.sample(0.5), 1.0);
+/// assert_eq!(pulse.sample(1.0), 0.0);
+/// assert_eq!(pulse.sample(1.5), 1.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Tween<T> {
+    from: T,
+    to: T,
+    duration: f32,
+    easing: Easing,
+    repeat: Repeat,
+    elapsed: f32,
+}
+
+impl<T: Tweenable> Tween<T> {
+    /// Creates a linear tween from `from` to `to`, taking `duration` seconds.
+    pub fn new(from: T, to: T, duration: f32) -> Self {
+        Tween {
+            from,
+            to,
+            duration,
+            easing: Easing::Linear,
+            repeat: Repeat::Once,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Sets the easing curve, [`Easing::Linear`] by default.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Sets what happens after reaching the end, [`Repeat::Once`] by default.
+    pub fn with_repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// The value `time` seconds after the start of the tween.
+    ///
+    /// Times before the start are treated as the start.
+    /// A tween with a duration of zero or less is always at its end value.
+    pub fn sample(&self, time: f32) -> T {
+        T::interpolate(self.from, self.to, ease(self.progress(time), self.easing))
+    }
+
+    /// Moves the tween forward by `delta_time` seconds, returning the new value.
+    pub fn advance(&mut self, delta_time: f32) -> T {
+        self.elapsed += delta_time.max(0.0);
+        self.value()
+    }
+
+    /// The value at the time the tween was advanced to.
+    pub fn value(&self) -> T {
+        self.sample(self.elapsed)
+    }
+
+    /// Whether a [`Repeat::Once`] tween was advanced past its end, repeating tweens never finish.
+    pub fn is_finished(&self) -> bool {
+        self.repeat == Repeat::Once && self.elapsed >= self.duration
+    }
+
+    /// Rewinds the tween back to its start.
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    /// The position within the tween before easing, in `0.0..=1.0`.
+    fn progress(&self, time: f32) -> f32 {
+        if self.duration.is_nan() || self.duration <= 0.0 {
+            return 1.0;
+        }
+
+        let cycles: f32 = time.max(0.0) / self.duration;
+        match self.repeat {
+            Repeat::Once => once_progress(time, self.duration),
+            Repeat::Loop => cycles.fract(),
+            Repeat::PingPong if cycles.floor() % 2.0 == 0.0 => cycles.fract(),
+            Repeat::PingPong => 1.0 - cycles.fract(),
+        }
+    }
+}
+
+/// Plays tweens one after another, each starting a delay after the previous one ended.
+///
+/// Every tween plays for its duration once, regardless of its [`Repeat`] mode.
+/// While waiting for the delay before a tween, the end value of the previous one is held.
+///
+/// # Example
+/// ```rust
+/// # use germterm::anim::{Timeline, Tween};
+/// // Slide in, wait for a second, then slide back out
+/// let toast = Timeline::new(Tween::new((-20, 1), (2, 1), 0.25))
+///     .then(1.0, Tween::new((2, 1), (-20, 1), 0.25));
+///
+/// assert_eq!(toast.duration(), 1.5);
+/// assert_eq!(toast.sample(0.0), (-20, 1));
+/// assert_eq!(toast.sample(0.75), (2, 1));
+/// assert_eq!(toast.sample(1.5), (-20, 1));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Timeline<T> {
+    /// Every tween along with the time it starts at.
+    tweens: Vec<(f32, Tween<T>)>,
+    elapsed: f32,
+}
+
+impl<T: Tweenable> Timeline<T> {
+    /// Creates a timeline starting with the given tween.
+    pub fn new(first: Tween<T>) -> Self {
+        Timeline {
+            tweens: vec![(0.0, first)],
+            elapsed: 0.0,
+        }
+    }
+
+    /// Appends a tween starting `delay` seconds after the end of the previous one.
+    pub fn then(mut self, delay: f32, tween: Tween<T>) -> Self {
+        let start: f32 = self.duration() + delay.max(0.0);
+        self.tweens.push((start, tween));
+        self
+    }
+
+    /// The time the last tween ends at.
+    pub fn duration(&self) -> f32 {
+        let (start, last) = &self.tweens[self.tweens.len() - 1];
+        start + last.duration.max(0.0)
+    }
+
+    /// The value `time` seconds after the start of the timeline.
+    pub fn sample(&self, time: f32) -> T {
+        // The last tween which started by `time`, or the first one if none did yet
+        let index: usize = self
+            .tweens
+            .iter()
+            .rposition(|(start, _)| *start <= time)
+            .unwrap_or(0);
+        let (start, tween) = &self.tweens[index];
+
+        let progress: f32 = once_progress(time - start, tween.duration);
+        T::interpolate(tween.from, tween.to, ease(progress, tween.easing))
+    }
+
+    /// Moves the timeline forward by `delta_time` seconds, returning the new value.
+    pub fn advance(&mut self, delta_time: f32) -> T {
+        self.elapsed += delta_time.max(0.0);
+        self.value()
+    }
+
+    /// The value at the time the timeline was advanced to.
+    pub fn value(&self) -> T {
+        self.sample(self.elapsed)
+    }
+
+    /// Whether the timeline was advanced past the end of its last tween.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration()
+    }
+
+    /// Rewinds the timeline back to its start.
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+}
+
+/// The position within a tween played once before easing, in `0.0..=1.0`.
+#[inline]
+fn once_progress(time: f32, duration: f32) -> f32 {
+    if duration.is_nan() || duration <= 0.0 {
+        return 1.0;
+    }
+    (time.max(0.0) / duration).min(1.0)
+}
+
+/// Advances a tween by the delta time of the current frame, returning its new value.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{anim::{Repeat, Tween, update_tween}, color::Color, draw::draw_text};
+/// # use germterm::{engine::Engine, layer::create_layer, rich_text::RichText};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// let mut blink = Tween::new(Color::WHITE, Color::DARK_GRAY, 0.6).with_repeat(Repeat::PingPong);
+///
+/// // Every frame
+/// let color: Color = update_tween(&engine, &mut blink);
+/// draw_text(&mut engine, layer, 2, 1, RichText::new("PRESS START").with_fg(color));
+/// ```
+pub fn update_tween<T: Tweenable>(engine: &Engine, tween: &mut Tween<T>) -> T {
+    tween.advance(engine.delta_time)
+}
+
+/// Advances a timeline by the delta time of the current frame, returning its new value.
+pub fn update_timeline<T: Tweenable>(engine: &Engine, timeline: &mut Timeline<T>) -> T {
+    timeline.advance(engine.delta_time)
+}
@@ -0,0 +1,258 @@
+//! Loading of drawable assets from text, like ASCII art title screens and level layouts.
+//!
+//! # Markup
+//! [`TextArt::parse`] reads text line by line, styled with the same markup as [`crate::rich_text::parse_markup`]:
+//! - `[orange bold]` opens a tag, taking a space separated list of attributes and colors, with `on <color>` setting the background.
+//! - `[/]` closes the most recently opened tag. Tags nest, and may continue across lines.
+//! - `[[` is a literal `[`.
+//!
+//! Colors are either hex codes (`#RRGGBB` or `#RRGGBBAA`) or the lowercase names of the
+//! [`Color`](crate::color::Color) constants, like `red`, `dark_gray` or `clear`.
+//!
+//! Text outside of any tag is drawn white. Cells without a background keep the background underneath,
+//! so spaces which aren't given a background are fully transparent.
+//!
+//! ```text
+//! [orange bold]GERMTERM[/]
+//! [#808080]  press [white]enter[/] to start[/]
+//! ```
+
+use std::fmt;
+
+use crate::{
+    cell::{Cell, CellFormat},
+    grapheme::for_each_grapheme,
+    rich_text::{Attributes, MarkupError, MarkupErrorKind, RichText, parse_markup},
+};
+
+/// A grid of styled cells parsed from text, drawn with [`crate::draw::draw_text_art`].
+#[derive(Clone)]
+pub struct TextArt {
+    width: u16,
+    height: u16,
+    pub(crate) cells: Vec<Cell>,
+}
+
+/// A [`TextArt`] parsing error, pointing at the tag which caused it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextArtError {
+    pub kind: MarkupErrorKind,
+    /// The line of the tag, starting at 1.
+    pub line: usize,
+    /// The column of the tag's opening bracket in chars, starting at 1.
+    pub column: usize,
+}
+
+impl TextArtError {
+    /// Locates the byte offset of a markup error within the lines of `source`.
+    fn locate(source: &str, error: MarkupError) -> Self {
+        let before: &str = &source[..error.offset];
+        let line_start: usize = before.rfind('\n').map_or(0, |newline| newline + 1);
+        Self {
+            kind: error.kind,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl fmt::Display for TextArtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (line, column) = (self.line, self.column);
+        match &self.kind {
+            MarkupErrorKind::UnclosedTag => write!(f, "unclosed tag at {line}:{column}"),
+            MarkupErrorKind::UnmatchedClose => {
+                write!(f, "no matching tag to close at {line}:{column}")
+            }
+            MarkupErrorKind::UnclosedSpan => {
+                write!(f, "tag opened at {line}:{column} is never closed")
+            }
+            MarkupErrorKind::UnknownStyle(style) => {
+                write!(f, "unknown style `{style}` at {line}:{column}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TextArtError {}
+
+impl TextArt {
+    /// Parses text with optional style markup, see the [module docs](self) for the syntax.
+    ///
+    /// The art is as wide as its longest line, shorter lines are padded with transparent cells.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::asset::TextArt;
+    /// let art = TextArt::parse("[red]/\\[/]\n\\/").unwrap();
+    /// assert_eq!((art.width(), art.height()), (2, 2));
+    ///
+    /// let error = TextArt::parse("ok\n  [nope]").err().unwrap();
+    /// assert_eq!((error.line, error.column), (2, 3));
+    /// ```
+    pub fn parse(source: &str) -> Result<Self, TextArtError> {
+        let spans: Vec<RichText> =
+            parse_markup(source).map_err(|error| TextArtError::locate(source, error))?;
+
+        let mut rows: Vec<Vec<Cell>> = vec![Vec::new()];
+        for span in &spans {
+            let mut lines = span.text.split('\n').peekable();
+            while let Some(line) = lines.next() {
+                let is_line_end: bool = lines.peek().is_some();
+                let line: &str = match is_line_end {
+                    true => line.strip_suffix('\r').unwrap_or(line),
+                    false => line,
+                };
+                if let Some(row) = rows.last_mut() {
+                    push_text_cells(row, line, span);
+                }
+                if is_line_end {
+                    rows.push(Vec::new());
+                }
+            }
+        }
+        // Like `str::lines`, a trailing line break doesn't start another line
+        if source.is_empty() || source.ends_with('\n') {
+            rows.pop();
+        }
+
+        let width: usize = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height: usize = rows.len();
+        let mut cells: Vec<Cell> = Vec::with_capacity(width * height);
+        for mut row in rows {
+            row.resize(width, Cell::EMPTY);
+            cells.extend(row);
+        }
+
+        Ok(Self {
+            width: width.min(u16::MAX as usize) as u16,
+            height: height.min(u16::MAX as usize) as u16,
+            cells,
+        })
+    }
+
+    /// Makes every cell holding `ch` transparent, for art which marks empty space with eg. `.`
+    /// rather than spaces.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::asset::TextArt;
+    /// let art = TextArt::parse("..#..\n.###.").unwrap().with_transparent_char('.');
+    /// assert_eq!(art.cell(0, 0).unwrap().ch, ' ');
+    /// ```
+    pub fn with_transparent_char(mut self, ch: char) -> Self {
+        for cell in self.cells.iter_mut().filter(|cell| cell.ch == ch) {
+            *cell = Cell::EMPTY;
+        }
+        self
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Returns the cell at the given position, or `None` if it's out of bounds.
+    ///
+    /// Transparent cells are [`Cell::EMPTY`].
+    pub fn cell(&self, x: u16, y: u16) -> Option<Cell> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(self.cells[y as usize * self.width as usize + x as usize])
+    }
+}
+
+fn push_text_cells(row: &mut Vec<Cell>, text: &str, span: &RichText) {
+    let mut attributes: Attributes = span.attributes;
+    if span.bg.a() == 0 {
+        attributes |= Attributes::NO_BG_COLOR;
+    }
+    let cell: Cell = Cell {
+        ch: ' ',
+        fg: span.fg,
+        bg: span.bg,
+        attributes,
+        format: CellFormat::Standard,
+        format_data: 0,
+        link: None,
+    };
+
+    for_each_grapheme(text, |ch, format_data, width| {
+        if width == 0 {
+            return;
+        }
+        row.push(Cell {
+            ch,
+            format_data,
+            ..cell
+        });
+        if width == 2 {
+            row.push(Cell {
+                ch: Cell::WIDE_CONTINUATION,
+                ..cell
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        color::Color,
+        draw::draw_text_art,
+        engine::{Engine, end_frame, init, start_frame},
+        layer::create_layer,
+        test_support::composed_cells,
+    };
+
+    #[test]
+    fn art_round_trips_into_the_drawn_cells() {
+        let art: TextArt =
+            TextArt::parse("[red bold]/\\[/] [[x\r\n[#00ff00 on blue]\\/[/]").unwrap();
+        assert_eq!((art.width(), art.height()), (5, 2));
+
+        let mut engine: Engine = Engine::offscreen(6, 3);
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+        start_frame(&mut engine);
+        draw_text_art(&mut engine, layer, (1, 1), &art);
+        end_frame(&mut engine).unwrap();
+
+        let cells: Vec<Cell> = composed_cells(&engine);
+        let rows: Vec<String> = cells
+            .chunks(6)
+            .map(|row| row.iter().map(|cell| cell.ch).collect())
+            .collect();
+        assert_eq!(rows, ["      ", " /\\ [x", " \\/   "]);
+
+        let cell = |x: usize, y: usize| cells[y * 6 + x];
+        assert_eq!(cell(1, 1).fg, Color::RED);
+        assert!(cell(1, 1).attributes.contains(Attributes::BOLD));
+        assert_eq!(cell(4, 1).fg, Color::WHITE);
+        assert!(!cell(4, 1).attributes.contains(Attributes::BOLD));
+        assert_eq!((cell(2, 2).fg, cell(2, 2).bg), (Color::GREEN, Color::BLUE));
+        // Cells without a background keep the one underneath
+        assert_eq!(cell(1, 1).bg, cell(0, 0).bg);
+    }
+
+    #[test]
+    fn errors_point_at_the_line_and_column_of_their_tag() {
+        let error: TextArtError = TextArt::parse("[red]ok[/]\nnö [nope]x").err().unwrap();
+        assert_eq!(
+            error.kind,
+            MarkupErrorKind::UnknownStyle("nope".to_string())
+        );
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.to_string(), "unknown style `nope` at 2:4");
+
+        let error: TextArtError = TextArt::parse("a\n[red]b\nc").err().unwrap();
+        assert_eq!(error.kind, MarkupErrorKind::UnclosedSpan);
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+}
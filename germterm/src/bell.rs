@@ -0,0 +1,195 @@
+//! Terminal bell with an optional visual flash.
+//!
+//! Ring the bell using [`bell`]. How the bell is presented is controlled with [`set_bell_mode`],
+//! the visual mode is an accessible alternative for users who can't hear or have disabled the audible bell.
+
+use crate::{
+    color::Color,
+    draw::draw_text,
+    engine::Engine,
+    layer::LayerIndex,
+    rich_text::{Attributes, RichText},
+};
+
+/// How [`bell`] is presented to the user.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BellMode {
+    /// Emits the terminal bell character (`BEL`), which the terminal turns into a sound or its own visual bell.
+    Audible,
+    /// Tints the whole screen with `color`, fading out over `duration_sec`. `BEL` is never emitted.
+    ///
+    /// The alpha of `color` is the strength of the tint at the start of the flash.
+    Visual { color: Color, duration_sec: f32 },
+    /// Both [`BellMode::Audible`] and [`BellMode::Visual`].
+    Both { color: Color, duration_sec: f32 },
+}
+
+pub(crate) struct BellState {
+    mode: BellMode,
+    is_audible_pending: bool,
+    flash_remaining_sec: f32,
+}
+
+impl BellState {
+    pub fn new() -> Self {
+        Self {
+            mode: BellMode::Audible,
+            is_audible_pending: false,
+            flash_remaining_sec: 0.0,
+        }
+    }
+}
+
+/// Sets how [`bell`] is presented, [`BellMode::Audible`] by default.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{bell::{BellMode, set_bell_mode}, color::Color, engine::Engine};
+/// let mut engine = Engine::new(40, 20);
+/// set_bell_mode(
+///     &mut engine,
+///     BellMode::Visual {
+///         color: Color::WHITE.with_alpha(120),
+///         duration_sec: 0.25,
+///     },
+/// );
+/// ```
+pub fn set_bell_mode(engine: &mut Engine, mode: BellMode) {
+    engine.bell.mode = mode;
+}
+
+/// Rings the bell.
+///
+/// Multiple rings within the same frame result in a single `BEL`.
+/// Ringing during an active visual flash restarts its fade instead of stacking the tint.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{bell::bell, engine::Engine};
+/// let mut engine = Engine::new(40, 20);
+/// bell(&mut engine);
+/// ```
+pub fn bell(engine: &mut Engine) {
+    let state: &mut BellState = &mut engine.bell;
+
+    match state.mode {
+        BellMode::Audible => state.is_audible_pending = true,
+        BellMode::Visual { duration_sec, .. } => state.flash_remaining_sec = duration_sec,
+        BellMode::Both { duration_sec, .. } => {
+            state.is_audible_pending = true;
+            state.flash_remaining_sec = duration_sec;
+        }
+    }
+}
+
+pub(crate) fn reset_bell(state: &mut BellState) {
+    state.is_audible_pending = false;
+    state.flash_remaining_sec = 0.0;
+}
+
+/// Returns whether a `BEL` should be emitted this frame, clearing the pending ring.
+pub(crate) fn take_pending_audible_bell(engine: &mut Engine) -> bool {
+    std::mem::take(&mut engine.bell.is_audible_pending)
+}
+
+/// Draws the visual flash on top of everything else and advances its fade.
+pub(crate) fn update_and_draw_bell_flash(engine: &mut Engine) {
+    let (color, duration_sec): (Color, f32) = match engine.bell.mode {
+        BellMode::Audible => return,
+        BellMode::Visual {
+            color,
+            duration_sec,
+        }
+        | BellMode::Both {
+            color,
+            duration_sec,
+        } => (color, duration_sec),
+    };
+
+    if engine.bell.flash_remaining_sec <= 0.0 || duration_sec <= 0.0 {
+        return;
+    }
+
+    let t: f32 = (engine.bell.flash_remaining_sec / duration_sec).clamp(0.0, 1.0);
+    let tint: Color = color.with_alpha((color.a() as f32 * t).round() as u8);
+    engine.bell.flash_remaining_sec -= engine.delta_time;
+
+    // Never culled, the flash is the whole point of ringing the bell
+    let row_rich_text: RichText = RichText::new(" ".repeat(engine.frame.width as usize))
+        .with_fg(Color::CLEAR)
+        .with_bg(tint)
+        .with_attributes(Attributes::NO_FG_COLOR)
+        .with_priority(u8::MAX);
+    let top_layer: LayerIndex = LayerIndex(engine.max_layer_index);
+
+    for row in 0..engine.frame.height {
+        draw_text(engine, top_layer, 0, row as i16, row_rich_text.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BellMode, bell, set_bell_mode};
+    use crate::{
+        cell::Cell,
+        color::{Color, blend_source_over},
+        engine::{Engine, end_frame, init, start_frame},
+        rich_text::Attributes,
+        test_support::SharedOutput,
+    };
+
+    fn remote_engine(output: &SharedOutput) -> Engine {
+        let mut engine: Engine = Engine::remote(4, 2, output.clone()).limit_fps(0);
+        init(&mut engine).unwrap();
+        output.take();
+        engine
+    }
+
+    fn render_frame(engine: &mut Engine, delta_time: f32) {
+        start_frame(engine);
+        engine.delta_time = delta_time;
+        end_frame(engine).unwrap();
+    }
+
+    #[test]
+    fn rings_within_a_frame_emit_a_single_bel() {
+        let output = SharedOutput::default();
+        let mut engine: Engine = remote_engine(&output);
+
+        start_frame(&mut engine);
+        bell(&mut engine);
+        bell(&mut engine);
+        end_frame(&mut engine).unwrap();
+        assert_eq!(output.take().matches('\x07').count(), 1);
+
+        render_frame(&mut engine, 0.1);
+        assert!(!output.take().contains('\x07'));
+    }
+
+    #[test]
+    fn visual_bell_fades_out_without_emitting_bel() {
+        let output = SharedOutput::default();
+        let mut engine: Engine = remote_engine(&output);
+        let color: Color = Color::WHITE.with_alpha(200);
+        set_bell_mode(
+            &mut engine,
+            BellMode::Visual {
+                color,
+                duration_sec: 0.2,
+            },
+        );
+
+        bell(&mut engine);
+        let mut flash_cells: Vec<Cell> = Vec::new();
+        for _ in 0..3 {
+            render_frame(&mut engine, 0.1);
+            flash_cells.push(engine.frame.old()[0]);
+        }
+
+        assert!(!output.take().contains('\x07'));
+        assert_eq!(flash_cells[0].bg, blend_source_over(Color::BLACK, color));
+        assert!(flash_cells[1].bg.r() > 0 && flash_cells[1].bg.r() < flash_cells[0].bg.r());
+        // Faded out, leaving the untouched terminal background
+        assert!(flash_cells[2].attributes.contains(Attributes::NO_BG_COLOR));
+    }
+}
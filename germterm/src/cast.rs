@@ -0,0 +1,391 @@
+//! Recording sessions in the [asciinema](https://asciinema.org) v2 `.cast` format.
+//!
+//! [`start_cast_recording`] records everything the engine writes to the terminal, frame by frame,
+//! so the session can be replayed with `asciinema play` or embedded on a website with the asciinema player.
+//! Every frame is written to the given writer as soon as it's drawn, so long sessions don't pile up in memory.
+//!
+//! By default timestamps are the sum of the delta times of the recorded frames, which keeps recordings of sessions
+//! played back with [`crate::replay`] identical between runs.
+//! To sync a recording with eg. an audio track, [`set_cast_timeline`] switches to the wall clock time at which frames
+//! were composed or flushed to the terminal instead. Both are kept for every frame, see [`cast_frame_timestamps`].
+//!
+//! ## Notes
+//! Text printed with [`crate::inline::print_above_inline`] isn't recorded, but the region below it is.
+
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crossterm::{Command, cursor, style, terminal};
+
+use crate::engine::Engine;
+
+/// Which clock the timestamps of recorded frames follow, see [`set_cast_timeline`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CastTimeline {
+    /// The sum of the delta times of the recorded frames, deterministic for sessions played back with [`crate::replay`].
+    #[default]
+    DeltaTime,
+    /// The moment the frame finished composing, before anything was written to the terminal.
+    Composed,
+    /// The moment the frame was flushed to the terminal, which is when it becomes visible.
+    Flushed,
+}
+
+/// The wall clock times of a recorded frame, relative to the start of the recording.
+///
+/// Measured with [`Instant`], the same clock the engine paces and times frames with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameTimestamps {
+    /// When the frame finished composing.
+    pub composed: Duration,
+    /// When the frame was flushed to the terminal, never before [`FrameTimestamps::composed`].
+    pub flushed: Duration,
+}
+
+pub(crate) struct CastRecorder {
+    writer: Box<dyn Write + Send>,
+    timeline: CastTimeline,
+    started_at: Instant,
+    /// Seconds since the recording started, as the sum of the delta times.
+    elapsed: f64,
+    frame_timestamps: Vec<FrameTimestamps>,
+    /// Everything written to the terminal during the current frame.
+    frame_output: Vec<u8>,
+    needs_repaint: bool,
+}
+
+/// Starts recording the session into `writer`, finishing any recording in progress first.
+///
+/// The header is written right away, sized to the terminal, or to the engine if the terminal size is unknown
+/// or the engine is [remote](Engine::remote).
+/// The screen is cleared and fully repainted by the next [`crate::engine::end_frame`],
+/// as the player starts out with a blank screen.
+///
+/// Writes are frequent and small, so a buffered writer is recommended.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{engine::{Engine, end_frame, exit_cleanup, init, start_frame}};
+/// # use germterm::cast::{start_cast_recording, stop_cast_recording};
+/// # use std::{fs::File, io::BufWriter};
+/// let mut engine = Engine::new(40, 20);
+/// init(&mut engine);
+///
+/// let file = File::create("session.cast").unwrap();
+/// start_cast_recording(&mut engine, BufWriter::new(file)).unwrap();
+///
+/// for _ in 0..600 {
+///     start_frame(&mut engine);
+///     // ...
+///     end_frame(&mut engine);
+/// }
+///
+/// stop_cast_recording(&mut engine).unwrap();
+/// exit_cleanup(&mut engine);
+/// ```
+pub fn start_cast_recording(
+    engine: &mut Engine,
+    writer: impl Write + Send + 'static,
+) -> io::Result<()> {
+    stop_cast_recording(engine)?;
+
+    let engine_size: (u16, u16) = (engine.frame.width, engine.frame.height);
+    let (width, height): (u16, u16) = if engine.remote {
+        engine_size
+    } else {
+        terminal::size().unwrap_or(engine_size)
+    };
+    let timestamp: u64 = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+
+    let mut writer: Box<dyn Write + Send> = Box::new(writer);
+    writeln!(
+        writer,
+        "{{\"version\": 2, \"width\": {width}, \"height\": {height}, \"timestamp\": {timestamp}}}",
+    )?;
+    writer.flush()?;
+
+    engine.cast_recording = Some(CastRecorder {
+        writer,
+        timeline: CastTimeline::DeltaTime,
+        started_at: Instant::now(),
+        elapsed: 0.0,
+        frame_timestamps: Vec::new(),
+        frame_output: Vec::new(),
+        needs_repaint: true,
+    });
+    Ok(())
+}
+
+/// Stops recording, writing a final event which restores the default style and shows the cursor again.
+///
+/// Called by [`crate::engine::exit_cleanup`] when a recording is in progress. Does nothing when not recording.
+pub fn stop_cast_recording(engine: &mut Engine) -> io::Result<()> {
+    let Some(mut cast) = engine.cast_recording.take() else {
+        return Ok(());
+    };
+
+    let mut restore: String = String::new();
+    // Writing into a `String` never fails
+    let _ = style::SetAttribute(style::Attribute::Reset).write_ansi(&mut restore);
+    let _ = cursor::Show.write_ansi(&mut restore);
+    let time: f64 = match cast.timeline {
+        CastTimeline::DeltaTime => cast.elapsed,
+        CastTimeline::Composed | CastTimeline::Flushed => cast.started_at.elapsed().as_secs_f64(),
+    };
+    write_cast_event(&mut cast.writer, time, &restore)?;
+    cast.writer.flush()
+}
+
+/// Sets which clock the timestamps of the recorded frames follow, [`CastTimeline::DeltaTime`] by default.
+///
+/// Applies to the frames recorded from now on, the timestamps of a single recording should stick to one timeline.
+/// Does nothing when not recording.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{cast::{CastTimeline, set_cast_timeline, start_cast_recording}, engine::Engine};
+/// # use std::{fs::File, io::BufWriter};
+/// let mut engine = Engine::new(40, 20);
+/// let file = File::create("session.cast").unwrap();
+/// start_cast_recording(&mut engine, BufWriter::new(file)).unwrap();
+/// // Frames show up in the recording when they became visible, to line up with an audio track
+/// set_cast_timeline(&mut engine, CastTimeline::Flushed);
+/// ```
+pub fn set_cast_timeline(engine: &mut Engine, timeline: CastTimeline) {
+    if let Some(cast) = &mut engine.cast_recording {
+        cast.timeline = timeline;
+    }
+}
+
+/// The compose and flush times of every frame recorded so far, in order. Empty when not recording.
+///
+/// Both timelines are kept regardless of [`set_cast_timeline`], as the `.cast` format only has room for one.
+pub fn cast_frame_timestamps(engine: &Engine) -> &[FrameTimestamps] {
+    engine
+        .cast_recording
+        .as_ref()
+        .map_or(&[], |cast| &cast.frame_timestamps)
+}
+
+/// Whether a session is currently being recorded.
+pub fn is_recording_cast(engine: &Engine) -> bool {
+    engine.cast_recording.is_some()
+}
+
+/// Whether the frame has to be fully repainted, which is the case for the first recorded frame,
+/// as the player starts out with a blank screen.
+pub(crate) fn take_cast_repaint(cast: &mut CastRecorder) -> bool {
+    std::mem::take(&mut cast.needs_repaint)
+}
+
+/// Writes everything drawn during the frame as a single event, and advances the time by the frame's delta time.
+///
+/// Called right after the frame was flushed to the terminal, `composed_at` being when it finished composing.
+pub(crate) fn record_cast_frame(
+    cast: &mut CastRecorder,
+    delta_time: f32,
+    composed_at: Instant,
+) -> io::Result<()> {
+    let flushed: Duration = cast.started_at.elapsed();
+    let timestamps = FrameTimestamps {
+        composed: composed_at
+            .saturating_duration_since(cast.started_at)
+            .min(flushed),
+        flushed,
+    };
+    cast.frame_timestamps.push(timestamps);
+
+    if !cast.frame_output.is_empty() {
+        let time: f64 = match cast.timeline {
+            CastTimeline::DeltaTime => cast.elapsed,
+            CastTimeline::Composed => timestamps.composed.as_secs_f64(),
+            CastTimeline::Flushed => timestamps.flushed.as_secs_f64(),
+        };
+        let output = String::from_utf8_lossy(&cast.frame_output);
+        write_cast_event(&mut cast.writer, time, &output)?;
+        cast.writer.flush()?;
+        cast.frame_output.clear();
+    }
+    cast.elapsed += delta_time.max(0.0) as f64;
+    Ok(())
+}
+
+/// Writes to the terminal, keeping a copy of the output for the recording if there is one.
+pub(crate) struct CastTee<'a, W: Write> {
+    terminal: &'a mut W,
+    recorded: Option<&'a mut Vec<u8>>,
+}
+
+impl<'a, W: Write> CastTee<'a, W> {
+    pub fn new(terminal: &'a mut W, cast: Option<&'a mut CastRecorder>) -> Self {
+        Self {
+            terminal,
+            recorded: cast.map(|cast| &mut cast.frame_output),
+        }
+    }
+}
+
+impl<W: Write> Write for CastTee<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written: usize = self.terminal.write(buf)?;
+        if let Some(recorded) = &mut self.recorded {
+            recorded.extend_from_slice(&buf[..written]);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.terminal.flush()
+    }
+}
+
+fn write_cast_event(writer: &mut impl Write, time: f64, data: &str) -> io::Result<()> {
+    write!(writer, "[{time:.6}, \"o\", \"")?;
+    for ch in data.chars() {
+        match ch {
+            '"' => writer.write_all(b"\\\"")?,
+            '\\' => writer.write_all(b"\\\\")?,
+            '\n' => writer.write_all(b"\\n")?,
+            '\r' => writer.write_all(b"\\r")?,
+            '\t' => writer.write_all(b"\\t")?,
+            ch if ch.is_control() => write!(writer, "\\u{:04x}", ch as u32)?,
+            ch => write!(writer, "{ch}")?,
+        }
+    }
+    writer.write_all(b"\"]\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CastTimeline, cast_frame_timestamps, set_cast_timeline, start_cast_recording,
+        stop_cast_recording,
+    };
+    use crate::{
+        draw::draw_text,
+        engine::{Engine, end_frame, init, start_frame},
+        layer::create_layer,
+        test_support::SharedOutput,
+    };
+
+    /// Records a few frames with changing contents on the given timeline, returning the recording.
+    fn record_frames(engine: &mut Engine, timeline: CastTimeline, frame_count: usize) -> String {
+        let layer = create_layer(engine, 0);
+        init(engine).unwrap();
+        let cast = SharedOutput::default();
+        start_cast_recording(engine, cast.clone()).unwrap();
+        set_cast_timeline(engine, timeline);
+
+        for frame in 0..frame_count {
+            start_frame(engine);
+            engine.delta_time = 0.25;
+            draw_text(engine, layer, 0, 0, frame.to_string());
+            end_frame(engine).unwrap();
+        }
+        cast.take()
+    }
+
+    /// The times of the `"o"` events, skipping the header.
+    fn event_times(recording: &str) -> Vec<String> {
+        recording
+            .lines()
+            .skip(1)
+            .map(|line| line[1..line.find(',').unwrap()].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn frames_get_ordered_compose_and_flush_timestamps() {
+        let mut engine: Engine = Engine::remote(8, 2, SharedOutput::default()).limit_fps(0);
+        let recording: String = record_frames(&mut engine, CastTimeline::Flushed, 3);
+
+        let timestamps = cast_frame_timestamps(&engine).to_vec();
+        assert_eq!(timestamps.len(), 3);
+        for pair in timestamps.windows(2) {
+            assert!(pair[0].flushed <= pair[1].composed);
+        }
+        for frame in &timestamps {
+            assert!(frame.composed <= frame.flushed);
+        }
+
+        let expected: Vec<String> = timestamps
+            .iter()
+            .map(|frame| format!("{:.6}", frame.flushed.as_secs_f64()))
+            .collect();
+        assert_eq!(event_times(&recording), expected);
+    }
+
+    #[test]
+    fn composed_timeline_uses_compose_timestamps() {
+        let mut engine: Engine = Engine::remote(8, 2, SharedOutput::default()).limit_fps(0);
+        let recording: String = record_frames(&mut engine, CastTimeline::Composed, 3);
+
+        let expected: Vec<String> = cast_frame_timestamps(&engine)
+            .iter()
+            .map(|frame| format!("{:.6}", frame.composed.as_secs_f64()))
+            .collect();
+        assert_eq!(event_times(&recording), expected);
+    }
+
+    #[test]
+    fn delta_time_timeline_is_deterministic() {
+        let mut engine: Engine = Engine::remote(8, 2, SharedOutput::default()).limit_fps(0);
+        let recording: String = record_frames(&mut engine, CastTimeline::DeltaTime, 3);
+
+        assert_eq!(
+            event_times(&recording),
+            ["0.000000", "0.250000", "0.500000"]
+        );
+        stop_cast_recording(&mut engine).unwrap();
+        assert!(cast_frame_timestamps(&engine).is_empty());
+    }
+
+    #[test]
+    fn recordings_are_valid_asciinema_v2() {
+        let mut engine: Engine = Engine::remote(8, 2, SharedOutput::default()).limit_fps(0);
+        let cast = SharedOutput::default();
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+        start_cast_recording(&mut engine, cast.clone()).unwrap();
+        set_cast_timeline(&mut engine, CastTimeline::Flushed);
+        for text in ["héllo", "\"quoted\"", "back\\slash"] {
+            start_frame(&mut engine);
+            draw_text(&mut engine, layer, 0, 0, text);
+            end_frame(&mut engine).unwrap();
+        }
+        stop_cast_recording(&mut engine).unwrap();
+
+        // Taking the output checks that it's valid UTF-8
+        let recording: String = cast.take();
+        let mut lines = recording.lines();
+        let header: serde_json::Value = serde_json::from_str(lines.next().unwrap()).unwrap();
+        assert_eq!(header["version"], 2);
+        assert_eq!(
+            (header["width"].as_u64(), header["height"].as_u64()),
+            (Some(8), Some(2))
+        );
+        assert!(header["timestamp"].is_u64());
+
+        let events: Vec<(f64, String)> = lines
+            .map(|line| {
+                let event: serde_json::Value = serde_json::from_str(line).unwrap();
+                assert_eq!(event[1], "o");
+                (
+                    event[0].as_f64().unwrap(),
+                    event[2].as_str().unwrap().to_string(),
+                )
+            })
+            .collect();
+        // A frame per drawn text, followed by the restore sequence
+        assert_eq!(events.len(), 4);
+        assert!(events.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        assert!(events[0].1.contains("héllo"));
+        assert!(events[1].1.contains("\"quoted\""));
+        assert!(events[2].1.contains('\\'));
+        assert!(events[3].1.ends_with("\x1b[?25h"));
+    }
+}
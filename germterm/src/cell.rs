@@ -1,23 +1,46 @@
-use crate::{color::Color, rich_text::Attributes};
+use crate::{color::Color, link::LinkId, rich_text::Attributes};
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum CellFormat {
     Standard,
     Twoxel,
     Octad,
     Blocktad,
+    Sextant,
+    Quadrant,
 }
 
-#[derive(Clone, Copy, Eq, PartialEq)]
+/// A single terminal cell.
+///
+/// Wide characters (eg. CJK or emoji) take up two cells: the leading cell holds the character,
+/// while the trailing one holds [`Cell::WIDE_CONTINUATION`] and is never drawn on its own.
+///
+/// A cell holds a whole grapheme cluster, eg. a letter with combining marks or a ZWJ emoji sequence.
+/// Clusters made of more than one char are interned, with `ch` being a char from the Supplementary Private Use Area-B
+/// standing in for the full cluster when drawn, marked as such by the `format_data` of the `Standard` cell.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Cell {
     pub ch: char,
     pub fg: Color,
     pub bg: Color,
     pub attributes: Attributes,
     pub format: CellFormat,
+    /// The filled sub-cells of `Octad`, `Blocktad`, `Sextant` and `Quadrant` cells as a bitmask.
+    /// For `Standard` cells it marks interned grapheme clusters, and is `0` for plain chars and the other formats.
+    ///
+    /// Octads use the bit order of braille dots, while the block based formats number their sub-cells
+    /// row by row, left to right, starting at the top-left. Sub-cells are merged on the mask,
+    /// with `ch` only looked up from it afterwards.
+    pub format_data: u8,
+    /// The hyperlink the cell is part of, see [`crate::link`].
+    pub link: Option<LinkId>,
 }
 
 impl Cell {
+    /// Marks the trailing half of a wide character.
+    pub const WIDE_CONTINUATION: char = '\0';
+
     pub const EMPTY: Cell = Cell {
         ch: ' ',
         fg: Color::CLEAR,
@@ -26,5 +49,7 @@ impl Cell {
             Attributes::NO_FG_COLOR.bits() | Attributes::NO_BG_COLOR.bits(),
         ),
         format: CellFormat::Standard,
+        format_data: 0,
+        link: None,
     };
 }
@@ -9,6 +9,9 @@
 //! - [`Color`] stores RGBA in a single `u32` (`0xRRGGBBAA`).
 //! - [`ColorRgb`] stores RGB without alpha (`0xRRGGBB00`).
 //!
+//! Colors can also be created and adjusted in HSV or HSL space, eg. using [`Color::from_hsv`],
+//! [`Color::with_hue`] or [`Color::lighten`].
+//!
 //! The library is built with alpha blending support as one of it's core features,
 //! which is why [`Color`] sees considerably more use compared to [`ColorRgb`].
 //!
@@ -17,10 +20,26 @@
 //! - [`GradientStop`] represents a single stop in a color gradient.
 //! - [`ColorGradient`] stores a sequence of stops and can be sampled along
 //!   a normalized `0.0..=1.0` range using [`sample_gradient`].
+//! - Stops are interpolated in sRGB by default, see [`ColorSpace`] for perceptually smoother alternatives.
+//! - [`PathGradient`] maps a [`ColorGradient`] onto a polyline by distance,
+//!   sampled using [`sample_path_gradient`] or [`sample_path_gradient_at_vertex`].
+//!
+//! ## Terminal color depth
+//!
+//! - [`ColorDepth`] describes the colors a terminal can display, detected using [`detect_color_depth`].
+//! - [`nearest_ansi256`] and [`nearest_ansi16`] quantize colors for terminals without truecolor support.
 //!
 //! ## Interpolation
 //!
 //! - [`lerp`] allows fast linear interpolation between two [`Color`]s.
+//! - [`ease`] remaps interpolation positions using [`Easing`] curves.
+//!
+//! ## Serde
+//!
+//! With the `serde` feature enabled, colors and gradients can be stored in eg. theme files.
+//! [`Color`] is written as a `"#rrggbbaa"` hex string, and read from either a `"#rrggbb"` or `"#rrggbbaa"`
+//! hex string or a `{ r, g, b, a }` map, with `a` defaulting to `255`.
+//! Gradient stops have to be within `0.0..=1.0`, and are sorted by their position when read.
 
 use std::sync::Arc;
 
@@ -193,6 +212,192 @@ impl Color {
             (a.clamp(0.0, 1.0) * 255.0) as u8,
         )
     }
+
+    /// Creates a color from hue (degrees), saturation and value.
+    ///
+    /// The hue wraps around, so `-30.0` and `330.0` are the same hue.
+    /// Saturation and value are clamped to `0.0..=1.0`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::color::Color;
+    /// assert_eq!(Color::from_hsv(0.0, 1.0, 1.0, 255), Color::RED);
+    /// assert_eq!(Color::from_hsv(120.0, 1.0, 1.0, 255), Color::GREEN);
+    /// assert_eq!(Color::from_hsv(600.0, 1.0, 1.0, 255), Color::BLUE);
+    /// assert_eq!(Color::from_hsv(60.0, 1.0, 1.0, 255), Color::YELLOW);
+    /// assert_eq!(Color::from_hsv(180.0, 1.0, 0.5, 255), Color::new(0, 128, 128, 255));
+    /// assert_eq!(Color::from_hsv(42.0, 0.0, 1.0, 255), Color::WHITE);
+    /// assert_eq!(Color::from_hsv(42.0, 1.0, 0.0, 255), Color::BLACK);
+    /// ```
+    pub fn from_hsv(h: f32, s: f32, v: f32, a: u8) -> Self {
+        let s: f32 = s.clamp(0.0, 1.0);
+        let v: f32 = v.clamp(0.0, 1.0);
+        let chroma: f32 = v * s;
+        let (r, g, b): (f32, f32, f32) = hue_to_rgb(h, chroma);
+        let m: f32 = v - chroma;
+
+        Color::new(
+            channel_from_f32(r + m),
+            channel_from_f32(g + m),
+            channel_from_f32(b + m),
+            a,
+        )
+    }
+
+    /// Creates a color from hue (degrees), saturation and lightness.
+    ///
+    /// The hue wraps around, so `-30.0` and `330.0` are the same hue.
+    /// Saturation and lightness are clamped to `0.0..=1.0`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::color::Color;
+    /// assert_eq!(Color::from_hsl(0.0, 1.0, 0.5, 255), Color::RED);
+    /// assert_eq!(Color::from_hsl(240.0, 1.0, 0.25, 255), Color::new(0, 0, 128, 255));
+    /// assert_eq!(Color::from_hsl(42.0, 0.5, 1.0, 255), Color::WHITE);
+    /// assert_eq!(Color::from_hsl(42.0, 0.5, 0.0, 255), Color::BLACK);
+    /// ```
+    pub fn from_hsl(h: f32, s: f32, l: f32, a: u8) -> Self {
+        let s: f32 = s.clamp(0.0, 1.0);
+        let l: f32 = l.clamp(0.0, 1.0);
+        let chroma: f32 = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let (r, g, b): (f32, f32, f32) = hue_to_rgb(h, chroma);
+        let m: f32 = l - chroma / 2.0;
+
+        Color::new(
+            channel_from_f32(r + m),
+            channel_from_f32(g + m),
+            channel_from_f32(b + m),
+            a,
+        )
+    }
+
+    /// The hue in degrees, in the range `0.0..360.0`. Grays have a hue of `0.0`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::color::Color;
+    /// assert_eq!(Color::RED.hue(), 0.0);
+    /// assert_eq!(Color::CYAN.hue(), 180.0);
+    /// assert_eq!(Color::new(255, 0, 128, 255).hue().round(), 330.0);
+    /// ```
+    pub fn hue(&self) -> f32 {
+        let (r, g, b, _) = self.rgba_f32();
+        let max: f32 = r.max(g).max(b);
+        let chroma: f32 = max - r.min(g).min(b);
+
+        if chroma == 0.0 {
+            return 0.0;
+        }
+
+        let sector: f32 = if max == r {
+            ((g - b) / chroma).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / chroma + 2.0
+        } else {
+            (r - g) / chroma + 4.0
+        };
+        (sector * 60.0).rem_euclid(360.0)
+    }
+
+    /// The HSV saturation, in the range `0.0..=1.0`.
+    pub fn saturation(&self) -> f32 {
+        let (r, g, b, _) = self.rgba_f32();
+        let max: f32 = r.max(g).max(b);
+
+        if max == 0.0 {
+            0.0
+        } else {
+            (max - r.min(g).min(b)) / max
+        }
+    }
+
+    /// The HSV value, in the range `0.0..=1.0`.
+    pub fn value(&self) -> f32 {
+        let (r, g, b, _) = self.rgba_f32();
+        r.max(g).max(b)
+    }
+
+    /// Returns the color with its hue replaced, keeping the saturation, value and alpha.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::color::Color;
+    /// assert_eq!(Color::RED.with_hue(240.0), Color::BLUE);
+    /// ```
+    pub fn with_hue(&self, h: f32) -> Self {
+        Color::from_hsv(h, self.saturation(), self.value(), self.a())
+    }
+
+    /// Increases the HSL lightness by `amount`, clamped to white.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::color::Color;
+    /// assert_eq!(Color::new(0, 0, 128, 255).lighten(0.25), Color::BLUE);
+    /// assert_eq!(Color::BLUE.lighten(1.0), Color::WHITE);
+    /// ```
+    pub fn lighten(&self, amount: f32) -> Self {
+        let (h, s, l): (f32, f32, f32) = self.hsl();
+        Color::from_hsl(h, s, l + amount, self.a())
+    }
+
+    /// Decreases the HSL lightness by `amount`, clamped to black.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::color::Color;
+    /// assert_eq!(Color::BLUE.darken(0.25), Color::new(0, 0, 128, 255));
+    /// ```
+    pub fn darken(&self, amount: f32) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// Increases the HSL saturation by `amount`, use a negative amount to desaturate.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::color::Color;
+    /// assert_eq!(Color::RED.saturate(-1.0), Color::new(128, 128, 128, 255));
+    /// ```
+    pub fn saturate(&self, amount: f32) -> Self {
+        let (h, s, l): (f32, f32, f32) = self.hsl();
+        Color::from_hsl(h, s + amount, l, self.a())
+    }
+
+    fn hsl(&self) -> (f32, f32, f32) {
+        let (r, g, b, _) = self.rgba_f32();
+        let max: f32 = r.max(g).max(b);
+        let min: f32 = r.min(g).min(b);
+        let l: f32 = (max + min) / 2.0;
+        let s: f32 = if max == min {
+            0.0
+        } else {
+            (max - min) / (1.0 - (2.0 * l - 1.0).abs())
+        };
+        (self.hue(), s, l)
+    }
+}
+
+/// Maps a hue in degrees to RGB components with the given chroma, without the lightness offset.
+#[inline]
+fn hue_to_rgb(h: f32, chroma: f32) -> (f32, f32, f32) {
+    let sector: f32 = h.rem_euclid(360.0) / 60.0;
+    let x: f32 = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+
+    match sector as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    }
+}
+
+#[inline]
+fn channel_from_f32(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
 }
 
 /// A packed RGB color stored in an `u32`.
@@ -246,15 +451,144 @@ impl From<ColorRgb> for Color {
     }
 }
 
+/// Parses the `RRGGBB` or `RRGGBBAA` hex digits of a color, without the leading `#`.
+pub(crate) fn parse_hex_color(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let value: u32 = u32::from_str_radix(hex, 16).ok()?;
+    match hex.len() {
+        6 => Some(Color((value << 8) | 0xFF)),
+        8 => Some(Color(value)),
+        _ => None,
+    }
+}
+
+/// Parses a `#RRGGBB` or `#RRGGBBAA` hex code, or the lowercase name of a [`Color`] constant like `dark_gray`.
+pub(crate) fn parse_color_value(value: &str) -> Option<Color> {
+    if let Some(hex) = value.strip_prefix('#') {
+        return parse_hex_color(hex);
+    }
+
+    Some(match value {
+        "clear" => Color::CLEAR,
+        "white" => Color::WHITE,
+        "dark_gray" => Color::DARK_GRAY,
+        "light_gray" => Color::LIGHT_GRAY,
+        "black" => Color::BLACK,
+        "red" => Color::RED,
+        "green" => Color::GREEN,
+        "blue" => Color::BLUE,
+        "yellow" => Color::YELLOW,
+        "cyan" => Color::CYAN,
+        "teal" => Color::TEAL,
+        "violet" => Color::VIOLET,
+        "pink" => Color::PINK,
+        "orange" => Color::ORANGE,
+        "dark_green" => Color::DARK_GREEN,
+        _ => return None,
+    })
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Color {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("#{:08x}", self.0))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Color {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ColorVisitor)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct ColorVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for ColorVisitor {
+    type Value = Color;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a \"#rrggbbaa\" hex string or an { r, g, b, a } map")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Color, E> {
+        value
+            .strip_prefix('#')
+            .and_then(parse_hex_color)
+            .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(value), &self))
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Color, A::Error> {
+        use serde::de::Error;
+        const CHANNELS: [&str; 4] = ["r", "g", "b", "a"];
+
+        let mut channels: [Option<u8>; 4] = [None; 4];
+        while let Some(key) = map.next_key::<String>()? {
+            let Some(index) = CHANNELS.iter().position(|&channel| channel == key) else {
+                return Err(A::Error::unknown_field(&key, &CHANNELS));
+            };
+            if channels[index].is_some() {
+                return Err(A::Error::duplicate_field(CHANNELS[index]));
+            }
+            channels[index] = Some(map.next_value()?);
+        }
+
+        let [r, g, b, a] = channels;
+        Ok(Color::new(
+            r.ok_or_else(|| A::Error::missing_field("r"))?,
+            g.ok_or_else(|| A::Error::missing_field("g"))?,
+            b.ok_or_else(|| A::Error::missing_field("b"))?,
+            a.unwrap_or(255),
+        ))
+    }
+}
+
 /// A single stop in a [`ColorGradient`].
 ///
 /// Each stop specifies a position `t` in the normalized range `0.0..=1.0`
 /// and a [`Color`] at that position. Gradients are created by interpolating
 /// between multiple stops.
 #[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "GradientStopDef")
+)]
 pub struct GradientStop {
     pub t: f32,
     pub color: Color,
+    /// Easing applied to the segment between this stop and the next one.
+    pub easing: Easing,
+}
+
+/// The unvalidated form of a [`GradientStop`] read by serde.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct GradientStopDef {
+    t: f32,
+    color: Color,
+    #[serde(default)]
+    easing: Easing,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<GradientStopDef> for GradientStop {
+    type Error = String;
+
+    fn try_from(stop: GradientStopDef) -> Result<Self, Self::Error> {
+        if !(0.0..=1.0).contains(&stop.t) {
+            return Err(format!(
+                "gradient stop position {} is outside of 0.0..=1.0",
+                stop.t
+            ));
+        }
+        Ok(GradientStop::new(stop.t, stop.color).with_easing(stop.easing))
+    }
 }
 
 impl GradientStop {
@@ -267,10 +601,78 @@ impl GradientStop {
     /// let stop = GradientStop::new(0.5, Color::RED);
     /// ```
     pub fn new(t: f32, color: Color) -> Self {
-        GradientStop { t, color }
+        GradientStop {
+            t,
+            color,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Sets the easing of the segment that follows this stop.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use germterm::color::{GradientStop, Color, Easing};
+    /// let stop = GradientStop::new(0.0, Color::RED).with_easing(Easing::Smoothstep);
+    /// ```
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
     }
 }
 
+/// Easing curve remapping the position within a gradient segment or a [`crate::anim::Tween`], see [`ease`].
+///
+/// The polynomial curves are named after their degree, and whether they're slow at the start (`In`),
+/// the end (`Out`) or both (`InOut`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+#[non_exhaustive]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    /// Made of two quadratic halves.
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    /// Made of two cubic halves.
+    CubicInOut,
+    /// Slow at both ends, following a single cubic curve that's gentler than [`Easing::CubicInOut`].
+    Smoothstep,
+    /// Wobbles back and forth with a growing amplitude before snapping to the end, undershooting below `0.0`.
+    ElasticIn,
+    /// Overshoots the end and wobbles around it with a decaying amplitude, like a spring.
+    ElasticOut,
+    /// Bounces off the start a few times with a growing height before reaching the end.
+    BounceIn,
+    /// Bounces off the end a few times like a dropped ball, settling on it.
+    BounceOut,
+}
+
+/// The color space gradient stops are interpolated in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ColorSpace {
+    /// Interpolates the raw sRGB channels. Fastest, but midpoints between distant hues can look muddy and dark.
+    #[default]
+    Srgb,
+    /// Interpolates light intensities, resulting in brighter midpoints than [`ColorSpace::Srgb`].
+    LinearRgb,
+    /// Interpolates in the perceptual OKLab space, giving even steps in perceived lightness and hue.
+    Oklab,
+}
+
 /// A simple n-color gradient.
 ///
 /// Stores a sequence of color stops [`GradientStop`] that can be sampled
@@ -282,8 +684,37 @@ impl GradientStop {
 /// The gradient is internally reference-counted [`Arc`] so it can be
 /// cheaply cloned and shared.
 #[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "ColorGradientDef")
+)]
 pub struct ColorGradient {
     pub stops: Arc<Vec<GradientStop>>,
+    pub color_space: ColorSpace,
+}
+
+/// The unvalidated form of a [`ColorGradient`] read by serde.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ColorGradientDef {
+    stops: Vec<GradientStop>,
+    #[serde(default)]
+    color_space: ColorSpace,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ColorGradientDef> for ColorGradient {
+    type Error = &'static str;
+
+    fn try_from(mut gradient: ColorGradientDef) -> Result<Self, Self::Error> {
+        if gradient.stops.is_empty() {
+            return Err("gradient must have at least 1 stop");
+        }
+
+        gradient.stops.sort_by(|a, b| a.t.total_cmp(&b.t));
+        Ok(ColorGradient::new(gradient.stops).with_color_space(gradient.color_space))
+    }
 }
 
 impl ColorGradient {
@@ -300,8 +731,32 @@ impl ColorGradient {
 
         ColorGradient {
             stops: Arc::new(stops),
+            color_space: ColorSpace::Srgb,
         }
     }
+
+    /// Sets the color space the stops are interpolated in, [`ColorSpace::Srgb`] by default.
+    ///
+    /// The alpha channel is always interpolated linearly.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use germterm::color::{ColorGradient, ColorSpace, GradientStop, Color, sample_gradient};
+    /// let srgb = ColorGradient::new(vec![
+    ///     GradientStop::new(0.0, Color::BLUE),
+    ///     GradientStop::new(1.0, Color::YELLOW),
+    /// ]);
+    /// let oklab = srgb.clone().with_color_space(ColorSpace::Oklab);
+    ///
+    /// assert_eq!(sample_gradient(&srgb, 0.5), Color::new(128, 128, 127, 255));
+    /// assert_eq!(sample_gradient(&oklab, 0.5), Color::new(108, 171, 199, 255));
+    /// assert_eq!(sample_gradient(&oklab, 1.0), Color::YELLOW);
+    /// ```
+    pub fn with_color_space(mut self, color_space: ColorSpace) -> Self {
+        self.color_space = color_space;
+        self
+    }
 }
 
 /// Samples a color from a `ColorGradient` at a normalized position `t`.
@@ -331,14 +786,319 @@ pub fn sample_gradient(gradient: &ColorGradient, t: f32) -> Color {
         let b = &window[1];
 
         if t >= a.t && t <= b.t {
-            let local_t = (t - a.t) / (b.t - a.t);
-            return lerp(a.color, b.color, local_t);
+            let local_t = ease((t - a.t) / (b.t - a.t), a.easing);
+            return match gradient.color_space {
+                ColorSpace::Srgb => lerp(a.color, b.color, local_t),
+                ColorSpace::LinearRgb => {
+                    lerp_in_space(a.color, b.color, local_t, srgb_to_linear, linear_to_srgb)
+                }
+                ColorSpace::Oklab => {
+                    lerp_in_space(a.color, b.color, local_t, srgb_to_oklab, oklab_to_srgb)
+                }
+            };
         }
     }
 
     gradient.stops.last().unwrap().color
 }
 
+/// Remaps `t` in `0.0..=1.0` using an [`Easing`] curve.
+///
+/// Every curve maps `0.0` to `0.0` and `1.0` to `1.0`, though the elastic ones leave the range in between.
+///
+/// # Example
+/// ```rust
+/// # use germterm::color::{Easing, ease};
+/// assert_eq!(ease(0.5, Easing::Linear), 0.5);
+/// assert_eq!(ease(0.5, Easing::CubicIn), 0.125);
+/// assert_eq!(ease(1.0, Easing::BounceOut), 1.0);
+///
+/// use Easing::*;
+/// let halfway: [(Easing, f32); 12] = [
+///     (Linear, 0.5),
+///     (QuadIn, 0.25),
+///     (QuadOut, 0.75),
+///     (QuadInOut, 0.5),
+///     (CubicIn, 0.125),
+///     (CubicOut, 0.875),
+///     (CubicInOut, 0.5),
+///     (Smoothstep, 0.5),
+///     (ElasticIn, -0.015625),
+///     (ElasticOut, 1.015625),
+///     (BounceIn, 0.234375),
+///     (BounceOut, 0.765625),
+/// ];
+/// for (easing, expected) in halfway {
+///     assert!((ease(0.5, easing) - expected).abs() < 1e-5, "{easing:?}");
+/// #   assert!(ease(0.0, easing).abs() < 1e-6 && (ease(1.0, easing) - 1.0).abs() < 1e-6);
+/// }
+/// ```
+#[inline]
+pub fn ease(t: f32, easing: Easing) -> f32 {
+    const ELASTIC_PERIOD: f32 = 2.0 * std::f32::consts::PI / 3.0;
+
+    match easing {
+        Easing::Linear => t,
+        Easing::QuadIn => t * t,
+        Easing::QuadOut => t * (2.0 - t),
+        Easing::QuadInOut if t < 0.5 => 2.0 * t * t,
+        Easing::QuadInOut => 1.0 - (2.0 - 2.0 * t).powi(2) / 2.0,
+        Easing::CubicIn => t * t * t,
+        Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+        Easing::CubicInOut if t < 0.5 => 4.0 * t * t * t,
+        Easing::CubicInOut => 1.0 - (2.0 - 2.0 * t).powi(3) / 2.0,
+        Easing::Smoothstep => t * t * (3.0 - 2.0 * t),
+        Easing::ElasticIn | Easing::ElasticOut if t <= 0.0 || t >= 1.0 => t,
+        Easing::ElasticIn => {
+            -(2.0f32).powf(10.0 * t - 10.0) * ((10.0 * t - 10.75) * ELASTIC_PERIOD).sin()
+        }
+        Easing::ElasticOut => {
+            (2.0f32).powf(-10.0 * t) * ((10.0 * t - 0.75) * ELASTIC_PERIOD).sin() + 1.0
+        }
+        Easing::BounceIn => 1.0 - bounce_out(1.0 - t),
+        Easing::BounceOut => bounce_out(t),
+    }
+}
+
+#[inline]
+fn bounce_out(t: f32) -> f32 {
+    const STRENGTH: f32 = 7.5625;
+    const WIDTH: f32 = 2.75;
+
+    if t < 1.0 / WIDTH {
+        STRENGTH * t * t
+    } else if t < 2.0 / WIDTH {
+        let t: f32 = t - 1.5 / WIDTH;
+        STRENGTH * t * t + 0.75
+    } else if t < 2.5 / WIDTH {
+        let t: f32 = t - 2.25 / WIDTH;
+        STRENGTH * t * t + 0.9375
+    } else {
+        let t: f32 = t - 2.625 / WIDTH;
+        STRENGTH * t * t + 0.984375
+    }
+}
+
+/// Interpolates the color channels in another color space, and the alpha channel linearly.
+#[inline]
+fn lerp_in_space(
+    a: Color,
+    b: Color,
+    t: f32,
+    to_space: fn([f32; 3]) -> [f32; 3],
+    from_space: fn([f32; 3]) -> [f32; 3],
+) -> Color {
+    let t: f32 = t.clamp(0.0, 1.0);
+    let (a_r, a_g, a_b, a_a) = a.rgba_f32();
+    let (b_r, b_g, b_b, b_a) = b.rgba_f32();
+    let a_space: [f32; 3] = to_space([a_r, a_g, a_b]);
+    let b_space: [f32; 3] = to_space([b_r, b_g, b_b]);
+
+    let mixed: [f32; 3] = std::array::from_fn(|i| a_space[i] + (b_space[i] - a_space[i]) * t);
+    let [r, g, b] = from_space(mixed);
+    let alpha: f32 = a_a + (b_a - a_a) * t;
+
+    Color::new(
+        channel_from_f32(r),
+        channel_from_f32(g),
+        channel_from_f32(b),
+        channel_from_f32(alpha),
+    )
+}
+
+#[inline]
+fn srgb_channel_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+#[inline]
+fn linear_channel_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn srgb_to_linear(rgb: [f32; 3]) -> [f32; 3] {
+    rgb.map(srgb_channel_to_linear)
+}
+
+fn linear_to_srgb(rgb: [f32; 3]) -> [f32; 3] {
+    rgb.map(|c| linear_channel_to_srgb(c.clamp(0.0, 1.0)))
+}
+
+/// Conversion matrices from Björn Ottosson's OKLab reference implementation.
+fn srgb_to_oklab(rgb: [f32; 3]) -> [f32; 3] {
+    let [r, g, b] = srgb_to_linear(rgb);
+
+    let l: f32 = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m: f32 = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s: f32 = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+fn oklab_to_srgb(lab: [f32; 3]) -> [f32; 3] {
+    let [lightness, a, b] = lab;
+
+    let l: f32 = (lightness + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+    let m: f32 = (lightness - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+    let s: f32 = (lightness - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+
+    linear_to_srgb([
+        4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+        -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+        -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+    ])
+}
+
+/// The end of a path that distances along a [`PathGradient`] are measured from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathAnchor {
+    /// Distances are measured from the first vertex of the path.
+    Head,
+    /// Distances are measured from the last vertex of the path.
+    Tail,
+}
+
+/// A [`ColorGradient`] laid out along a polyline by distance rather than by vertex index.
+///
+/// The gradient spans `span` cells from the anchored end of the path, anything past that samples the last stop.
+/// This keeps colors tied to the distance along the path, so a growing path extends
+/// the gradient instead of stretching it.
+///
+/// Distances are aspect-corrected, a vertical step of 1 row is treated as 2 columns long,
+/// matching the 1:2 width to height ratio of terminal cells.
+///
+/// The path uses the same floating point cell coordinate space as the drawing functions.
+/// Draw the path using [`crate::draw::draw_path_twoxel`].
+#[derive(Clone)]
+pub struct PathGradient {
+    pub gradient: ColorGradient,
+    pub anchor: PathAnchor,
+    pub span: f32,
+    vertices: Vec<(f32, f32)>,
+    cumulative_lengths: Vec<f32>,
+}
+
+impl PathGradient {
+    /// Creates a new path gradient, measuring the segment lengths of `path`.
+    ///
+    /// # Panics
+    /// - If `span` is not greater than `0.0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use germterm::color::{ColorGradient, GradientStop, Color, PathGradient, PathAnchor, sample_path_gradient_at_vertex};
+    /// let gradient = ColorGradient::new(vec![
+    ///     GradientStop::new(0.0, Color::RED),
+    ///     GradientStop::new(1.0, Color::BLUE),
+    /// ]);
+    /// let path = [(0.0, 0.0), (4.0, 0.0), (4.0, 2.0)];
+    /// let path_gradient = PathGradient::new(&path, gradient, PathAnchor::Head, 8.0);
+    ///
+    /// assert_eq!(path_gradient.total_length(), 8.0);
+    /// assert_eq!(sample_path_gradient_at_vertex(&path_gradient, 2), Color::BLUE);
+    /// ```
+    pub fn new(
+        path: &[(f32, f32)],
+        gradient: ColorGradient,
+        anchor: PathAnchor,
+        span: f32,
+    ) -> Self {
+        assert!(span > 0.0, "Path gradient span must be greater than 0.0");
+
+        let mut path_gradient: PathGradient = PathGradient {
+            gradient,
+            anchor,
+            span,
+            vertices: Vec::with_capacity(path.len()),
+            cumulative_lengths: Vec::with_capacity(path.len()),
+        };
+        set_gradient_path(&mut path_gradient, path);
+        path_gradient
+    }
+
+    /// The aspect-corrected length of the whole path.
+    pub fn total_length(&self) -> f32 {
+        self.cumulative_lengths.last().copied().unwrap_or(0.0)
+    }
+
+    /// The number of vertices in the path.
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// The path the gradient was measured from.
+    pub fn path(&self) -> &[(f32, f32)] {
+        &self.vertices
+    }
+
+    /// The aspect-corrected distance of a vertex from the anchored end of the path.
+    ///
+    /// # Panics
+    /// - If `vertex_index` is out of bounds.
+    pub fn vertex_distance(&self, vertex_index: usize) -> f32 {
+        let length_from_head: f32 = self.cumulative_lengths[vertex_index];
+        match self.anchor {
+            PathAnchor::Head => length_from_head,
+            PathAnchor::Tail => self.total_length() - length_from_head,
+        }
+    }
+}
+
+/// Re-measures a [`PathGradient`] for a new path, reusing its allocation.
+///
+/// Useful for paths that change every frame, like the body of a snake.
+pub fn set_gradient_path(path_gradient: &mut PathGradient, path: &[(f32, f32)]) {
+    path_gradient.vertices.clear();
+    path_gradient.vertices.extend_from_slice(path);
+    path_gradient.cumulative_lengths.clear();
+
+    let mut length: f32 = 0.0;
+    let mut prev: Option<(f32, f32)> = None;
+    for &pos in path {
+        if let Some(prev) = prev {
+            length += aspect_corrected_distance(prev, pos);
+        }
+        path_gradient.cumulative_lengths.push(length);
+        prev = Some(pos);
+    }
+}
+
+/// Samples a [`PathGradient`] at a distance from the anchored end of its path.
+///
+/// Negative distances sample the first stop, distances past `span` sample the last stop.
+#[inline]
+pub fn sample_path_gradient(path_gradient: &PathGradient, distance: f32) -> Color {
+    sample_gradient(&path_gradient.gradient, distance / path_gradient.span)
+}
+
+/// Samples a [`PathGradient`] at one of the vertices of its path.
+///
+/// # Panics
+/// - If `vertex_index` is out of bounds.
+#[inline]
+pub fn sample_path_gradient_at_vertex(path_gradient: &PathGradient, vertex_index: usize) -> Color {
+    sample_path_gradient(path_gradient, path_gradient.vertex_distance(vertex_index))
+}
+
+/// Distance between two cell positions, treating a row as twice as tall as a column is wide.
+#[inline]
+pub(crate) fn aspect_corrected_distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    (b.0 - a.0).hypot((b.1 - a.1) * 2.0)
+}
+
 /// Linearly interpolates between two [`Color`]s.
 ///
 /// Computes a color between `a` and `b` using the parameter `t`,
@@ -371,6 +1131,43 @@ pub fn lerp(a: Color, b: Color, t: f32) -> Color {
     Color::new(out_r, out_g, out_b, out_a)
 }
 
+/// A transformation of colors, applied to composed cells by [`crate::layer::set_layer_effect`]
+/// and [`crate::engine::set_screen_effect`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColorEffect {
+    /// Multiplies the RGB channels by a factor, where values above `1.0` brighten instead.
+    Dim(f32),
+    /// Replaces colors with a gray of the same luminance.
+    Grayscale,
+    /// Mixes colors towards the RGB of the tint, by the alpha of the tint.
+    Tint(Color),
+}
+
+/// Applies a [`ColorEffect`] to a color, keeping its alpha.
+///
+/// # Example
+/// ```rust
+/// # use germterm::color::{Color, ColorEffect, apply_color_effect};
+/// let color = Color::new(200, 100, 50, 255);
+/// assert_eq!(apply_color_effect(color, ColorEffect::Dim(0.5)), Color::new(100, 50, 25, 255));
+/// assert_eq!(apply_color_effect(Color::WHITE, ColorEffect::Grayscale), Color::WHITE);
+/// ```
+pub fn apply_color_effect(color: Color, effect: ColorEffect) -> Color {
+    let (r, g, b, a) = color.rgba();
+    match effect {
+        ColorEffect::Dim(factor) => {
+            let scale = |channel: u8| (channel as f32 * factor).round().clamp(0.0, 255.0) as u8;
+            Color::new(scale(r), scale(g), scale(b), a)
+        }
+        ColorEffect::Grayscale => {
+            let luminance: f32 = 0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32;
+            let gray: u8 = luminance.round().clamp(0.0, 255.0) as u8;
+            Color::new(gray, gray, gray, a)
+        }
+        ColorEffect::Tint(tint) => lerp(color, tint.with_alpha(a), tint.a() as f32 / 255.0),
+    }
+}
+
 #[inline]
 pub(crate) fn blend_source_over(bottom: Color, top: Color) -> Color {
     let (tr, tg, tb, ta) = top.rgba();
@@ -403,3 +1200,238 @@ pub(crate) fn blend_source_over(bottom: Color, top: Color) -> Color {
 
     Color::new(out_r, out_g, out_b, out_a as u8)
 }
+
+/// The amount of colors a terminal is able to display.
+///
+/// Colors are quantized to the nearest available color when drawing to terminals without truecolor support.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ColorDepth {
+    /// 24-bit RGB colors.
+    #[default]
+    TrueColor,
+    /// The 6x6x6 color cube and grayscale ramp of the 256 color palette, see [`nearest_ansi256`].
+    Ansi256,
+    /// The 16 base colors, see [`nearest_ansi16`].
+    Ansi16,
+}
+
+/// Detects the color depth of the terminal from the `COLORTERM` and `TERM` environment variables.
+///
+/// Most terminals support truecolor without advertising it, so it's assumed unless the terminal is known to be limited:
+/// - `COLORTERM` set to `truecolor` or `24bit`, or `TERM` containing `direct` results in [`ColorDepth::TrueColor`].
+/// - `TERM` containing `256color` results in [`ColorDepth::Ansi256`].
+/// - `TERM` set to a terminal limited to the base colors, eg. `linux`, `vt100` or `xterm-16color`,
+///   results in [`ColorDepth::Ansi16`].
+/// - Anything else, including an unset `TERM` (eg. on Windows), results in [`ColorDepth::TrueColor`].
+pub fn detect_color_depth() -> ColorDepth {
+    let colorterm: String = std::env::var("COLORTERM").unwrap_or_default();
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        return ColorDepth::TrueColor;
+    }
+
+    match std::env::var("TERM") {
+        Ok(term) if term.contains("direct") => ColorDepth::TrueColor,
+        Ok(term) if term.contains("256color") => ColorDepth::Ansi256,
+        Ok(term) if is_limited_to_ansi16(&term) => ColorDepth::Ansi16,
+        _ => ColorDepth::TrueColor,
+    }
+}
+
+/// Whether `term` names a terminal that only supports the 16 base colors.
+fn is_limited_to_ansi16(term: &str) -> bool {
+    const ANSI16_TERMS: [&str; 8] = [
+        "linux",
+        "vt100",
+        "vt102",
+        "vt220",
+        "ansi",
+        "cons25",
+        "xterm-color",
+        "dumb",
+    ];
+    ANSI16_TERMS.contains(&term) || term.ends_with("-16color") || term.ends_with("-8color")
+}
+
+const ANSI256_CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The xterm default values of the 16 base colors.
+const ANSI16_PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// The index of the nearest color in the 256 color palette, ignoring alpha.
+///
+/// Only the color cube (`16..=231`) and the grayscale ramp (`232..=255`) are considered,
+/// as the first 16 colors are commonly changed by terminal themes.
+///
+/// # Example
+/// ```rust
+/// # use germterm::color::{Color, nearest_ansi256};
+/// assert_eq!(nearest_ansi256(Color::BLACK), 16);
+/// assert_eq!(nearest_ansi256(Color::WHITE), 231);
+/// assert_eq!(nearest_ansi256(Color::RED), 196);
+/// assert_eq!(nearest_ansi256(Color::ORANGE), 214);
+/// assert_eq!(nearest_ansi256(Color::new(128, 128, 128, 255)), 244);
+/// assert_eq!(nearest_ansi256(Color::new(95, 135, 175, 255)), 67);
+/// ```
+pub fn nearest_ansi256(color: Color) -> u8 {
+    let (r, g, b): (u8, u8, u8) = color.rgb();
+
+    let cube_index = |channel: u8| -> usize {
+        (0..ANSI256_CUBE_LEVELS.len())
+            .min_by_key(|&i| ANSI256_CUBE_LEVELS[i].abs_diff(channel))
+            .unwrap()
+    };
+    let (cube_r, cube_g, cube_b): (usize, usize, usize) =
+        (cube_index(r), cube_index(g), cube_index(b));
+    let cube_color: (u8, u8, u8) = (
+        ANSI256_CUBE_LEVELS[cube_r],
+        ANSI256_CUBE_LEVELS[cube_g],
+        ANSI256_CUBE_LEVELS[cube_b],
+    );
+
+    // The grayscale ramp goes from 8 to 238 in steps of 10
+    let average: u32 = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_step: u32 = (average.saturating_sub(3) / 10).min(23);
+    let gray_level: u8 = (8 + gray_step * 10) as u8;
+
+    if squared_distance((r, g, b), (gray_level, gray_level, gray_level))
+        < squared_distance((r, g, b), cube_color)
+    {
+        232 + gray_step as u8
+    } else {
+        (16 + cube_r * 36 + cube_g * 6 + cube_b) as u8
+    }
+}
+
+/// The index of the nearest of the 16 base colors, ignoring alpha.
+///
+/// Distances are measured against the xterm default palette.
+///
+/// # Example
+/// ```rust
+/// # use germterm::color::{Color, nearest_ansi16};
+/// assert_eq!(nearest_ansi16(Color::BLACK), 0);
+/// assert_eq!(nearest_ansi16(Color::WHITE), 15);
+/// assert_eq!(nearest_ansi16(Color::RED), 9);
+/// assert_eq!(nearest_ansi16(Color::new(128, 0, 0, 255)), 1);
+/// assert_eq!(nearest_ansi16(Color::TEAL), 6);
+/// assert_eq!(nearest_ansi16(Color::DARK_GRAY), 8);
+/// ```
+pub fn nearest_ansi16(color: Color) -> u8 {
+    let rgb: (u8, u8, u8) = color.rgb();
+
+    (0..ANSI16_PALETTE.len())
+        .min_by_key(|&i| squared_distance(rgb, ANSI16_PALETTE[i]))
+        .unwrap() as u8
+}
+
+/// The xterm default value of a color of the 256 color palette.
+pub(crate) fn ansi256_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI16_PALETTE[index as usize],
+        16..=231 => {
+            let cube_index: usize = index as usize - 16;
+            (
+                ANSI256_CUBE_LEVELS[cube_index / 36],
+                ANSI256_CUBE_LEVELS[cube_index / 6 % 6],
+                ANSI256_CUBE_LEVELS[cube_index % 6],
+            )
+        }
+        232..=255 => {
+            let level: u8 = 8 + (index - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+#[inline]
+fn squared_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let dr: u32 = a.0.abs_diff(b.0) as u32;
+    let dg: u32 = a.1.abs_diff(b.1) as u32;
+    let db: u32 = a.2.abs_diff(b.2) as u32;
+    dr * dr + dg * dg + db * db
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_known_limited_terminals_fall_back_to_ansi16() {
+        for term in [
+            "linux",
+            "vt100",
+            "xterm-color",
+            "screen-16color",
+            "rxvt-8color",
+        ] {
+            assert!(is_limited_to_ansi16(term), "{term}");
+        }
+        for term in ["xterm", "screen", "tmux", "alacritty", "xterm-kitty"] {
+            assert!(!is_limited_to_ansi16(term), "{term}");
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn colors_and_gradients_round_trip_through_serde() {
+        use crate::test_support::serde_round_trip;
+
+        for color in [Color::RED, Color::CLEAR, Color::new(1, 2, 3, 4)] {
+            assert_eq!(serde_round_trip(&color), (color, color));
+        }
+        assert_eq!(serde_json::to_string(&Color::RED).unwrap(), "\"#ff0000ff\"");
+
+        for easing in [Easing::Linear, Easing::QuadInOut, Easing::BounceOut] {
+            assert_eq!(serde_round_trip(&easing), (easing, easing));
+        }
+        for color_space in [ColorSpace::Srgb, ColorSpace::LinearRgb, ColorSpace::Oklab] {
+            assert_eq!(serde_round_trip(&color_space), (color_space, color_space));
+        }
+
+        let stop: GradientStop = GradientStop::new(0.25, Color::BLUE).with_easing(Easing::CubicIn);
+        for read in <[GradientStop; 2]>::from(serde_round_trip(&stop)) {
+            assert_eq!(
+                (read.t, read.color, read.easing),
+                (0.25, Color::BLUE, Easing::CubicIn)
+            );
+        }
+
+        let gradient: ColorGradient = ColorGradient::new(vec![
+            GradientStop::new(0.0, Color::RED),
+            GradientStop::new(1.0, Color::GREEN).with_easing(Easing::QuadOut),
+        ])
+        .with_color_space(ColorSpace::Oklab);
+        for read in <[ColorGradient; 2]>::from(serde_round_trip(&gradient)) {
+            assert_eq!(read.color_space, ColorSpace::Oklab);
+            let stops: Vec<(f32, Color, Easing)> = read
+                .stops
+                .iter()
+                .map(|stop| (stop.t, stop.color, stop.easing))
+                .collect();
+            assert_eq!(
+                stops,
+                [
+                    (0.0, Color::RED, Easing::Linear),
+                    (1.0, Color::GREEN, Easing::QuadOut)
+                ]
+            );
+        }
+    }
+}
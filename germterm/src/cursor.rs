@@ -0,0 +1,130 @@
+//! Control over the terminal cursor, eg. for showing the edit position of text inputs.
+//!
+//! The engine keeps the cursor hidden by default. [`set_cursor`] shows it for the current frame only,
+//! so it's hidden again as soon as a frame doesn't set it.
+//! The cursor is placed after all cells are drawn, so it always lands on top of the frame.
+
+use std::io::{self, Write};
+
+use crossterm::{cursor as ctcursor, execute, queue};
+
+use crate::engine::Engine;
+
+/// The shape of the terminal cursor, and whether it blinks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CursorStyle {
+    /// The shape configured by the user in their terminal.
+    #[default]
+    TerminalDefault,
+    BlinkingBlock,
+    SteadyBlock,
+    BlinkingUnderline,
+    SteadyUnderline,
+    BlinkingBar,
+    SteadyBar,
+}
+
+pub(crate) struct CursorState {
+    requested: Option<(u16, u16, CursorStyle)>,
+    shown: Option<CursorStyle>,
+}
+
+impl CursorState {
+    pub fn new() -> Self {
+        Self {
+            requested: None,
+            shown: None,
+        }
+    }
+}
+
+/// Shows the cursor at the given cell for the current frame.
+///
+/// Positions outside of the frame are ignored, leaving the cursor hidden.
+/// Calling it again within the same frame overrides the previous call.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{cursor::{CursorStyle, set_cursor}, draw::draw_text, engine::Engine, layer::create_layer};
+/// # let mut engine = Engine::new(40, 20);
+/// # let layer = create_layer(&mut engine, 0);
+/// let input: &str = "hello";
+/// draw_text(&mut engine, layer, 2, 1, input);
+/// set_cursor(&mut engine, 2 + input.len() as i16, 1, CursorStyle::BlinkingBar);
+/// ```
+pub fn set_cursor(engine: &mut Engine, x: i16, y: i16, style: CursorStyle) {
+    let is_in_bounds: bool =
+        (0..engine.frame.width as i16).contains(&x) && (0..engine.frame.height as i16).contains(&y);
+
+    engine.cursor.requested = is_in_bounds.then_some((x as u16, y as u16, style));
+}
+
+/// Hides the cursor for the current frame, undoing [`set_cursor`].
+pub fn hide_cursor(engine: &mut Engine) {
+    engine.cursor.requested = None;
+}
+
+pub(crate) fn reset_cursor_request(cursor_state: &mut CursorState) {
+    cursor_state.requested = None;
+}
+
+pub(crate) fn is_cursor_shown(cursor_state: &CursorState) -> bool {
+    cursor_state.shown.is_some()
+}
+
+pub(crate) fn reset_cursor_state(cursor_state: &mut CursorState) {
+    cursor_state.requested = None;
+    cursor_state.shown = None;
+}
+
+/// Places the cursor if the frame requested it, or hides it if it's still shown from an earlier frame.
+///
+/// Returns the row of the frame the cursor was placed at.
+pub(crate) fn draw_cursor(
+    writer: &mut impl Write,
+    cursor_state: &mut CursorState,
+    origin_row: u16,
+) -> io::Result<Option<u16>> {
+    let Some((x, y, style)) = cursor_state.requested else {
+        if cursor_state.shown.take().is_some() {
+            execute!(writer, ctcursor::Hide)?;
+        }
+        return Ok(None);
+    };
+
+    queue!(writer, ctcursor::MoveTo(x, y + origin_row))?;
+    if cursor_state.shown != Some(style) {
+        queue!(writer, build_crossterm_cursor_style(style))?;
+    }
+    if cursor_state.shown.is_none() {
+        queue!(writer, ctcursor::Show)?;
+    }
+    cursor_state.shown = Some(style);
+
+    writer.flush()?;
+    Ok(Some(y))
+}
+
+/// Restores the cursor shape configured by the user, if a different one was shown.
+pub(crate) fn restore_cursor_style(
+    writer: &mut impl Write,
+    cursor_state: &mut CursorState,
+) -> io::Result<()> {
+    let shown: Option<CursorStyle> = cursor_state.shown.take();
+    if shown.is_some_and(|style| style != CursorStyle::TerminalDefault) {
+        execute!(writer, ctcursor::SetCursorStyle::DefaultUserShape)?;
+    }
+    Ok(())
+}
+
+fn build_crossterm_cursor_style(style: CursorStyle) -> ctcursor::SetCursorStyle {
+    match style {
+        CursorStyle::TerminalDefault => ctcursor::SetCursorStyle::DefaultUserShape,
+        CursorStyle::BlinkingBlock => ctcursor::SetCursorStyle::BlinkingBlock,
+        CursorStyle::SteadyBlock => ctcursor::SetCursorStyle::SteadyBlock,
+        CursorStyle::BlinkingUnderline => ctcursor::SetCursorStyle::BlinkingUnderScore,
+        CursorStyle::SteadyUnderline => ctcursor::SetCursorStyle::SteadyUnderScore,
+        CursorStyle::BlinkingBar => ctcursor::SetCursorStyle::BlinkingBar,
+        CursorStyle::SteadyBar => ctcursor::SetCursorStyle::SteadyBar,
+    }
+}
@@ -15,6 +15,7 @@
 //! ## Coordinate space
 //!
 //! All drawing functions operate in the same coordinate space, where `x` and `y` refer to terminal columns and rows.
+//! Layers with a camera set by [`crate::layer::set_layer_camera`] shift that space, letting drawing happen in world coordinates.
 //!
 //! Some primitives (such as [`draw_octad`] and [`draw_twoxel`]) allow for a higher
 //! sub-cell drawing precision using floating point coordinates combined with Unicode tricks.
@@ -30,15 +31,34 @@
 //! All functions in this module are immediate-mode.
 //! They do not render directly, instead they enqueue draw calls
 //! that are consumed by the engine at the end of the frame.
+//!
+//! ## Out of bounds drawing
+//!
+//! Every drawing function accepts any coordinates, including the extremes of `i16`, infinite or NaN floats,
+//! and negative sizes. Whatever falls outside of the screen is silently dropped, it never panics.
 
 use crate::{
-    cell::CellFormat,
-    color::Color,
+    asset::TextArt,
+    cell::{Cell, CellFormat},
+    color::{Color, PathGradient, sample_path_gradient, sample_path_gradient_at_vertex},
     engine::Engine,
+    font::{
+        BLOCK_FONT, BLOCK_GLYPH_SIZE, MINI_FONT, MINI_FONT_KERNING_PAIRS, MINI_GLYPH_HEIGHT,
+        MiniGlyph,
+    },
     fps_counter::get_fps,
-    frame::DrawCall,
-    layer::LayerIndex,
+    frame::{DrawCall, compose_layer_area, compose_queued_layers, compose_queued_layers_beneath},
+    grapheme::{for_each_grapheme_range, push_grapheme, text_grapheme_width},
+    hex::{HexLayout, HexPosition},
+    layer::{LayerIndex, layer_camera},
+    link::LinkId,
+    render_target::RenderTarget,
     rich_text::{Attributes, RichText},
+    sprite::Sprite,
+};
+use std::{
+    ops::{Index, Range},
+    sync::Arc,
 };
 
 #[rustfmt::skip]
@@ -61,6 +81,39 @@ pub(crate) static BLOCKTAD_CHAR_LUT: [char; 256] = [
     '▄', '𜷛', '𜷜', '𜷝', '𜷞', '▙', '𜷟', '𜷠', '𜷡', '𜷢', '▟', '𜷣', '▆', '𜷤', '𜷥', '█',
 ];
 
+#[rustfmt::skip]
+pub(crate) static SEXTANT_CHAR_LUT: [char; 64] = [
+    ' ', '🬀', '🬁', '🬂', '🬃', '🬄', '🬅', '🬆', '🬇', '🬈', '🬉', '🬊', '🬋', '🬌', '🬍', '🬎',
+    '🬏', '🬐', '🬑', '🬒', '🬓', '▌', '🬔', '🬕', '🬖', '🬗', '🬘', '🬙', '🬚', '🬛', '🬜', '🬝',
+    '🬞', '🬟', '🬠', '🬡', '🬢', '🬣', '🬤', '🬥', '🬦', '🬧', '▐', '🬨', '🬩', '🬪', '🬫', '🬬',
+    '🬭', '🬮', '🬯', '🬰', '🬱', '🬲', '🬳', '🬴', '🬵', '🬶', '🬷', '🬸', '🬹', '🬺', '🬻', '█',
+];
+
+#[rustfmt::skip]
+pub(crate) static QUADRANT_CHAR_LUT: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+/// The character displaying the sub-cells of a `mask`, see [`Cell::format_data`].
+#[inline]
+pub(crate) fn sub_cell_char(format: CellFormat, mask: u8) -> char {
+    match format {
+        CellFormat::Octad => char::from_u32(0x2800 + mask as u32).unwrap_or(' '),
+        CellFormat::Blocktad => BLOCKTAD_CHAR_LUT[mask as usize],
+        CellFormat::Sextant => SEXTANT_CHAR_LUT[mask as usize & 0x3F],
+        CellFormat::Quadrant => QUADRANT_CHAR_LUT[mask as usize & 0x0F],
+        CellFormat::Standard | CellFormat::Twoxel => ' ',
+    }
+}
+
+/// A draw call filling the sub-cells of a `mask` of a single cell.
+#[inline]
+fn sub_cell_rich_text(format: CellFormat, mask: u8) -> RichText {
+    RichText::new(sub_cell_char(format, mask).to_string())
+        .with_cell_format(format)
+        .with_format_data(mask)
+}
+
 /// Draws text at the given coordinates.
 ///
 /// Accepts either a `&str` or `String` or `RichText`.
@@ -79,10 +132,118 @@ pub fn draw_text(
     y: i16,
     text: impl Into<RichText>,
 ) {
-    let layer = &mut engine.frame.layered_draw_queue[layer_index.0];
     let rich_text: RichText = text.into();
+    push_draw_call(engine, layer_index, DrawCall { rich_text, x, y });
+}
+
+/// Draws spans one after another on a single row, starting at the given coordinates.
+///
+/// Each span starts where the previous one ended, taking wide characters into account.
+/// Pairs well with the spans produced by [`crate::rich_text::parse_markup`].
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_spans, layer::create_layer, engine::Engine, rich_text::parse_markup_lossy};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// draw_spans(&mut engine, layer, 2, 1, parse_markup_lossy("[bold red]error:[/] disk full"));
+/// ```
+pub fn draw_spans(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    x: i16,
+    y: i16,
+    spans: impl IntoIterator<Item = RichText>,
+) {
+    let mut x: i16 = x;
+    for rich_text in spans {
+        let width: usize = text_width(&rich_text.text);
+
+        let next_x: i16 = x.saturating_add(width.min(i16::MAX as usize) as i16);
+        push_draw_call(engine, layer_index, DrawCall { rich_text, x, y });
+        x = next_x;
+    }
+}
+
+/// How [`draw_text_wrapped`] breaks text into lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WrapMode {
+    /// Lines are broken at exactly the maximum width, even in the middle of a word.
+    Character,
+    /// Lines are broken at spaces, only breaking words that don't fit on a line of their own.
+    Word,
+}
+
+/// Horizontal alignment of the lines drawn by [`draw_text_wrapped`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextAlign {
+    Left,
+    Center,
+    Right,
+}
+
+/// Draws text wrapped to fit within `max_width` columns, returning the number of rows drawn.
+///
+/// Lines are broken according to `wrap` and aligned within `max_width` according to `align`.
+/// Widths are measured in terminal columns, so wide characters like CJK take up two columns.
+/// Newlines in the text always start a new line.
+/// Spaces at the points where a line was wrapped are swallowed.
+///
+/// The returned row count can be used to stack content underneath.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::{draw_text_wrapped, TextAlign, WrapMode}, layer::create_layer, engine::Engine};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// let rows = draw_text_wrapped(
+///     &mut engine,
+///     layer,
+///     (2, 1),
+///     16,
+///     "The quick brown fox jumps over the lazy dog",
+///     WrapMode::Word,
+///     TextAlign::Center,
+/// );
+/// draw_text_wrapped(&mut engine, layer, (2, 1 + rows as i16), 16, "- Someone", WrapMode::Word, TextAlign::Right);
+/// ```
+pub fn draw_text_wrapped(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    pos: (i16, i16),
+    max_width: u16,
+    text: impl Into<RichText>,
+    wrap: WrapMode,
+    align: TextAlign,
+) -> u16 {
+    if max_width == 0 {
+        return 0;
+    }
+
+    let rich_text: RichText = text.into();
+    let lines: Vec<&str> = wrap_text(&rich_text.text, max_width as usize, wrap);
+
+    for (row, line) in lines.iter().enumerate() {
+        let line_width: u16 = text_width(line).min(max_width as usize) as u16;
+        let x_offset: u16 = match align {
+            TextAlign::Left => 0,
+            TextAlign::Center => (max_width - line_width) / 2,
+            TextAlign::Right => max_width - line_width,
+        };
 
-    layer.0.push(DrawCall { rich_text, x, y });
+        let draw_call: DrawCall = DrawCall {
+            rich_text: RichText {
+                text: Arc::new(line.to_string()),
+                ..rich_text.clone()
+            },
+            x: pos.0.saturating_add(x_offset as i16),
+            y: pos.1.saturating_add(row as i16),
+        };
+        push_draw_call(engine, layer_index, draw_call);
+    }
+
+    lines.len() as u16
 }
 
 /// Fills the entire screen with the specified [`Color`].
@@ -95,217 +256,2183 @@ pub fn draw_text(
 /// fill_screen(&mut engine, layer, Color::PINK);
 /// ```
 pub fn fill_screen(engine: &mut Engine, layer_index: LayerIndex, color: Color) {
-    let width: i16 = engine.frame.width as i16;
-    let height: i16 = engine.frame.height as i16;
+    let (min_x, min_y, end_x, end_y) = visible_area(engine, layer_index, 1, 1);
 
-    draw_rect(engine, layer_index, 0, 0, width, height, color);
+    draw_rect_area(
+        engine,
+        layer_index,
+        (min_x, min_y),
+        (end_x - min_x, end_y - min_y),
+        color,
+    );
 }
 
 /// Erases a rect area, restoring the default bg color and deleting the characters.
 ///
+/// Follows the same size rules as [`draw_rect`].
+///
 /// # Example
 /// ```rust,no_run
 /// # use germterm::{draw::erase_rect, layer::create_layer, engine::Engine};
 /// let mut engine = Engine::new(40, 20);
 /// let layer = create_layer(&mut engine, 0);
-/// erase_rect(&mut engine, layer, 2, 2, 6, 3);
+/// erase_rect(&mut engine, layer, (2, 2), (6, 3));
 /// ```
-pub fn erase_rect(
-    engine: &mut Engine,
-    layer_index: LayerIndex,
-    x: i16,
-    y: i16,
-    width: i16,
-    height: i16,
-) {
-    let row_text: String = " ".repeat(width as usize);
-    let row_rich_text = RichText::new(row_text)
+pub fn erase_rect(engine: &mut Engine, layer_index: LayerIndex, pos: (i16, i16), size: (i16, i16)) {
+    let Some((x, rows, cols)) = clip_rect(
+        engine,
+        layer_index,
+        (pos.0 as i32, pos.1 as i32),
+        (size.0 as i32, size.1 as i32),
+    ) else {
+        return;
+    };
+
+    let row_rich_text = RichText::new(" ".repeat(cols))
         .with_fg(Color::CLEAR)
         .with_bg(Color::CLEAR)
         .with_attributes(Attributes::NO_FG_COLOR | Attributes::NO_BG_COLOR);
-
-    for row in 0..height {
-        draw_text(engine, layer_index, x, y + row, row_rich_text.clone())
+    for row in rows {
+        draw_text(engine, layer_index, x, row, row_rich_text.clone())
     }
 }
 
 /// Draws a filled rect area with the specified [`Color`].
 ///
+/// The rect spans from `pos` to `pos + size`, so negative sizes extend it
+/// left and up from `pos` instead, without covering the column and row of `pos` itself.
+/// Nothing is drawn when either side of `size` is zero.
+///
+/// Every function taking a rect takes it as a `pos` and `size` following these rules,
+/// only [`draw_rect_corners`] takes two corners instead.
+///
 /// # Example
 /// ```rust,no_run
 /// # use germterm::{draw::draw_rect, layer::create_layer, engine::Engine, color::Color};
 /// let mut engine = Engine::new(40, 20);
 /// let layer = create_layer(&mut engine, 0);
-/// draw_rect(&mut engine, layer, 10, 5, 20, 10, Color::CYAN);
+/// draw_rect(&mut engine, layer, (10, 5), (20, 10), Color::CYAN);
+///
+/// // Covers the same cells
+/// draw_rect(&mut engine, layer, (30, 15), (-20, -10), Color::CYAN);
 /// ```
 pub fn draw_rect(
     engine: &mut Engine,
     layer_index: LayerIndex,
-    x: i16,
-    y: i16,
-    width: i16,
-    height: i16,
+    pos: (i16, i16),
+    size: (i16, i16),
     color: Color,
 ) {
-    let row_text: String = " ".repeat(width as usize);
-    let row_rich_text: RichText = RichText::new(&row_text)
-        .with_fg(Color::CLEAR)
-        .with_bg(color)
-        .with_attributes(Attributes::NO_FG_COLOR);
+    draw_rect_area(
+        engine,
+        layer_index,
+        (pos.0 as i32, pos.1 as i32),
+        (size.0 as i32, size.1 as i32),
+        color,
+    );
+}
+
+/// Draws a filled rect area with the specified [`Color`], spanning between two corner cells given in any order.
+///
+/// Both corner cells are covered, so equal corners draw a single cell.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_rect_corners, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// // A selection box dragged up and to the left
+/// let drag_start: (i16, i16) = (30, 12);
+/// let drag_current: (i16, i16) = (8, 3);
+/// draw_rect_corners(&mut engine, layer, drag_start, drag_current, Color::TEAL);
+/// ```
+pub fn draw_rect_corners(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    a: (i16, i16),
+    b: (i16, i16),
+    color: Color,
+) {
+    let min: (i32, i32) = (a.0.min(b.0) as i32, a.1.min(b.1) as i32);
+    let max: (i32, i32) = (a.0.max(b.0) as i32, a.1.max(b.1) as i32);
+
+    draw_rect_area(
+        engine,
+        layer_index,
+        min,
+        (max.0 - min.0 + 1, max.1 - min.1 + 1),
+        color,
+    );
+}
+
+/// The set of box-drawing characters used by [`draw_rect_outline`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RectStyle {
+    /// `+`, `-` and `|`, for terminals and fonts without box-drawing characters.
+    Ascii,
+    /// `┌`, `─` and `│`.
+    #[default]
+    Light,
+    /// `┏`, `━` and `┃`.
+    Heavy,
+    /// `╔`, `═` and `║`.
+    Double,
+    /// `╭`, `─` and `│`.
+    Rounded,
+}
 
-    for row in 0..height {
-        draw_text(engine, layer_index, x, y + row, row_rich_text.clone())
+impl RectStyle {
+    /// The chars of the style as `[top-left, top-right, bottom-left, bottom-right, horizontal, vertical, single]`.
+    const fn chars(self) -> [char; 7] {
+        match self {
+            RectStyle::Ascii => ['+', '+', '+', '+', '-', '|', '+'],
+            RectStyle::Light => ['┌', '┐', '└', '┘', '─', '│', '┼'],
+            RectStyle::Heavy => ['┏', '┓', '┗', '┛', '━', '┃', '╋'],
+            RectStyle::Double => ['╔', '╗', '╚', '╝', '═', '║', '╬'],
+            RectStyle::Rounded => ['╭', '╮', '╰', '╯', '─', '│', '┼'],
+        }
     }
 }
 
-/// Draws a single octad at the specified sub-cell position.
-///
-/// A single octad is represented by a single [braille dot character](https://en.wikipedia.org/wiki/Braille_Patterns)
-/// from the 8-dot set (⣿).
-/// The character will be drawn in one of the 8 possible sub-positions of a cell,
-/// based on the passed floating point coordinates.
+/// Draws the outline of a rect area with box-drawing characters, leaving the inside untouched.
 ///
-/// The coordinate space is based on cols and rows (`x` and `y`), just like the rest of the drawing API.
+/// The fg, bg, attributes and priority of the border cells are taken from `border`, its text is ignored.
+/// The rect spans from `pos` to `pos + size`, following the same size rules as [`draw_rect`].
 ///
-/// When drawing multiple octads to the same cell, at differing sub-positions, the octads will merge into a single multi-dot character.
-/// Merged octads possess a technical limitation of having to share the same `fg` color.
-/// Because of this, the entire merged octad cluster inherits the `fg` color of the last drawn octad in the cell.
+/// Rects only 1 cell tall are drawn as a horizontal line and rects only 1 cell wide as a vertical line,
+/// while a single cell is drawn as a cross (`+` or `┼`, `╋`, `╬`).
 ///
 /// # Example
 /// ```rust,no_run
-/// # use germterm::{draw::draw_octad, layer::create_layer, engine::Engine, color::Color};
+/// # use germterm::{draw::{RectStyle, draw_rect_outline}, layer::create_layer, engine::Engine, color::Color, rich_text::RichText};
 /// let mut engine = Engine::new(40, 20);
 /// let layer = create_layer(&mut engine, 0);
 ///
-/// // The following octads would occupy the same cell,
-/// // resulting in a merged octad cluster being drawn
-/// draw_octad(&mut engine, layer, 3.0, 4.0, Color::YELLOW);
-/// draw_octad(&mut engine, layer, 3.0, 4.5, Color::YELLOW);
+/// let border = RichText::new("").with_fg(Color::CYAN);
+/// draw_rect_outline(&mut engine, layer, (2, 1), (20, 8), RectStyle::Rounded, border);
 /// ```
-pub fn draw_octad(engine: &mut Engine, layer_index: LayerIndex, x: f32, y: f32, color: Color) {
-    let cell_x: i16 = x.floor() as i16;
-    let cell_y: i16 = y.floor() as i16;
+pub fn draw_rect_outline(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    pos: (i16, i16),
+    size: (i16, i16),
+    style: RectStyle,
+    border: impl Into<RichText>,
+) {
+    let border: RichText = border.into();
+    let (x, y, width, height) = (pos.0 as i32, pos.1 as i32, size.0 as i32, size.1 as i32);
+    let (first_col, last_col): (i32, i32) = (x.min(x + width), x.max(x + width) - 1);
+    let (first_row, last_row): (i32, i32) = (y.min(y + height), y.max(y + height) - 1);
+    let Some((clipped_x, rows, cols)) = clip_rect(engine, layer_index, (x, y), (width, height))
+    else {
+        return;
+    };
 
-    let sub_x: u8 = ((x - cell_x as f32) * 2.0).clamp(0.0, 1.0) as u8;
-    let sub_y_float: f32 = (y - cell_y as f32) * 4.0;
-    let sub_y: usize = sub_y_float.floor().clamp(0.0, 3.0) as usize;
-    let offset: usize = match (sub_x, sub_y) {
-        (0, 0) => 0,
-        (0, 1) => 1,
-        (0, 2) => 2,
-        (0, 3) => 6,
-        (1, 0) => 3,
-        (1, 1) => 4,
-        (1, 2) => 5,
-        (1, 3) => 7,
-        _ => panic!("Octad sub-position ({sub_x}, {sub_y}) falls out of range."),
+    let [
+        top_left,
+        top_right,
+        bottom_left,
+        bottom_right,
+        horizontal,
+        vertical,
+        single,
+    ] = style.chars();
+    let char_at = |col: i32, row: i32| -> char {
+        let is_edge_col: bool = col == first_col || col == last_col;
+        let is_edge_row: bool = row == first_row || row == last_row;
+        match (first_col == last_col, first_row == last_row) {
+            (true, true) => single,
+            (true, false) => vertical,
+            (false, true) => horizontal,
+            _ if !is_edge_row => vertical,
+            _ if !is_edge_col => horizontal,
+            _ => match (col == first_col, row == first_row) {
+                (true, true) => top_left,
+                (false, true) => top_right,
+                (true, false) => bottom_left,
+                (false, false) => bottom_right,
+            },
+        }
+    };
+    let side_cols: &[i32] = if first_col == last_col {
+        &[first_col]
+    } else {
+        &[first_col, last_col]
     };
 
-    let braille_char: char = std::char::from_u32(0x2800 + (1 << offset)).unwrap();
-    let rich_text: RichText = RichText::new(braille_char.to_string())
-        .with_fg(color)
-        .with_cell_format(CellFormat::Octad);
+    for row in rows {
+        let row_i32: i32 = row as i32;
+        let cells: Vec<(i32, String)> = if row_i32 == first_row || row_i32 == last_row {
+            let first_clipped_col: i32 = clipped_x as i32;
+            let row_text: String = (first_clipped_col..first_clipped_col + cols as i32)
+                .map(|col| char_at(col, row_i32))
+                .collect();
+            vec![(first_clipped_col, row_text)]
+        } else {
+            side_cols
+                .iter()
+                .map(|&col| (col, char_at(col, row_i32).to_string()))
+                .collect()
+        };
 
-    draw_text(engine, layer_index, cell_x, cell_y, rich_text);
+        for (col, text) in cells {
+            // Side columns outside of the screen are dropped when queued
+            let Ok(col) = i16::try_from(col) else {
+                continue;
+            };
+            let rich_text: RichText = RichText {
+                text: Arc::new(text),
+                ..border.clone()
+            };
+            draw_text(engine, layer_index, col, row, rich_text);
+        }
+    }
 }
 
-/// Draws a single blocktad at the specified sub-cell position.
+/// Draws the outline of a rect area made of twoxels, spanning between two corner positions given in any order.
 ///
-/// Blocktads are represented by the 2x4 square blocky characters from the
-/// [Symbols for Legacy Computing Supplement](https://en.wikipedia.org/wiki/Symbols_for_Legacy_Computing_Supplement) Unicode block.
-/// The character will be drawn in one of the 8 possible sub-positions of a cell,
-/// based on the passed floating point coordinates.
+/// Positions use the same floating point coordinate space as [`draw_twoxel`], and both corners are included,
+/// so the outline is a single twoxel thick. Useful for pixel-art UIs built from twoxels.
 ///
-/// The coordinate space is based on cols and rows (`x` and `y`), just like the rest of the drawing API.
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_rect_outline_twoxel, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// draw_rect_outline_twoxel(&mut engine, layer, (2.0, 1.5), (20.0, 8.0), Color::YELLOW);
+/// ```
+pub fn draw_rect_outline_twoxel(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    a: (f32, f32),
+    b: (f32, f32),
+    color: Color,
+) {
+    let corners: [(f32, f32); 4] = [a, (b.0, a.1), b, (a.0, b.1)];
+    for i in 0..corners.len() {
+        draw_line_twoxel(
+            engine,
+            layer_index,
+            corners[i],
+            corners[(i + 1) % corners.len()],
+            color,
+        );
+    }
+}
+
+/// Draws the drop shadow of a rect area, following the same size rules as [`draw_rect`].
 ///
-/// When drawing multiple blocktads to the same cell, at differing sub-positions, the blocktads will merge into a single character representing both.
-/// Merged blocktads possess a technical limitation of having to share the same `fg` color.
-/// Because of this, the entire merged blocktad cluster inherits the `fg` color of the last drawn blocktad in the cell.
+/// The shadow is the rect moved by `offset`, without the part covered by the rect itself,
+/// so every shadowed cell is drawn exactly once. A translucent `color` darkens the contents underneath.
+///
+/// With `soft` set, the outer edges of the shadow only cover the half of their cells closest to the rect,
+/// using half block characters, which replace the characters underneath while keeping their background.
 ///
 /// # Example
 /// ```rust,no_run
-/// # use germterm::{draw::draw_blocktad, layer::create_layer, engine::Engine, color::Color};
+/// # use germterm::{draw::{draw_rect, draw_shadow}, layer::create_layer, engine::Engine, color::Color};
 /// let mut engine = Engine::new(40, 20);
-/// let layer = create_layer(&mut engine, 0);
+/// let layer = create_layer(&mut engine, 1);
 ///
-/// // The following blocktads would occupy the same cell,
-/// // resulting in a merged blocktad cluster being drawn
-/// draw_blocktad(&mut engine, layer, 3.0, 4.0, Color::GREEN);
-/// draw_blocktad(&mut engine, layer, 3.0, 4.5, Color::GREEN);
+/// let (panel_pos, panel_size): ((i16, i16), (i16, i16)) = ((4, 2), (20, 8));
+/// draw_shadow(&mut engine, layer, panel_pos, panel_size, (2, 1), Color::BLACK.with_alpha(120), true);
+/// draw_rect(&mut engine, layer, panel_pos, panel_size, Color::DARK_GRAY);
 /// ```
-///
-/// /// # Notes
-/// The characters may not show up on all fonts, as the [Symbols for Legacy Computing Supplement](https://en.wikipedia.org/wiki/Symbols_for_Legacy_Computing_Supplement)
-/// Unicode block is a relatively recent addition. Use with caution.
-pub fn draw_blocktad(engine: &mut Engine, layer_index: LayerIndex, x: f32, y: f32, color: Color) {
-    let cell_x: i16 = x.floor() as i16;
-    let cell_y: i16 = y.floor() as i16;
+pub fn draw_shadow(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    pos: (i16, i16),
+    size: (i16, i16),
+    offset: (i16, i16),
+    color: Color,
+    soft: bool,
+) {
+    let (x, y, width, height) = (pos.0 as i32, pos.1 as i32, size.0 as i32, size.1 as i32);
+    let (rect_x, rect_y): (i32, i32) = (x.min(x + width), y.min(y + height));
+    let (width, height): (i32, i32) = (width.abs(), height.abs());
+    let (rect_end_x, rect_end_y): (i32, i32) = (rect_x + width, rect_y + height);
+    let (shadow_x, shadow_y): (i32, i32) = (rect_x + offset.0 as i32, rect_y + offset.1 as i32);
+    let (shadow_end_x, shadow_end_y): (i32, i32) = (shadow_x + width, shadow_y + height);
+    let Some((first_col, rows, cols)) =
+        clip_rect(engine, layer_index, (shadow_x, shadow_y), (width, height))
+    else {
+        return;
+    };
 
-    let sub_x: usize = (((x - cell_x as f32) * 2.0).floor().clamp(0.0, 1.0)) as usize;
-    let sub_y: usize = (((y - cell_y as f32) * 4.0).floor().clamp(0.0, 3.0)) as usize;
-    let offset: usize = sub_y * 2 + sub_x;
-    let mask: usize = 1 << offset;
+    // The outer edges, facing away from the rect
+    let outer_col: Option<i32> = match offset.0.signum() {
+        1 => Some(shadow_end_x - 1),
+        -1 => Some(shadow_x),
+        _ => None,
+    };
+    let outer_row: Option<i32> = match offset.1.signum() {
+        1 => Some(shadow_end_y - 1),
+        -1 => Some(shadow_y),
+        _ => None,
+    };
+    let half_block_at = |col: i32, row: i32| -> Option<char> {
+        if !soft {
+            return None;
+        }
+        match (Some(col) == outer_col, Some(row) == outer_row) {
+            (false, false) => None,
+            (true, false) if offset.0 > 0 => Some('▌'),
+            (true, false) => Some('▐'),
+            (false, true) if offset.1 > 0 => Some('▀'),
+            (false, true) => Some('▄'),
+            (true, true) => Some(match (offset.0 > 0, offset.1 > 0) {
+                (true, true) => '▘',
+                (false, true) => '▝',
+                (true, false) => '▖',
+                (false, false) => '▗',
+            }),
+        }
+    };
+    let is_covered_by_rect = |col: i32, row: i32| -> bool {
+        (rect_x..rect_end_x).contains(&col) && (rect_y..rect_end_y).contains(&row)
+    };
 
-    let blocktad_char: char = BLOCKTAD_CHAR_LUT[mask];
-    let rich_text: RichText = RichText::new(blocktad_char.to_string())
-        .with_fg(color)
-        .with_cell_format(CellFormat::Blocktad);
+    let first_col: i32 = first_col as i32;
+    let end_col: i32 = first_col + cols as i32;
+    for row in rows {
+        let row_i32: i32 = row as i32;
+        let mut run_start: Option<i32> = None;
 
-    draw_text(engine, layer_index, cell_x, cell_y, rich_text);
+        for col in first_col..=end_col {
+            let is_shadowed: bool = col < end_col && !is_covered_by_rect(col, row_i32);
+            let half_block: Option<char> = half_block_at(col, row_i32).filter(|_| is_shadowed);
+            let is_full: bool = is_shadowed && half_block.is_none();
+            match (is_full, run_start) {
+                (true, None) => run_start = Some(col),
+                (false, Some(start)) => {
+                    draw_rect_area(
+                        engine,
+                        layer_index,
+                        (start, row_i32),
+                        (col - start, 1),
+                        color,
+                    );
+                    run_start = None;
+                }
+                _ => {}
+            }
+
+            if let Some(half_block) = half_block {
+                let rich_text: RichText = RichText::new(half_block.to_string()).with_fg(color);
+                draw_text(engine, layer_index, col as i16, row, rich_text);
+            }
+        }
+    }
 }
 
-/// Draws a single twoxel at the specified sub-cell position.
+fn draw_rect_area(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    pos: (i32, i32),
+    size: (i32, i32),
+    color: Color,
+) {
+    let Some((x, rows, cols)) = clip_rect(engine, layer_index, pos, size) else {
+        return;
+    };
+
+    let row_rich_text: RichText = RichText::new(" ".repeat(cols))
+        .with_fg(Color::CLEAR)
+        .with_bg(color)
+        .with_attributes(Attributes::NO_FG_COLOR);
+    for row in rows {
+        draw_text(engine, layer_index, x, row, row_rich_text.clone())
+    }
+}
+
+/// Clips a rect spanning from `pos` to `pos + size` in either direction to the visible area of a layer.
 ///
-/// A single twoxel is represented by one of the half block characters (`▀` or `▄`) from the [Block Elements unicode block](https://en.wikipedia.org/wiki/Block_Elements).
+/// Returns the first column, the rows and the amount of columns of what's left, or `None` if nothing is.
+fn clip_rect(
+    engine: &Engine,
+    layer_index: LayerIndex,
+    pos: (i32, i32),
+    size: (i32, i32),
+) -> Option<(i16, Range<i16>, usize)> {
+    let (min_x, min_y, end_x, end_y) = visible_area(engine, layer_index, 1, 1);
+    let first_col: i32 = pos.0.min(pos.0 + size.0).max(min_x);
+    let end_col: i32 = pos.0.max(pos.0 + size.0).min(end_x);
+    let first_row: i32 = pos.1.min(pos.1 + size.1).max(min_y);
+    let end_row: i32 = pos.1.max(pos.1 + size.1).min(end_y);
+    if first_col >= end_col || first_row >= end_row {
+        return None;
+    }
+
+    // The visible area of a layer may reach just past the range of `i16` through its camera
+    let x: i16 = i16::try_from(first_col).ok()?;
+    let first_row: i16 = i16::try_from(first_row).ok()?;
+    let end_row: i16 = i16::try_from(end_row).unwrap_or(i16::MAX);
+    Some((x, first_row..end_row, (end_col - first_col) as usize))
+}
+
+/// Returns the cell at the given position as composed from everything drawn so far this frame,
+/// on the layers up to and including `layer_index`.
 ///
-/// /// The character will be drawn in one of the 2 possible vertical sub-positions of a cell,
-/// based on the passed floating point coordinates.
+/// Particles and floating texts are only drawn when the frame ends, so they aren't included.
+/// Returns `None` for positions outside of the screen.
 ///
-/// The coordinate space is based on cols and rows (`x` and `y`), just like the rest of the drawing API.
+/// Every call composes the whole frame so far into a scratch buffer, so it's best used sparingly.
+/// The queued draw calls are left untouched, the frame itself is still composed by [`crate::engine::end_frame`].
 ///
-/// When drawing a twoxel on top of an opposing twoxel occupying the same cell, both twoxels will be merged into the same cell.
-/// Merged twoxels display their color fully independently on one another within the same cell.
-/// This operation utilizes both the `fg` and `bg` channels, contrary to a single non-merged twoxel only utilizing the `fg` channel.
+/// # Example
+/// ```rust
+/// # use germterm::{draw::{draw_rect, get_composed_cell}, layer::create_layer, engine::{Engine, init}, color::Color};
+/// let mut engine = Engine::offscreen(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// init(&mut engine).unwrap();
+/// draw_rect(&mut engine, layer, (10, 5), (20, 10), Color::CYAN);
+///
+/// let cell = get_composed_cell(&engine, layer, 12, 6).unwrap();
+/// assert!(cell.bg == Color::CYAN);
+/// ```
+pub fn get_composed_cell(engine: &Engine, layer_index: LayerIndex, x: i16, y: i16) -> Option<Cell> {
+    let width: usize = engine.frame.width as usize;
+    let (x, y) = to_screen(engine, layer_index, x as i32, y as i32);
+    if !is_in_bounds(x, y, (0, 0, width as i32, engine.frame.height as i32)) {
+        return None;
+    }
+
+    let cells: Vec<Cell> = compose_queued_layers(
+        &engine.frame.layered_draw_queue,
+        layer_index.0,
+        engine.frame.width,
+        engine.frame.height,
+        engine.default_blending_color,
+    );
+    Some(cells[y as usize * width + x as usize])
+}
+
+/// Returns the color of the octad at the specified sub-cell position, as composed from everything drawn so far this frame
+/// on the layers up to and including `layer_index`, or `None` if there's no octad.
+///
+/// Merged octads share their `fg` color, so every octad of a cell has the same color.
+/// Comes with the same cost as [`get_composed_cell`].
 ///
 /// # Example
-/// ```rust,no_run
-/// # use germterm::{draw::draw_twoxel, layer::create_layer, engine::Engine, color::Color};
-/// let mut engine = Engine::new(40, 20);
+/// ```rust
+/// # use germterm::{draw::{draw_octad, get_composed_octad}, layer::create_layer, engine::{Engine, init}, color::Color};
+/// let mut engine = Engine::offscreen(40, 20);
 /// let layer = create_layer(&mut engine, 0);
+/// init(&mut engine).unwrap();
+/// draw_octad(&mut engine, layer, 3.0, 4.5, Color::YELLOW);
 ///
-/// // The following twoxels would occupy the same cell,
-/// // resulting in a merged twoxel with independent colors
+/// assert!(get_composed_octad(&engine, layer, 3.0, 4.5) == Some(Color::YELLOW));
+/// assert!(get_composed_octad(&engine, layer, 3.5, 4.5).is_none());
+/// ```
+pub fn get_composed_octad(
+    engine: &Engine,
+    layer_index: LayerIndex,
+    x: f32,
+    y: f32,
+) -> Option<Color> {
+    let (cell_x, cell_y, mask) = octad_at(x, y);
+    let cell: Cell = get_composed_cell(engine, layer_index, cell_x, cell_y)?;
+
+    let is_set: bool = cell.format == CellFormat::Octad && cell.format_data & mask != 0;
+    is_set.then_some(cell.fg)
+}
+
+/// Returns the color of the twoxel at the specified sub-cell position, as composed from everything drawn so far this frame
+/// on the layers up to and including `layer_index`, or `None` if there's nothing there.
+///
+/// Halves of a cell without a twoxel take on the background color of the cell, just like they're displayed.
+/// Comes with the same cost as [`get_composed_cell`].
+///
+/// # Example
+/// ```rust
+/// # use germterm::{draw::{draw_rect, draw_twoxel, get_composed_twoxel}, layer::create_layer, engine::{Engine, init}, color::Color};
+/// let mut engine = Engine::offscreen(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// init(&mut engine).unwrap();
+/// draw_rect(&mut engine, layer, (3, 4), (1, 1), Color::BLUE);
 /// draw_twoxel(&mut engine, layer, 3.0, 4.0, Color::RED);
-/// draw_twoxel(&mut engine, layer, 3.0, 4.5, Color::CYAN);
+///
+/// // The lower half shows the rect underneath
+/// assert!(get_composed_twoxel(&engine, layer, 3.0, 4.0).is_some());
+/// assert!(get_composed_twoxel(&engine, layer, 3.0, 4.5) == Some(Color::BLUE));
 /// ```
-pub fn draw_twoxel(engine: &mut Engine, layer_index: LayerIndex, x: f32, y: f32, color: Color) {
+pub fn get_composed_twoxel(
+    engine: &Engine,
+    layer_index: LayerIndex,
+    x: f32,
+    y: f32,
+) -> Option<Color> {
     let cell_x: i16 = x.floor() as i16;
     let cell_y: i16 = y.floor() as i16;
+    let is_upper: bool = (y - cell_y as f32) < 0.5;
+    let cell: Cell = get_composed_cell(engine, layer_index, cell_x, cell_y)?;
 
-    let sub_y_float: f32 = (y - cell_y as f32) * 2.0;
-    let sub_y: usize = sub_y_float.floor().clamp(0.0, 1.0) as usize;
+    let bg: Option<Color> = (!cell.attributes.contains(Attributes::NO_BG_COLOR)).then_some(cell.bg);
+    match (cell.format, cell.ch) {
+        (CellFormat::Twoxel, '▀') if is_upper => Some(cell.fg),
+        (CellFormat::Twoxel, '▄') if !is_upper => Some(cell.fg),
+        _ => bg,
+    }
+}
 
-    let half_block: char = match sub_y {
-        0 => '▀',
-        1 => '▄',
-        _ => panic!("Twoxel 'sub_y': {sub_y} falls out of range."),
-    };
-    let rich_text: RichText = RichText::new(half_block.to_string())
-        .with_fg(color)
-        .with_cell_format(CellFormat::Twoxel);
+/// Returns whether any octad lies within the rectangle spanned by the sub-cell positions `a` and `b`, corners included,
+/// as composed from everything drawn so far this frame on `layer_index` alone.
+///
+/// Useful for collision checks against eg. bricks drawn on a dedicated layer, where checking a whole sweep
+/// with [`get_composed_octad`] would compose the frame once per dot. Parts of the rectangle outside of the screen are ignored.
+/// Only the cells within the rectangle are composed, the other layers and the visibility of the layer don't matter.
+///
+/// # Example
+/// ```rust
+/// # use germterm::{draw::{any_octad_in_rect, draw_octad}, layer::create_layer, engine::{Engine, init}, color::Color};
+/// let mut engine = Engine::offscreen(40, 20);
+/// let bricks = create_layer(&mut engine, 0);
+/// init(&mut engine).unwrap();
+///
+/// // A brick 4 dots wide, in the top row of dots of the cells (5, 2) and (6, 2)
+/// for dot in 0..4 {
+///     draw_octad(&mut engine, bricks, 5.0 + dot as f32 * 0.5, 2.0, Color::ORANGE);
+/// }
+///
+/// assert!(any_octad_in_rect(&engine, bricks, (6.5, 2.0), (8.0, 3.0)));
+/// // The dot right below the brick is free
+/// assert!(!any_octad_in_rect(&engine, bricks, (6.0, 2.25), (6.0, 2.25)));
+/// ```
+pub fn any_octad_in_rect(
+    engine: &Engine,
+    layer_index: LayerIndex,
+    a: (f32, f32),
+    b: (f32, f32),
+) -> bool {
+    let (min_x, min_y, max_x, max_y) = visible_area(engine, layer_index, 2, 4);
+    let (a_x, a_y) = to_sub_cell_grid(a, 2.0, 4.0);
+    let (b_x, b_y) = to_sub_cell_grid(b, 2.0, 4.0);
+    let (start_x, end_x): (i32, i32) = (a_x.min(b_x).max(min_x), a_x.max(b_x).min(max_x - 1));
+    let (start_y, end_y): (i32, i32) = (a_y.min(b_y).max(min_y), a_y.max(b_y).min(max_y - 1));
+    if start_x > end_x || start_y > end_y {
+        return false;
+    }
 
-    draw_text(engine, layer_index, cell_x, cell_y, rich_text)
+    let origin: (i32, i32) = (start_x.div_euclid(2), start_y.div_euclid(4));
+    let cols: usize = (end_x.div_euclid(2) - origin.0 + 1) as usize;
+    let rows: usize = (end_y.div_euclid(4) - origin.1 + 1) as usize;
+    let cells: Vec<Cell> = compose_layer_area(
+        &engine.frame.layered_draw_queue[layer_index.0],
+        origin,
+        cols as u16,
+        rows as u16,
+        engine.default_blending_color,
+    );
+    (start_y..=end_y).any(|dot_y| {
+        (start_x..=end_x).any(|dot_x| {
+            let (col, row): (usize, usize) = (
+                (dot_x.div_euclid(2) - origin.0) as usize,
+                (dot_y.div_euclid(4) - origin.1) as usize,
+            );
+            let (dot_x, dot_y): (usize, usize) =
+                (dot_x.rem_euclid(2) as usize, dot_y.rem_euclid(4) as usize);
+            let cell: &Cell = &cells[row * cols + col];
+            let bit: u8 = 1 << octad_dot_offset(dot_x, dot_y);
+            cell.format == CellFormat::Octad && cell.format_data & bit != 0
+        })
+    })
 }
 
-/// Draws the current FPS.
+/// Fills the area of contiguous cells sharing the background color of the starting cell with the specified [`Color`].
 ///
-/// The retrieved value is an EMA (Exponential Moving Average).
+/// Cells are compared as composed from everything drawn so far this frame on the layers up to and including `layer_index`,
+/// see [`get_composed_cell`]. Cells are contiguous when they share an edge, the fill never leaves the screen.
 ///
-/// This is purely a convenience helper that draws with the default style.
-/// If you wish to display the FPS in a more stylized way, look into [`get_fps`].
+/// The area is filled using [`draw_rect`], so characters within it are covered just the same.
 ///
 /// # Example
-/// ```rust,no_run
+/// ```rust
+/// # use germterm::{draw::{draw_rect, erase_rect, flood_fill, get_composed_cell}, layer::create_layer, engine::{Engine, init}, color::Color};
+/// let mut engine = Engine::offscreen(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// init(&mut engine).unwrap();
+///
+/// // A bordered box, filled from the inside
+/// draw_rect(&mut engine, layer, (4, 2), (12, 8), Color::WHITE);
+/// erase_rect(&mut engine, layer, (5, 3), (10, 6));
+/// flood_fill(&mut engine, layer, 8, 5, Color::ORANGE);
+///
+/// assert!(get_composed_cell(&engine, layer, 5, 3).unwrap().bg == Color::ORANGE);
+/// assert!(get_composed_cell(&engine, layer, 4, 2).unwrap().bg == Color::WHITE);
+/// ```
+pub fn flood_fill(engine: &mut Engine, layer_index: LayerIndex, x: i16, y: i16, color: Color) {
+    let width: usize = engine.frame.width as usize;
+    let height: usize = engine.frame.height as usize;
+    let (x, y) = to_screen(engine, layer_index, x as i32, y as i32);
+    if !is_in_bounds(x, y, (0, 0, width as i32, height as i32)) {
+        return;
+    }
+    let (min_x, min_y, _, _) = visible_area(engine, layer_index, 1, 1);
+
+    let cells: Vec<Cell> = compose_queued_layers(
+        &engine.frame.layered_draw_queue,
+        layer_index.0,
+        engine.frame.width,
+        engine.frame.height,
+        engine.default_blending_color,
+    );
+    let bg_of = |cell: &Cell| -> Option<Color> {
+        (!cell.attributes.contains(Attributes::NO_BG_COLOR)).then_some(cell.bg)
+    };
+
+    let start: usize = y as usize * width + x as usize;
+    let target_bg: Option<Color> = bg_of(&cells[start]);
+    let filled: Vec<bool> = flood_grid(width, height, start, |i| bg_of(&cells[i]) == target_bg);
+
+    for (row, row_filled) in filled.chunks_exact(width).enumerate() {
+        let mut col: usize = 0;
+        while col < width {
+            if !row_filled[col] {
+                col += 1;
+                continue;
+            }
+            let run_start: usize = col;
+            while col < width && row_filled[col] {
+                col += 1;
+            }
+            draw_rect(
+                engine,
+                layer_index,
+                (
+                    (min_x + run_start as i32) as i16,
+                    (min_y + row as i32) as i16,
+                ),
+                ((col - run_start) as i16, 1),
+                color,
+            );
+        }
+    }
+}
+
+/// Draws a line of cells filled with the specified [`Color`] between two cell positions.
+///
+/// The line is rasterized using Bresenham's algorithm and includes both endpoints.
+/// Swapping `a` and `b` results in the exact same line.
+///
+/// Parts of the line that fall outside of the screen are clipped.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_line, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// draw_line(&mut engine, layer, (2, 2), (30, 12), Color::ORANGE);
+/// ```
+pub fn draw_line(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    a: (i16, i16),
+    b: (i16, i16),
+    color: Color,
+) {
+    let bounds: (i32, i32, i32, i32) = visible_area(engine, layer_index, 1, 1);
+    let cell_rich_text: RichText = RichText::new(" ")
+        .with_fg(Color::CLEAR)
+        .with_bg(color)
+        .with_attributes(Attributes::NO_FG_COLOR);
+
+    rasterize_line(
+        (a.0 as i32, a.1 as i32),
+        (b.0 as i32, b.1 as i32),
+        bounds,
+        |x, y| {
+            draw_text(
+                engine,
+                layer_index,
+                x as i16,
+                y as i16,
+                cell_rich_text.clone(),
+            )
+        },
+    );
+}
+
+/// Draws a line of twoxels between two sub-cell positions.
+///
+/// Positions use the same floating point coordinate space as [`draw_twoxel`].
+/// The line is rasterized on the twoxel grid (1 column and 2 rows per cell),
+/// so twoxels sharing a cell get merged just like when drawn individually.
+///
+/// Swapping `a` and `b` results in the exact same line.
+/// Parts of the line that fall outside of the screen are clipped.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_line_twoxel, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// draw_line_twoxel(&mut engine, layer, (2.0, 2.0), (30.0, 12.5), Color::CYAN);
+/// ```
+pub fn draw_line_twoxel(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    a: (f32, f32),
+    b: (f32, f32),
+    color: Color,
+) {
+    let bounds: (i32, i32, i32, i32) = visible_area(engine, layer_index, 1, 2);
+
+    rasterize_line(
+        to_sub_cell_grid(a, 1.0, 2.0),
+        to_sub_cell_grid(b, 1.0, 2.0),
+        bounds,
+        |x, y| draw_twoxel(engine, layer_index, x as f32, y as f32 * 0.5, color),
+    );
+}
+
+/// Draws the path of a [`PathGradient`] as a polyline of twoxels.
+///
+/// Every segment is rasterized like [`draw_line_twoxel`], with each twoxel colored by its distance along the path,
+/// so colors stay anchored to the path's geometry rather than its vertex count.
+/// Twoxels shared by consecutive segments are only drawn once.
+///
+/// Use [`crate::color::set_gradient_path`] to move the path, eg. every frame.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_path_twoxel, layer::create_layer, engine::Engine, color::{Color, ColorGradient, GradientStop, PathGradient, PathAnchor}};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// let gradient = ColorGradient::new(vec![
+///     GradientStop::new(0.0, Color::CYAN),
+///     GradientStop::new(1.0, Color::VIOLET),
+/// ]);
+/// let path = [(2.0, 2.0), (20.0, 2.0), (20.0, 10.5)];
+/// let path_gradient = PathGradient::new(&path, gradient, PathAnchor::Head, 30.0);
+/// draw_path_twoxel(&mut engine, layer, &path_gradient);
+/// ```
+pub fn draw_path_twoxel(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    path_gradient: &PathGradient,
+) {
+    let path: &[(f32, f32)] = path_gradient.path();
+    let bounds: (i32, i32, i32, i32) = visible_area(engine, layer_index, 1, 2);
+
+    if let [pos] = path {
+        let (x, y) = to_sub_cell_grid(*pos, 1.0, 2.0);
+        if is_in_bounds(x, y, bounds) {
+            let color: Color = sample_path_gradient_at_vertex(path_gradient, 0);
+            draw_twoxel(engine, layer_index, x as f32, y as f32 * 0.5, color);
+        }
+        return;
+    }
+
+    for (i, segment) in path.windows(2).enumerate() {
+        let a: (i32, i32) = to_sub_cell_grid(segment[0], 1.0, 2.0);
+        let b: (i32, i32) = to_sub_cell_grid(segment[1], 1.0, 2.0);
+        let start_distance: f32 = path_gradient.vertex_distance(i);
+        let end_distance: f32 = path_gradient.vertex_distance(i + 1);
+        // Twoxels are square once aspect-corrected, so grid distances map directly onto path distances
+        let grid_length: f32 = ((b.0 - a.0) as f32).hypot((b.1 - a.1) as f32);
+
+        rasterize_line(a, b, bounds, |x, y| {
+            if i > 0 && (x, y) == a {
+                return;
+            }
+
+            let t: f32 = if grid_length > 0.0 {
+                ((x - a.0) as f32).hypot((y - a.1) as f32) / grid_length
+            } else {
+                0.0
+            };
+            let distance: f32 = start_distance + (end_distance - start_distance) * t;
+            let color: Color = sample_path_gradient(path_gradient, distance);
+            draw_twoxel(engine, layer_index, x as f32, y as f32 * 0.5, color);
+        });
+    }
+}
+
+/// Draws a line of octads between two sub-cell positions.
+///
+/// Positions use the same floating point coordinate space as [`draw_octad`].
+/// The line is rasterized on the octad grid (2 columns and 4 rows per cell),
+/// so octads sharing a cell get merged into a single multi-dot character just like when drawn individually.
+///
+/// Swapping `a` and `b` results in the exact same line.
+/// Parts of the line that fall outside of the screen are clipped.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_line_octad, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// draw_line_octad(&mut engine, layer, (2.0, 2.0), (30.5, 12.25), Color::YELLOW);
+/// ```
+pub fn draw_line_octad(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    a: (f32, f32),
+    b: (f32, f32),
+    color: Color,
+) {
+    let bounds: (i32, i32, i32, i32) = visible_area(engine, layer_index, 2, 4);
+
+    rasterize_line(
+        to_sub_cell_grid(a, 2.0, 4.0),
+        to_sub_cell_grid(b, 2.0, 4.0),
+        bounds,
+        |x, y| draw_octad(engine, layer_index, x as f32 * 0.5, y as f32 * 0.25, color),
+    );
+}
+
+/// Fills every cell whose center lies within a hex with the specified [`Color`].
+///
+/// Uses the same hit-testing as [`HexLayout::hex_at`], so neighboring hexes tile without gaps or overlaps.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_hex_filled, hex::{HexLayout, HexPosition}, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// let layout = HexLayout::default();
+/// draw_hex_filled(&mut engine, layer, HexPosition::new(2, 1), &layout, Color::DARK_GREEN);
+/// ```
+pub fn draw_hex_filled(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    hex: HexPosition,
+    layout: &HexLayout,
+    color: Color,
+) {
+    let corners: [(f32, f32); 6] = layout.corners(hex);
+    let min_x: i32 = corners.iter().map(|c| c.0).fold(f32::MAX, f32::min).floor() as i32;
+    let max_x: i32 = corners.iter().map(|c| c.0).fold(f32::MIN, f32::max).ceil() as i32;
+    let min_y: i32 = corners.iter().map(|c| c.1).fold(f32::MAX, f32::min).floor() as i32;
+    let max_y: i32 = corners.iter().map(|c| c.1).fold(f32::MIN, f32::max).ceil() as i32;
+
+    let bounds: (i32, i32, i32, i32) = visible_area(engine, layer_index, 1, 1);
+    let min_x: i32 = min_x.max(bounds.0);
+    let max_x: i32 = max_x.min(bounds.2);
+    let min_y: i32 = min_y.max(bounds.1);
+    let max_y: i32 = max_y.min(bounds.3);
+
+    for y in min_y..max_y {
+        // Hexes are convex, so the covered cells of a row form a single run
+        let mut run_start: Option<i32> = None;
+        let mut run_end: i32 = min_x;
+
+        for x in min_x..max_x {
+            if layout.hex_at((x as f32 + 0.5, y as f32 + 0.5)) == hex {
+                run_start.get_or_insert(x);
+                run_end = x + 1;
+            }
+        }
+
+        if let Some(run_start) = run_start {
+            draw_rect(
+                engine,
+                layer_index,
+                (run_start as i16, y as i16),
+                ((run_end - run_start) as i16, 1),
+                color,
+            );
+        }
+    }
+}
+
+/// Draws the outline of a hex using octad lines between its corners.
+///
+/// Parts of the outline that fall outside of the screen are clipped.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_hex_outline, hex::{HexLayout, HexPosition}, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// let layout = HexLayout::default();
+/// for hex in HexPosition::new(3, 2).ring(1) {
+///     draw_hex_outline(&mut engine, layer, hex, &layout, Color::LIGHT_GRAY);
+/// }
+/// ```
+pub fn draw_hex_outline(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    hex: HexPosition,
+    layout: &HexLayout,
+    color: Color,
+) {
+    let corners: [(f32, f32); 6] = layout.corners(hex);
+
+    for i in 0..6 {
+        draw_line_octad(engine, layer_index, corners[i], corners[(i + 1) % 6], color);
+    }
+}
+
+/// Draws a circle outline made of octads.
+///
+/// `center` uses the same floating point coordinate space as [`draw_octad`],
+/// while `radius` is measured in octads. Since octads are roughly square on most terminals,
+/// the result looks round without having to compensate for the cell aspect ratio.
+///
+/// A `radius` of `0` draws a single octad, negative values draw nothing.
+/// Parts of the circle that fall outside of the screen are clipped.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_circle_octad, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// draw_circle_octad(&mut engine, layer, (20.0, 10.0), 12, Color::GREEN);
+/// ```
+pub fn draw_circle_octad(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    center: (f32, f32),
+    radius: i16,
+    color: Color,
+) {
+    draw_ellipse_octad(engine, layer_index, center, (radius, radius), color);
+}
+
+/// Draws a filled circle made of octads.
+///
+/// Works just like [`draw_circle_octad`], but fills the inside of the circle as well.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::fill_circle_octad, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// fill_circle_octad(&mut engine, layer, (20.0, 10.0), 12, Color::GREEN);
+/// ```
+pub fn fill_circle_octad(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    center: (f32, f32),
+    radius: i16,
+    color: Color,
+) {
+    fill_ellipse_octad(engine, layer_index, center, (radius, radius), color);
+}
+
+/// Draws an ellipse outline made of octads.
+///
+/// `radii` specifies the horizontal and vertical radius measured in octads.
+/// Otherwise it works just like [`draw_circle_octad`].
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_ellipse_octad, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// draw_ellipse_octad(&mut engine, layer, (20.0, 10.0), (30, 12), Color::GREEN);
+/// ```
+pub fn draw_ellipse_octad(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    center: (f32, f32),
+    radii: (i16, i16),
+    color: Color,
+) {
+    let bounds: (i32, i32, i32, i32) = visible_area(engine, layer_index, 2, 4);
+
+    rasterize_ellipse(
+        to_sub_cell_grid(center, 2.0, 4.0),
+        radii,
+        false,
+        bounds,
+        |x, y| draw_octad(engine, layer_index, x as f32 * 0.5, y as f32 * 0.25, color),
+    );
+}
+
+/// Draws a filled ellipse made of octads.
+///
+/// Works just like [`draw_ellipse_octad`], but fills the inside of the ellipse as well.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::fill_ellipse_octad, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// fill_ellipse_octad(&mut engine, layer, (20.0, 10.0), (30, 12), Color::GREEN);
+/// ```
+pub fn fill_ellipse_octad(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    center: (f32, f32),
+    radii: (i16, i16),
+    color: Color,
+) {
+    let bounds: (i32, i32, i32, i32) = visible_area(engine, layer_index, 2, 4);
+
+    rasterize_ellipse(
+        to_sub_cell_grid(center, 2.0, 4.0),
+        radii,
+        true,
+        bounds,
+        |x, y| draw_octad(engine, layer_index, x as f32 * 0.5, y as f32 * 0.25, color),
+    );
+}
+
+/// Draws a closed polygon outline made of octads, connecting every point to the next and the last one back to the first.
+///
+/// The coordinate space is based on cols and rows (`x` and `y`), just like the rest of the drawing API.
+/// Octads falling outside of the screen are skipped.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_polygon_octad, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// draw_polygon_octad(&mut engine, layer, &[(4.0, 2.0), (30.0, 6.0), (12.0, 16.0)], Color::CYAN);
+/// ```
+pub fn draw_polygon_octad(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    points: &[(f32, f32)],
+    color: Color,
+) {
+    let bounds: (i32, i32, i32, i32) = visible_area(engine, layer_index, 2, 4);
+
+    for (i, &a) in points.iter().enumerate() {
+        let b: (f32, f32) = points[(i + 1) % points.len()];
+        rasterize_line(
+            to_sub_cell_grid(a, 2.0, 4.0),
+            to_sub_cell_grid(b, 2.0, 4.0),
+            bounds,
+            |x, y| draw_octad(engine, layer_index, x as f32 * 0.5, y as f32 * 0.25, color),
+        );
+    }
+}
+
+/// Draws a filled polygon made of octads.
+///
+/// Every octad whose center lies inside of the polygon is drawn. Non-convex and self-intersecting polygons
+/// are filled following the even-odd rule, so overlapping parts of a self-intersecting polygon are left empty.
+/// Polygons with less than 3 points or without any area draw nothing.
+///
+/// The fill doesn't include the outline, combine it with [`draw_polygon_octad`] to get crisp edges.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::fill_polygon_octad, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// // An "L" shape
+/// let points = [(2.0, 2.0), (6.0, 2.0), (6.0, 12.0), (14.0, 12.0), (14.0, 16.0), (2.0, 16.0)];
+/// fill_polygon_octad(&mut engine, layer, &points, Color::ORANGE);
+/// ```
+pub fn fill_polygon_octad(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    points: &[(f32, f32)],
+    color: Color,
+) {
+    let bounds: (i32, i32, i32, i32) = visible_area(engine, layer_index, 2, 4);
+
+    rasterize_polygon(points, 2.0, 4.0, bounds, |x, y| {
+        draw_octad(engine, layer_index, x as f32 * 0.5, y as f32 * 0.25, color)
+    });
+}
+
+/// Draws a filled triangle made of octads.
+///
+/// Works just like [`fill_polygon_octad`] with 3 points.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::fill_triangle_octad, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// fill_triangle_octad(&mut engine, layer, (4.0, 2.0), (30.0, 6.0), (12.0, 16.0), Color::CYAN);
+/// ```
+pub fn fill_triangle_octad(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    a: (f32, f32),
+    b: (f32, f32),
+    c: (f32, f32),
+    color: Color,
+) {
+    fill_polygon_octad(engine, layer_index, &[a, b, c], color);
+}
+
+/// Fills the area of contiguous empty octad positions around the specified sub-cell position with octads.
+///
+/// The area is bounded by octads drawn so far this frame on the layers up to and including `layer_index`,
+/// and by the edges of the screen. Positions are contiguous when they share an edge, so diagonal gaps in an outline don't leak.
+/// Other characters don't bound the area, and get replaced by the octads just like with [`draw_octad`].
+/// Nothing is filled when the starting position already holds an octad.
+///
+/// # Example
+/// ```rust
+/// # use germterm::{draw::{draw_circle_octad, flood_fill_octad, get_composed_octad}, layer::create_layer, engine::{Engine, init}, color::Color};
+/// let mut engine = Engine::offscreen(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// init(&mut engine).unwrap();
+/// draw_circle_octad(&mut engine, layer, (20.0, 10.0), 12, Color::GREEN);
+/// flood_fill_octad(&mut engine, layer, 20.0, 10.0, Color::GREEN);
+///
+/// assert!(get_composed_octad(&engine, layer, 20.0, 10.0) == Some(Color::GREEN));
+/// assert!(get_composed_octad(&engine, layer, 0.0, 0.0).is_none());
+/// ```
+pub fn flood_fill_octad(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    x: f32,
+    y: f32,
+    color: Color,
+) {
+    let width: usize = engine.frame.width as usize;
+    let height: usize = engine.frame.height as usize;
+    let (min_x, min_y, _, _) = visible_area(engine, layer_index, 1, 1);
+    let (grid_x, grid_y) = to_sub_cell_grid((x, y), 2.0, 4.0);
+    let (grid_x, grid_y): (i32, i32) = (grid_x - min_x * 2, grid_y - min_y * 4);
+    if !is_in_bounds(grid_x, grid_y, (0, 0, width as i32 * 2, height as i32 * 4)) {
+        return;
+    }
+
+    let cells: Vec<Cell> = compose_queued_layers(
+        &engine.frame.layered_draw_queue,
+        layer_index.0,
+        engine.frame.width,
+        engine.frame.height,
+        engine.default_blending_color,
+    );
+    let dot_bit = |dot: usize| -> (usize, u8) {
+        let (dot_x, dot_y) = (dot % (width * 2), dot / (width * 2));
+        let cell_index: usize = (dot_y / 4) * width + dot_x / 2;
+        (cell_index, 1 << octad_dot_offset(dot_x % 2, dot_y % 4))
+    };
+    let is_dot_set = |dot: usize| -> bool {
+        let (cell_index, bit) = dot_bit(dot);
+        let cell: &Cell = &cells[cell_index];
+        cell.format == CellFormat::Octad && cell.format_data & bit != 0
+    };
+
+    let start: usize = grid_y as usize * width * 2 + grid_x as usize;
+    if is_dot_set(start) {
+        return;
+    }
+    let filled: Vec<bool> = flood_grid(width * 2, height * 4, start, |dot| !is_dot_set(dot));
+
+    // Dots are gathered per cell, so each cell takes a single draw call
+    let mut masks: Vec<u8> = vec![0; width * height];
+    for dot in (0..filled.len()).filter(|&dot| filled[dot]) {
+        let (cell_index, bit) = dot_bit(dot);
+        masks[cell_index] |= bit;
+    }
+    for (cell_index, &mask) in masks.iter().enumerate() {
+        if mask == 0 {
+            continue;
+        }
+        let rich_text: RichText = sub_cell_rich_text(CellFormat::Octad, mask).with_fg(color);
+        draw_text(
+            engine,
+            layer_index,
+            (min_x + (cell_index % width) as i32) as i16,
+            (min_y + (cell_index / width) as i32) as i16,
+            rich_text,
+        );
+    }
+}
+
+/// Draws a circle outline made of blocktads.
+///
+/// Blocktads share the octad sub-cell grid, so this works just like [`draw_circle_octad`].
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_circle_blocktad, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// draw_circle_blocktad(&mut engine, layer, (20.0, 10.0), 12, Color::GREEN);
+/// ```
+pub fn draw_circle_blocktad(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    center: (f32, f32),
+    radius: i16,
+    color: Color,
+) {
+    let bounds: (i32, i32, i32, i32) = visible_area(engine, layer_index, 2, 4);
+
+    rasterize_ellipse(
+        to_sub_cell_grid(center, 2.0, 4.0),
+        (radius, radius),
+        false,
+        bounds,
+        |x, y| draw_blocktad(engine, layer_index, x as f32 * 0.5, y as f32 * 0.25, color),
+    );
+}
+
+/// Draws a filled circle made of blocktads.
+///
+/// Blocktads share the octad sub-cell grid, so this works just like [`fill_circle_octad`].
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::fill_circle_blocktad, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// fill_circle_blocktad(&mut engine, layer, (20.0, 10.0), 12, Color::GREEN);
+/// ```
+pub fn fill_circle_blocktad(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    center: (f32, f32),
+    radius: i16,
+    color: Color,
+) {
+    let bounds: (i32, i32, i32, i32) = visible_area(engine, layer_index, 2, 4);
+
+    rasterize_ellipse(
+        to_sub_cell_grid(center, 2.0, 4.0),
+        (radius, radius),
+        true,
+        bounds,
+        |x, y| draw_blocktad(engine, layer_index, x as f32 * 0.5, y as f32 * 0.25, color),
+    );
+}
+
+/// Draws a single octad at the specified sub-cell position.
+///
+/// A single octad is represented by a single [braille dot character](https://en.wikipedia.org/wiki/Braille_Patterns)
+/// from the 8-dot set (⣿).
+/// The character will be drawn in one of the 8 possible sub-positions of a cell,
+/// based on the passed floating point coordinates.
+///
+/// The coordinate space is based on cols and rows (`x` and `y`), just like the rest of the drawing API.
+///
+/// When drawing multiple octads to the same cell, at differing sub-positions, the octads will merge into a single multi-dot character.
+/// Merged octads possess a technical limitation of having to share the same `fg` color.
+/// Because of this, the entire merged octad cluster inherits the `fg` color of the last drawn octad in the cell.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_octad, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// // The following octads would occupy the same cell,
+/// // resulting in a merged octad cluster being drawn
+/// draw_octad(&mut engine, layer, 3.0, 4.0, Color::YELLOW);
+/// draw_octad(&mut engine, layer, 3.0, 4.5, Color::YELLOW);
+/// ```
+pub fn draw_octad(engine: &mut Engine, layer_index: LayerIndex, x: f32, y: f32, color: Color) {
+    draw_octad_with_priority(engine, layer_index, x, y, color, RichText::DEFAULT_PRIORITY);
+}
+
+pub(crate) fn draw_octad_with_priority(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    x: f32,
+    y: f32,
+    color: Color,
+    priority: u8,
+) {
+    let (cell_x, cell_y, mask) = octad_at(x, y);
+    let rich_text: RichText = sub_cell_rich_text(CellFormat::Octad, mask)
+        .with_fg(color)
+        .with_priority(priority);
+
+    draw_text(engine, layer_index, cell_x, cell_y, rich_text);
+}
+
+/// Erases a single octad at the specified sub-cell position, leaving the other octads of the cell in place.
+///
+/// Only octads already composed underneath are erased, ie. ones drawn earlier on the same layer or on lower layers.
+/// Once the last octad of a cell is erased, the cell is left without a character and `fg` color, keeping its `bg`.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::{draw_octad, erase_octad}, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// draw_octad(&mut engine, layer, 3.0, 4.0, Color::YELLOW);
+/// draw_octad(&mut engine, layer, 3.0, 4.5, Color::YELLOW);
+///
+/// // Only the dot at (3.0, 4.5) is left
+/// erase_octad(&mut engine, layer, 3.0, 4.0);
+/// ```
+pub fn erase_octad(engine: &mut Engine, layer_index: LayerIndex, x: f32, y: f32) {
+    let (cell_x, cell_y, mask) = octad_at(x, y);
+    let rich_text: RichText = sub_cell_rich_text(CellFormat::Octad, mask).erasing_sub_cells();
+
+    draw_text(engine, layer_index, cell_x, cell_y, rich_text);
+}
+
+/// The cell and the single dot mask of the octad at a sub-cell position.
+fn octad_at(x: f32, y: f32) -> (i16, i16, u8) {
+    let cell_x: i16 = x.floor() as i16;
+    let cell_y: i16 = y.floor() as i16;
+
+    let sub_x: usize = ((x - cell_x as f32) * 2.0).clamp(0.0, 1.0) as usize;
+    let sub_y_float: f32 = (y - cell_y as f32) * 4.0;
+    let sub_y: usize = sub_y_float.floor().clamp(0.0, 3.0) as usize;
+    let offset: usize = octad_dot_offset(sub_x, sub_y);
+
+    (cell_x, cell_y, 1 << offset)
+}
+
+/// The bit of a braille char's dot mask which represents a sub-cell position.
+#[inline]
+pub(crate) fn octad_dot_offset(sub_x: usize, sub_y: usize) -> usize {
+    match (sub_x, sub_y) {
+        (0, 0) => 0,
+        (0, 1) => 1,
+        (0, 2) => 2,
+        (0, 3) => 6,
+        (1, 0) => 3,
+        (1, 1) => 4,
+        (1, 2) => 5,
+        (1, 3) => 7,
+        _ => panic!("Octad sub-position ({sub_x}, {sub_y}) falls out of range."),
+    }
+}
+
+/// Draws a single blocktad at the specified sub-cell position.
+///
+/// Blocktads are represented by the 2x4 square blocky characters from the
+/// [Symbols for Legacy Computing Supplement](https://en.wikipedia.org/wiki/Symbols_for_Legacy_Computing_Supplement) Unicode block.
+/// The character will be drawn in one of the 8 possible sub-positions of a cell,
+/// based on the passed floating point coordinates.
+///
+/// The coordinate space is based on cols and rows (`x` and `y`), just like the rest of the drawing API.
+///
+/// When drawing multiple blocktads to the same cell, at differing sub-positions, the blocktads will merge into a single character representing both.
+/// Merged blocktads possess a technical limitation of having to share the same `fg` color.
+/// Because of this, the entire merged blocktad cluster inherits the `fg` color of the last drawn blocktad in the cell.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_blocktad, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// // The following blocktads would occupy the same cell,
+/// // resulting in a merged blocktad cluster being drawn
+/// draw_blocktad(&mut engine, layer, 3.0, 4.0, Color::GREEN);
+/// draw_blocktad(&mut engine, layer, 3.0, 4.5, Color::GREEN);
+/// ```
+///
+/// /// # Notes
+/// The characters may not show up on all fonts, as the [Symbols for Legacy Computing Supplement](https://en.wikipedia.org/wiki/Symbols_for_Legacy_Computing_Supplement)
+/// Unicode block is a relatively recent addition. Use with caution.
+pub fn draw_blocktad(engine: &mut Engine, layer_index: LayerIndex, x: f32, y: f32, color: Color) {
+    let (cell_x, cell_y, mask) = blocktad_at(x, y);
+    let rich_text: RichText = sub_cell_rich_text(CellFormat::Blocktad, mask).with_fg(color);
+
+    draw_text(engine, layer_index, cell_x, cell_y, rich_text);
+}
+
+/// Erases a single blocktad at the specified sub-cell position, leaving the other blocktads of the cell in place.
+///
+/// Behaves just like [`erase_octad`] does for octads.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::{draw_blocktad, erase_blocktad}, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// draw_blocktad(&mut engine, layer, 3.0, 4.0, Color::GREEN);
+/// draw_blocktad(&mut engine, layer, 3.0, 4.5, Color::GREEN);
+///
+/// // Only the block at (3.0, 4.5) is left
+/// erase_blocktad(&mut engine, layer, 3.0, 4.0);
+/// ```
+pub fn erase_blocktad(engine: &mut Engine, layer_index: LayerIndex, x: f32, y: f32) {
+    let (cell_x, cell_y, mask) = blocktad_at(x, y);
+    let rich_text: RichText = sub_cell_rich_text(CellFormat::Blocktad, mask).erasing_sub_cells();
+
+    draw_text(engine, layer_index, cell_x, cell_y, rich_text);
+}
+
+/// The cell and the single block mask of the blocktad at a sub-cell position.
+fn blocktad_at(x: f32, y: f32) -> (i16, i16, u8) {
+    let cell_x: i16 = x.floor() as i16;
+    let cell_y: i16 = y.floor() as i16;
+
+    let sub_x: usize = (((x - cell_x as f32) * 2.0).floor().clamp(0.0, 1.0)) as usize;
+    let sub_y: usize = (((y - cell_y as f32) * 4.0).floor().clamp(0.0, 3.0)) as usize;
+    let offset: usize = sub_y * 2 + sub_x;
+
+    (cell_x, cell_y, 1 << offset)
+}
+
+/// Draws a single sextant at the specified sub-cell position.
+///
+/// Sextants are represented by the 2x3 block characters from the
+/// [Symbols for Legacy Computing](https://en.wikipedia.org/wiki/Symbols_for_Legacy_Computing) Unicode block.
+/// The character will be drawn in one of the 6 possible sub-positions of a cell,
+/// based on the passed floating point coordinates.
+///
+/// The coordinate space is based on cols and rows (`x` and `y`), just like the rest of the drawing API.
+///
+/// Sextants merge just like blocktads do, with the merged cluster inheriting the `fg` color of the last drawn sextant in the cell.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_sextant, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// // The following sextants would occupy the same cell,
+/// // resulting in a merged sextant cluster being drawn
+/// draw_sextant(&mut engine, layer, 3.0, 4.0, Color::GREEN);
+/// draw_sextant(&mut engine, layer, 3.5, 4.5, Color::GREEN);
+/// ```
+///
+/// # Notes
+/// The characters may not show up on all fonts, as the [Symbols for Legacy Computing](https://en.wikipedia.org/wiki/Symbols_for_Legacy_Computing)
+/// Unicode block is a relatively recent addition, albeit with wider support than blocktads.
+pub fn draw_sextant(engine: &mut Engine, layer_index: LayerIndex, x: f32, y: f32, color: Color) {
+    let (cell_x, cell_y, mask) = sextant_at(x, y);
+    let rich_text: RichText = sub_cell_rich_text(CellFormat::Sextant, mask).with_fg(color);
+
+    draw_text(engine, layer_index, cell_x, cell_y, rich_text);
+}
+
+/// Erases a single sextant at the specified sub-cell position, leaving the other sextants of the cell in place.
+///
+/// Behaves just like [`erase_octad`] does for octads.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::{draw_sextant, erase_sextant}, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// draw_sextant(&mut engine, layer, 3.0, 4.0, Color::GREEN);
+/// draw_sextant(&mut engine, layer, 3.0, 4.5, Color::GREEN);
+///
+/// // Only the block at (3.0, 4.5) is left
+/// erase_sextant(&mut engine, layer, 3.0, 4.0);
+/// ```
+pub fn erase_sextant(engine: &mut Engine, layer_index: LayerIndex, x: f32, y: f32) {
+    let (cell_x, cell_y, mask) = sextant_at(x, y);
+    let rich_text: RichText = sub_cell_rich_text(CellFormat::Sextant, mask).erasing_sub_cells();
+
+    draw_text(engine, layer_index, cell_x, cell_y, rich_text);
+}
+
+/// The cell and the single block mask of the sextant at a sub-cell position.
+fn sextant_at(x: f32, y: f32) -> (i16, i16, u8) {
+    let cell_x: i16 = x.floor() as i16;
+    let cell_y: i16 = y.floor() as i16;
+
+    let sub_x: usize = (((x - cell_x as f32) * 2.0).floor().clamp(0.0, 1.0)) as usize;
+    let sub_y: usize = (((y - cell_y as f32) * 3.0).floor().clamp(0.0, 2.0)) as usize;
+    let offset: usize = sub_y * 2 + sub_x;
+
+    (cell_x, cell_y, 1 << offset)
+}
+
+/// Draws a single quadrant at the specified sub-cell position.
+///
+/// Quadrants are represented by the 2x2 quadrant characters from the [Block Elements unicode block](https://en.wikipedia.org/wiki/Block_Elements),
+/// which show up on virtually every font, unlike blocktads and sextants.
+/// The character will be drawn in one of the 4 possible sub-positions of a cell,
+/// based on the passed floating point coordinates.
+///
+/// The coordinate space is based on cols and rows (`x` and `y`), just like the rest of the drawing API.
+///
+/// Quadrants merge just like blocktads do, with the merged cluster inheriting the `fg` color of the last drawn quadrant in the cell.
+///
+/// # Example
+/// ```rust
+/// # use germterm::{draw::draw_quadrant, layer::create_layer, engine::{Engine, end_frame, init, start_frame}, color::Color};
+/// # use germterm::screenshot::{ScreenshotFormat, screenshot};
+/// let mut engine = Engine::offscreen(17, 1);
+/// let layer = create_layer(&mut engine, 0);
+/// init(&mut engine).unwrap();
+/// start_frame(&mut engine);
+///
+/// // The diagonal quadrants merge into a single '▚'
+/// draw_quadrant(&mut engine, layer, 0.0, 0.0, Color::GREEN);
+/// draw_quadrant(&mut engine, layer, 0.5, 0.5, Color::GREEN);
+/// # for mask in 0..16 {
+/// #     for (bit, (sub_x, sub_y)) in [(0.0, 0.0), (0.5, 0.0), (0.0, 0.5), (0.5, 0.5)].into_iter().enumerate() {
+/// #         if mask & (1 << bit) != 0 {
+/// #             draw_quadrant(&mut engine, layer, 1.0 + mask as f32 + sub_x, sub_y, Color::GREEN);
+/// #         }
+/// #     }
+/// # }
+///
+/// end_frame(&mut engine).unwrap();
+/// assert!(screenshot(&engine, ScreenshotFormat::PlainText).starts_with('▚'));
+/// # assert_eq!(screenshot(&engine, ScreenshotFormat::PlainText), "▚ ▘▝▀▖▌▞▛▗▚▐▜▄▙▟█\n");
+/// ```
+pub fn draw_quadrant(engine: &mut Engine, layer_index: LayerIndex, x: f32, y: f32, color: Color) {
+    let (cell_x, cell_y, mask) = quadrant_at(x, y);
+    let rich_text: RichText = sub_cell_rich_text(CellFormat::Quadrant, mask).with_fg(color);
+
+    draw_text(engine, layer_index, cell_x, cell_y, rich_text);
+}
+
+/// Erases a single quadrant at the specified sub-cell position, leaving the other quadrants of the cell in place.
+///
+/// Behaves just like [`erase_octad`] does for octads.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::{draw_quadrant, erase_quadrant}, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// draw_quadrant(&mut engine, layer, 3.0, 4.0, Color::GREEN);
+/// draw_quadrant(&mut engine, layer, 3.0, 4.5, Color::GREEN);
+///
+/// // Only the block at (3.0, 4.5) is left
+/// erase_quadrant(&mut engine, layer, 3.0, 4.0);
+/// ```
+pub fn erase_quadrant(engine: &mut Engine, layer_index: LayerIndex, x: f32, y: f32) {
+    let (cell_x, cell_y, mask) = quadrant_at(x, y);
+    let rich_text: RichText = sub_cell_rich_text(CellFormat::Quadrant, mask).erasing_sub_cells();
+
+    draw_text(engine, layer_index, cell_x, cell_y, rich_text);
+}
+
+/// The cell and the single block mask of the quadrant at a sub-cell position.
+fn quadrant_at(x: f32, y: f32) -> (i16, i16, u8) {
+    let cell_x: i16 = x.floor() as i16;
+    let cell_y: i16 = y.floor() as i16;
+
+    let sub_x: usize = (((x - cell_x as f32) * 2.0).floor().clamp(0.0, 1.0)) as usize;
+    let sub_y: usize = (((y - cell_y as f32) * 2.0).floor().clamp(0.0, 1.0)) as usize;
+    let offset: usize = sub_y * 2 + sub_x;
+
+    (cell_x, cell_y, 1 << offset)
+}
+
+/// Draws a single twoxel at the specified sub-cell position.
+///
+/// A single twoxel is represented by one of the half block characters (`▀` or `▄`) from the [Block Elements unicode block](https://en.wikipedia.org/wiki/Block_Elements).
+///
+/// /// The character will be drawn in one of the 2 possible vertical sub-positions of a cell,
+/// based on the passed floating point coordinates.
+///
+/// The coordinate space is based on cols and rows (`x` and `y`), just like the rest of the drawing API.
+///
+/// When drawing a twoxel on top of an opposing twoxel occupying the same cell, both twoxels will be merged into the same cell.
+/// Merged twoxels display their color fully independently on one another within the same cell.
+/// This operation utilizes both the `fg` and `bg` channels, contrary to a single non-merged twoxel only utilizing the `fg` channel.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_twoxel, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// // The following twoxels would occupy the same cell,
+/// // resulting in a merged twoxel with independent colors
+/// draw_twoxel(&mut engine, layer, 3.0, 4.0, Color::RED);
+/// draw_twoxel(&mut engine, layer, 3.0, 4.5, Color::CYAN);
+/// ```
+pub fn draw_twoxel(engine: &mut Engine, layer_index: LayerIndex, x: f32, y: f32, color: Color) {
+    let cell_x: i16 = x.floor() as i16;
+    let cell_y: i16 = y.floor() as i16;
+
+    let sub_y_float: f32 = (y - cell_y as f32) * 2.0;
+    let sub_y: usize = sub_y_float.floor().clamp(0.0, 1.0) as usize;
+
+    let half_block: char = match sub_y {
+        0 => '▀',
+        1 => '▄',
+        _ => panic!("Twoxel 'sub_y': {sub_y} falls out of range."),
+    };
+    let rich_text: RichText = RichText::new(half_block.to_string())
+        .with_fg(color)
+        .with_cell_format(CellFormat::Twoxel);
+
+    draw_text(engine, layer_index, cell_x, cell_y, rich_text)
+}
+
+/// Draws the last frame composed by an offscreen engine, with its top-left corner at the given coordinates.
+///
+/// `source` should be created with [`Engine::offscreen`] and have had its frame ended with [`crate::engine::end_frame`].
+///
+/// Cells without any visible content are skipped, letting the contents underneath show through.
+/// Cells without a background color keep the background underneath.
+/// Sub-cell characters (octads, twoxels, blocktads) are drawn as-is and don't merge with the contents underneath.
+///
+/// # Example
+/// ```rust
+/// # use germterm::{draw::{draw_engine_output, draw_text, get_composed_cell}, layer::create_layer, engine::{Engine, end_frame, init, start_frame}};
+/// # fn main() -> std::io::Result<()> {
+/// let mut engine = Engine::offscreen(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// // Two independent views, each with their own layers and particles
+/// let mut left = Engine::offscreen(20, 20);
+/// let left_layer = create_layer(&mut left, 0);
+/// let mut right = Engine::offscreen(20, 20);
+/// let right_layer = create_layer(&mut right, 0);
+///
+/// init(&mut engine)?;
+/// init(&mut left)?;
+/// init(&mut right)?;
+///
+/// start_frame(&mut left);
+/// draw_text(&mut left, left_layer, 1, 1, "Player 1");
+/// end_frame(&mut left)?;
+///
+/// start_frame(&mut right);
+/// draw_text(&mut right, right_layer, 1, 1, "Player 2");
+/// end_frame(&mut right)?;
+///
+/// start_frame(&mut engine);
+/// draw_engine_output(&mut engine, layer, 0, 0, &left);
+/// draw_engine_output(&mut engine, layer, 20, 0, &right);
+///
+/// let cell = get_composed_cell(&engine, layer, 21, 1);
+/// assert_eq!(cell.map(|cell| cell.ch), Some('P'));
+/// end_frame(&mut engine)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn draw_engine_output(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    x: i16,
+    y: i16,
+    source: &Engine,
+) {
+    let frame = source.frame.current();
+    draw_cell_grid(
+        engine,
+        layer_index,
+        x,
+        y,
+        &frame,
+        source.frame.width,
+        source.frame.height,
+    );
+}
+
+/// Draws the contents of a [`RenderTarget`], with its top-left corner at `pos`.
+///
+/// The contents were composed once by [`crate::render_target::redraw_render_target`], so drawing them
+/// only queues a draw call per run of equally styled cells, regardless of how many draw calls they were made of.
+/// The contents are drawn with the same rules as [`draw_engine_output`].
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::{draw_rect, draw_target}, layer::create_layer, engine::Engine, color::Color};
+/// # use germterm::render_target::{RenderTarget, redraw_render_target};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// let mut border = RenderTarget::new(40, 20);
+/// redraw_render_target(&mut border, |border_engine, border_layer| {
+///     draw_rect(border_engine, border_layer, (0, 0), (40, 20), Color::DARK_GRAY);
+///     draw_rect(border_engine, border_layer, (1, 1), (38, 18), Color::BLACK);
+/// });
+///
+/// draw_target(&mut engine, layer, (0, 0), &border);
+/// ```
+pub fn draw_target(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    pos: (i16, i16),
+    target: &RenderTarget,
+) {
+    draw_cell_grid(
+        engine,
+        layer_index,
+        pos.0,
+        pos.1,
+        &target.cells,
+        target.width(),
+        target.height(),
+    );
+}
+
+/// Draws a [`TextArt`], with its top-left corner at `pos`.
+///
+/// Transparent cells of the art leave whatever is underneath untouched.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{asset::TextArt, draw::draw_text_art, layer::create_layer, engine::Engine};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// let title = TextArt::parse("[orange bold]GERMTERM[/]\n  [dark_gray]press enter[/]").unwrap();
+/// draw_text_art(&mut engine, layer, (4, 2), &title);
+/// ```
+pub fn draw_text_art(engine: &mut Engine, layer_index: LayerIndex, pos: (i16, i16), art: &TextArt) {
+    draw_cell_grid(
+        engine,
+        layer_index,
+        pos.0,
+        pos.1,
+        &art.cells,
+        art.width(),
+        art.height(),
+    );
+}
+
+/// Draws a translucent copy of a region of the layers beneath `layer_index`, with its top-left corner at `pos`.
+///
+/// Meant for drag and drop feedback, the region is the rect of the dragged panel, following the same size rules as [`draw_rect`],
+/// and the copy follows the cursor. `alpha` scales the opacity of the copied cells.
+///
+/// The copy is made of everything drawn so far this frame on the layers beneath, so the panel should be drawn first.
+/// The ghost itself is never copied, as it's not beneath its own layer.
+/// Comes with the same cost as [`get_composed_cell`].
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_drag_ghost, layer::create_layer, engine::Engine};
+/// # use germterm::{crossterm::event::MouseButton, input::{DragState, active_drag}};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 1);
+/// let drag_state = DragState::new(MouseButton::Left);
+///
+/// if let Some(drag) = active_drag(&drag_state) {
+///     let (panel_pos, panel_size): ((i16, i16), (i16, i16)) = ((0, 4), (20, 4));
+///     let pos = (drag.current.0 - (drag.start.0 - panel_pos.0), drag.current.1 - (drag.start.1 - panel_pos.1));
+///     draw_drag_ghost(&mut engine, layer, panel_pos, panel_size, pos, 120);
+/// }
+/// ```
+pub fn draw_drag_ghost(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    region_pos: (i16, i16),
+    region_size: (i16, i16),
+    pos: (i16, i16),
+    alpha: u8,
+) {
+    let cols: i32 = engine.frame.width as i32;
+    let rows: i32 = engine.frame.height as i32;
+    let (x, y) = (region_pos.0 as i32, region_pos.1 as i32);
+    let (width, height) = (region_size.0 as i32, region_size.1 as i32);
+    let (region_x, region_y): (i32, i32) = (x.min(x + width), y.min(y + height));
+    let first_col: i32 = region_x.max(0);
+    let end_col: i32 = (region_x + width.abs()).min(cols);
+    let first_row: i32 = region_y.max(0);
+    let end_row: i32 = (region_y + height.abs()).min(rows);
+    if first_col >= end_col || first_row >= end_row {
+        return;
+    }
+
+    let frame: Vec<Cell> = compose_queued_layers_beneath(
+        &engine.frame.layered_draw_queue,
+        layer_index.0,
+        engine.frame.width,
+        engine.frame.height,
+        engine.default_blending_color,
+    );
+    let fade = |color: Color| color.with_alpha((color.a() as u16 * alpha as u16 / 255) as u8);
+    let ghost_rows: Vec<Vec<Cell>> = (first_row..end_row)
+        .map(|row| {
+            (first_col..end_col)
+                .map(|col| {
+                    let mut cell: Cell = frame[(row * cols + col) as usize];
+                    cell.fg = fade(cell.fg);
+                    cell.bg = fade(cell.bg);
+                    cell
+                })
+                .collect()
+        })
+        .collect();
+
+    let x: i32 = pos.0 as i32 + (first_col - region_x);
+    for (row_offset, cells) in ghost_rows.into_iter().enumerate() {
+        let y: i32 = pos.1 as i32 + (first_row - region_y) + row_offset as i32;
+        if x > i16::MAX as i32 || y > i16::MAX as i32 {
+            break;
+        }
+        draw_cell_row(engine, layer_index, x as i16, y as i16, cells.into_iter());
+    }
+}
+
+/// Draws small proportional text made of octads.
+///
+/// Uses a built-in bitmap font with variable glyph widths, where capital letters are 5 octads tall.
+/// This allows for smooth labels that fit into much less space than regular text, eg. on charts.
+///
+/// `pos` is the top-left corner of the text and uses the same floating point coordinate space as [`draw_octad`].
+/// Lines are separated by `\n` and are 7 octads apart.
+/// Characters outside of printable ASCII are drawn as `?`.
+///
+/// The text is drawn through [`draw_octad`], so it merges with other octads sharing the same cells.
+/// Parts of the text that fall outside of the screen are clipped.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_mini_text, layer::create_layer, engine::Engine, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// draw_mini_text(&mut engine, layer, (2.0, 1.5), "Temperature: 21.5C", Color::WHITE);
+/// ```
+pub fn draw_mini_text(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    pos: (f32, f32),
+    text: &str,
+    color: Color,
+) {
+    let bounds: (i32, i32, i32, i32) = visible_area(engine, layer_index, 2, 4);
+    let origin: (i32, i32) = to_sub_cell_grid(pos, 2.0, 4.0);
+
+    for (line_index, line) in text.split('\n').enumerate() {
+        let line_y: i32 = origin.1 + line_index as i32 * (MINI_GLYPH_HEIGHT + 1);
+        let mut glyph_x: i32 = origin.0;
+        let mut prev_ch: Option<char> = None;
+
+        for ch in line.chars() {
+            if let Some(prev_ch) = prev_ch {
+                glyph_x += mini_glyph(prev_ch).width as i32 + 1 - mini_kerning(prev_ch, ch);
+            }
+            prev_ch = Some(ch);
+
+            let glyph: &MiniGlyph = mini_glyph(ch);
+            for (row_offset, row) in glyph.rows.iter().enumerate() {
+                let y: i32 = line_y + row_offset as i32;
+                for col_offset in 0..glyph.width {
+                    let x: i32 = glyph_x + col_offset as i32;
+                    let is_set: bool = row & (1 << (glyph.width - 1 - col_offset)) != 0;
+
+                    if is_set && is_in_bounds(x, y, bounds) {
+                        draw_octad(engine, layer_index, x as f32 * 0.5, y as f32 * 0.25, color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Measures the size of text drawn with [`draw_mini_text`] in cols and rows.
+///
+/// The width is the width of the widest line.
+///
+/// # Example
+/// ```rust
+/// # use germterm::draw::measure_mini_text;
+/// let (width, height) = measure_mini_text("Hi!");
+/// assert_eq!((width, height), (3.5, 1.5));
+///
+/// // Kerned pairs are drawn without a gap
+/// assert_eq!(measure_mini_text("AV").0, measure_mini_text("A").0 + measure_mini_text("V").0);
+/// ```
+pub fn measure_mini_text(text: &str) -> (f32, f32) {
+    let mut max_width: i32 = 0;
+    let mut line_count: i32 = 0;
+
+    for line in text.split('\n') {
+        let mut width: i32 = 0;
+        let mut prev_ch: Option<char> = None;
+
+        for ch in line.chars() {
+            if let Some(prev_ch) = prev_ch {
+                width += 1 - mini_kerning(prev_ch, ch);
+            }
+            width += mini_glyph(ch).width as i32;
+            prev_ch = Some(ch);
+        }
+
+        max_width = max_width.max(width);
+        line_count += 1;
+    }
+
+    let height: i32 = line_count * (MINI_GLYPH_HEIGHT + 1) - 1;
+    (max_width as f32 * 0.5, height as f32 * 0.25)
+}
+
+/// A built-in font for [`draw_big_text`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BigFont {
+    /// The proportional font of [`draw_mini_text`] drawn with twoxels, 3 cells tall including descenders.
+    /// Most glyphs are 3 cells wide.
+    Compact,
+    /// A 5x5 cell font drawn with full blocks, for printable ASCII up to `_`. Lowercase letters use the uppercase glyphs.
+    Block,
+}
+
+/// A glyph of a [`BigFont`], with the highest of the `width` bits of a row as its leftmost pixel.
+struct BigGlyph {
+    width: i32,
+    rows: [u8; 6],
+}
+
+impl BigFont {
+    /// The glyph height in pixels.
+    #[inline]
+    fn glyph_height(self) -> i32 {
+        match self {
+            BigFont::Compact => MINI_GLYPH_HEIGHT,
+            BigFont::Block => BLOCK_GLYPH_SIZE,
+        }
+    }
+
+    /// The amount of pixels per cell vertically.
+    #[inline]
+    fn pixels_per_row(self) -> i32 {
+        match self {
+            BigFont::Compact => 2,
+            BigFont::Block => 1,
+        }
+    }
+
+    /// The glyph of a char, or a filled box for chars the font has no glyph for.
+    fn glyph(self, ch: char) -> BigGlyph {
+        match self {
+            BigFont::Compact if (' '..='~').contains(&ch) => {
+                let glyph: &MiniGlyph = mini_glyph(ch);
+                BigGlyph {
+                    width: glyph.width as i32,
+                    rows: glyph.rows,
+                }
+            }
+            BigFont::Compact => BigGlyph {
+                width: 3,
+                rows: [0b111, 0b111, 0b111, 0b111, 0b111, 0b000],
+            },
+            BigFont::Block => {
+                let ch: char = ch.to_ascii_uppercase();
+                let rows: [u8; 5] = match ch {
+                    ' '..='_' => BLOCK_FONT[ch as usize - 0x20],
+                    _ => [0b11111; 5],
+                };
+                BigGlyph {
+                    width: BLOCK_GLYPH_SIZE,
+                    rows: [rows[0], rows[1], rows[2], rows[3], rows[4], 0],
+                }
+            }
+        }
+    }
+
+    /// The offset from the start of the glyph of `left` to the start of the glyph of `right`, in pixels.
+    #[inline]
+    fn advance(self, left: char, right: char) -> i32 {
+        let kerning: i32 = match self {
+            BigFont::Compact => mini_kerning(left, right),
+            BigFont::Block => 0,
+        };
+        self.glyph(left).width + 1 - kerning
+    }
+}
+
+/// Draws large text for eg. title screens, with its top-left corner at `pos`.
+///
+/// Lines are separated by `\n` and are a single row apart. Characters the font has no glyph for are drawn as filled boxes.
+/// [`BigFont::Compact`] is drawn through [`draw_twoxel`] and [`BigFont::Block`] through [`draw_rect`],
+/// so translucent colors and layering behave just like they do for those.
+/// Parts of the text that fall outside of the screen are clipped.
+///
+/// # Example
+/// ```rust
+/// # use germterm::{draw::{BigFont, draw_big_text, measure_big_text}, layer::create_layer, engine::{Engine, end_frame, init, start_frame}, color::Color};
+/// # use germterm::screenshot::{ScreenshotFormat, screenshot};
+/// let mut engine = Engine::offscreen(12, 3);
+/// let layer = create_layer(&mut engine, 0);
+/// init(&mut engine).unwrap();
+/// start_frame(&mut engine);
+///
+/// // Centered horizontally
+/// let (width, _) = measure_big_text("Hi!", BigFont::Compact);
+/// draw_big_text(&mut engine, layer, ((12 - width as i16) / 2, 0), "Hi!", Color::ORANGE, BigFont::Compact);
+///
+/// end_frame(&mut engine).unwrap();
+/// // Cells with both twoxels set show '▀', with the lower half in the bg color
+/// assert_eq!(
+///     screenshot(&engine, ScreenshotFormat::PlainText),
+///     "  ▀ ▀ ▀ ▀\n  ▀▀▀ ▀ ▀\n  ▀ ▀ ▀ ▀\n",
+/// );
+/// ```
+pub fn draw_big_text(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    pos: (i16, i16),
+    text: &str,
+    color: Color,
+    font: BigFont,
+) {
+    let pixels_per_row: i32 = font.pixels_per_row();
+    let bounds: (i32, i32, i32, i32) = visible_area(engine, layer_index, 1, pixels_per_row);
+    let line_height: i32 = font.glyph_height() + pixels_per_row;
+
+    for (line_index, line) in text.split('\n').enumerate() {
+        let line_y: i32 = pos.1 as i32 * pixels_per_row + line_index as i32 * line_height;
+        let mut glyph_x: i32 = pos.0 as i32;
+        let mut prev_ch: Option<char> = None;
+
+        for ch in line.chars() {
+            if let Some(prev_ch) = prev_ch {
+                glyph_x += font.advance(prev_ch, ch);
+            }
+            prev_ch = Some(ch);
+
+            let glyph: BigGlyph = font.glyph(ch);
+            for (row_offset, &row) in glyph.rows.iter().enumerate() {
+                let y: i32 = line_y + row_offset as i32;
+                for col_offset in 0..glyph.width {
+                    let x: i32 = glyph_x + col_offset;
+                    let is_set: bool = row & (1 << (glyph.width - 1 - col_offset)) != 0;
+                    if !is_set || !is_in_bounds(x, y, bounds) {
+                        continue;
+                    }
+
+                    match font {
+                        BigFont::Compact => {
+                            draw_twoxel(engine, layer_index, x as f32, y as f32 * 0.5, color)
+                        }
+                        BigFont::Block => {
+                            draw_rect(engine, layer_index, (x as i16, y as i16), (1, 1), color)
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Measures the size of text drawn with [`draw_big_text`] in cols and rows.
+///
+/// The width is the width of the widest line.
+///
+/// # Example
+/// ```rust
+/// # use germterm::draw::{BigFont, measure_big_text};
+/// assert_eq!(measure_big_text("GAME\nOVER", BigFont::Block), (23, 11));
+/// ```
+pub fn measure_big_text(text: &str, font: BigFont) -> (u16, u16) {
+    let mut max_width: i32 = 0;
+    let mut line_count: i32 = 0;
+
+    for line in text.split('\n') {
+        let mut width: i32 = 0;
+        let mut prev_ch: Option<char> = None;
+
+        for ch in line.chars() {
+            if let Some(prev_ch) = prev_ch {
+                width += font.advance(prev_ch, ch);
+            }
+            prev_ch = Some(ch);
+        }
+        if let Some(last_ch) = prev_ch {
+            width += font.glyph(last_ch).width;
+        }
+
+        max_width = max_width.max(width);
+        line_count += 1;
+    }
+
+    let glyph_rows: i32 = font.glyph_height() / font.pixels_per_row();
+    let height: i32 = line_count * (glyph_rows + 1) - 1;
+    (
+        max_width.clamp(0, u16::MAX as i32) as u16,
+        height.clamp(0, u16::MAX as i32) as u16,
+    )
+}
+
+/// Draws a [`Sprite`] with its top-left pixel at the specified sub-cell position.
+///
+/// The position uses the same floating point coordinate space as [`draw_twoxel`] and is snapped to the twoxel grid.
+/// Each pixel is drawn as a single twoxel, meaning 2 vertically stacked pixels end up sharing a cell.
+///
+/// Pixels are blended using the same rules as [`draw_twoxel`], fully transparent pixels are skipped.
+/// Parts of the sprite that fall outside of the screen are clipped.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_sprite, layer::create_layer, engine::Engine, color::Color, sprite::Sprite};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// let sprite = Sprite::new(2, 2, vec![Color::RED, Color::GREEN, Color::BLUE, Color::YELLOW]);
+/// draw_sprite(&mut engine, layer, 4.0, 2.5, &sprite);
+/// ```
+pub fn draw_sprite(engine: &mut Engine, layer_index: LayerIndex, x: f32, y: f32, sprite: &Sprite) {
+    draw_sprite_scaled(engine, layer_index, x, y, sprite, 1);
+}
+
+/// Draws a [`Sprite`] scaled up by an integer factor.
+///
+/// Each pixel is drawn as a `scale` x `scale` block of twoxels.
+/// A `scale` of `0`, like an empty sprite, draws nothing. Otherwise works just like [`draw_sprite`].
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_sprite_scaled, layer::create_layer, engine::Engine, color::Color, sprite::Sprite};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// let sprite = Sprite::new(2, 2, vec![Color::RED, Color::GREEN, Color::BLUE, Color::YELLOW]);
+/// draw_sprite_scaled(&mut engine, layer, 4.0, 2.5, &sprite, 3);
+/// ```
+pub fn draw_sprite_scaled(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    x: f32,
+    y: f32,
+    sprite: &Sprite,
+    scale: u16,
+) {
+    if sprite.width() == 0 || sprite.height() == 0 {
+        return;
+    }
+
+    let bounds: (i32, i32, i32, i32) = visible_area(engine, layer_index, 1, 2);
+    let origin: (i32, i32) = to_sub_cell_grid((x, y), 1.0, 2.0);
+    let scale: i32 = scale as i32;
+
+    // Sharing the text between draw calls, avoiding an allocation per pixel
+    let upper_half: RichText = RichText::new("▀").with_cell_format(CellFormat::Twoxel);
+    let lower_half: RichText = RichText::new("▄").with_cell_format(CellFormat::Twoxel);
+
+    for (row, pixels) in sprite
+        .pixels()
+        .chunks_exact(sprite.width() as usize)
+        .enumerate()
+    {
+        for sub_row in 0..scale {
+            let twoxel_y: i32 = origin.1 + row as i32 * scale + sub_row;
+            if twoxel_y < bounds.1 || twoxel_y >= bounds.3 {
+                continue;
+            }
+
+            let half: &RichText = if twoxel_y.rem_euclid(2) == 0 {
+                &upper_half
+            } else {
+                &lower_half
+            };
+
+            for (col, &color) in pixels.iter().enumerate() {
+                if color.a() == 0 {
+                    continue;
+                }
+
+                for sub_col in 0..scale {
+                    let twoxel_x: i32 = origin.0 + col as i32 * scale + sub_col;
+                    if twoxel_x < bounds.0 || twoxel_x >= bounds.2 {
+                        continue;
+                    }
+
+                    let draw_call: DrawCall = DrawCall {
+                        rich_text: half.clone().with_fg(color),
+                        x: twoxel_x as i16,
+                        y: twoxel_y.div_euclid(2) as i16,
+                    };
+                    push_draw_call(engine, layer_index, draw_call);
+                }
+            }
+        }
+    }
+}
+
+/// Draws the current FPS.
+///
+/// The retrieved value is an EMA (Exponential Moving Average).
+///
+/// This is purely a convenience helper that draws with the default style.
+/// If you wish to display the FPS in a more stylized way, look into [`get_fps`].
+///
+/// # Example
+/// ```rust,no_run
 /// # use germterm::{draw::draw_fps_counter, layer::create_layer, engine::Engine};
 /// let mut engine = Engine::new(40, 20);
 /// let layer = create_layer(&mut engine, 0);
@@ -315,3 +2442,1529 @@ pub fn draw_fps_counter(engine: &mut Engine, layer_index: LayerIndex, x: i16, y:
     let text: String = format!("FPS: {:2.0}", get_fps(engine));
     draw_text(engine, layer_index, x, y, text);
 }
+
+/// Converts a floating point cell-space position into integer sub-cell grid coordinates.
+///
+/// Coordinates are clamped to the `i16` range first, as that's the range of the cell coordinate space.
+#[inline]
+fn to_sub_cell_grid(pos: (f32, f32), scale_x: f32, scale_y: f32) -> (i32, i32) {
+    let x: f32 = pos.0.clamp(i16::MIN as f32, i16::MAX as f32);
+    let y: f32 = pos.1.clamp(i16::MIN as f32, i16::MAX as f32);
+    ((x * scale_x).floor() as i32, (y * scale_y).floor() as i32)
+}
+
+/// Bresenham line rasterization calling `plot` for every in-bounds point between `a` and `b`.
+///
+/// Endpoints are sorted beforehand so that the result does not depend on their order.
+fn rasterize_line(
+    a: (i32, i32),
+    b: (i32, i32),
+    bounds: (i32, i32, i32, i32),
+    mut plot: impl FnMut(i32, i32),
+) {
+    let ((x0, y0), (x1, y1)) = if a <= b { (a, b) } else { (b, a) };
+
+    let dx: i32 = (x1 - x0).abs();
+    let dy: i32 = -(y1 - y0).abs();
+    let step_x: i32 = if x0 < x1 { 1 } else { -1 };
+    let step_y: i32 = if y0 < y1 { 1 } else { -1 };
+
+    let (mut x, mut y) = (x0, y0);
+    let mut error: i32 = dx + dy;
+
+    loop {
+        if is_in_bounds(x, y, bounds) {
+            plot(x, y);
+        }
+
+        if x == x1 && y == y1 {
+            break;
+        }
+
+        let error_doubled: i32 = error * 2;
+        if error_doubled >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if error_doubled <= dx {
+            error += dx;
+            y += step_y;
+        }
+    }
+}
+
+/// Even-odd scanline polygon rasterization calling `plot` for every in-bounds sub-cell whose center lies inside of the polygon.
+///
+/// Edges are treated as half-open vertically, so vertices shared by two edges are only crossed once.
+fn rasterize_polygon(
+    points: &[(f32, f32)],
+    scale_x: f32,
+    scale_y: f32,
+    bounds: (i32, i32, i32, i32),
+    mut plot: impl FnMut(i32, i32),
+) {
+    if points.len() < 3 {
+        return;
+    }
+
+    let scaled: Vec<(f32, f32)> = points
+        .iter()
+        .map(|&(x, y)| (x * scale_x, y * scale_y))
+        .collect();
+    let min_y: f32 = scaled.iter().map(|p| p.1).fold(f32::MAX, f32::min);
+    let max_y: f32 = scaled.iter().map(|p| p.1).fold(f32::MIN, f32::max);
+
+    let first_row: i32 = ((min_y - 0.5).ceil() as i32).max(bounds.1);
+    let end_row: i32 = ((max_y - 0.5).ceil() as i32).min(bounds.3);
+    let mut crossings: Vec<f32> = Vec::new();
+
+    for y in first_row..end_row {
+        let center_y: f32 = y as f32 + 0.5;
+
+        crossings.clear();
+        for (i, &(x0, y0)) in scaled.iter().enumerate() {
+            let (x1, y1) = scaled[(i + 1) % scaled.len()];
+            if (y0 <= center_y) != (y1 <= center_y) {
+                crossings.push(x0 + (center_y - y0) / (y1 - y0) * (x1 - x0));
+            }
+        }
+        crossings.sort_by(f32::total_cmp);
+
+        for span in crossings.chunks_exact(2) {
+            let start_x: i32 = ((span[0] - 0.5).ceil() as i32).max(bounds.0);
+            let end_x: i32 = ((span[1] - 0.5).ceil() as i32).min(bounds.2);
+            for x in start_x..end_x {
+                plot(x, y);
+            }
+        }
+    }
+}
+
+/// Marks every point of a `width` x `height` grid reachable from `start` through edge-sharing points matching `can_fill`.
+///
+/// Uses an explicit stack, so large areas can't overflow the call stack.
+fn flood_grid(
+    width: usize,
+    height: usize,
+    start: usize,
+    can_fill: impl Fn(usize) -> bool,
+) -> Vec<bool> {
+    let mut filled: Vec<bool> = vec![false; width * height];
+    let mut stack: Vec<usize> = vec![start];
+    filled[start] = true;
+
+    while let Some(index) = stack.pop() {
+        let (x, y) = (index % width, index / width);
+        let neighbors: [Option<usize>; 4] = [
+            (x > 0).then(|| index - 1),
+            (x + 1 < width).then(|| index + 1),
+            (y > 0).then(|| index - width),
+            (y + 1 < height).then(|| index + width),
+        ];
+
+        for neighbor in neighbors.into_iter().flatten() {
+            if !filled[neighbor] && can_fill(neighbor) {
+                filled[neighbor] = true;
+                stack.push(neighbor);
+            }
+        }
+    }
+    filled
+}
+
+/// Splits text into lines no wider than `max_width` columns.
+///
+/// Every returned line is guaranteed to fit, words that don't are hard-broken in [`WrapMode::Word`] too.
+/// The only exception are lines made of a single wide character when `max_width` is `1`.
+fn wrap_text(text: &str, max_width: usize, wrap: WrapMode) -> Vec<&str> {
+    let mut lines: Vec<&str> = vec![];
+
+    for paragraph in text.split('\n') {
+        let mut rest: &str = paragraph;
+
+        loop {
+            // Byte index right after the last grapheme that still fits
+            let limit: usize = match fitting_prefix_len(rest, max_width) {
+                limit if limit == rest.len() => {
+                    lines.push(rest);
+                    break;
+                }
+                limit => limit,
+            };
+
+            let break_index: usize = match wrap {
+                WrapMode::Character => limit,
+                WrapMode::Word if rest[limit..].starts_with(' ') => limit,
+                WrapMode::Word => match rest[..limit].rfind(' ') {
+                    Some(space_index) if !rest[..space_index].trim_end().is_empty() => space_index,
+                    _ => limit,
+                },
+            };
+
+            lines.push(rest[..break_index].trim_end_matches(' '));
+            rest = rest[break_index..].trim_start_matches(' ');
+
+            if rest.is_empty() {
+                break;
+            }
+        }
+    }
+
+    lines
+}
+
+/// The length in bytes of the longest prefix of `text` that's at most `max_width` columns wide,
+/// always including at least the first grapheme so wrapping makes progress.
+fn fitting_prefix_len(text: &str, max_width: usize) -> usize {
+    let mut width: usize = 0;
+    let mut len: usize = 0;
+    let mut is_full: bool = false;
+
+    for_each_grapheme_range(text, |range| {
+        if is_full {
+            return;
+        }
+        width += text_grapheme_width(&text[range.clone()]);
+        if width > max_width && len > 0 {
+            is_full = true;
+            return;
+        }
+        len = range.end;
+    });
+    len
+}
+
+/// The display width of text in columns.
+#[inline]
+fn text_width(text: &str) -> usize {
+    let mut width: usize = 0;
+    for_each_grapheme_range(text, |range| width += text_grapheme_width(&text[range]));
+    width
+}
+
+/// Queues a draw call in the coordinates of the layer, which are shifted onto the screen by the layer's camera
+/// once the frame is composed.
+fn push_draw_call(engine: &mut Engine, layer_index: LayerIndex, draw_call: DrawCall) {
+    engine.frame.layered_draw_queue[layer_index.0]
+        .draw_calls
+        .push(draw_call);
+}
+
+/// Shifts a position from the coordinates of a layer onto the screen.
+#[inline]
+fn to_screen(engine: &Engine, layer_index: LayerIndex, x: i32, y: i32) -> (i32, i32) {
+    let camera: (i16, i16) = layer_camera(engine, layer_index);
+    (x + camera.0 as i32, y + camera.1 as i32)
+}
+
+/// The area of the screen in the coordinates of a layer, scaled up to a sub-cell grid,
+/// as `(min_x, min_y, end_x, end_y)`.
+fn visible_area(
+    engine: &Engine,
+    layer_index: LayerIndex,
+    scale_x: i32,
+    scale_y: i32,
+) -> (i32, i32, i32, i32) {
+    let camera: (i16, i16) = layer_camera(engine, layer_index);
+    let (min_x, min_y): (i32, i32) = (-(camera.0 as i32), -(camera.1 as i32));
+    (
+        min_x * scale_x,
+        min_y * scale_y,
+        (min_x + engine.frame.width as i32) * scale_x,
+        (min_y + engine.frame.height as i32) * scale_y,
+    )
+}
+
+#[inline]
+fn is_in_bounds(x: i32, y: i32, bounds: (i32, i32, i32, i32)) -> bool {
+    x >= bounds.0 && y >= bounds.1 && x < bounds.2 && y < bounds.3
+}
+
+fn draw_cell_grid(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    x: i16,
+    y: i16,
+    cells: &impl Index<usize, Output = Cell>,
+    width: u16,
+    height: u16,
+) {
+    let width: usize = width as usize;
+    for row in 0..height as usize {
+        let row_y: i32 = y as i32 + row as i32;
+        if row_y > i16::MAX as i32 {
+            break;
+        }
+
+        let row_cells = (0..width).map(|col| cells[row * width + col]);
+        draw_cell_row(engine, layer_index, x, row_y as i16, row_cells);
+    }
+}
+
+/// Draws a row of already composed cells, merging consecutive cells sharing the same style into a single draw call.
+///
+/// Cells without any visible content are skipped.
+pub(crate) fn draw_cell_row(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    x: i16,
+    y: i16,
+    cells: impl Iterator<Item = Cell>,
+) {
+    let mut run_text: String = String::new();
+    let mut run_x: i32 = x as i32;
+    let mut run_style: Option<CellStyle> = None;
+
+    for (col, cell) in cells.enumerate() {
+        let cell_x: i32 = x as i32 + col as i32;
+        if cell_x > i16::MAX as i32 {
+            break;
+        }
+
+        // Wide chars re-create their continuation cell when drawn
+        if cell.ch == Cell::WIDE_CONTINUATION {
+            continue;
+        }
+
+        let style: Option<CellStyle> = stamped_cell_style(&cell);
+        if style != run_style || style.is_none() {
+            if let Some(run_style) = run_style {
+                draw_cell_run(engine, layer_index, run_x, y, &run_text, run_style);
+            }
+            run_text.clear();
+            run_x = cell_x;
+            run_style = style;
+        }
+
+        if style.is_some() {
+            push_grapheme(&mut run_text, &cell);
+        }
+    }
+
+    if let Some(run_style) = run_style {
+        draw_cell_run(engine, layer_index, run_x, y, &run_text, run_style);
+    }
+}
+
+/// The colors, attributes, link, format and sub-cell mask of a run of stamped cells.
+type CellStyle = (Color, Color, Attributes, Option<LinkId>, CellFormat, u8);
+
+#[inline]
+fn draw_cell_run(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    x: i32,
+    y: i16,
+    text: &str,
+    (fg, bg, attributes, link, format, format_data): CellStyle,
+) {
+    let rich_text: RichText = RichText {
+        link,
+        ..RichText::new(text)
+            .with_fg(fg)
+            .with_bg(bg)
+            .with_attributes(attributes)
+            .with_cell_format(format)
+            .with_format_data(format_data)
+    };
+    draw_text(engine, layer_index, x as i16, y, rich_text);
+}
+
+/// Returns the style a composed cell should be redrawn with, or `None` if the cell has no visible content.
+///
+/// A missing background color is turned into a fully transparent one,
+/// so that it keeps the background underneath instead of erasing it.
+/// Sub-cell cells keep their format and mask, so they merge with the sub-cells underneath like the original draws would.
+#[inline]
+fn stamped_cell_style(cell: &Cell) -> Option<CellStyle> {
+    let no_fg_color: bool = cell.attributes.contains(Attributes::NO_FG_COLOR);
+    let no_bg_color: bool = cell.attributes.contains(Attributes::NO_BG_COLOR);
+
+    if no_bg_color && (no_fg_color || cell.ch == ' ') {
+        return None;
+    }
+
+    let bg: Color = if no_bg_color { Color::CLEAR } else { cell.bg };
+    // Interned clusters are pushed as their full text and interned again when drawn
+    let format_data: u8 = match cell.format {
+        CellFormat::Standard => 0,
+        _ => cell.format_data,
+    };
+    Some((
+        cell.fg,
+        bg,
+        cell.attributes & !Attributes::NO_BG_COLOR,
+        cell.link,
+        cell.format,
+        format_data,
+    ))
+}
+
+/// Midpoint ellipse rasterization calling `plot` exactly once for every in-bounds point of the ellipse.
+///
+/// When `filled` is set, every point inside of the ellipse gets plotted as well.
+fn rasterize_ellipse(
+    center: (i32, i32),
+    radii: (i16, i16),
+    filled: bool,
+    bounds: (i32, i32, i32, i32),
+    mut plot: impl FnMut(i32, i32),
+) {
+    if radii.0 < 0 || radii.1 < 0 {
+        return;
+    }
+
+    let rx: i64 = radii.0 as i64;
+    let ry: i64 = radii.1 as i64;
+
+    // Horizontal extent `(min_x, max_x)` of the outline in each row of the bottom-right quadrant
+    let mut rows: Vec<(i64, i64)> = vec![(i64::MAX, i64::MIN); ry as usize + 1];
+    let mut mark = |x: i64, y: i64| {
+        let row: &mut (i64, i64) = &mut rows[y as usize];
+        *row = (row.0.min(x), row.1.max(x));
+    };
+
+    if ry == 0 {
+        mark(0, 0);
+        mark(rx, 0);
+    } else {
+        let rx2: i64 = rx * rx;
+        let ry2: i64 = ry * ry;
+        let (mut x, mut y): (i64, i64) = (0, ry);
+        let mut px: i64 = 0;
+        let mut py: i64 = 2 * rx2 * y;
+
+        // Decision variables are scaled by 4 to stay in integer arithmetic
+        let mut p: i64 = 4 * ry2 - 4 * rx2 * ry + rx2;
+        while px < py {
+            mark(x, y);
+            x += 1;
+            px += 2 * ry2;
+            if p < 0 {
+                p += 4 * (ry2 + px);
+            } else {
+                y -= 1;
+                py -= 2 * rx2;
+                p += 4 * (ry2 + px - py);
+            }
+        }
+
+        p = ry2 * (2 * x + 1) * (2 * x + 1) + 4 * rx2 * (y - 1) * (y - 1) - 4 * rx2 * ry2;
+        while y >= 0 {
+            mark(x, y);
+            y -= 1;
+            py -= 2 * rx2;
+            if p > 0 {
+                p += 4 * (rx2 - py);
+            } else {
+                x += 1;
+                px += 2 * ry2;
+                p += 4 * (rx2 - py + px);
+            }
+        }
+    }
+
+    let (cx, cy): (i64, i64) = (center.0 as i64, center.1 as i64);
+    let (bounds_min_x, bounds_min_y): (i64, i64) = (bounds.0 as i64, bounds.1 as i64);
+    let (bounds_end_x, bounds_end_y): (i64, i64) = (bounds.2 as i64, bounds.3 as i64);
+    let mut plot_span = |y: i64, from_x: i64, to_x: i64| {
+        if y < bounds_min_y || y >= bounds_end_y {
+            return;
+        }
+        for x in from_x.max(bounds_min_x)..=to_x.min(bounds_end_x - 1) {
+            plot(x as i32, y as i32);
+        }
+    };
+
+    for (dy, &(min_x, max_x)) in rows.iter().enumerate() {
+        let dy: i64 = dy as i64;
+        // Mirroring the quadrant, skipping the rows and columns that would otherwise get plotted twice
+        let mirrored_rows: &[i64] = if dy == 0 { &[0] } else { &[-dy, dy] };
+
+        for &row_dy in mirrored_rows {
+            if filled {
+                plot_span(cy + row_dy, cx - max_x, cx + max_x);
+            } else {
+                plot_span(cy + row_dy, cx + min_x.max(1), cx + max_x);
+                plot_span(cy + row_dy, cx - max_x, cx - min_x);
+            }
+        }
+    }
+}
+
+#[inline]
+fn mini_glyph(ch: char) -> &'static MiniGlyph {
+    match ch {
+        ' '..='~' => &MINI_FONT[ch as usize - 0x20],
+        _ => &MINI_FONT['?' as usize - 0x20],
+    }
+}
+
+#[inline]
+fn mini_kerning(left: char, right: char) -> i32 {
+    MINI_FONT_KERNING_PAIRS.contains(&(left, right)) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ops::RangeInclusive;
+
+    use crate::{
+        color::{ColorGradient, GradientStop, PathAnchor},
+        engine::{end_frame, init, start_frame},
+        layer::{create_layer, set_layer_camera, set_layer_visible},
+        render_target::redraw_render_target,
+        screenshot::{ScreenshotFormat, screenshot},
+        sprite::ResizeFilter,
+        test_support::composed_cells,
+    };
+
+    #[test]
+    fn wrapping_measures_display_width() {
+        assert_eq!(
+            wrap_text("日本語のテキスト", 6, WrapMode::Character),
+            ["日本語", "のテキ", "スト"]
+        );
+        assert_eq!(
+            wrap_text("ab 日本 語", 5, WrapMode::Word),
+            ["ab", "日本", "語"]
+        );
+        // A wide character wider than the line still makes progress
+        assert_eq!(wrap_text("日本", 1, WrapMode::Character), ["日", "本"]);
+        assert_eq!(text_width("e\u{301}日"), 3);
+    }
+
+    #[test]
+    fn drag_ghost_copies_the_layers_beneath_without_feeding_back() {
+        let mut engine: Engine = Engine::offscreen(4, 1);
+        let panel_layer = create_layer(&mut engine, 0);
+        let ghost_layer = create_layer(&mut engine, 1);
+        init(&mut engine).unwrap();
+
+        let mut frames: Vec<Vec<Cell>> = Vec::new();
+        for _ in 0..3 {
+            start_frame(&mut engine);
+            draw_rect(&mut engine, panel_layer, (0, 0), (2, 1), Color::RED);
+            // Overlapping the panel, so a copy of the last frame would include the ghost itself
+            draw_drag_ghost(&mut engine, ghost_layer, (0, 0), (3, 1), (1, 0), 255);
+            end_frame(&mut engine).unwrap();
+            frames.push(composed_cells(&engine));
+        }
+
+        let frame: &[Cell] = &frames[0];
+        assert_eq!([frame[1].bg, frame[2].bg], [Color::RED, Color::RED]);
+        // The blank cell of the region is copied as a blank cell
+        assert!(frame[3].attributes.contains(Attributes::NO_BG_COLOR));
+        assert!(frames.iter().all(|later| later == frame));
+    }
+
+    #[test]
+    fn octads_map_to_the_braille_dots_of_their_sub_position() {
+        // Braille dots 1 to 8, see the Unicode braille patterns block
+        let dots: [((f32, f32), char); 8] = [
+            ((0.0, 0.0), '\u{2801}'),
+            ((0.0, 0.25), '\u{2802}'),
+            ((0.0, 0.5), '\u{2804}'),
+            ((0.5, 0.0), '\u{2808}'),
+            ((0.5, 0.25), '\u{2810}'),
+            ((0.5, 0.5), '\u{2820}'),
+            ((0.0, 0.75), '\u{2840}'),
+            ((0.5, 0.75), '\u{2880}'),
+        ];
+        let mut engine: Engine = Engine::offscreen(9, 1);
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+
+        start_frame(&mut engine);
+        for (cell_x, &((sub_x, sub_y), _)) in dots.iter().enumerate() {
+            draw_octad(
+                &mut engine,
+                layer,
+                cell_x as f32 + sub_x,
+                sub_y,
+                Color::WHITE,
+            );
+            draw_octad(&mut engine, layer, 8.0 + sub_x, sub_y, Color::WHITE);
+        }
+        end_frame(&mut engine).unwrap();
+
+        let cells: Vec<Cell> = composed_cells(&engine);
+        for (cell, (_, ch)) in cells.iter().zip(dots) {
+            assert_eq!(cell.ch, ch);
+            assert_eq!(cell.format, CellFormat::Octad);
+            assert_eq!(sub_cell_char(CellFormat::Octad, cell.format_data), ch);
+        }
+        assert_eq!((cells[8].ch, cells[8].format_data), ('\u{28FF}', 0xFF));
+    }
+
+    #[test]
+    fn erasing_an_octad_clears_only_its_braille_dot() {
+        let mut engine: Engine = Engine::offscreen(2, 1);
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+
+        start_frame(&mut engine);
+        draw_octad(&mut engine, layer, 0.0, 0.0, Color::WHITE);
+        draw_octad(&mut engine, layer, 0.5, 0.75, Color::WHITE);
+        erase_octad(&mut engine, layer, 0.0, 0.0);
+        draw_octad(&mut engine, layer, 1.0, 0.0, Color::WHITE);
+        erase_octad(&mut engine, layer, 1.0, 0.0);
+        end_frame(&mut engine).unwrap();
+
+        let cells: Vec<Cell> = composed_cells(&engine);
+        assert_eq!(
+            (cells[0].ch, cells[0].format, cells[0].format_data),
+            ('\u{2880}', CellFormat::Octad, 0x80)
+        );
+        // Erasing the last dot leaves a blank cell
+        assert_eq!((cells[1].ch, cells[1].format), (' ', CellFormat::Standard));
+        assert!(cells[1].attributes.contains(Attributes::NO_FG_COLOR));
+    }
+
+    #[test]
+    fn drawn_targets_merge_octads_and_keep_clusters() {
+        let mut target: RenderTarget = RenderTarget::new(2, 1);
+        redraw_render_target(&mut target, |target_engine, target_layer| {
+            draw_octad(target_engine, target_layer, 0.0, 0.0, Color::WHITE);
+            draw_text(target_engine, target_layer, 1, 0, "e\u{301}");
+        });
+
+        let mut engine: Engine = Engine::offscreen(2, 1);
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+        start_frame(&mut engine);
+        draw_octad(&mut engine, layer, 0.5, 0.0, Color::WHITE);
+        draw_target(&mut engine, layer, (0, 0), &target);
+        end_frame(&mut engine).unwrap();
+
+        let cells: Vec<Cell> = composed_cells(&engine);
+        assert_eq!(
+            (cells[0].ch, cells[0].format, cells[0].format_data),
+            ('\u{2809}', CellFormat::Octad, 0x09)
+        );
+        let mut cluster: String = String::new();
+        push_grapheme(&mut cluster, &cells[1]);
+        assert_eq!(cluster, "e\u{301}");
+    }
+
+    #[test]
+    fn octads_in_rect_only_come_from_the_given_layer() {
+        let mut engine: Engine = Engine::offscreen(8, 4);
+        let beneath = create_layer(&mut engine, 0);
+        let bricks = create_layer(&mut engine, 1);
+        init(&mut engine).unwrap();
+        set_layer_camera(&mut engine, bricks, (1, 1));
+        set_layer_visible(&mut engine, bricks, false);
+
+        start_frame(&mut engine);
+        draw_octad(&mut engine, beneath, 1.0, 1.0, Color::RED);
+        draw_octad(&mut engine, bricks, 3.0, 1.0, Color::RED);
+        draw_octad(&mut engine, bricks, 4.5, 1.25, Color::RED);
+        draw_octad(&mut engine, bricks, 5.0, 1.0, Color::RED);
+        draw_text(&mut engine, bricks, 5, 1, "x");
+
+        assert!(!any_octad_in_rect(&engine, bricks, (1.0, 1.0), (1.0, 1.0)));
+        assert!(any_octad_in_rect(&engine, bricks, (3.0, 1.0), (3.0, 1.0)));
+        assert!(!any_octad_in_rect(&engine, bricks, (3.5, 1.0), (4.5, 1.0)));
+        assert!(any_octad_in_rect(&engine, bricks, (4.5, 1.0), (4.0, 1.5)));
+        // Covered by the text drawn afterwards
+        assert!(!any_octad_in_rect(&engine, bricks, (5.0, 1.0), (5.5, 1.75)));
+        end_frame(&mut engine).unwrap();
+    }
+
+    #[test]
+    fn composed_cells_read_back_the_layers_up_to_the_given_one() {
+        let mut engine: Engine = Engine::offscreen(4, 2);
+        let bottom = create_layer(&mut engine, 0);
+        let top = create_layer(&mut engine, 1);
+        let hidden = create_layer(&mut engine, 2);
+        init(&mut engine).unwrap();
+        set_layer_visible(&mut engine, hidden, false);
+
+        start_frame(&mut engine);
+        draw_rect(&mut engine, bottom, (0, 0), (4, 2), Color::RED);
+        draw_rect(
+            &mut engine,
+            top,
+            (0, 0),
+            (2, 1),
+            Color::BLUE.with_alpha(128),
+        );
+        draw_text(&mut engine, top, 3, 1, "x");
+        draw_rect(&mut engine, hidden, (0, 0), (4, 2), Color::GREEN);
+
+        let read_back: Vec<Cell> = (0..8)
+            .map(|cell_index| get_composed_cell(&engine, hidden, cell_index % 4, cell_index / 4))
+            .map(Option::unwrap)
+            .collect();
+        assert_eq!(
+            get_composed_cell(&engine, bottom, 0, 0).unwrap().bg,
+            Color::RED
+        );
+        assert_eq!(get_composed_cell(&engine, bottom, 3, 1).unwrap().ch, ' ');
+        assert_eq!(get_composed_cell(&engine, top, 3, 1).unwrap().ch, 'x');
+        for (x, y) in [(4, 0), (0, 2), (-1, 0), (0, -1)] {
+            assert!(get_composed_cell(&engine, top, x, y).is_none());
+        }
+        end_frame(&mut engine).unwrap();
+
+        // Reading back leaves the queued draw calls to the frame, which matches what was read
+        let cells: Vec<Cell> = composed_cells(&engine);
+        assert_eq!(read_back, cells);
+        assert_ne!(cells[0].bg, Color::RED);
+        assert_ne!(cells[0].bg, Color::BLUE);
+        assert_eq!(cells[2].bg, Color::RED);
+    }
+
+    #[test]
+    fn composed_octads_and_twoxels_read_back_their_sub_cells() {
+        let mut engine: Engine = Engine::offscreen(4, 2);
+        let bottom = create_layer(&mut engine, 0);
+        let top = create_layer(&mut engine, 1);
+        init(&mut engine).unwrap();
+
+        start_frame(&mut engine);
+        draw_rect(&mut engine, bottom, (0, 0), (4, 1), Color::RED);
+        draw_octad(&mut engine, top, 1.5, 0.75, Color::GREEN);
+        draw_twoxel(&mut engine, top, 2.0, 1.5, Color::CYAN);
+
+        assert_eq!(
+            get_composed_octad(&engine, top, 1.5, 0.75),
+            Some(Color::GREEN)
+        );
+        assert_eq!(
+            get_composed_octad(&engine, top, 1.9, 0.99),
+            Some(Color::GREEN)
+        );
+        assert_eq!(get_composed_octad(&engine, top, 1.0, 0.75), None);
+        assert_eq!(get_composed_octad(&engine, top, 1.5, 0.5), None);
+        assert_eq!(get_composed_octad(&engine, bottom, 1.5, 0.75), None);
+        // Merged octads share the color of the last one drawn
+        draw_octad(&mut engine, top, 1.0, 0.0, Color::YELLOW);
+        assert_eq!(
+            get_composed_octad(&engine, top, 1.5, 0.75),
+            Some(Color::YELLOW)
+        );
+        assert_eq!(
+            get_composed_octad(&engine, top, 1.0, 0.0),
+            Some(Color::YELLOW)
+        );
+
+        let twoxel: Option<Color> = get_composed_twoxel(&engine, top, 2.0, 1.5);
+        // The other half of a twoxel cell and cells without twoxels show their bg, if any
+        assert_eq!(get_composed_twoxel(&engine, top, 2.0, 1.0), None);
+        assert_eq!(
+            get_composed_twoxel(&engine, top, 3.0, 0.0),
+            Some(Color::RED)
+        );
+        assert_eq!(
+            get_composed_twoxel(&engine, top, 3.0, 0.5),
+            Some(Color::RED)
+        );
+        assert_eq!(get_composed_twoxel(&engine, top, 3.0, 1.0), None);
+        assert_eq!(get_composed_twoxel(&engine, top, 4.0, 0.0), None);
+        end_frame(&mut engine).unwrap();
+
+        let twoxel_cell: Cell = composed_cells(&engine)[4 + 2];
+        assert_eq!(
+            (twoxel_cell.ch, twoxel_cell.format),
+            ('▄', CellFormat::Twoxel)
+        );
+        assert_eq!(twoxel, Some(twoxel_cell.fg));
+    }
+
+    #[test]
+    fn flood_fills_stay_within_their_border() {
+        let bordered_box = |engine: &mut Engine, layer: LayerIndex| {
+            draw_rect(engine, layer, (1, 1), (4, 4), Color::WHITE);
+            erase_rect(engine, layer, (2, 2), (2, 2));
+        };
+        assert_eq!(
+            red_cells(|engine, layer| {
+                bordered_box(engine, layer);
+                flood_fill(engine, layer, 3, 2, Color::RED);
+            }),
+            area(2..=3, 2..=3)
+        );
+        // Starting outside of the box fills everything around it, but not its inside
+        let outside: Vec<(usize, usize)> = red_cells(|engine, layer| {
+            bordered_box(engine, layer);
+            flood_fill(engine, layer, 0, 5, Color::RED);
+        });
+        assert_eq!(outside.len(), 6 * 6 - 4 * 4);
+        assert!(!outside.contains(&(2, 2)) && !outside.contains(&(1, 1)));
+
+        // Cells touching only at their corners don't let the fill through
+        assert_eq!(
+            red_cells(|engine, layer| {
+                draw_line(engine, layer, (0, 3), (3, 0), Color::WHITE);
+                flood_fill(engine, layer, 0, 0, Color::RED);
+            }),
+            [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (0, 2)]
+        );
+
+        // Starting on the border fills only its contiguous cells, starting off screen fills nothing
+        assert_eq!(
+            red_cells(|engine, layer| {
+                draw_line(engine, layer, (0, 3), (3, 0), Color::WHITE);
+                flood_fill(engine, layer, 2, 1, Color::RED);
+            }),
+            [(2, 1)]
+        );
+        assert_eq!(
+            red_cells(|engine, layer| flood_fill(engine, layer, 6, 0, Color::RED)),
+            []
+        );
+    }
+
+    /// The `(x, y)` positions of the set octad dots on a 3x2 screen, in row order.
+    fn octad_dots(draw: impl FnOnce(&mut Engine, LayerIndex)) -> Vec<(usize, usize)> {
+        let mut engine: Engine = Engine::offscreen(3, 2);
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+        start_frame(&mut engine);
+        draw(&mut engine, layer);
+        end_frame(&mut engine).unwrap();
+
+        let cells: Vec<Cell> = composed_cells(&engine);
+        area(0..=5, 0..=7)
+            .into_iter()
+            .filter(|&(x, y)| {
+                let cell: &Cell = &cells[(y / 4) * 3 + x / 2];
+                let bit: u8 = 1 << octad_dot_offset(x % 2, y % 4);
+                cell.format == CellFormat::Octad && cell.format_data & bit != 0
+            })
+            .collect()
+    }
+
+    /// Draws an octad at every dot position.
+    fn draw_dots(engine: &mut Engine, layer: LayerIndex, dots: &[(usize, usize)]) {
+        for &(x, y) in dots {
+            draw_octad(engine, layer, x as f32 * 0.5, y as f32 * 0.25, Color::WHITE);
+        }
+    }
+
+    /// The `(x, y)` positions of the `#` dots in rows of dot art, in row order.
+    fn dot_art(rows: [&str; 8]) -> Vec<(usize, usize)> {
+        rows.iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.char_indices()
+                    .filter(|&(_, ch)| ch == '#')
+                    .map(move |(x, _)| (x, y))
+            })
+            .collect()
+    }
+
+    /// An "L" shape with its notch at the top right, filling most of a 3x2 screen.
+    const L_SHAPE: [(f32, f32); 6] = [
+        (0.0, 0.0),
+        (1.0, 0.0),
+        (1.0, 1.25),
+        (2.5, 1.25),
+        (2.5, 1.75),
+        (0.0, 1.75),
+    ];
+
+    #[test]
+    fn filled_polygons_cover_the_dots_centered_inside() {
+        let triangle: Vec<(usize, usize)> = octad_dots(|engine, layer| {
+            fill_triangle_octad(
+                engine,
+                layer,
+                (0.0, 0.0),
+                (3.0, 0.0),
+                (0.0, 2.0),
+                Color::RED,
+            );
+        });
+        assert_eq!(
+            triangle,
+            dot_art([
+                "######", //
+                "#####.", //
+                "####..", //
+                "###...", //
+                "###...", //
+                "##....", //
+                "#.....", //
+                "......", //
+            ])
+        );
+
+        let l_shape: Vec<(usize, usize)> = octad_dots(|engine, layer| {
+            fill_polygon_octad(engine, layer, &L_SHAPE, Color::RED);
+        });
+        assert_eq!(
+            l_shape,
+            dot_art([
+                "##....", //
+                "##....", //
+                "##....", //
+                "##....", //
+                "##....", //
+                "#####.", //
+                "#####.", //
+                "......", //
+            ])
+        );
+    }
+
+    #[test]
+    fn polygon_outlines_connect_every_point() {
+        let l_shape: Vec<(usize, usize)> = octad_dots(|engine, layer| {
+            draw_polygon_octad(engine, layer, &L_SHAPE, Color::RED);
+        });
+        assert_eq!(
+            l_shape,
+            dot_art([
+                "###...", //
+                "#.#...", //
+                "#.#...", //
+                "#.#...", //
+                "#.#...", //
+                "#.####", //
+                "#....#", //
+                "######", //
+            ])
+        );
+    }
+
+    #[test]
+    fn octad_circles_are_round_around_their_center() {
+        let outline: Vec<(usize, usize)> = octad_dots(|engine, layer| {
+            draw_circle_octad(engine, layer, (1.0, 1.0), 2, Color::RED);
+        });
+        assert_eq!(
+            outline,
+            dot_art([
+                "......", //
+                "......", //
+                ".###..", //
+                "#...#.", //
+                "#...#.", //
+                "#...#.", //
+                ".###..", //
+                "......", //
+            ])
+        );
+
+        let filled: Vec<(usize, usize)> = octad_dots(|engine, layer| {
+            fill_circle_octad(engine, layer, (1.0, 1.0), 2, Color::RED);
+        });
+        assert_eq!(
+            filled,
+            dot_art([
+                "......", //
+                "......", //
+                ".###..", //
+                "#####.", //
+                "#####.", //
+                "#####.", //
+                ".###..", //
+                "......", //
+            ])
+        );
+    }
+
+    #[test]
+    fn degenerate_octad_circle_radii() {
+        for radius in [0, -1, -5] {
+            let outline: Vec<(usize, usize)> = octad_dots(|engine, layer| {
+                draw_circle_octad(engine, layer, (1.25, 1.125), radius, Color::RED);
+            });
+            let filled: Vec<(usize, usize)> = octad_dots(|engine, layer| {
+                fill_circle_octad(engine, layer, (1.25, 1.125), radius, Color::RED);
+            });
+            // A single dot for a radius of 0, nothing for negative ones
+            let expected: &[(usize, usize)] = if radius == 0 { &[(2, 4)] } else { &[] };
+            assert_eq!(outline, expected);
+            assert_eq!(filled, expected);
+
+            let mut engine: Engine = Engine::offscreen(3, 2);
+            let layer = create_layer(&mut engine, 0);
+            init(&mut engine).unwrap();
+            start_frame(&mut engine);
+            fill_circle_blocktad(&mut engine, layer, (1.25, 1.125), radius, Color::RED);
+            end_frame(&mut engine).unwrap();
+            let drawn_cells: usize = composed_cells(&engine)
+                .iter()
+                .filter(|&&cell| cell != Cell::EMPTY)
+                .count();
+            assert_eq!(drawn_cells, expected.len());
+        }
+    }
+
+    #[test]
+    fn octad_circles_are_clipped_at_the_screen_edges() {
+        let top_left: Vec<(usize, usize)> = octad_dots(|engine, layer| {
+            fill_circle_octad(engine, layer, (0.0, 0.0), 3, Color::RED);
+        });
+        assert_eq!(
+            top_left,
+            dot_art([
+                "####..", //
+                "####..", //
+                "###...", //
+                "##....", //
+                "......", //
+                "......", //
+                "......", //
+                "......", //
+            ])
+        );
+
+        let bottom_right: Vec<(usize, usize)> = octad_dots(|engine, layer| {
+            draw_circle_octad(engine, layer, (2.5, 1.75), 3, Color::RED);
+        });
+        assert_eq!(
+            bottom_right,
+            dot_art([
+                "......", //
+                "......", //
+                "......", //
+                "......", //
+                "....##", //
+                "...#..", //
+                "..#...", //
+                "..#...", //
+            ])
+        );
+
+        let off_screen: Vec<(usize, usize)> = octad_dots(|engine, layer| {
+            fill_circle_octad(engine, layer, (-4.0, 0.0), 3, Color::RED);
+        });
+        assert_eq!(off_screen, []);
+    }
+
+    #[test]
+    fn octad_flood_fills_stay_within_their_border() {
+        // The outline of the dots from (1, 1) to (4, 6)
+        let outline: Vec<(usize, usize)> = area(1..=4, 1..=6)
+            .into_iter()
+            .filter(|&(x, y)| x == 1 || x == 4 || y == 1 || y == 6)
+            .collect();
+        let filled: Vec<(usize, usize)> = octad_dots(|engine, layer| {
+            draw_dots(engine, layer, &outline);
+            flood_fill_octad(engine, layer, 1.0, 0.5, Color::RED);
+        });
+        assert_eq!(filled, area(1..=4, 1..=6));
+
+        // Dots touching only at their corners don't let the fill through
+        let diagonal: [(usize, usize); 4] = [(3, 0), (2, 1), (1, 2), (0, 3)];
+        let filled: Vec<(usize, usize)> = octad_dots(|engine, layer| {
+            draw_dots(engine, layer, &diagonal);
+            flood_fill_octad(engine, layer, 0.0, 0.0, Color::RED);
+        });
+        assert_eq!(
+            filled,
+            [
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (3, 0),
+                (0, 1),
+                (1, 1),
+                (2, 1),
+                (0, 2),
+                (1, 2),
+                (0, 3)
+            ]
+        );
+
+        // Starting on a set dot or off screen fills nothing
+        for start in [(2.0, 0.25), (3.0, 0.0), (-0.5, 0.0)] {
+            let filled: Vec<(usize, usize)> = octad_dots(|engine, layer| {
+                draw_dots(engine, layer, &outline);
+                flood_fill_octad(engine, layer, start.0, start.1, Color::RED);
+            });
+            assert_eq!(filled, outline);
+        }
+    }
+
+    #[test]
+    fn empty_sprites_draw_nothing() {
+        let mut engine: Engine = Engine::offscreen(6, 6);
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+
+        let source: Sprite = Sprite::filled(2, 2, Color::RED);
+        for sprite in [
+            Sprite::filled(0, 3, Color::RED),
+            Sprite::filled(3, 0, Color::RED),
+            source.resized(0, 2, ResizeFilter::Nearest),
+            source.resized(2, 0, ResizeFilter::Box),
+        ] {
+            start_frame(&mut engine);
+            draw_sprite(&mut engine, layer, 1.0, 1.0, &sprite);
+            draw_sprite_scaled(&mut engine, layer, 1.0, 1.0, &sprite, 3);
+            end_frame(&mut engine).unwrap();
+
+            assert!(composed_cells(&engine).iter().all(|cell| cell.ch == ' '));
+        }
+    }
+
+    /// The `(x, y)` positions of the cells with a red bg on a 6x6 screen, in row order.
+    fn red_cells(draw: impl FnOnce(&mut Engine, LayerIndex)) -> Vec<(usize, usize)> {
+        let mut engine: Engine = Engine::offscreen(6, 6);
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+        start_frame(&mut engine);
+        draw(&mut engine, layer);
+        end_frame(&mut engine).unwrap();
+
+        let cells: Vec<Cell> = composed_cells(&engine);
+        (0..cells.len())
+            .filter(|&cell_index| cells[cell_index].bg == Color::RED)
+            .map(|cell_index| (cell_index % 6, cell_index / 6))
+            .collect()
+    }
+
+    /// The positions of every cell in the inclusive ranges, in row order.
+    fn area(cols: RangeInclusive<usize>, rows: RangeInclusive<usize>) -> Vec<(usize, usize)> {
+        rows.flat_map(|y| cols.clone().map(move |x| (x, y)))
+            .collect()
+    }
+
+    /// An offscreen engine of 3x2 cells with its frame drawn by `draw` and ended.
+    fn scripted_output(draw: impl FnOnce(&mut Engine, LayerIndex)) -> Engine {
+        let mut source: Engine = Engine::offscreen(3, 2);
+        let layer = create_layer(&mut source, 0);
+        init(&mut source).unwrap();
+        start_frame(&mut source);
+        draw(&mut source, layer);
+        end_frame(&mut source).unwrap();
+        source
+    }
+
+    #[test]
+    fn engine_outputs_compose_side_by_side() {
+        let left: Engine = scripted_output(|source, layer| draw_text(source, layer, 0, 0, "ab"));
+        let right: Engine = scripted_output(|source, layer| {
+            draw_rect(source, layer, (0, 1), (3, 1), Color::RED);
+            draw_text(source, layer, 1, 1, "x");
+        });
+
+        let mut engine: Engine = Engine::offscreen(6, 2);
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+        start_frame(&mut engine);
+        draw_rect(&mut engine, layer, (0, 0), (6, 2), Color::BLUE);
+        draw_engine_output(&mut engine, layer, 0, 0, &left);
+        draw_engine_output(&mut engine, layer, 3, 0, &right);
+        end_frame(&mut engine).unwrap();
+
+        let cells: Vec<Cell> = composed_cells(&engine);
+        let text: String = cells.iter().map(|cell| cell.ch).collect();
+        assert_eq!(text, "ab        x ");
+        // Cells without a background keep the one underneath, blank ones are skipped entirely
+        let red_bg: Color = composed_cells(&right)[3].bg;
+        let blue_bg: Color = cells[2].bg;
+        let bgs: Vec<Color> = cells.iter().map(|cell| cell.bg).collect();
+        assert_eq!(bgs[..9], [blue_bg; 9]);
+        assert_eq!(bgs[9..], [red_bg; 3]);
+        assert_eq!(cells[10].fg, composed_cells(&right)[4].fg);
+    }
+
+    /// The plain text screenshot of `text` drawn in the compact big font on a 20x3 screen.
+    fn compact_text_dump(text: &str) -> String {
+        let mut engine: Engine = Engine::offscreen(20, 3);
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+        start_frame(&mut engine);
+        draw_big_text(
+            &mut engine,
+            layer,
+            (0, 0),
+            text,
+            Color::WHITE,
+            BigFont::Compact,
+        );
+        end_frame(&mut engine).unwrap();
+        screenshot(&engine, ScreenshotFormat::PlainText)
+    }
+
+    #[test]
+    fn compact_big_text_dumps_glyph_by_glyph() {
+        // Cells with both twoxels set show '▀' over a bg of the same color
+        assert_eq!(
+            compact_text_dump("Germ"),
+            [
+                "▄▀▀  ▄  ▄ ▄ ▄▄▄▄",
+                "▀ ▀ ▀▄▀ ▀▀  ▀ ▀ ▀",
+                " ▀▀  ▀▀ ▀   ▀ ▀ ▀",
+                "",
+            ]
+            .join("\n")
+        );
+        // Chars without a glyph are drawn as a filled box
+        assert_eq!(compact_text_dump("é"), "▀▀▀\n▀▀▀\n▀▀▀\n");
+    }
+
+    #[test]
+    fn lines_cover_the_cells_between_their_endpoints() {
+        let line = |a: (i16, i16), b: (i16, i16)| {
+            red_cells(|engine, layer| draw_line(engine, layer, a, b, Color::RED))
+        };
+        assert_eq!(line((1, 2), (4, 2)), area(1..=4, 2..=2));
+        assert_eq!(line((3, 1), (3, 4)), area(3..=3, 1..=4));
+        assert_eq!(line((3, 3), (3, 3)), [(3, 3)]);
+
+        // One cell per row, stepping sideways halfway through
+        let steep: [(usize, usize); 6] = [(1, 0), (1, 1), (1, 2), (2, 3), (2, 4), (2, 5)];
+        assert_eq!(line((1, 0), (2, 5)), steep);
+        assert_eq!(line((2, 5), (1, 0)), steep);
+        assert_eq!(line((4, 2), (1, 2)), area(1..=4, 2..=2));
+    }
+
+    #[test]
+    fn lines_are_clipped_to_the_screen() {
+        let line = |a: (i16, i16), b: (i16, i16)| {
+            red_cells(|engine, layer| draw_line(engine, layer, a, b, Color::RED))
+        };
+        let diagonal: Vec<(usize, usize)> = (0..6).map(|i| (i, i)).collect();
+        assert_eq!(line((-3, -3), (8, 8)), diagonal);
+        assert_eq!(line((-4, 3), (2, 3)), area(0..=2, 3..=3));
+        assert_eq!(line((-5, 1), (-1, 4)), []);
+        assert_eq!(line((2, 6), (4, 9)), []);
+    }
+
+    /// The `(x, y)` twoxel grid positions holding a twoxel on a 6x3 screen, in row order.
+    fn twoxels(draw: impl FnOnce(&mut Engine, LayerIndex)) -> Vec<(usize, usize)> {
+        let mut engine: Engine = Engine::offscreen(6, 3);
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+        start_frame(&mut engine);
+        draw(&mut engine, layer);
+
+        area(0..=5, 0..=5)
+            .into_iter()
+            .filter(|&(x, y)| {
+                get_composed_twoxel(&engine, layer, x as f32, y as f32 * 0.5).is_some()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sub_cell_lines_cover_the_positions_between_their_endpoints() {
+        let twoxel_line = |a: (f32, f32), b: (f32, f32)| {
+            twoxels(|engine, layer| draw_line_twoxel(engine, layer, a, b, Color::RED))
+        };
+        assert_eq!(twoxel_line((0.0, 1.5), (5.0, 1.5)), area(0..=5, 3..=3));
+        assert_eq!(twoxel_line((2.0, 2.5), (2.0, 0.0)), area(2..=2, 0..=5));
+        assert_eq!(
+            twoxel_line((4.0, 0.0), (5.0, 2.5)),
+            [(4, 0), (4, 1), (4, 2), (5, 3), (5, 4), (5, 5)]
+        );
+        assert_eq!(
+            twoxel_line((-2.0, -1.0), (8.0, 4.0)),
+            (0..6).map(|i| (i, i)).collect::<Vec<_>>()
+        );
+
+        let octad_line = |a: (f32, f32), b: (f32, f32)| {
+            octad_dots(|engine, layer| draw_line_octad(engine, layer, a, b, Color::RED))
+        };
+        assert_eq!(octad_line((0.0, 0.5), (2.5, 0.5)), area(0..=5, 2..=2));
+        assert_eq!(octad_line((1.5, 1.75), (1.5, 0.0)), area(3..=3, 0..=7));
+        assert_eq!(
+            octad_line((0.5, 0.0), (1.0, 1.25)),
+            [(1, 0), (1, 1), (1, 2), (2, 3), (2, 4), (2, 5)]
+        );
+        assert_eq!(octad_line((-1.0, 1.0), (1.0, 1.0)), area(0..=2, 4..=4));
+        assert_eq!(octad_line((3.0, 0.0), (4.0, 2.0)), []);
+    }
+
+    #[test]
+    fn negative_rect_sizes_extend_left_and_up() {
+        let rect = |width: i16, height: i16| {
+            red_cells(|engine, layer| draw_rect(engine, layer, (3, 3), (width, height), Color::RED))
+        };
+        assert_eq!(rect(2, 2), area(3..=4, 3..=4));
+        assert_eq!(rect(-2, 2), area(1..=2, 3..=4));
+        assert_eq!(rect(2, -2), area(3..=4, 1..=2));
+        assert_eq!(rect(-2, -2), area(1..=2, 1..=2));
+    }
+
+    #[test]
+    fn degenerate_rect_sizes() {
+        let rect = |width: i16, height: i16| {
+            red_cells(|engine, layer| draw_rect(engine, layer, (3, 3), (width, height), Color::RED))
+        };
+        assert_eq!(rect(0, 0), []);
+        assert_eq!(rect(0, 2), []);
+        assert_eq!(rect(-2, 0), []);
+        assert_eq!(rect(1, 1), [(3, 3)]);
+        assert_eq!(rect(-1, -1), [(2, 2)]);
+        assert_eq!(rect(1, -3), area(3..=3, 0..=2));
+        // Clipped by the screen
+        assert_eq!(rect(-10, 1), area(0..=2, 3..=3));
+    }
+
+    #[test]
+    fn erased_rects_follow_the_same_size_rules() {
+        let erased = |width: i16, height: i16| {
+            let red: Vec<(usize, usize)> = red_cells(|engine, layer| {
+                draw_rect(engine, layer, (0, 0), (6, 6), Color::RED);
+                erase_rect(engine, layer, (3, 3), (width, height));
+            });
+            area(0..=5, 0..=5)
+                .into_iter()
+                .filter(|position| !red.contains(position))
+                .collect::<Vec<(usize, usize)>>()
+        };
+        assert_eq!(erased(2, 2), area(3..=4, 3..=4));
+        assert_eq!(erased(-2, 2), area(1..=2, 3..=4));
+        assert_eq!(erased(2, -2), area(3..=4, 1..=2));
+        assert_eq!(erased(-2, -2), area(1..=2, 1..=2));
+        assert_eq!(erased(0, 2), []);
+    }
+
+    #[test]
+    fn rect_corners_cover_both_corners_in_any_order() {
+        let corners = |a: (i16, i16), b: (i16, i16)| {
+            red_cells(|engine, layer| draw_rect_corners(engine, layer, a, b, Color::RED))
+        };
+        let expected: Vec<(usize, usize)> = area(1..=4, 2..=3);
+        assert_eq!(corners((1, 2), (4, 3)), expected);
+        assert_eq!(corners((4, 3), (1, 2)), expected);
+        assert_eq!(corners((1, 3), (4, 2)), expected);
+        assert_eq!(corners((4, 2), (1, 3)), expected);
+        assert_eq!(corners((2, 2), (2, 2)), [(2, 2)]);
+        assert_eq!(corners((2, 0), (2, 5)), area(2..=2, 0..=5));
+        assert_eq!(corners((-3, 1), (1, -3)), area(0..=1, 0..=1));
+    }
+
+    #[test]
+    fn rect_outlines_use_the_chars_of_their_style() {
+        let outlines: [(RectStyle, [&str; 3]); 5] = [
+            (RectStyle::Ascii, ["+---+", "|   |", "+---+"]),
+            (RectStyle::Light, ["┌───┐", "│   │", "└───┘"]),
+            (RectStyle::Heavy, ["┏━━━┓", "┃   ┃", "┗━━━┛"]),
+            (RectStyle::Double, ["╔═══╗", "║   ║", "╚═══╝"]),
+            (RectStyle::Rounded, ["╭───╮", "│   │", "╰───╯"]),
+        ];
+        for (style, expected) in outlines {
+            let mut engine: Engine = Engine::offscreen(5, 3);
+            let layer = create_layer(&mut engine, 0);
+            init(&mut engine).unwrap();
+            start_frame(&mut engine);
+            draw_rect_outline(&mut engine, layer, (0, 0), (5, 3), style, "");
+            end_frame(&mut engine).unwrap();
+
+            let cells: Vec<Cell> = composed_cells(&engine);
+            let rows: Vec<String> = cells
+                .chunks(5)
+                .map(|row| row.iter().map(|cell| cell.ch).collect())
+                .collect();
+            assert_eq!(rows, expected, "{style:?}");
+        }
+    }
+
+    #[test]
+    fn shadow_cells_are_blended_exactly_once() {
+        let shadow_color: Color = Color::BLACK.with_alpha(128);
+        let mut engine: Engine = Engine::offscreen(6, 5);
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+        start_frame(&mut engine);
+        draw_rect(&mut engine, layer, (0, 0), (6, 5), Color::WHITE);
+        draw_shadow(
+            &mut engine,
+            layer,
+            (0, 0),
+            (3, 2),
+            (1, 1),
+            shadow_color,
+            false,
+        );
+        // A single translucent cell to compare against
+        draw_rect(&mut engine, layer, (5, 4), (1, 1), shadow_color);
+        end_frame(&mut engine).unwrap();
+
+        let cells: Vec<Cell> = composed_cells(&engine);
+        let bg_at = |x: usize, y: usize| cells[y * 6 + x].bg;
+        let shadowed: Color = bg_at(5, 4);
+        assert_ne!(shadowed, Color::WHITE);
+        // The right edge, the bottom edge and the corner where both meet
+        for (x, y) in [(3, 1), (1, 2), (2, 2), (3, 2)] {
+            assert_eq!(bg_at(x, y), shadowed, "({x}, {y})");
+        }
+        // Under the rect itself and past the shadow
+        assert_eq!(bg_at(1, 1), Color::WHITE);
+        assert_eq!(bg_at(4, 2), Color::WHITE);
+    }
+
+    #[test]
+    fn extreme_coordinates_and_sizes_never_panic() {
+        const INTS: [i16; 6] = [i16::MIN, i16::MIN + 1, -1, 0, 3, i16::MAX];
+        const FLOATS: [f32; 10] = [
+            f32::MIN,
+            -1.0e6,
+            -0.5,
+            0.0,
+            3.5,
+            1.0e6,
+            f32::MAX,
+            f32::NAN,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+        ];
+        let int_points: Vec<(i16, i16)> = INTS
+            .iter()
+            .flat_map(|&x| INTS.iter().map(move |&y| (x, y)))
+            .collect();
+        let float_points: Vec<(f32, f32)> = FLOATS
+            .iter()
+            .flat_map(|&x| FLOATS.iter().map(move |&y| (x, y)))
+            .collect();
+
+        let mut source: Engine = Engine::offscreen(3, 2);
+        create_layer(&mut source, 0);
+        init(&mut source).unwrap();
+        let target: RenderTarget = RenderTarget::new(3, 2);
+        let art: TextArt = TextArt::parse("ab\ncd").unwrap();
+        let sprite: Sprite = Sprite::filled(2, 2, Color::RED);
+        let path: Vec<(f32, f32)> = float_points.iter().step_by(7).copied().collect();
+        let path_gradient: PathGradient = PathGradient::new(
+            &path,
+            ColorGradient::new(vec![GradientStop::new(0.0, Color::RED)]),
+            PathAnchor::Head,
+            1.0,
+        );
+
+        for camera in [
+            (0, 0),
+            (i16::MIN, i16::MIN),
+            (i16::MAX, i16::MAX),
+            (i16::MAX, i16::MIN),
+        ] {
+            let mut engine: Engine = Engine::offscreen(8, 4);
+            let layer = create_layer(&mut engine, 0);
+            init(&mut engine).unwrap();
+            set_layer_camera(&mut engine, layer, camera);
+
+            // A frame per position keeps the queue composed by the read-backs small
+            for &(x, y) in &int_points {
+                start_frame(&mut engine);
+                draw_text(&mut engine, layer, x, y, "日本e\u{301}");
+                draw_spans(
+                    &mut engine,
+                    layer,
+                    x,
+                    y,
+                    [RichText::new("ab"), RichText::new("c")],
+                );
+                for &(width, height) in &int_points {
+                    draw_rect(&mut engine, layer, (x, y), (width, height), Color::RED);
+                    erase_rect(&mut engine, layer, (x, y), (width, height));
+                    draw_rect_corners(&mut engine, layer, (x, y), (width, height), Color::RED);
+                    draw_rect_outline(
+                        &mut engine,
+                        layer,
+                        (x, y),
+                        (width, height),
+                        RectStyle::Light,
+                        "",
+                    );
+                    let size: (u16, u16) = (width as u16, height as u16);
+                    draw_shadow(
+                        &mut engine,
+                        layer,
+                        (x, y),
+                        (width, height),
+                        (y, x),
+                        Color::BLACK,
+                        true,
+                    );
+                    draw_drag_ghost(&mut engine, layer, (x, y), (width, height), (y, x), 128);
+                    draw_text_wrapped(
+                        &mut engine,
+                        layer,
+                        (x, y),
+                        size.0,
+                        "wrapped text",
+                        WrapMode::Word,
+                        TextAlign::Right,
+                    );
+                }
+                draw_line(&mut engine, layer, (x, y), (y, x), Color::RED);
+                draw_line(
+                    &mut engine,
+                    layer,
+                    (x, y),
+                    (x.saturating_neg(), 2),
+                    Color::RED,
+                );
+                flood_fill(&mut engine, layer, x, y, Color::BLUE);
+                get_composed_cell(&engine, layer, x, y);
+                draw_engine_output(&mut engine, layer, x, y, &source);
+                draw_target(&mut engine, layer, (x, y), &target);
+                draw_text_art(&mut engine, layer, (x, y), &art);
+                draw_big_text(&mut engine, layer, (x, y), "Hi", Color::RED, BigFont::Block);
+                draw_fps_counter(&mut engine, layer, x, y);
+                draw_hex_filled(
+                    &mut engine,
+                    layer,
+                    HexPosition::new(x as i32 * 1000, y as i32),
+                    &HexLayout::default(),
+                    Color::RED,
+                );
+                draw_hex_outline(
+                    &mut engine,
+                    layer,
+                    HexPosition::new(x as i32, y as i32 * 1000),
+                    &HexLayout::default(),
+                    Color::RED,
+                );
+                draw_circle_octad(&mut engine, layer, (0.0, 0.0), x, Color::RED);
+                fill_circle_octad(&mut engine, layer, (4.0, 2.0), x, Color::RED);
+                draw_ellipse_octad(&mut engine, layer, (0.0, 0.0), (x, y), Color::RED);
+                fill_ellipse_octad(&mut engine, layer, (4.0, 2.0), (x, y), Color::RED);
+                draw_circle_blocktad(&mut engine, layer, (0.0, 0.0), x, Color::RED);
+                fill_circle_blocktad(&mut engine, layer, (4.0, 2.0), y, Color::RED);
+                end_frame(&mut engine).unwrap();
+            }
+
+            for &(x, y) in &float_points {
+                start_frame(&mut engine);
+                draw_octad(&mut engine, layer, x, y, Color::RED);
+                erase_octad(&mut engine, layer, x, y);
+                draw_blocktad(&mut engine, layer, x, y, Color::RED);
+                erase_blocktad(&mut engine, layer, x, y);
+                draw_sextant(&mut engine, layer, x, y, Color::RED);
+                erase_sextant(&mut engine, layer, x, y);
+                draw_quadrant(&mut engine, layer, x, y, Color::RED);
+                erase_quadrant(&mut engine, layer, x, y);
+                draw_twoxel(&mut engine, layer, x, y, Color::RED);
+                draw_sprite(&mut engine, layer, x, y, &sprite);
+                draw_sprite_scaled(&mut engine, layer, x, y, &sprite, 3);
+                draw_mini_text(&mut engine, layer, (x, y), "Hi", Color::RED);
+                flood_fill_octad(&mut engine, layer, x, y, Color::BLUE);
+                get_composed_octad(&engine, layer, x, y);
+                get_composed_twoxel(&engine, layer, x, y);
+                draw_circle_octad(&mut engine, layer, (x, y), 3, Color::RED);
+                fill_ellipse_octad(&mut engine, layer, (x, y), (2, 1), Color::RED);
+                for b in [
+                    (f32::MAX, f32::NEG_INFINITY),
+                    (-1.0e6, 3.5),
+                    (f32::NAN, 0.0),
+                ] {
+                    draw_line_twoxel(&mut engine, layer, (x, y), b, Color::RED);
+                    draw_line_octad(&mut engine, layer, (x, y), b, Color::RED);
+                    draw_rect_outline_twoxel(&mut engine, layer, (x, y), b, Color::RED);
+                    fill_triangle_octad(&mut engine, layer, (x, y), b, (4.0, 2.0), Color::RED);
+                    any_octad_in_rect(&engine, layer, (x, y), b);
+                }
+                end_frame(&mut engine).unwrap();
+            }
+
+            start_frame(&mut engine);
+            draw_polygon_octad(&mut engine, layer, &path, Color::RED);
+            fill_polygon_octad(&mut engine, layer, &path, Color::RED);
+            draw_path_twoxel(&mut engine, layer, &path_gradient);
+
+            end_frame(&mut engine).unwrap();
+        }
+    }
+}
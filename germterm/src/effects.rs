@@ -0,0 +1,7 @@
+//! Ready-made visual effects built on top of the drawing functions.
+//!
+//! Effects are opt-in through cargo features, keeping the core lean:
+//! - `screensaver` - [`screensaver`], classic plasma, matrix rain and fire effects.
+
+#[cfg(feature = "screensaver")]
+pub mod screensaver;
@@ -0,0 +1,495 @@
+//! Classic self-contained screensaver effects: [`Plasma`], [`MatrixRain`] and [`Fire`].
+//!
+//! Each effect is configured through a palette and a couple of knobs, and is fully deterministic:
+//! the same seed and the same sequence of delta times always produce the same output.
+//! Randomness is derived from [`hash2`], so there's no hidden RNG state.
+//!
+//! Only available with the `screensaver` feature enabled.
+//!
+//! ## Usage
+//! [`Plasma`] is stateless and drawn from a point in time using [`draw_plasma`].
+//! [`MatrixRain`] and [`Fire`] carry their own simulation state, advanced using
+//! [`update_matrix_rain`] and [`update_fire`], and drawn using [`draw_matrix_rain`] and [`draw_fire`].
+
+use std::f32::consts::TAU;
+
+use crate::{
+    color::{Color, ColorGradient, GradientStop, sample_gradient},
+    draw::{draw_rect, draw_text, draw_twoxel},
+    engine::Engine,
+    layer::LayerIndex,
+    noise::hash2,
+    rich_text::{Attributes, RichText},
+};
+
+/// A smoothly shifting color field made of summed sine waves.
+#[derive(Clone)]
+pub struct Plasma {
+    /// Sampled with the field value in the range `0.0..=1.0`.
+    pub palette: ColorGradient,
+    /// How fast the field shifts over time.
+    pub speed: f32,
+    /// The size of the blobs in cells, larger values result in smoother fields.
+    pub scale: f32,
+    /// Draws each cell as two twoxels, doubling the vertical resolution.
+    pub twoxel: bool,
+    pub seed: u32,
+}
+
+impl Default for Plasma {
+    fn default() -> Self {
+        Self {
+            palette: ColorGradient::new(vec![
+                GradientStop::new(0.0, Color::new(20, 10, 60, 255)),
+                GradientStop::new(0.35, Color::VIOLET),
+                GradientStop::new(0.65, Color::PINK),
+                GradientStop::new(1.0, Color::new(255, 230, 120, 255)),
+            ]),
+            speed: 1.0,
+            scale: 8.0,
+            twoxel: true,
+            seed: 0,
+        }
+    }
+}
+
+/// Draws a [`Plasma`] field filling the area at `pos` with the given `size` in cells, at the point in time `time`.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{engine::Engine, layer::create_layer};
+/// # use germterm::effects::screensaver::{Plasma, draw_plasma};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// let plasma = Plasma::default();
+/// let time: f32 = engine.game_time;
+/// draw_plasma(&mut engine, layer, &plasma, (0, 0), (40, 20), time);
+/// ```
+pub fn draw_plasma(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    plasma: &Plasma,
+    pos: (i16, i16),
+    size: (u16, u16),
+    time: f32,
+) {
+    let phases: [f32; 4] = std::array::from_fn(|i| unit(hash2(i as i32, 0, plasma.seed)) * TAU);
+    let t: f32 = time * plasma.speed;
+    let scale: f32 = plasma.scale.max(f32::EPSILON);
+
+    let plasma_at = |x: f32, y: f32| -> Color {
+        // Cells are roughly twice as tall as they're wide
+        let (x, y): (f32, f32) = (x / scale, y * 2.0 / scale);
+        let value: f32 = (x + t + phases[0]).sin()
+            + (y - t * 0.7 + phases[1]).sin()
+            + ((x + y) * 0.7 + t * 0.5 + phases[2]).sin()
+            + ((x * x + y * y).sqrt() - t * 1.3 + phases[3]).sin();
+        sample_gradient(&plasma.palette, (value + 4.0) / 8.0)
+    };
+
+    for row in 0..size.1 {
+        for col in 0..size.0 {
+            let x: i16 = pos.0.saturating_add(col as i16);
+            let y: i16 = pos.1.saturating_add(row as i16);
+
+            if plasma.twoxel {
+                for half in [0.0, 0.5] {
+                    let color: Color = plasma_at(col as f32 + 0.5, row as f32 + half + 0.25);
+                    draw_twoxel(engine, layer_index, x as f32, y as f32 + half, color);
+                }
+            } else {
+                let color: Color = plasma_at(col as f32 + 0.5, row as f32 + 0.5);
+                draw_rect(engine, layer_index, (x, y), (1, 1), color);
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct MatrixRainConfig {
+    /// Sampled along each trail, from `0.0` at its tail to `1.0` right behind its head.
+    pub palette: ColorGradient,
+    pub head_color: Color,
+    /// The range of speeds a drop can fall at in cells per second.
+    pub speed: (f32, f32),
+    /// How crowded the screen gets in the range `0.0..=1.0`, lower values leave longer gaps between drops.
+    pub density: f32,
+    /// The range of trail lengths in cells.
+    pub trail_length: (u16, u16),
+    /// The glyphs drops are made of, picked at random and changing every once in a while.
+    pub glyphs: &'static str,
+    pub seed: u32,
+}
+
+impl Default for MatrixRainConfig {
+    fn default() -> Self {
+        Self {
+            palette: ColorGradient::new(vec![
+                GradientStop::new(0.0, Color::new(0, 40, 0, 255)),
+                GradientStop::new(1.0, Color::new(60, 255, 90, 255)),
+            ]),
+            head_color: Color::new(220, 255, 220, 255),
+            speed: (6.0, 16.0),
+            density: 0.6,
+            trail_length: (6, 16),
+            glyphs: "ｱｲｳｴｵｶｷｸｹｺｻｼｽｾｿﾀﾁﾂﾃﾄﾅﾆﾇﾈﾉﾊﾋﾌﾍﾎﾏﾐﾑﾒﾓﾔﾕﾖﾗﾘﾙﾚﾛﾜ0123456789Z:.=*+<>",
+            seed: 0,
+        }
+    }
+}
+
+/// Columns of glyphs falling down the screen, each led by a bright head.
+pub struct MatrixRain {
+    pub config: MatrixRainConfig,
+    rows: u16,
+    glyphs: Vec<char>,
+    drops: Vec<RainDrop>,
+    time: f32,
+}
+
+struct RainDrop {
+    head_y: f32,
+    speed: f32,
+    length: f32,
+    generation: u32,
+}
+
+impl MatrixRain {
+    /// Creates the rain for an area of `cols` by `rows` cells, with drops scattered across it.
+    pub fn new(cols: u16, rows: u16, config: MatrixRainConfig) -> Self {
+        let mut rain: MatrixRain = Self {
+            glyphs: config.glyphs.chars().collect(),
+            config,
+            rows,
+            drops: Vec::with_capacity(cols as usize),
+            time: 0.0,
+        };
+
+        for col in 0..cols {
+            let mut drop: RainDrop = RainDrop {
+                head_y: 0.0,
+                speed: 0.0,
+                length: 0.0,
+                generation: 0,
+            };
+            respawn_drop(&rain.config, rows, col, &mut drop);
+            // Starting mid-fall, so the screen doesn't begin empty
+            drop.head_y +=
+                unit(hash2(col as i32, -1, rain.config.seed)) * (rows as f32 + drop.length);
+            rain.drops.push(drop);
+        }
+        rain
+    }
+}
+
+/// Advances the drops of a [`MatrixRain`] by `delta_time` seconds.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{engine::Engine, layer::create_layer};
+/// # use germterm::effects::screensaver::{MatrixRain, MatrixRainConfig, draw_matrix_rain, update_matrix_rain};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// let mut rain = MatrixRain::new(40, 20, MatrixRainConfig::default());
+///
+/// update_matrix_rain(&mut rain, engine.delta_time);
+/// draw_matrix_rain(&mut engine, layer, &rain, (0, 0));
+/// ```
+pub fn update_matrix_rain(rain: &mut MatrixRain, delta_time: f32) {
+    rain.time += delta_time;
+
+    for (col, drop) in rain.drops.iter_mut().enumerate() {
+        drop.head_y += drop.speed * delta_time;
+        if drop.head_y - drop.length > rain.rows as f32 {
+            drop.generation = drop.generation.wrapping_add(1);
+            respawn_drop(&rain.config, rain.rows, col as u16, drop);
+        }
+    }
+}
+
+/// Draws a [`MatrixRain`] with its top-left corner at `pos`.
+///
+/// Only the glyphs are drawn, the background is left untouched.
+pub fn draw_matrix_rain(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    rain: &MatrixRain,
+    pos: (i16, i16),
+) {
+    if rain.glyphs.is_empty() {
+        return;
+    }
+
+    let seed: u32 = rain.config.seed;
+    for (col, drop) in rain.drops.iter().enumerate() {
+        let head_row: i32 = drop.head_y.floor() as i32;
+        let first_row: i32 = (head_row - drop.length as i32 + 1).max(0);
+        let last_row: i32 = head_row.min(rain.rows as i32 - 1);
+
+        for row in first_row..=last_row {
+            // Every cell swaps its glyph a few times per second, at its own random moment
+            let stagger: f32 = unit(hash2(col as i32, row, seed));
+            let tick: i32 = (rain.time * 3.0 + stagger) as i32;
+            let glyph_hash: u32 = hash2(col as i32, row, seed ^ tick as u32);
+            let glyph: char = rain.glyphs[glyph_hash as usize % rain.glyphs.len()];
+
+            let rich_text: RichText = if row == head_row {
+                RichText::new(glyph)
+                    .with_fg(rain.config.head_color)
+                    .with_attributes(Attributes::BOLD | Attributes::NO_BG_COLOR)
+            } else {
+                let t: f32 = 1.0 - (head_row - row) as f32 / drop.length;
+                RichText::new(glyph)
+                    .with_fg(sample_gradient(&rain.config.palette, t))
+                    .with_attributes(Attributes::NO_BG_COLOR)
+            };
+
+            let x: i16 = pos.0.saturating_add(col as i16);
+            let y: i16 = pos.1.saturating_add(row as i16);
+            draw_text(engine, layer_index, x, y, rich_text);
+        }
+    }
+}
+
+fn respawn_drop(config: &MatrixRainConfig, rows: u16, col: u16, drop: &mut RainDrop) {
+    let hash: u32 = hash2(col as i32, drop.generation as i32, config.seed);
+    let (min_length, max_length): (u16, u16) = (
+        config.trail_length.0.max(1),
+        config.trail_length.1.max(config.trail_length.0).max(1),
+    );
+
+    drop.speed = lerp(config.speed.0, config.speed.1, unit(hash));
+    drop.length = (min_length + (hash >> 8) as u16 % (max_length - min_length + 1)) as f32;
+    // The gap above the screen grows as the density drops
+    let gap: f32 = unit(hash.rotate_left(16)) * rows as f32 / config.density.clamp(0.05, 1.0);
+    drop.head_y = -gap;
+}
+
+#[derive(Clone)]
+pub struct FireConfig {
+    /// Sampled with the heat in the range `0.0..=1.0`, heat of `0.0` is never drawn.
+    pub palette: ColorGradient,
+    /// The amount of simulation steps per second.
+    pub speed: f32,
+    /// The average heat lost per half-cell the flames rise, higher values result in shorter flames.
+    pub cooling: f32,
+    pub seed: u32,
+}
+
+impl Default for FireConfig {
+    fn default() -> Self {
+        Self {
+            palette: ColorGradient::new(vec![
+                GradientStop::new(0.0, Color::new(30, 0, 0, 255)),
+                GradientStop::new(0.3, Color::new(200, 20, 0, 255)),
+                GradientStop::new(0.6, Color::ORANGE),
+                GradientStop::new(0.85, Color::YELLOW),
+                GradientStop::new(1.0, Color::WHITE),
+            ]),
+            speed: 30.0,
+            cooling: 0.04,
+            seed: 0,
+        }
+    }
+}
+
+/// The classic cellular fire: the bottom row is kept at full heat,
+/// which rises up while randomly cooling down and drifting sideways.
+///
+/// Simulated at twice the vertical resolution, each cell is drawn as two twoxels.
+pub struct Fire {
+    pub config: FireConfig,
+    cols: u16,
+    heat_rows: u16,
+    heat: Vec<f32>,
+    step: u32,
+    step_accumulator: f32,
+}
+
+impl Fire {
+    /// Creates a fire for an area of `cols` by `rows` cells, starting out with only the bottom row burning.
+    pub fn new(cols: u16, rows: u16, config: FireConfig) -> Self {
+        let heat_rows: u16 = rows.saturating_mul(2);
+        let mut heat: Vec<f32> = vec![0.0; cols as usize * heat_rows as usize];
+        if heat_rows > 0 {
+            let bottom_row_start: usize = (heat_rows as usize - 1) * cols as usize;
+            heat[bottom_row_start..].fill(1.0);
+        }
+
+        Self {
+            config,
+            cols,
+            heat_rows,
+            heat,
+            step: 0,
+            step_accumulator: 0.0,
+        }
+    }
+}
+
+/// Advances a [`Fire`] by `delta_time` seconds.
+///
+/// The simulation runs in fixed steps of `1.0 / speed` seconds, so the flames move at the same pace regardless of the frame rate.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{engine::Engine, layer::create_layer};
+/// # use germterm::effects::screensaver::{Fire, FireConfig, draw_fire, update_fire};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// let mut fire = Fire::new(40, 10, FireConfig::default());
+///
+/// update_fire(&mut fire, engine.delta_time);
+/// draw_fire(&mut engine, layer, &fire, (0, 10));
+/// ```
+pub fn update_fire(fire: &mut Fire, delta_time: f32) {
+    if fire.config.speed <= 0.0 {
+        return;
+    }
+
+    let step_duration: f32 = 1.0 / fire.config.speed;
+    fire.step_accumulator += delta_time;
+    while fire.step_accumulator >= step_duration {
+        fire.step_accumulator -= step_duration;
+        step_fire(fire);
+    }
+}
+
+/// Draws a [`Fire`] with its top-left corner at `pos`.
+pub fn draw_fire(engine: &mut Engine, layer_index: LayerIndex, fire: &Fire, pos: (i16, i16)) {
+    let cols: usize = fire.cols as usize;
+
+    for heat_row in 0..fire.heat_rows as usize {
+        for col in 0..cols {
+            let heat: f32 = fire.heat[heat_row * cols + col];
+            if heat <= 0.0 {
+                continue;
+            }
+
+            let x: f32 = pos.0 as f32 + col as f32;
+            let y: f32 = pos.1 as f32 + heat_row as f32 * 0.5;
+            let color: Color = sample_gradient(&fire.config.palette, heat);
+            draw_twoxel(engine, layer_index, x, y, color);
+        }
+    }
+}
+
+fn step_fire(fire: &mut Fire) {
+    let cols: usize = fire.cols as usize;
+    fire.step = fire.step.wrapping_add(1);
+
+    // Every row pulls the heat from a random neighbor of the cell underneath it, cooling it down on the way
+    for heat_row in 0..(fire.heat_rows as usize).saturating_sub(1) {
+        for col in 0..cols {
+            let hash: u32 = hash2(
+                col as i32,
+                heat_row as i32,
+                fire.config.seed ^ fire.step.wrapping_mul(0x9e37_79b9),
+            );
+            let drift: i32 = (hash % 3) as i32 - 1;
+            let source_col: usize = (col as i32 + drift).clamp(0, cols as i32 - 1) as usize;
+            let cooling: f32 = unit(hash >> 2) * fire.config.cooling * 2.0;
+
+            let below: f32 = fire.heat[(heat_row + 1) * cols + source_col];
+            fire.heat[heat_row * cols + col] = (below - cooling).max(0.0);
+        }
+    }
+}
+
+/// Maps a hash onto `0.0..=1.0`.
+#[inline]
+fn unit(hash: u32) -> f32 {
+    hash as f32 / u32::MAX as f32
+}
+
+#[inline]
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::{end_frame, init, start_frame},
+        layer::create_layer,
+        test_support::frame_hash,
+    };
+
+    /// Uneven delta times, as a real frame loop would produce.
+    const DELTA_TIMES: [f32; 6] = [0.035, 0.05, 0.1, 0.04, 0.25, 0.05];
+
+    /// Renders a frame per delta time, drawing with `draw`, returning the hash of each frame.
+    fn frame_hashes(mut draw: impl FnMut(&mut Engine, LayerIndex, f32)) -> Vec<u64> {
+        let mut engine: Engine = Engine::offscreen(24, 12);
+        let layer: LayerIndex = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+
+        DELTA_TIMES
+            .iter()
+            .map(|&delta_time| {
+                start_frame(&mut engine);
+                draw(&mut engine, layer, delta_time);
+                end_frame(&mut engine).unwrap();
+                frame_hash(&engine)
+            })
+            .collect()
+    }
+
+    /// Checks that seed `7` renders the same frames on every run, ending in `last_frame_hash`,
+    /// which has to be updated whenever the look of the effect changes on purpose.
+    fn assert_deterministic(last_frame_hash: u64, hashes: impl Fn(u32) -> Vec<u64>) {
+        let first_run: Vec<u64> = hashes(7);
+        assert_eq!(first_run.last(), Some(&last_frame_hash));
+        assert_eq!(first_run, hashes(7));
+        assert_ne!(first_run, hashes(8));
+        // Every frame moves on from the last one
+        assert!(first_run.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn plasma_is_deterministic_under_a_seed() {
+        assert_deterministic(0x57d1_e9c5_c42f_4935, |seed| {
+            let plasma: Plasma = Plasma {
+                seed,
+                ..Default::default()
+            };
+            let mut time: f32 = 0.0;
+            frame_hashes(|engine, layer, delta_time| {
+                time += delta_time;
+                draw_plasma(engine, layer, &plasma, (0, 0), (24, 12), time);
+            })
+        });
+    }
+
+    #[test]
+    fn matrix_rain_is_deterministic_under_a_seed() {
+        assert_deterministic(0xbade_2387_7536_ed2e, |seed| {
+            let config: MatrixRainConfig = MatrixRainConfig {
+                seed,
+                ..Default::default()
+            };
+            let mut rain: MatrixRain = MatrixRain::new(24, 12, config);
+            frame_hashes(|engine, layer, delta_time| {
+                update_matrix_rain(&mut rain, delta_time);
+                draw_matrix_rain(engine, layer, &rain, (0, 0));
+            })
+        });
+    }
+
+    #[test]
+    fn fire_is_deterministic_under_a_seed() {
+        assert_deterministic(0x4551_263d_d883_58a7, |seed| {
+            let config: FireConfig = FireConfig {
+                seed,
+                ..Default::default()
+            };
+            let mut fire: Fire = Fire::new(24, 12, config);
+            frame_hashes(|engine, layer, delta_time| {
+                update_fire(&mut fire, delta_time);
+                draw_fire(engine, layer, &fire, (0, 0));
+            })
+        });
+    }
+}
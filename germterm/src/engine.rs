@@ -5,51 +5,223 @@
 //! Essentially, this is the central "body" that coordinates everything.
 
 use crate::{
-    color::{Color, ColorRgb},
-    draw::erase_rect,
-    fps_counter::{FpsCounter, update_fps_counter},
-    fps_limiter::{self, FpsLimiter, wait_for_next_frame},
-    frame::{FramePair, compose_frame_buffer, draw_to_terminal},
-    layer::{Layer, LayerIndex, create_layer},
-    particle::{ParticleState, update_and_draw_particles},
+    adaptive_quality::{AdaptiveQuality, end_frame_timing, start_frame_timing},
+    bell::{BellState, reset_bell, take_pending_audible_bell, update_and_draw_bell_flash},
+    cast::{CastRecorder, CastTee, record_cast_frame, stop_cast_recording, take_cast_repaint},
+    color::{Color, ColorDepth, ColorEffect, ColorRgb, detect_color_depth},
+    cursor::{
+        CursorState, draw_cursor, is_cursor_shown, reset_cursor_request, reset_cursor_state,
+        restore_cursor_style,
+    },
+    floating_text::{FloatingTextState, update_and_draw_floating_texts},
+    fps_counter::{FpsCounter, record_changed_cells, reset_fps_counter, update_fps_counter},
+    fps_limiter::{
+        self, FpsLimiter, reset_fps_limiter, wait_for_next_frame, wait_for_next_frame_with,
+    },
+    frame::{
+        FramePair, TerminalColors, apply_cell_effect, clear_for_repaint, compose_frame_buffer,
+        cull_draw_calls, dither_backgrounds, draw_to_terminal, move_into_view, set_color_depth,
+    },
+    idle::{IdleState, end_idle_frame, reset_idle_state, start_idle_frame, wait_while_idle},
+    inline::{
+        InlineMode, InlineViewport, leave_inline_region, park_inline_cursor, refresh_inline_region,
+        reserve_inline_region, touch_inline_row,
+    },
+    input::{
+        InputSampling, InputSource, KeyState, TimedEvent, advance_key_state, reset_key_state,
+        sample_input_while_idle,
+    },
+    layer::Layer,
+    particle::{EmitterSlot, ParticleState, update_and_draw_particles, update_emitters},
+    replay::{InputPlayback, InputRecording, next_played_back_frame, record_frame},
+    theme::ThemeState,
 };
-use crossterm::{cursor, event, execute, terminal};
+use crossterm::{cursor, event, execute, queue, terminal};
 use std::{
-    io::{self},
-    time::Duration,
+    collections::VecDeque,
+    io::{self, Write},
+    panic,
+    sync::{Mutex, Once, PoisonError},
+    time::{Duration, Instant},
 };
 
+const DEFAULT_DELTA_TIME: f32 = 0.01667;
+/// Slightly longer than the common initial key repeat delay, so held keys don't flicker before repeats kick in.
+const DEFAULT_KEY_REPEAT_TIMEOUT: Duration = Duration::from_millis(600);
+
 pub struct Engine {
     pub delta_time: f32,
     pub game_time: f32,
-    pub stdout: io::Stdout,
+    /// Where frames and terminal control sequences are written to, the standard output unless created with [`Engine::remote`].
+    pub output: Box<dyn Write + Send>,
     pub(crate) default_blending_color: Color,
     pub(crate) fps_counter: FpsCounter,
     pub(crate) max_layer_index: usize,
     pub(crate) frame: FramePair,
     pub(crate) fps_limiter: FpsLimiter,
     pub(crate) particle_state: Vec<ParticleState>,
+    pub(crate) particle_emitters: Vec<EmitterSlot>,
+    pub(crate) floating_text_state: Vec<FloatingTextState>,
+    pub(crate) input_queue: VecDeque<TimedEvent>,
+    pub(crate) input_source: InputSource,
+    pub(crate) key_state: KeyState,
+    pub(crate) input_recording: Option<InputRecording>,
+    pub(crate) input_playback: Option<InputPlayback>,
+    pub(crate) cast_recording: Option<CastRecorder>,
+    input_sampling: InputSampling,
+    cell_budget: Option<usize>,
+    culled_draw_calls: usize,
+    pub(crate) bell: BellState,
+    pub(crate) adaptive_quality: Option<AdaptiveQuality>,
+    pub(crate) idle: IdleState,
+    pub(crate) cursor: CursorState,
+    pub(crate) theme: ThemeState,
+    pub(crate) terminal_colors: TerminalColors,
+    screen_effect: Option<ColorEffect>,
     title: &'static str,
+    mouse_capture: bool,
+    bracketed_paste: bool,
+    focus_events: bool,
+    keyboard_enhancement: bool,
+    is_keyboard_enhanced: bool,
+    synchronized_output: bool,
+    pub(crate) hyperlinks: bool,
+    pub(crate) offscreen: bool,
+    pub(crate) remote: bool,
+    /// Whether the terminal gets cleared and the next frame fully repainted, eg. after a resize.
+    needs_repaint: bool,
+    pub(crate) inline: Option<InlineViewport>,
 }
 
 impl Engine {
     pub fn new(cols: u16, rows: u16) -> Self {
+        let default_blending_color: Color = match termbg::rgb(Duration::from_millis(100)) {
+            Ok(rgb) => Color::new(rgb.r as u8, rgb.g as u8, rgb.b as u8, 255),
+            Err(_) => Color::BLACK,
+        };
+        Self::with_blending_color(cols, rows, default_blending_color)
+    }
+
+    /// Creates an engine that never touches the terminal.
+    ///
+    /// Calling [`end_frame`] composes the frame, but instead of drawing it to the terminal
+    /// the composed cells are kept around, so they can be drawn onto another engine
+    /// using [`crate::draw::draw_engine_output`].
+    /// This allows for eg. split-screen views or picture-in-picture minimaps,
+    /// each with their own layers and particles.
+    ///
+    /// Offscreen engines are uncapped by default, as frame pacing is left to the engine drawing to the terminal.
+    /// [`init`] and [`exit_cleanup`] still have to be called, but leave the terminal state alone.
+    ///
+    /// The default blending color is [`Color::BLACK`], use [`override_default_blending_color`] to match the parent engine.
+    pub fn offscreen(cols: u16, rows: u16) -> Self {
+        Self {
+            offscreen: true,
+            fps_limiter: FpsLimiter::new(0, 0.001, 0.002),
+            ..Self::with_blending_color(cols, rows, Color::BLACK)
+        }
+    }
+
+    /// Creates an engine drawing into `output` instead of the local terminal, eg. a socket serving a game over SSH or telnet.
+    ///
+    /// The local terminal is never touched: the size is given up front, the background color isn't queried,
+    /// and [`init`] and [`exit_cleanup`] only write the alternate screen, mouse and focus sequences into `output`.
+    ///
+    /// This shifts some responsibilities over to the caller:
+    /// - Putting the remote terminal into raw mode, eg. through the pty of the SSH session or telnet option negotiation.
+    /// - Reporting size changes with [`set_size`], eg. from a telnet NAWS report.
+    /// - Reading input from the connection and handing it over with [`crate::input::push_input`],
+    ///   as the local terminal is never polled for input.
+    /// - Picking the [`ColorDepth`], which defaults to [`ColorDepth::TrueColor`], and the default blending color,
+    ///   which defaults to [`Color::BLACK`].
+    ///
+    /// The kitty keyboard protocol and [`Engine::inline`] rendering rely on querying the terminal,
+    /// so they aren't supported for remote engines.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::{draw::draw_text, engine::{Engine, end_frame, exit_cleanup, init, set_size, start_frame}};
+    /// # use germterm::layer::create_layer;
+    /// # use std::{io::{self, Write}, sync::{Arc, Mutex}};
+    /// #[derive(Clone, Default)]
+    /// struct Socket(Arc<Mutex<Vec<u8>>>);
+    ///
+    /// impl Write for Socket {
+    ///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    ///         self.0.lock().unwrap().write(buf)
+    ///     }
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let socket = Socket::default();
+    /// let mut engine = Engine::remote(20, 4, socket.clone()).limit_fps(0);
+    /// let layer = create_layer(&mut engine, 0);
+    /// init(&mut engine).unwrap();
+    ///
+    /// start_frame(&mut engine);
+    /// draw_text(&mut engine, layer, 1, 1, "Welcome!");
+    /// end_frame(&mut engine).unwrap();
+    ///
+    /// // The client reported a new window size
+    /// set_size(&mut engine, 40, 10);
+    ///
+    /// exit_cleanup(&mut engine).unwrap();
+    /// let bytes: Vec<u8> = socket.0.lock().unwrap().clone();
+    /// assert!(String::from_utf8(bytes).unwrap().contains("Welcome!"));
+    /// ```
+    pub fn remote(cols: u16, rows: u16, output: impl Write + Send + 'static) -> Self {
         Self {
-            delta_time: 0.01667,
+            remote: true,
+            input_source: InputSource::Pushed,
+            output: Box::new(output),
+            terminal_colors: TerminalColors::new(ColorDepth::TrueColor),
+            ..Self::with_blending_color(cols, rows, Color::BLACK)
+        }
+    }
+
+    fn with_blending_color(cols: u16, rows: u16, default_blending_color: Color) -> Self {
+        Self {
+            delta_time: DEFAULT_DELTA_TIME,
             game_time: 0.0,
             title: "my-awesome-terminal",
-            stdout: io::stdout(),
+            mouse_capture: true,
+            bracketed_paste: true,
+            focus_events: true,
+            keyboard_enhancement: false,
+            is_keyboard_enhanced: false,
+            synchronized_output: true,
+            hyperlinks: true,
+            offscreen: false,
+            remote: false,
+            needs_repaint: false,
+            inline: None,
+            output: Box::new(io::stdout()),
             max_layer_index: 0,
             frame: FramePair::new(cols, rows),
             fps_limiter: FpsLimiter::new(60, 0.001, 0.002),
             fps_counter: FpsCounter::new(0.3),
             particle_state: Vec::with_capacity(512),
-            default_blending_color: {
-                match termbg::rgb(Duration::from_millis(100)) {
-                    Ok(rgb) => Color::new(rgb.r as u8, rgb.g as u8, rgb.b as u8, 255),
-                    Err(_) => Color::BLACK,
-                }
-            },
+            particle_emitters: Vec::new(),
+            floating_text_state: Vec::with_capacity(32),
+            input_queue: VecDeque::new(),
+            input_source: InputSource::Terminal,
+            key_state: KeyState::new(DEFAULT_KEY_REPEAT_TIMEOUT),
+            input_recording: None,
+            input_playback: None,
+            cast_recording: None,
+            input_sampling: InputSampling::PerFrame,
+            cell_budget: None,
+            culled_draw_calls: 0,
+            bell: BellState::new(),
+            adaptive_quality: None,
+            idle: IdleState::new(),
+            cursor: CursorState::new(),
+            theme: ThemeState::new(),
+            terminal_colors: TerminalColors::new(detect_color_depth()),
+            screen_effect: None,
+            default_blending_color,
         }
     }
 
@@ -58,6 +230,117 @@ impl Engine {
         self
     }
 
+    /// Whether [`init`] should enable mouse capture, making mouse events show up in [`crate::input::poll_input`].
+    ///
+    /// Enabled by default. [`exit_cleanup`] disables the capture again when it was enabled.
+    pub fn enable_mouse_capture(mut self, value: bool) -> Self {
+        self.mouse_capture = value;
+        self
+    }
+
+    /// Whether [`init`] should enable bracketed paste, making pasted text show up as a single [`event::Event::Paste`]
+    /// instead of a key event per char.
+    ///
+    /// Enabled by default. [`exit_cleanup`] disables it again when it was enabled.
+    pub fn enable_bracketed_paste(mut self, value: bool) -> Self {
+        self.bracketed_paste = value;
+        self
+    }
+
+    /// Whether [`init`] should enable focus reporting, making [`event::Event::FocusGained`]
+    /// and [`event::Event::FocusLost`] show up when the terminal window gains or loses focus.
+    ///
+    /// Enabled by default. [`exit_cleanup`] disables it again when it was enabled.
+    pub fn enable_focus_events(mut self, value: bool) -> Self {
+        self.focus_events = value;
+        self
+    }
+
+    /// Whether [`init`] should enable the kitty keyboard protocol on terminals supporting it,
+    /// see [`is_keyboard_enhanced`].
+    ///
+    /// The protocol reports key releases and repeats, and disambiguates key combos which legacy terminal input can't tell apart,
+    /// like `Ctrl+I` and `Tab`. Terminals supporting it include kitty, WezTerm, foot, Ghostty and Alacritty,
+    /// on other terminals input silently falls back to the legacy behavior.
+    ///
+    /// Disabled by default. [`exit_cleanup`] disables the protocol again when it was enabled.
+    pub fn enable_keyboard_enhancement(mut self, value: bool) -> Self {
+        self.keyboard_enhancement = value;
+        self
+    }
+
+    /// Whether frames are wrapped in synchronized update sequences, so the terminal presents each frame at once instead of tearing mid-frame.
+    ///
+    /// Enabled by default. Terminals without support ignore the sequences, but some misbehave, in which case it can be disabled.
+    pub fn synchronized_output(mut self, value: bool) -> Self {
+        self.synchronized_output = value;
+        self
+    }
+
+    /// Whether text drawn with [`crate::rich_text::RichText::with_link`] is written as clickable OSC 8 hyperlinks.
+    ///
+    /// Enabled by default. Terminals without support show the plain text, but the sequences end up as noise
+    /// when the output is piped into a file, in which case it can be disabled.
+    pub fn hyperlinks(mut self, value: bool) -> Self {
+        self.hyperlinks = value;
+        self
+    }
+
+    /// How often the terminal gets sampled for input events, see [`InputSampling`].
+    ///
+    /// Defaults to [`InputSampling::PerFrame`].
+    pub fn input_sampling(mut self, value: InputSampling) -> Self {
+        self.input_sampling = value;
+        self
+    }
+
+    /// How long a key counts as held after its last press or repeat, on terminals that don't report key releases.
+    ///
+    /// Should be longer than the initial key repeat delay of the terminal, so held keys aren't released before they start repeating.
+    /// Defaults to 600 ms.
+    pub fn key_repeat_timeout(mut self, value: Duration) -> Self {
+        self.key_state.repeat_timeout = value;
+        self
+    }
+
+    /// Limits the amount of non-empty cells a frame can cover, allowing the frame to degrade gracefully on small terminals.
+    ///
+    /// When the draw calls of a frame would cover more cells than the budget, whole draw calls are culled
+    /// starting from the lowest [`crate::rich_text::RichText::priority`], until the frame fits.
+    /// Within the same priority, the most recently queued draw calls are culled first.
+    ///
+    /// Disabled (`None`) by default. The amount of culled draw calls can be retrieved with [`culled_draw_calls`].
+    pub fn cell_budget(mut self, value: Option<usize>) -> Self {
+        self.cell_budget = value;
+        self
+    }
+
+    /// The colors the terminal is able to display, colors are quantized to the nearest available color when drawing.
+    ///
+    /// Detected from the environment using [`detect_color_depth`] by default.
+    pub fn color_depth(mut self, value: ColorDepth) -> Self {
+        set_color_depth(&mut self.terminal_colors, value);
+        self
+    }
+
+    /// Whether backgrounds are dithered when drawing with [`ColorDepth::Ansi256`],
+    /// which breaks up the banding of smooth gradients.
+    ///
+    /// The quantization error of a background is carried over to the next cell of the row.
+    /// Cells using sub-cell formats, eg. octads, are left alone. Disabled by default.
+    pub fn dithering(mut self, value: bool) -> Self {
+        self.terminal_colors.dithering = value;
+        self
+    }
+
+    /// Renders into a region of the normal screen instead of the alternate screen, see [`crate::inline`].
+    ///
+    /// The region is as tall as the engine. `None` by default.
+    pub fn inline(mut self, value: Option<InlineMode>) -> Self {
+        self.inline = value.map(InlineViewport::new);
+        self
+    }
+
     /// A value of `0` will result in uncapped FPS.
     pub fn limit_fps(mut self, value: u32) -> Self {
         fps_limiter::limit_fps(&mut self.fps_limiter, value);
@@ -65,6 +348,32 @@ impl Engine {
     }
 }
 
+/// Resizes the engine to `cols` x `rows` cells, eg. after the terminal of a [remote](Engine::remote) engine reported a resize.
+///
+/// The terminal is cleared and fully repainted by the next [`end_frame`]. Layers are kept.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{crossterm::event::Event, engine::{Engine, set_size}, input::poll_input};
+/// let mut engine = Engine::new(40, 20);
+///
+/// for event in poll_input() {
+///     if let Event::Resize(cols, rows) = event {
+///         set_size(&mut engine, cols, rows);
+///     }
+/// }
+/// ```
+pub fn set_size(engine: &mut Engine, cols: u16, rows: u16) {
+    if (cols, rows) == (engine.frame.width, engine.frame.height) {
+        return;
+    }
+
+    let layers: Vec<Layer> = std::mem::take(&mut engine.frame.layered_draw_queue);
+    engine.frame = FramePair::new(cols, rows);
+    engine.frame.layered_draw_queue = layers;
+    engine.needs_repaint = true;
+}
+
 /// Overrides the default blending color.
 ///
 /// Only use this if you need to support terminals where the background color cannot
@@ -73,9 +382,51 @@ pub fn override_default_blending_color(engine: &mut Engine, color: ColorRgb) {
     engine.default_blending_color = color.into();
 }
 
+/// Sets a [`ColorEffect`] applied to the whole composed frame right before it's drawn, `None` by default.
+///
+/// The frame is compared to the previous one with the effect applied, so turning it on or off
+/// redraws exactly the cells that change. See [`crate::layer::set_layer_effect`] for applying it below a certain layer.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{color::ColorEffect, engine::{Engine, set_screen_effect}};
+/// let mut engine = Engine::new(40, 20);
+/// set_screen_effect(&mut engine, Some(ColorEffect::Grayscale));
+/// ```
+pub fn set_screen_effect(engine: &mut Engine, effect: Option<ColorEffect>) {
+    engine.screen_effect = effect;
+}
+
+/// The amount of draw calls culled in the last frame due to the cell budget, see [`Engine::cell_budget`].
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::engine::{Engine, culled_draw_calls};
+/// let engine = Engine::new(40, 20).cell_budget(Some(200));
+/// let culled = culled_draw_calls(&engine);
+/// ```
+pub fn culled_draw_calls(engine: &Engine) -> usize {
+    engine.culled_draw_calls
+}
+
+/// Whether the kitty keyboard protocol is active, requested with [`Engine::enable_keyboard_enhancement`].
+///
+/// While active, key releases are reported by the terminal, so [`crate::input::is_key_held`]
+/// tracks keys exactly instead of relying on the key repeat timeout.
+pub fn is_keyboard_enhanced(engine: &Engine) -> bool {
+    engine.is_keyboard_enhanced
+}
+
 /// This function should be called once after constructing the [`Engine`] and defining layers,
 /// and before entering the main update loop to initialize the engine.
 ///
+/// The same engine can go through multiple [`init`] and [`exit_cleanup`] cycles, eg. to temporarily
+/// hand the terminal over to a subprocess. Layers persist between sessions, while timers,
+/// FPS metrics, alive particles and floating texts are reset, so each session starts with a `game_time` of zero.
+///
+/// For engines drawing to the local terminal, the first call installs a panic hook which restores the terminal
+/// before the panic message gets printed, the same way [`exit_cleanup`] does. The hook set before is still called afterwards.
+///
 /// # Example
 /// ```rust,no_run
 /// # use germterm::{layer::create_layer, engine::{Engine, init}};
@@ -92,14 +443,59 @@ pub fn init(engine: &mut Engine) -> io::Result<()> {
             .resize_with(layer_count, Layer::new);
     }
 
-    terminal::enable_raw_mode()?;
-    execute!(
-        engine.stdout,
-        terminal::EnterAlternateScreen,
-        terminal::SetTitle(engine.title),
-        event::EnableMouseCapture,
-        cursor::Hide,
-    )?;
+    engine.game_time = 0.0;
+    engine.delta_time = DEFAULT_DELTA_TIME;
+    engine.particle_state.clear();
+    engine.floating_text_state.clear();
+    engine.input_queue.clear();
+    reset_key_state(&mut engine.key_state);
+    reset_bell(&mut engine.bell);
+    reset_fps_counter(&mut engine.fps_counter);
+    reset_fps_limiter(&mut engine.fps_limiter);
+    reset_idle_state(&mut engine.idle);
+    reset_cursor_state(&mut engine.cursor);
+
+    if engine.offscreen {
+        return Ok(());
+    }
+    if engine.remote && engine.inline.is_some() {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "inline rendering isn't supported for remote engines",
+        ));
+    }
+
+    // Remote terminals are put into raw mode by the caller
+    if !engine.remote {
+        terminal::enable_raw_mode()?;
+    }
+    match &mut engine.inline {
+        Some(viewport) => {
+            execute!(engine.output, cursor::Hide)?;
+            reserve_inline_region(&mut engine.output, viewport, engine.frame.height)?;
+        }
+        None => execute!(
+            engine.output,
+            terminal::EnterAlternateScreen,
+            terminal::SetTitle(engine.title),
+            cursor::Hide,
+        )?,
+    }
+    if engine.mouse_capture {
+        execute!(engine.output, event::EnableMouseCapture)?;
+    }
+    if engine.bracketed_paste {
+        execute!(engine.output, event::EnableBracketedPaste)?;
+    }
+    if engine.focus_events {
+        execute!(engine.output, event::EnableFocusChange)?;
+    }
+    if !engine.remote {
+        enable_keyboard_enhancement(engine, terminal::supports_keyboard_enhancement)?;
+    }
+    if !engine.remote {
+        restore_terminal_on_panic(Some(terminal_modes(engine)));
+    }
     Ok(())
 }
 
@@ -107,36 +503,175 @@ pub fn init(engine: &mut Engine) -> io::Result<()> {
 ///
 /// Not calling ['exit_cleanup'] before exiting the program
 /// will result in a messed up terminal state. (Be nice, clean up after yourself!)
+///
+/// The engine can be initialized again afterwards using [`init`], in which case the first frame is fully repainted.
+///
+/// A session recording in progress is stopped, see [`crate::cast::stop_cast_recording`].
 pub fn exit_cleanup(engine: &mut Engine) -> io::Result<()> {
-    terminal::disable_raw_mode()?;
+    if engine.offscreen {
+        return Ok(());
+    }
+
+    stop_cast_recording(engine)?;
+
+    // The alternate screen is blank when re-entered, so the diff baseline has to be too
+    engine.frame.reset_frames();
+    for layer in engine.frame.layered_draw_queue.iter_mut() {
+        layer.draw_calls.clear();
+    }
+
+    restore_cursor_style(&mut engine.output, &mut engine.cursor)?;
+    if let Some(viewport) = &engine.inline {
+        leave_inline_region(&mut engine.output, viewport, engine.frame.height)?;
+    }
+    if !engine.remote {
+        terminal::disable_raw_mode()?;
+        restore_terminal_on_panic(None);
+    }
+    let modes: TerminalModes = terminal_modes(engine);
+    write_terminal_restore(&mut engine.output, modes)?;
+    engine.is_keyboard_enhanced = false;
+    Ok(())
+}
+
+/// Pushes the kitty keyboard protocol flags when requested with [`Engine::enable_keyboard_enhancement`]
+/// and `query_support` reports the terminal supports them.
+fn enable_keyboard_enhancement(
+    engine: &mut Engine,
+    query_support: impl FnOnce() -> io::Result<bool>,
+) -> io::Result<()> {
+    // Querying support fails on some terminals, which is treated the same as no support
+    if !engine.keyboard_enhancement || !query_support().unwrap_or(false) {
+        return Ok(());
+    }
+
     execute!(
-        engine.stdout,
-        terminal::LeaveAlternateScreen,
-        terminal::EnableLineWrap,
-        cursor::Show,
-        event::DisableMouseCapture
+        engine.output,
+        event::PushKeyboardEnhancementFlags(
+            event::KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                | event::KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+        ),
     )?;
+    engine.is_keyboard_enhanced = true;
+    engine.key_state.reports_releases = true;
     Ok(())
 }
 
+/// The terminal modes turned on by [`init`], which have to be turned off again once the session ends.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct TerminalModes {
+    alternate_screen: bool,
+    mouse_capture: bool,
+    bracketed_paste: bool,
+    focus_events: bool,
+    keyboard_enhancement: bool,
+}
+
+fn terminal_modes(engine: &Engine) -> TerminalModes {
+    TerminalModes {
+        alternate_screen: engine.inline.is_none(),
+        mouse_capture: engine.mouse_capture,
+        bracketed_paste: engine.bracketed_paste,
+        focus_events: engine.focus_events,
+        keyboard_enhancement: engine.is_keyboard_enhanced,
+    }
+}
+
+/// Turns the terminal modes of a session off again and shows the cursor.
+fn write_terminal_restore(writer: &mut impl Write, modes: TerminalModes) -> io::Result<()> {
+    if modes.alternate_screen {
+        queue!(writer, terminal::LeaveAlternateScreen)?;
+    }
+    queue!(writer, terminal::EnableLineWrap, cursor::Show)?;
+    if modes.mouse_capture {
+        queue!(writer, event::DisableMouseCapture)?;
+    }
+    if modes.bracketed_paste {
+        queue!(writer, event::DisableBracketedPaste)?;
+    }
+    if modes.focus_events {
+        queue!(writer, event::DisableFocusChange)?;
+    }
+    if modes.keyboard_enhancement {
+        queue!(writer, event::PopKeyboardEnhancementFlags)?;
+    }
+    writer.flush()
+}
+
+/// The modes of the local terminal restored by the panic hook, as long as a session is running.
+static PANIC_RESTORE: Mutex<Option<TerminalModes>> = Mutex::new(None);
+
+/// Sets the modes of the local terminal the panic hook restores, installing the hook on first use.
+fn restore_terminal_on_panic(modes: Option<TerminalModes>) {
+    static INSTALL_HOOK: Once = Once::new();
+    INSTALL_HOOK.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            // Restored first, so the panic message doesn't end up on the alternate screen
+            let _ = terminal::disable_raw_mode();
+            let _ = restore_terminal_after_panic(&mut io::stdout());
+            previous_hook(info);
+        }));
+    });
+    *PANIC_RESTORE.lock().unwrap_or_else(PoisonError::into_inner) = modes;
+}
+
+/// Writes the restore sequence of the running session, if any, which only happens once.
+fn restore_terminal_after_panic(writer: &mut impl Write) -> io::Result<()> {
+    let modes: Option<TerminalModes> = PANIC_RESTORE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    match modes {
+        Some(modes) => write_terminal_restore(writer, modes),
+        None => Ok(()),
+    }
+}
+
 /// Prepares a fresh frame state.
 ///
 /// This function should be called once at the start of each frame inside the update loop.
 ///
 /// Drawing should only happen after this is called for predictable results.
 pub fn start_frame(engine: &mut Engine) {
-    engine.delta_time = wait_for_next_frame(&mut engine.fps_limiter);
-    update_fps_counter(&mut engine.fps_counter, engine.delta_time);
-
-    let lowest_layer_index: LayerIndex = create_layer(engine, 0);
-    erase_rect(
-        engine,
-        lowest_layer_index,
-        0,
-        0,
-        engine.frame.width as i16,
-        engine.frame.height as i16,
+    wait_while_idle(
+        &mut engine.idle,
+        engine.input_source,
+        !engine.input_queue.is_empty(),
+    );
+    engine.delta_time = match engine.input_sampling {
+        InputSampling::PerFrame => wait_for_next_frame(&mut engine.fps_limiter),
+        InputSampling::HighResolution { poll_interval } => {
+            wait_for_next_frame_with(&mut engine.fps_limiter, |remaining| {
+                sample_input_while_idle(
+                    &mut engine.input_queue,
+                    engine.input_source,
+                    remaining,
+                    poll_interval,
+                )
+            })
+        }
+    };
+    if let Some(input_playback) = &mut engine.input_playback {
+        engine.input_queue.clear();
+        match next_played_back_frame(input_playback) {
+            Some(delta_time) => engine.delta_time = delta_time,
+            None => engine.input_playback = None,
+        }
+    }
+    if let Some(input_recording) = &mut engine.input_recording {
+        record_frame(input_recording, engine.delta_time);
+    }
+    start_idle_frame(
+        &mut engine.idle,
+        engine.input_source,
+        !engine.input_queue.is_empty(),
     );
+    reset_cursor_request(&mut engine.cursor);
+    update_fps_counter(&mut engine.fps_counter, engine.delta_time);
+    if let Some(adaptive_quality) = &mut engine.adaptive_quality {
+        start_frame_timing(adaptive_quality);
+    }
 }
 
 /// Renders the contents to the terminal and ends the frame.
@@ -145,22 +680,375 @@ pub fn start_frame(engine: &mut Engine) {
 ///
 /// No drawing should be happening after this function is called in the update loop.
 pub fn end_frame(engine: &mut Engine) -> io::Result<()> {
+    update_emitters(engine);
     update_and_draw_particles(engine);
+    update_and_draw_floating_texts(engine);
+    update_and_draw_bell_flash(engine);
 
     let height = engine.frame.height;
     let width = engine.frame.width;
+    // A resized terminal may have reflowed the region, so it's located again and fully repainted
+    if let Some(viewport) = engine.inline.as_mut().filter(|_| !engine.offscreen)
+        && refresh_inline_region(&mut engine.output, viewport, height)?
+    {
+        engine.frame.reset_frames();
+    }
+    let is_cast_repaint: bool = engine
+        .cast_recording
+        .as_mut()
+        .is_some_and(take_cast_repaint);
+    let is_full_repaint: bool =
+        !engine.offscreen && (std::mem::take(&mut engine.needs_repaint) || is_cast_repaint);
+    if is_full_repaint {
+        engine.frame.reset_frames();
+    }
+
     let (current, layered) = engine.frame.current_mut_and_layered_mut();
+    move_into_view(layered, width, height);
+    engine.culled_draw_calls = match engine.cell_budget {
+        Some(cell_budget) => cull_draw_calls(layered, width, height, cell_budget),
+        None => 0,
+    };
     compose_frame_buffer(
         current,
-        layered.iter_mut().flat_map(|v| v.0.drain(..)),
+        layered,
         width,
         height,
         engine.default_blending_color,
     );
-    let diff_products = engine.frame.diff();
-    draw_to_terminal(&mut engine.stdout, diff_products)?;
-    engine.frame.swap_frames();
+    if let Some(effect) = engine.screen_effect {
+        apply_cell_effect(
+            &mut engine.frame.current_mut(),
+            width,
+            height,
+            effect,
+            engine.default_blending_color,
+        );
+    }
+    if engine.terminal_colors.dithering && engine.terminal_colors.depth == ColorDepth::Ansi256 {
+        dither_backgrounds(&mut engine.frame.current_mut(), width, height);
+    }
+    let composed_at: Instant = Instant::now();
+
+    // Offscreen engines keep the composed frame around to be drawn by another engine
+    if !engine.offscreen {
+        let is_audible_bell: bool = take_pending_audible_bell(engine);
+        let mut changed_cells: usize = 0;
+        let origin_row: u16 = engine
+            .inline
+            .as_ref()
+            .map_or(0, |viewport| viewport.origin_row);
+        let diff_products = engine.frame.diff().inspect(|product| {
+            changed_cells += 1;
+            if let Some(viewport) = &mut engine.inline {
+                touch_inline_row(viewport, product.y);
+            }
+        });
+
+        let mut output = CastTee::new(&mut engine.output, engine.cast_recording.as_mut());
+        // Everything moving the cursor around is part of the update, so it's never seen mid-frame
+        if engine.synchronized_output {
+            queue!(output, terminal::BeginSynchronizedUpdate)?;
+        }
+        if is_full_repaint {
+            clear_for_repaint(&mut output, origin_row)?;
+            // Cast players and freshly resized terminals may show the cursor
+            if !is_cursor_shown(&engine.cursor) {
+                queue!(output, cursor::Hide)?;
+            }
+        }
+        draw_to_terminal(
+            &mut output,
+            diff_products,
+            &mut engine.terminal_colors,
+            origin_row,
+            engine.hyperlinks,
+        )?;
+        let cursor_row: Option<u16> = draw_cursor(&mut output, &mut engine.cursor, origin_row)?;
+        if let Some(viewport) = &mut engine.inline {
+            park_inline_cursor(&mut output, viewport, cursor_row)?;
+        }
+        if engine.synchronized_output {
+            queue!(output, terminal::EndSynchronizedUpdate)?;
+        }
+        if is_audible_bell {
+            output.write_all(b"\x07")?;
+        }
+        output.flush()?;
+
+        if let Some(cast) = &mut engine.cast_recording {
+            record_cast_frame(cast, engine.delta_time, composed_at)?;
+        }
+        engine.frame.swap_frames();
+        record_changed_cells(&mut engine.fps_counter, changed_cells);
+        end_idle_frame(&mut engine.idle, changed_cells == 0);
+    }
+
+    advance_key_state(&mut engine.key_state, Instant::now());
+
+    if let Some(adaptive_quality) = &mut engine.adaptive_quality {
+        end_frame_timing(adaptive_quality, engine.delta_time);
+    }
 
     engine.game_time += engine.delta_time;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        color::{Color, ColorEffect},
+        cursor::{CursorStyle, set_cursor},
+        draw::{draw_rect, draw_text},
+        layer::{LayerIndex, create_layer, set_layer_effect},
+        test_support::SharedOutput,
+    };
+
+    const BEGIN_SYNCHRONIZED_UPDATE: &str = "\x1b[?2026h";
+    const END_SYNCHRONIZED_UPDATE: &str = "\x1b[?2026l";
+
+    /// The output of every frame drawing `texts` in turn.
+    fn frame_outputs(synchronized_output: bool, texts: &[&str]) -> Vec<String> {
+        let output = SharedOutput::default();
+        let mut engine: Engine = Engine::remote(8, 2, output.clone())
+            .limit_fps(0)
+            .synchronized_output(synchronized_output);
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+        output.take();
+
+        texts
+            .iter()
+            .map(|text| {
+                start_frame(&mut engine);
+                draw_text(&mut engine, layer, 0, 0, *text);
+                end_frame(&mut engine).unwrap();
+                output.take()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn frames_are_wrapped_in_a_synchronized_update() {
+        for output in frame_outputs(true, &["first", "second", "second"]) {
+            assert!(output.starts_with(BEGIN_SYNCHRONIZED_UPDATE), "{output:?}");
+            assert!(output.ends_with(END_SYNCHRONIZED_UPDATE), "{output:?}");
+            assert_eq!(output.matches(BEGIN_SYNCHRONIZED_UPDATE).count(), 1);
+            assert_eq!(output.matches(END_SYNCHRONIZED_UPDATE).count(), 1);
+        }
+    }
+
+    #[test]
+    fn cursor_is_placed_within_the_synchronized_update() {
+        let output = SharedOutput::default();
+        let mut engine: Engine = Engine::remote(8, 2, output.clone()).limit_fps(0);
+        init(&mut engine).unwrap();
+        output.take();
+
+        start_frame(&mut engine);
+        set_cursor(&mut engine, 3, 1, CursorStyle::SteadyBar);
+        end_frame(&mut engine).unwrap();
+        let output: String = output.take();
+
+        // Moved to column 4, row 2 and shown, all before the update ends
+        let end: usize = output.find(END_SYNCHRONIZED_UPDATE).unwrap();
+        assert!(output[..end].contains("\x1b[2;4H"));
+        assert!(output[..end].contains("\x1b[?25h"));
+        assert!(output.ends_with(END_SYNCHRONIZED_UPDATE));
+    }
+
+    #[test]
+    fn synchronized_output_can_be_disabled() {
+        for output in frame_outputs(false, &["first", "second"]) {
+            assert!(!output.contains(BEGIN_SYNCHRONIZED_UPDATE));
+            assert!(!output.contains(END_SYNCHRONIZED_UPDATE));
+            assert!(output.contains('s'));
+        }
+    }
+
+    const ENABLE_MOUSE_CAPTURE: &str = "\x1b[?1000h\x1b[?1002h\x1b[?1003h\x1b[?1015h\x1b[?1006h";
+    const DISABLE_MOUSE_CAPTURE: &str = "\x1b[?1006l\x1b[?1015l\x1b[?1003l\x1b[?1002l\x1b[?1000l";
+
+    /// What [`init`] and [`exit_cleanup`] write for a remote engine titled `t`.
+    fn init_and_cleanup_outputs(configure: impl FnOnce(Engine) -> Engine) -> (String, String) {
+        let output = SharedOutput::default();
+        let mut engine: Engine = configure(Engine::remote(8, 2, output.clone()).title("t"));
+        init(&mut engine).unwrap();
+        let init_output: String = output.take();
+        exit_cleanup(&mut engine).unwrap();
+        (init_output, output.take())
+    }
+
+    #[test]
+    fn init_and_cleanup_write_the_mode_sequences() {
+        let (init_output, cleanup_output) = init_and_cleanup_outputs(|engine| engine);
+        assert_eq!(
+            init_output,
+            format!("\x1b[?1049h\x1b]0;t\x07\x1b[?25l{ENABLE_MOUSE_CAPTURE}\x1b[?2004h\x1b[?1004h")
+        );
+        assert_eq!(
+            cleanup_output,
+            format!("\x1b[?1049l\x1b[?7h\x1b[?25h{DISABLE_MOUSE_CAPTURE}\x1b[?2004l\x1b[?1004l")
+        );
+
+        // Modes which were never turned on aren't turned off either
+        let (init_output, cleanup_output) = init_and_cleanup_outputs(|engine| {
+            engine
+                .enable_mouse_capture(false)
+                .enable_bracketed_paste(false)
+                .enable_focus_events(false)
+        });
+        assert_eq!(init_output, "\x1b[?1049h\x1b]0;t\x07\x1b[?25l");
+        assert_eq!(cleanup_output, "\x1b[?1049l\x1b[?7h\x1b[?25h");
+    }
+
+    #[test]
+    fn sessions_on_the_same_engine_start_over() {
+        let output = SharedOutput::default();
+        let mut engine: Engine = Engine::remote(8, 2, output.clone())
+            .limit_fps(0)
+            .synchronized_output(false);
+        let layer = create_layer(&mut engine, 0);
+
+        let mut sessions: Vec<Vec<String>> = Vec::new();
+        for _ in 0..2 {
+            init(&mut engine).unwrap();
+            assert_eq!(engine.game_time, 0.0);
+            output.take();
+
+            let frames: Vec<String> = (0..3)
+                .map(|_| {
+                    start_frame(&mut engine);
+                    draw_text(&mut engine, layer, 0, 0, "hello");
+                    end_frame(&mut engine).unwrap();
+                    output.take()
+                })
+                .collect();
+            assert!(engine.game_time > 0.0);
+            exit_cleanup(&mut engine).unwrap();
+            sessions.push(frames);
+        }
+
+        // The second session repaints everything, rather than diffing against the last frame of the first one
+        assert!(sessions[1][0].contains("hello"));
+        assert!(!sessions[1][1].contains("hello"));
+        assert_eq!(sessions[0], sessions[1]);
+    }
+
+    #[test]
+    fn panics_restore_the_modes_of_the_running_session() {
+        let modes: TerminalModes = TerminalModes {
+            alternate_screen: true,
+            mouse_capture: false,
+            bracketed_paste: true,
+            focus_events: true,
+            keyboard_enhancement: true,
+        };
+        restore_terminal_on_panic(Some(modes));
+        restore_terminal_on_panic(None);
+        let mut output: Vec<u8> = Vec::new();
+        restore_terminal_after_panic(&mut output).unwrap();
+        assert!(output.is_empty());
+
+        restore_terminal_on_panic(Some(modes));
+        restore_terminal_after_panic(&mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\x1b[?1049l\x1b[?7h\x1b[?25h\x1b[?2004l\x1b[?1004l\x1b[<1u"
+        );
+        // Only restored once, eg. when panicking again while unwinding
+        let mut output: Vec<u8> = Vec::new();
+        restore_terminal_after_panic(&mut output).unwrap();
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn keyboard_enhancement_follows_the_queried_support() {
+        const PUSH_FLAGS: &str = "\x1b[>3u";
+        const POP_FLAGS: &str = "\x1b[<1u";
+
+        type QuerySupport = fn() -> io::Result<bool>;
+
+        // (requested, scripted query response, enhanced)
+        let scripted_responses: [(bool, QuerySupport, bool); 4] = [
+            (true, || Ok(true), true),
+            (true, || Ok(false), false),
+            (true, || Err(io::Error::other("no reply")), false),
+            (false, || panic!("queried without being requested"), false),
+        ];
+        for (requested, query_support, is_supported) in scripted_responses {
+            let output = SharedOutput::default();
+            let mut engine: Engine = Engine::remote(8, 2, output.clone())
+                .enable_mouse_capture(false)
+                .enable_bracketed_paste(false)
+                .enable_focus_events(false)
+                .enable_keyboard_enhancement(requested);
+            init(&mut engine).unwrap();
+            output.take();
+
+            enable_keyboard_enhancement(&mut engine, query_support).unwrap();
+            assert_eq!(is_keyboard_enhanced(&engine), is_supported);
+            assert_eq!(engine.key_state.reports_releases, is_supported);
+            assert_eq!(output.take() == PUSH_FLAGS, is_supported);
+
+            exit_cleanup(&mut engine).unwrap();
+            assert_eq!(output.take().ends_with(POP_FLAGS), is_supported);
+            assert!(!is_keyboard_enhanced(&engine));
+        }
+    }
+
+    /// The output of frames drawing the same rect, toggling an effect on, keeping it and toggling it off again.
+    fn toggled_effect_outputs(
+        mut set_effect: impl FnMut(&mut Engine, LayerIndex, Option<ColorEffect>),
+    ) -> Vec<String> {
+        let output = SharedOutput::default();
+        let mut engine: Engine = Engine::remote(2, 1, output.clone()).limit_fps(0);
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+        output.take();
+
+        [
+            None,
+            Some(ColorEffect::Dim(0.5)),
+            Some(ColorEffect::Dim(0.5)),
+            None,
+        ]
+        .into_iter()
+        .map(|effect| {
+            start_frame(&mut engine);
+            set_effect(&mut engine, layer, effect);
+            draw_rect(
+                &mut engine,
+                layer,
+                (0, 0),
+                (2, 1),
+                Color::new(200, 100, 50, 255),
+            );
+            end_frame(&mut engine).unwrap();
+            output.take()
+        })
+        .collect()
+    }
+
+    #[test]
+    fn toggling_effects_redraws_only_the_changed_frames() {
+        const ORIGINAL_BG: &str = "\x1b[48;2;200;100;50m";
+        const DIMMED_BG: &str = "\x1b[48;2;100;50;25m";
+
+        let layer_effect_outputs = toggled_effect_outputs(|engine, layer, effect| {
+            set_layer_effect(engine, layer, effect);
+        });
+        let screen_effect_outputs = toggled_effect_outputs(|engine, _, effect| {
+            set_screen_effect(engine, effect);
+        });
+        for outputs in [layer_effect_outputs, screen_effect_outputs] {
+            assert!(outputs[0].contains(ORIGINAL_BG), "{:?}", outputs[0]);
+            assert!(outputs[1].contains(DIMMED_BG), "{:?}", outputs[1]);
+            assert!(!outputs[1].contains(ORIGINAL_BG));
+            // Unchanged cells aren't redrawn while the effect stays on
+            assert!(!outputs[2].contains("48;2;"), "{:?}", outputs[2]);
+            assert!(outputs[3].contains(ORIGINAL_BG), "{:?}", outputs[3]);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Floating text effects, like damage numbers or score popups.
+//!
+//! Text spawned with [`spawn_floating_text`] moves with a constant velocity, optionally drifts sideways
+//! and fades out over its lifetime. Like particles, floating texts are automatically updated and drawn at the end of the frame.
+//!
+//! ## Notes
+//! Text can't be drawn between cells, so the position is rounded to the nearest cell when drawing.
+//! The text still moves smoothly in the sense that it switches rows exactly when its position crosses the halfway point between them.
+
+use crate::{
+    color::Color, draw::draw_text, engine::Engine, layer::LayerIndex, noise::perlin1,
+    rich_text::RichText,
+};
+
+/// How the alpha of a floating text changes over its lifetime.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FadeCurve {
+    /// The text stays fully visible until it despawns.
+    None,
+    /// The alpha decreases linearly.
+    Linear,
+    /// The alpha decreases slowly at first and quickly at the end, keeping the text readable for longer.
+    EaseIn,
+    /// The alpha decreases quickly at first and slowly at the end.
+    EaseOut,
+}
+
+/// Sideways wobble applied on top of the velocity, sampled from [`perlin1`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloatDrift {
+    /// Maximum horizontal offset in cells.
+    pub amplitude: f32,
+    /// How many times per second the drift changes direction, roughly.
+    pub frequency: f32,
+    pub seed: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloatSpec {
+    /// Velocity in cells per second.
+    pub velocity: (f32, f32),
+    pub lifetime_sec: f32,
+    pub fade: FadeCurve,
+    pub drift: Option<FloatDrift>,
+}
+
+impl Default for FloatSpec {
+    fn default() -> Self {
+        Self {
+            velocity: (0.0, -2.0),
+            lifetime_sec: 1.0,
+            fade: FadeCurve::EaseIn,
+            drift: None,
+        }
+    }
+}
+
+pub(crate) struct FloatingTextState {
+    layer_index: LayerIndex,
+    pos: (f32, f32),
+    rich_text: RichText,
+    spec: FloatSpec,
+    spawn_timestamp: f32,
+}
+
+/// Spawns a text at `pos` which floats away and fades out according to `spec`.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{color::Color, engine::Engine, layer::create_layer, rich_text::RichText};
+/// # use germterm::floating_text::{FloatSpec, spawn_floating_text};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// let text = RichText::new("+1").with_fg(Color::YELLOW);
+/// spawn_floating_text(&mut engine, layer, (20.0, 10.0), text, FloatSpec::default());
+/// ```
+pub fn spawn_floating_text(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    pos: (f32, f32),
+    rich_text: impl Into<RichText>,
+    spec: FloatSpec,
+) {
+    engine.floating_text_state.push(FloatingTextState {
+        layer_index,
+        pos,
+        rich_text: rich_text.into(),
+        spec,
+        spawn_timestamp: engine.game_time,
+    });
+}
+
+/// The amount of floating texts currently alive.
+pub fn floating_text_count(engine: &Engine) -> usize {
+    engine.floating_text_state.len()
+}
+
+pub(crate) fn update_and_draw_floating_texts(engine: &mut Engine) {
+    let mut i: usize = 0;
+    while i < engine.floating_text_state.len() {
+        let state: &mut FloatingTextState = &mut engine.floating_text_state[i];
+        let age: f32 = engine.game_time - state.spawn_timestamp;
+
+        // Expired entries are swapped out, keeping the storage around for the next spawns
+        if age >= state.spec.lifetime_sec {
+            engine.floating_text_state.swap_remove(i);
+            continue;
+        }
+
+        let t: f32 = (age / state.spec.lifetime_sec).clamp(0.0, 1.0);
+        let opacity: f32 = match state.spec.fade {
+            FadeCurve::None => 1.0,
+            FadeCurve::Linear => 1.0 - t,
+            FadeCurve::EaseIn => 1.0 - t * t,
+            FadeCurve::EaseOut => (1.0 - t) * (1.0 - t),
+        };
+        let drift_offset: f32 = match state.spec.drift {
+            Some(drift) => perlin1(age * drift.frequency, drift.seed) * drift.amplitude,
+            None => 0.0,
+        };
+
+        let x: i16 = (state.pos.0 + drift_offset).round() as i16;
+        let y: i16 = state.pos.1.round() as i16;
+        let mut rich_text: RichText = state.rich_text.clone();
+        rich_text.fg = fade_color(rich_text.fg, opacity);
+        rich_text.bg = fade_color(rich_text.bg, opacity);
+        let layer_index: LayerIndex = state.layer_index;
+
+        state.pos.0 += state.spec.velocity.0 * engine.delta_time;
+        state.pos.1 += state.spec.velocity.1 * engine.delta_time;
+
+        draw_text(engine, layer_index, x, y, rich_text);
+
+        i += 1;
+    }
+}
+
+#[inline]
+fn fade_color(color: Color, opacity: f32) -> Color {
+    color.with_alpha((color.a() as f32 * opacity).round() as u8)
+}
@@ -0,0 +1,198 @@
+//! Built-in bitmap font data.
+
+/// A single glyph of the proportional mini font used by [`crate::draw::draw_mini_text`].
+///
+/// Each row is a bitmask, where the highest of the `width` bits is the leftmost column.
+/// Rows `0..5` hold the glyph body, row `5` is reserved for descenders.
+pub(crate) struct MiniGlyph {
+    pub width: u8,
+    pub rows: [u8; 6],
+}
+
+/// Glyph height of the mini font in octads, including the descender row.
+pub(crate) const MINI_GLYPH_HEIGHT: i32 = 6;
+
+/// Printable ASCII glyphs of the mini font, indexed by `ch as usize - 0x20`.
+#[rustfmt::skip]
+pub(crate) static MINI_FONT: [MiniGlyph; 95] = [
+    MiniGlyph { width: 2, rows: [0b00, 0b00, 0b00, 0b00, 0b00, 0b00] }, // ' '
+    MiniGlyph { width: 1, rows: [0b1, 0b1, 0b1, 0b0, 0b1, 0b0] }, // '!'
+    MiniGlyph { width: 3, rows: [0b101, 0b101, 0b000, 0b000, 0b000, 0b000] }, // '"'
+    MiniGlyph { width: 5, rows: [0b01010, 0b11111, 0b01010, 0b11111, 0b01010, 0b00000] }, // '#'
+    MiniGlyph { width: 3, rows: [0b011, 0b110, 0b010, 0b011, 0b110, 0b000] }, // '$'
+    MiniGlyph { width: 3, rows: [0b101, 0b001, 0b010, 0b100, 0b101, 0b000] }, // '%'
+    MiniGlyph { width: 3, rows: [0b010, 0b101, 0b010, 0b101, 0b011, 0b000] }, // '&'
+    MiniGlyph { width: 1, rows: [0b1, 0b1, 0b0, 0b0, 0b0, 0b0] }, // "'"
+    MiniGlyph { width: 2, rows: [0b01, 0b10, 0b10, 0b10, 0b01, 0b00] }, // '('
+    MiniGlyph { width: 2, rows: [0b10, 0b01, 0b01, 0b01, 0b10, 0b00] }, // ')'
+    MiniGlyph { width: 3, rows: [0b000, 0b101, 0b010, 0b101, 0b000, 0b000] }, // '*'
+    MiniGlyph { width: 3, rows: [0b000, 0b010, 0b111, 0b010, 0b000, 0b000] }, // '+'
+    MiniGlyph { width: 1, rows: [0b0, 0b0, 0b0, 0b0, 0b1, 0b1] }, // ','
+    MiniGlyph { width: 3, rows: [0b000, 0b000, 0b111, 0b000, 0b000, 0b000] }, // '-'
+    MiniGlyph { width: 1, rows: [0b0, 0b0, 0b0, 0b0, 0b1, 0b0] }, // '.'
+    MiniGlyph { width: 3, rows: [0b001, 0b001, 0b010, 0b100, 0b100, 0b000] }, // '/'
+    MiniGlyph { width: 3, rows: [0b111, 0b101, 0b101, 0b101, 0b111, 0b000] }, // '0'
+    MiniGlyph { width: 3, rows: [0b010, 0b110, 0b010, 0b010, 0b111, 0b000] }, // '1'
+    MiniGlyph { width: 3, rows: [0b110, 0b001, 0b010, 0b100, 0b111, 0b000] }, // '2'
+    MiniGlyph { width: 3, rows: [0b110, 0b001, 0b010, 0b001, 0b110, 0b000] }, // '3'
+    MiniGlyph { width: 3, rows: [0b101, 0b101, 0b111, 0b001, 0b001, 0b000] }, // '4'
+    MiniGlyph { width: 3, rows: [0b111, 0b100, 0b110, 0b001, 0b110, 0b000] }, // '5'
+    MiniGlyph { width: 3, rows: [0b011, 0b100, 0b111, 0b101, 0b111, 0b000] }, // '6'
+    MiniGlyph { width: 3, rows: [0b111, 0b001, 0b010, 0b010, 0b010, 0b000] }, // '7'
+    MiniGlyph { width: 3, rows: [0b111, 0b101, 0b111, 0b101, 0b111, 0b000] }, // '8'
+    MiniGlyph { width: 3, rows: [0b111, 0b101, 0b111, 0b001, 0b110, 0b000] }, // '9'
+    MiniGlyph { width: 1, rows: [0b0, 0b1, 0b0, 0b1, 0b0, 0b0] }, // ':'
+    MiniGlyph { width: 2, rows: [0b00, 0b01, 0b00, 0b01, 0b10, 0b00] }, // ';'
+    MiniGlyph { width: 3, rows: [0b001, 0b010, 0b100, 0b010, 0b001, 0b000] }, // '<'
+    MiniGlyph { width: 3, rows: [0b000, 0b111, 0b000, 0b111, 0b000, 0b000] }, // '='
+    MiniGlyph { width: 3, rows: [0b100, 0b010, 0b001, 0b010, 0b100, 0b000] }, // '>'
+    MiniGlyph { width: 3, rows: [0b110, 0b001, 0b010, 0b000, 0b010, 0b000] }, // '?'
+    MiniGlyph { width: 4, rows: [0b0110, 0b1001, 0b1011, 0b1000, 0b0110, 0b0000] }, // '@'
+    MiniGlyph { width: 3, rows: [0b010, 0b101, 0b111, 0b101, 0b101, 0b000] }, // 'A'
+    MiniGlyph { width: 3, rows: [0b110, 0b101, 0b110, 0b101, 0b110, 0b000] }, // 'B'
+    MiniGlyph { width: 3, rows: [0b011, 0b100, 0b100, 0b100, 0b011, 0b000] }, // 'C'
+    MiniGlyph { width: 3, rows: [0b110, 0b101, 0b101, 0b101, 0b110, 0b000] }, // 'D'
+    MiniGlyph { width: 3, rows: [0b111, 0b100, 0b110, 0b100, 0b111, 0b000] }, // 'E'
+    MiniGlyph { width: 3, rows: [0b111, 0b100, 0b110, 0b100, 0b100, 0b000] }, // 'F'
+    MiniGlyph { width: 3, rows: [0b011, 0b100, 0b101, 0b101, 0b011, 0b000] }, // 'G'
+    MiniGlyph { width: 3, rows: [0b101, 0b101, 0b111, 0b101, 0b101, 0b000] }, // 'H'
+    MiniGlyph { width: 3, rows: [0b111, 0b010, 0b010, 0b010, 0b111, 0b000] }, // 'I'
+    MiniGlyph { width: 3, rows: [0b001, 0b001, 0b001, 0b101, 0b010, 0b000] }, // 'J'
+    MiniGlyph { width: 3, rows: [0b101, 0b101, 0b110, 0b101, 0b101, 0b000] }, // 'K'
+    MiniGlyph { width: 3, rows: [0b100, 0b100, 0b100, 0b100, 0b111, 0b000] }, // 'L'
+    MiniGlyph { width: 5, rows: [0b10001, 0b11011, 0b10101, 0b10001, 0b10001, 0b00000] }, // 'M'
+    MiniGlyph { width: 4, rows: [0b1001, 0b1101, 0b1011, 0b1001, 0b1001, 0b0000] }, // 'N'
+    MiniGlyph { width: 3, rows: [0b010, 0b101, 0b101, 0b101, 0b010, 0b000] }, // 'O'
+    MiniGlyph { width: 3, rows: [0b110, 0b101, 0b110, 0b100, 0b100, 0b000] }, // 'P'
+    MiniGlyph { width: 3, rows: [0b010, 0b101, 0b101, 0b110, 0b011, 0b000] }, // 'Q'
+    MiniGlyph { width: 3, rows: [0b110, 0b101, 0b110, 0b101, 0b101, 0b000] }, // 'R'
+    MiniGlyph { width: 3, rows: [0b011, 0b100, 0b010, 0b001, 0b110, 0b000] }, // 'S'
+    MiniGlyph { width: 3, rows: [0b111, 0b010, 0b010, 0b010, 0b010, 0b000] }, // 'T'
+    MiniGlyph { width: 3, rows: [0b101, 0b101, 0b101, 0b101, 0b111, 0b000] }, // 'U'
+    MiniGlyph { width: 3, rows: [0b101, 0b101, 0b101, 0b101, 0b010, 0b000] }, // 'V'
+    MiniGlyph { width: 5, rows: [0b10001, 0b10001, 0b10101, 0b11011, 0b10001, 0b00000] }, // 'W'
+    MiniGlyph { width: 3, rows: [0b101, 0b101, 0b010, 0b101, 0b101, 0b000] }, // 'X'
+    MiniGlyph { width: 3, rows: [0b101, 0b101, 0b010, 0b010, 0b010, 0b000] }, // 'Y'
+    MiniGlyph { width: 3, rows: [0b111, 0b001, 0b010, 0b100, 0b111, 0b000] }, // 'Z'
+    MiniGlyph { width: 2, rows: [0b11, 0b10, 0b10, 0b10, 0b11, 0b00] }, // '['
+    MiniGlyph { width: 3, rows: [0b100, 0b100, 0b010, 0b001, 0b001, 0b000] }, // '\\'
+    MiniGlyph { width: 2, rows: [0b11, 0b01, 0b01, 0b01, 0b11, 0b00] }, // ']'
+    MiniGlyph { width: 3, rows: [0b010, 0b101, 0b000, 0b000, 0b000, 0b000] }, // '^'
+    MiniGlyph { width: 3, rows: [0b000, 0b000, 0b000, 0b000, 0b111, 0b000] }, // '_'
+    MiniGlyph { width: 2, rows: [0b10, 0b01, 0b00, 0b00, 0b00, 0b00] }, // '`'
+    MiniGlyph { width: 3, rows: [0b000, 0b110, 0b001, 0b101, 0b011, 0b000] }, // 'a'
+    MiniGlyph { width: 3, rows: [0b100, 0b100, 0b110, 0b101, 0b110, 0b000] }, // 'b'
+    MiniGlyph { width: 3, rows: [0b000, 0b011, 0b100, 0b100, 0b011, 0b000] }, // 'c'
+    MiniGlyph { width: 3, rows: [0b001, 0b001, 0b011, 0b101, 0b011, 0b000] }, // 'd'
+    MiniGlyph { width: 3, rows: [0b000, 0b010, 0b101, 0b110, 0b011, 0b000] }, // 'e'
+    MiniGlyph { width: 2, rows: [0b01, 0b10, 0b11, 0b10, 0b10, 0b00] }, // 'f'
+    MiniGlyph { width: 3, rows: [0b000, 0b011, 0b101, 0b011, 0b001, 0b110] }, // 'g'
+    MiniGlyph { width: 3, rows: [0b100, 0b100, 0b110, 0b101, 0b101, 0b000] }, // 'h'
+    MiniGlyph { width: 1, rows: [0b1, 0b0, 0b1, 0b1, 0b1, 0b0] }, // 'i'
+    MiniGlyph { width: 2, rows: [0b01, 0b00, 0b01, 0b01, 0b01, 0b10] }, // 'j'
+    MiniGlyph { width: 3, rows: [0b100, 0b100, 0b101, 0b110, 0b101, 0b000] }, // 'k'
+    MiniGlyph { width: 1, rows: [0b1, 0b1, 0b1, 0b1, 0b1, 0b0] }, // 'l'
+    MiniGlyph { width: 5, rows: [0b00000, 0b11110, 0b10101, 0b10101, 0b10101, 0b00000] }, // 'm'
+    MiniGlyph { width: 3, rows: [0b000, 0b110, 0b101, 0b101, 0b101, 0b000] }, // 'n'
+    MiniGlyph { width: 3, rows: [0b000, 0b010, 0b101, 0b101, 0b010, 0b000] }, // 'o'
+    MiniGlyph { width: 3, rows: [0b000, 0b110, 0b101, 0b110, 0b100, 0b100] }, // 'p'
+    MiniGlyph { width: 3, rows: [0b000, 0b011, 0b101, 0b011, 0b001, 0b001] }, // 'q'
+    MiniGlyph { width: 3, rows: [0b000, 0b101, 0b110, 0b100, 0b100, 0b000] }, // 'r'
+    MiniGlyph { width: 3, rows: [0b000, 0b011, 0b100, 0b001, 0b110, 0b000] }, // 's'
+    MiniGlyph { width: 3, rows: [0b010, 0b111, 0b010, 0b010, 0b001, 0b000] }, // 't'
+    MiniGlyph { width: 3, rows: [0b000, 0b101, 0b101, 0b101, 0b011, 0b000] }, // 'u'
+    MiniGlyph { width: 3, rows: [0b000, 0b101, 0b101, 0b101, 0b010, 0b000] }, // 'v'
+    MiniGlyph { width: 5, rows: [0b00000, 0b10001, 0b10101, 0b10101, 0b01010, 0b00000] }, // 'w'
+    MiniGlyph { width: 3, rows: [0b000, 0b000, 0b101, 0b010, 0b101, 0b000] }, // 'x'
+    MiniGlyph { width: 3, rows: [0b000, 0b101, 0b101, 0b011, 0b001, 0b110] }, // 'y'
+    MiniGlyph { width: 3, rows: [0b000, 0b111, 0b001, 0b010, 0b111, 0b000] }, // 'z'
+    MiniGlyph { width: 3, rows: [0b011, 0b010, 0b110, 0b010, 0b011, 0b000] }, // '{'
+    MiniGlyph { width: 1, rows: [0b1, 0b1, 0b1, 0b1, 0b1, 0b0] }, // '|'
+    MiniGlyph { width: 3, rows: [0b110, 0b010, 0b011, 0b010, 0b110, 0b000] }, // '}'
+    MiniGlyph { width: 4, rows: [0b0000, 0b0000, 0b0101, 0b1010, 0b0000, 0b0000] }, // '~'
+];
+
+/// Glyph pairs drawn one column closer together than usual.
+#[rustfmt::skip]
+pub(crate) static MINI_FONT_KERNING_PAIRS: [(char, char); 36] = [
+    ('T', 'a'), ('T', 'c'), ('T', 'e'), ('T', 'g'), ('T', 'm'), ('T', 'n'), ('T', 'o'), ('T', 'p'),
+    ('T', 'q'), ('T', 'r'), ('T', 's'), ('T', 'u'), ('T', 'v'), ('T', 'w'), ('T', 'x'), ('T', 'y'),
+    ('T', 'z'), ('A', 'T'), ('T', 'A'), ('L', 'T'), ('L', 'Y'), ('L', 'V'), ('F', '.'), ('P', '.'),
+    ('T', '.'), ('T', ','), ('V', '.'), ('Y', '.'), ('r', '.'), ('r', ','), ('F', 'a'), ('F', 'o'),
+    ('P', 'o'), ('Y', 'o'), ('A', 'V'), ('V', 'A'),
+];
+
+/// Glyph size of the block font used by [`crate::draw::BigFont::Block`], in cells.
+pub(crate) const BLOCK_GLYPH_SIZE: i32 = 5;
+
+/// Glyphs of the block font for `' '..='_'`, indexed by `ch as usize - 0x20`.
+///
+/// Each row is a bitmask, where the highest of the 5 bits is the leftmost column.
+/// Lowercase letters use the uppercase glyphs.
+#[rustfmt::skip]
+pub(crate) static BLOCK_FONT: [[u8; 5]; 64] = [
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b00000], // ' '
+    [0b00100, 0b00100, 0b00100, 0b00000, 0b00100], // '!'
+    [0b01010, 0b01010, 0b00000, 0b00000, 0b00000], // '"'
+    [0b01010, 0b11111, 0b01010, 0b11111, 0b01010], // '#'
+    [0b01111, 0b10100, 0b01110, 0b00101, 0b11110], // '$'
+    [0b11001, 0b11010, 0b00100, 0b01011, 0b10011], // '%'
+    [0b01100, 0b10010, 0b01101, 0b10010, 0b01101], // '&'
+    [0b00100, 0b00100, 0b00000, 0b00000, 0b00000], // "'"
+    [0b00010, 0b00100, 0b00100, 0b00100, 0b00010], // '('
+    [0b01000, 0b00100, 0b00100, 0b00100, 0b01000], // ')'
+    [0b00000, 0b10101, 0b01110, 0b10101, 0b00000], // '*'
+    [0b00000, 0b00100, 0b01110, 0b00100, 0b00000], // '+'
+    [0b00000, 0b00000, 0b00000, 0b00100, 0b01000], // ','
+    [0b00000, 0b00000, 0b01110, 0b00000, 0b00000], // '-'
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b00100], // '.'
+    [0b00001, 0b00010, 0b00100, 0b01000, 0b10000], // '/'
+    [0b01110, 0b10011, 0b10101, 0b11001, 0b01110], // '0'
+    [0b00100, 0b01100, 0b00100, 0b00100, 0b01110], // '1'
+    [0b01110, 0b10001, 0b00110, 0b01000, 0b11111], // '2'
+    [0b11110, 0b00001, 0b01110, 0b00001, 0b11110], // '3'
+    [0b10001, 0b10001, 0b11111, 0b00001, 0b00001], // '4'
+    [0b11111, 0b10000, 0b11110, 0b00001, 0b11110], // '5'
+    [0b01110, 0b10000, 0b11110, 0b10001, 0b01110], // '6'
+    [0b11111, 0b00010, 0b00100, 0b01000, 0b01000], // '7'
+    [0b01110, 0b10001, 0b01110, 0b10001, 0b01110], // '8'
+    [0b01110, 0b10001, 0b01111, 0b00001, 0b01110], // '9'
+    [0b00000, 0b00100, 0b00000, 0b00100, 0b00000], // ':'
+    [0b00000, 0b00100, 0b00000, 0b00100, 0b01000], // ';'
+    [0b00010, 0b00100, 0b01000, 0b00100, 0b00010], // '<'
+    [0b00000, 0b01110, 0b00000, 0b01110, 0b00000], // '='
+    [0b01000, 0b00100, 0b00010, 0b00100, 0b01000], // '>'
+    [0b01110, 0b10001, 0b00110, 0b00000, 0b00100], // '?'
+    [0b01110, 0b10111, 0b10101, 0b10111, 0b01110], // '@'
+    [0b01110, 0b10001, 0b11111, 0b10001, 0b10001], // 'A'
+    [0b11110, 0b10001, 0b11110, 0b10001, 0b11110], // 'B'
+    [0b01111, 0b10000, 0b10000, 0b10000, 0b01111], // 'C'
+    [0b11110, 0b10001, 0b10001, 0b10001, 0b11110], // 'D'
+    [0b11111, 0b10000, 0b11110, 0b10000, 0b11111], // 'E'
+    [0b11111, 0b10000, 0b11110, 0b10000, 0b10000], // 'F'
+    [0b01111, 0b10000, 0b10011, 0b10001, 0b01111], // 'G'
+    [0b10001, 0b10001, 0b11111, 0b10001, 0b10001], // 'H'
+    [0b01110, 0b00100, 0b00100, 0b00100, 0b01110], // 'I'
+    [0b00111, 0b00010, 0b00010, 0b10010, 0b01100], // 'J'
+    [0b10001, 0b10010, 0b11100, 0b10010, 0b10001], // 'K'
+    [0b10000, 0b10000, 0b10000, 0b10000, 0b11111], // 'L'
+    [0b10001, 0b11011, 0b10101, 0b10001, 0b10001], // 'M'
+    [0b10001, 0b11001, 0b10101, 0b10011, 0b10001], // 'N'
+    [0b01110, 0b10001, 0b10001, 0b10001, 0b01110], // 'O'
+    [0b11110, 0b10001, 0b11110, 0b10000, 0b10000], // 'P'
+    [0b01110, 0b10001, 0b10101, 0b10010, 0b01101], // 'Q'
+    [0b11110, 0b10001, 0b11110, 0b10010, 0b10001], // 'R'
+    [0b01111, 0b10000, 0b01110, 0b00001, 0b11110], // 'S'
+    [0b11111, 0b00100, 0b00100, 0b00100, 0b00100], // 'T'
+    [0b10001, 0b10001, 0b10001, 0b10001, 0b01110], // 'U'
+    [0b10001, 0b10001, 0b10001, 0b01010, 0b00100], // 'V'
+    [0b10001, 0b10001, 0b10101, 0b11011, 0b10001], // 'W'
+    [0b10001, 0b01010, 0b00100, 0b01010, 0b10001], // 'X'
+    [0b10001, 0b01010, 0b00100, 0b00100, 0b00100], // 'Y'
+    [0b11111, 0b00010, 0b00100, 0b01000, 0b11111], // 'Z'
+    [0b01110, 0b01000, 0b01000, 0b01000, 0b01110], // '['
+    [0b10000, 0b01000, 0b00100, 0b00010, 0b00001], // '\\'
+    [0b01110, 0b00010, 0b00010, 0b00010, 0b01110], // ']'
+    [0b00100, 0b01010, 0b10001, 0b00000, 0b00000], // '^'
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b11111], // '_'
+];
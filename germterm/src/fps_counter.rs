@@ -2,9 +2,16 @@
 
 use crate::engine::Engine;
 
+/// The amount of most recent frames [`FrameStats`] are computed over.
+pub const FRAME_STATS_WINDOW: usize = 120;
+
 pub(crate) struct FpsCounter {
     fps_ema: f32,
     smoothing_factor: f32,
+    frame_times: [f32; FRAME_STATS_WINDOW],
+    frame_time_count: usize,
+    next_frame_time_index: usize,
+    changed_cells: usize,
 }
 
 impl FpsCounter {
@@ -12,10 +19,33 @@ impl FpsCounter {
         Self {
             fps_ema: 0.0,
             smoothing_factor,
+            frame_times: [0.0; FRAME_STATS_WINDOW],
+            frame_time_count: 0,
+            next_frame_time_index: 0,
+            changed_cells: 0,
         }
     }
 }
 
+/// Statistics about the most recent frames, see [`frame_stats`].
+///
+/// Frame times are in seconds and measure the whole frame, including the time spent waiting for the FPS limiter.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FrameStats {
+    /// Same as [`get_fps`].
+    pub fps: f32,
+    pub last_frame_time: f32,
+    pub min_frame_time: f32,
+    pub max_frame_time: f32,
+    /// 99% of the recent frames took at most this long.
+    pub p99_frame_time: f32,
+    /// The amount of cells that changed and were redrawn in the terminal in the last frame.
+    ///
+    /// A high count on a mostly static screen points at accidental full-screen redraws.
+    /// Always `0` for offscreen engines.
+    pub changed_cells: usize,
+}
+
 pub(crate) fn update_fps_counter(fps_counter: &mut FpsCounter, delta_time: f32) {
     if delta_time <= 0.0 {
         return;
@@ -29,6 +59,22 @@ pub(crate) fn update_fps_counter(fps_counter: &mut FpsCounter, delta_time: f32)
         fps_counter.fps_ema = fps_counter.fps_ema * (1.0 - fps_counter.smoothing_factor)
             + current_fps * fps_counter.smoothing_factor;
     }
+
+    fps_counter.frame_times[fps_counter.next_frame_time_index] = delta_time;
+    fps_counter.next_frame_time_index =
+        (fps_counter.next_frame_time_index + 1) % FRAME_STATS_WINDOW;
+    fps_counter.frame_time_count = (fps_counter.frame_time_count + 1).min(FRAME_STATS_WINDOW);
+}
+
+pub(crate) fn record_changed_cells(fps_counter: &mut FpsCounter, changed_cells: usize) {
+    fps_counter.changed_cells = changed_cells;
+}
+
+pub(crate) fn reset_fps_counter(fps_counter: &mut FpsCounter) {
+    fps_counter.fps_ema = 0.0;
+    fps_counter.frame_time_count = 0;
+    fps_counter.next_frame_time_index = 0;
+    fps_counter.changed_cells = 0;
 }
 
 /// Retrieves the current FPS EMA (Exponential Moving Average).
@@ -42,3 +88,114 @@ pub(crate) fn update_fps_counter(fps_counter: &mut FpsCounter, delta_time: f32)
 pub fn get_fps(engine: &Engine) -> f32 {
     engine.fps_counter.fps_ema
 }
+
+/// Computes [`FrameStats`] over the last [`FRAME_STATS_WINDOW`] frames.
+///
+/// Cheap enough to call every frame, nothing gets allocated.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{fps_counter::frame_stats, engine::Engine};
+/// let engine = Engine::new(40, 20);
+/// let stats = frame_stats(&engine);
+/// let summary = format!(
+///     "{:.0} fps, worst {:.1} ms, {} cells redrawn",
+///     stats.fps,
+///     stats.max_frame_time * 1000.0,
+///     stats.changed_cells,
+/// );
+/// ```
+pub fn frame_stats(engine: &Engine) -> FrameStats {
+    let fps_counter: &FpsCounter = &engine.fps_counter;
+    let count: usize = fps_counter.frame_time_count;
+    if count == 0 {
+        return FrameStats {
+            changed_cells: fps_counter.changed_cells,
+            ..Default::default()
+        };
+    }
+
+    let last_index: usize =
+        (fps_counter.next_frame_time_index + FRAME_STATS_WINDOW - 1) % FRAME_STATS_WINDOW;
+    let mut frame_times: [f32; FRAME_STATS_WINDOW] = fps_counter.frame_times;
+    let frame_times: &mut [f32] = &mut frame_times[..count];
+    frame_times.sort_unstable_by(f32::total_cmp);
+
+    let p99_index: usize = ((count as f32 * 0.99).ceil() as usize).clamp(1, count) - 1;
+
+    FrameStats {
+        fps: fps_counter.fps_ema,
+        last_frame_time: fps_counter.frame_times[last_index],
+        min_frame_time: frame_times[0],
+        max_frame_time: frame_times[count - 1],
+        p99_frame_time: frame_times[p99_index],
+        changed_cells: fps_counter.changed_cells,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FRAME_STATS_WINDOW, FrameStats, frame_stats, update_fps_counter};
+    use crate::{
+        draw::draw_text,
+        engine::{Engine, end_frame, init, start_frame},
+        layer::create_layer,
+        test_support::SharedOutput,
+    };
+
+    #[test]
+    fn stats_cover_only_the_most_recent_frames() {
+        let mut engine: Engine = Engine::offscreen(4, 1);
+        assert_eq!(frame_stats(&engine), FrameStats::default());
+
+        // Pushed out of the window by the frames after them
+        for _ in 0..80 {
+            update_fps_counter(&mut engine.fps_counter, 1.0);
+        }
+        for _ in 0..FRAME_STATS_WINDOW - 2 {
+            update_fps_counter(&mut engine.fps_counter, 0.01);
+        }
+        update_fps_counter(&mut engine.fps_counter, 0.05);
+        update_fps_counter(&mut engine.fps_counter, 0.05);
+        // Ignored, as they don't make up a frame
+        update_fps_counter(&mut engine.fps_counter, 0.0);
+        update_fps_counter(&mut engine.fps_counter, -1.0);
+
+        let stats: FrameStats = frame_stats(&engine);
+        assert_eq!(stats.last_frame_time, 0.05);
+        assert_eq!(stats.min_frame_time, 0.01);
+        assert_eq!(stats.max_frame_time, 0.05);
+        // 2 of the 120 frames are slower than the rest, more than 1%
+        assert_eq!(stats.p99_frame_time, 0.05);
+        assert!(stats.fps > 20.0 && stats.fps < 100.0);
+    }
+
+    #[test]
+    fn single_spike_stays_out_of_the_p99() {
+        let mut engine: Engine = Engine::offscreen(4, 1);
+        for _ in 0..FRAME_STATS_WINDOW - 1 {
+            update_fps_counter(&mut engine.fps_counter, 0.01);
+        }
+        update_fps_counter(&mut engine.fps_counter, 0.5);
+
+        let stats: FrameStats = frame_stats(&engine);
+        assert_eq!(stats.max_frame_time, 0.5);
+        assert_eq!(stats.p99_frame_time, 0.01);
+    }
+
+    #[test]
+    fn changed_cells_count_the_redrawn_cells() {
+        let mut engine: Engine = Engine::remote(8, 2, SharedOutput::default()).limit_fps(0);
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+
+        let mut changed_cells: Vec<usize> = Vec::new();
+        for text in ["abc", "abc", "abd"] {
+            start_frame(&mut engine);
+            draw_text(&mut engine, layer, 0, 0, text);
+            end_frame(&mut engine).unwrap();
+            changed_cells.push(frame_stats(&engine).changed_cells);
+        }
+        assert_eq!(changed_cells, [3, 0, 1]);
+    }
+}
@@ -32,7 +32,28 @@ pub fn limit_fps(fps_limiter: &mut FpsLimiter, value: u32) {
         .unwrap_or_else(Instant::now);
 }
 
+/// Restarts frame pacing from the current instant, as if the limiter was freshly created.
+pub fn reset_fps_limiter(fps_limiter: &mut FpsLimiter) {
+    fps_limiter.next_frame_timestamp = Instant::now()
+        .checked_add(fps_limiter.target_frametime)
+        .unwrap_or_else(Instant::now);
+}
+
 pub fn wait_for_next_frame(fps_limiter: &mut FpsLimiter) -> f32 {
+    let poll_interval_sec: Duration = fps_limiter.poll_interval_sec;
+    wait_for_next_frame_with(fps_limiter, |remaining| {
+        sleep(poll_interval_sec.min(remaining))
+    })
+}
+
+/// Same as [`wait_for_next_frame`], but calls `idle` instead of sleeping.
+///
+/// `idle` receives the remaining time it's allowed to spend before the final busy wait
+/// and gets called repeatedly until the time runs out.
+pub fn wait_for_next_frame_with(
+    fps_limiter: &mut FpsLimiter,
+    mut idle: impl FnMut(Duration),
+) -> f32 {
     if fps_limiter.target_frametime == Duration::ZERO {
         let delta_time: f32 = calc_delta_time(fps_limiter.next_frame_timestamp, Duration::ZERO);
         fps_limiter.next_frame_timestamp = Instant::now();
@@ -48,7 +69,7 @@ pub fn wait_for_next_frame(fps_limiter: &mut FpsLimiter) -> f32 {
             .next_frame_timestamp
             .saturating_duration_since(Instant::now())
             .saturating_sub(fps_limiter.spin_reserve_sec);
-        sleep(fps_limiter.poll_interval_sec.min(remaining));
+        idle(remaining);
     }
 
     // Busy wait at the end for precision
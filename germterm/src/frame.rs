@@ -1,15 +1,21 @@
 use crate::{
     cell::{Cell, CellFormat},
-    color::{Color, blend_source_over},
-    draw::BLOCKTAD_CHAR_LUT,
-    layer::Layer,
+    color::{
+        Color, ColorDepth, ColorEffect, ansi256_rgb, apply_color_effect, blend_source_over,
+        nearest_ansi16, nearest_ansi256,
+    },
+    draw::sub_cell_char,
+    grapheme::{for_each_grapheme, grapheme_width, push_grapheme},
+    layer::{Layer, LayerBlendMode, OctadColorMode},
+    link::LinkId,
     rich_text::{Attributes, RichText},
 };
-use crossterm::{cursor as ctcursor, queue, style as ctstyle};
+use crossterm::{cursor as ctcursor, queue, style as ctstyle, terminal as ctterminal};
 use std::{
-    io::{self, Stdout, Write},
+    collections::HashMap,
+    io::{self, Write},
     ops::{Index, IndexMut},
-    str::Chars,
+    sync::Arc,
 };
 
 #[derive(Clone)]
@@ -104,6 +110,11 @@ impl FramePair {
         Frame(self.frames.as_slice(), self.order as usize)
     }
 
+    /// The frame the current one gets diffed against, which is the last frame drawn to the terminal.
+    pub fn old(&self) -> Frame<'_> {
+        Frame(self.frames.as_slice(), 1 - self.order as usize)
+    }
+
     pub fn current_mut(&mut self) -> FrameMut<'_> {
         FrameMut(self.frames.as_mut_slice(), self.order as usize)
     }
@@ -116,6 +127,13 @@ impl FramePair {
         };
     }
 
+    /// Resets both frames back to empty cells.
+    ///
+    /// The next diff is then made against a blank screen, as if the frame pair was freshly created.
+    pub fn reset_frames(&mut self) {
+        self.frames.fill(Cell::EMPTY);
+    }
+
     pub fn current_mut_and_layered_mut(&mut self) -> (FrameMut<'_>, &mut Vec<Layer>) {
         let frame = FrameMut(&mut self.frames, self.order as usize);
         let layers = &mut self.layered_draw_queue;
@@ -123,79 +141,756 @@ impl FramePair {
     }
 }
 
+/// Removes whole draw calls until at most `cell_budget` cells would be covered, returning the amount of removed calls.
+///
+/// Calls are culled by lowest priority first and, within the same priority, by the most recently queued first,
+/// keeping the result stable between frames. Erasing draw calls never count towards the budget and are never culled.
+pub(crate) fn cull_draw_calls(
+    layers: &mut [Layer],
+    cols: u16,
+    rows: u16,
+    cell_budget: usize,
+) -> usize {
+    let mut coverage: Vec<u16> = vec![0; cols as usize * rows as usize];
+    let mut covered_cells: usize = 0;
+
+    for_each_covered_cell(layers, cols, rows, |cell_index| {
+        if coverage[cell_index] == 0 {
+            covered_cells += 1;
+        }
+        coverage[cell_index] = coverage[cell_index].saturating_add(1);
+    });
+
+    if covered_cells <= cell_budget {
+        return 0;
+    }
+
+    // (priority, layer index, call index), queue order is layer order followed by call order
+    let mut cull_order: Vec<(u8, usize, usize)> = layers
+        .iter()
+        .enumerate()
+        .flat_map(|(layer_index, layer)| {
+            layer
+                .draw_calls
+                .iter()
+                .enumerate()
+                .filter(|_| layer.is_visible)
+                .filter(|(_, draw_call)| !is_erasing_draw_call(draw_call))
+                .map(move |(call_index, draw_call)| {
+                    (draw_call.rich_text.priority, layer_index, call_index)
+                })
+        })
+        .collect();
+    cull_order.sort_unstable_by(|a, b| a.0.cmp(&b.0).then((b.1, b.2).cmp(&(a.1, a.2))));
+
+    let mut is_culled: Vec<Vec<bool>> = layers
+        .iter()
+        .map(|layer| vec![false; layer.draw_calls.len()])
+        .collect();
+    let mut culled_count: usize = 0;
+
+    for (_, layer_index, call_index) in cull_order {
+        if covered_cells <= cell_budget {
+            break;
+        }
+
+        let draw_call: &DrawCall = &layers[layer_index].draw_calls[call_index];
+        for_each_covered_cell_of_call(draw_call, cols, rows, |cell_index| {
+            coverage[cell_index] -= 1;
+            if coverage[cell_index] == 0 {
+                covered_cells -= 1;
+            }
+        });
+
+        is_culled[layer_index][call_index] = true;
+        culled_count += 1;
+    }
+
+    for (layer, is_culled) in layers.iter_mut().zip(is_culled) {
+        let mut is_culled = is_culled.into_iter();
+        layer
+            .draw_calls
+            .retain(|_| !is_culled.next().unwrap_or(false));
+    }
+
+    culled_count
+}
+
+fn is_erasing_draw_call(draw_call: &DrawCall) -> bool {
+    let rich_text: &RichText = &draw_call.rich_text;
+    rich_text
+        .attributes
+        .contains(Attributes::NO_FG_COLOR | Attributes::NO_BG_COLOR)
+        || rich_text.erases_sub_cells
+}
+
+fn for_each_covered_cell(layers: &[Layer], cols: u16, rows: u16, mut f: impl FnMut(usize)) {
+    let visible_layers = layers.iter().filter(|layer| layer.is_visible);
+    for draw_call in visible_layers.flat_map(|layer| layer.draw_calls.iter()) {
+        if is_erasing_draw_call(draw_call) {
+            continue;
+        }
+        for_each_covered_cell_of_call(draw_call, cols, rows, &mut f);
+    }
+}
+
+/// Calls `f` with the index of every in-bounds cell the draw call writes to, mirroring [`compose_draw_call`].
+fn for_each_covered_cell_of_call(
+    draw_call: &DrawCall,
+    cols: u16,
+    rows: u16,
+    mut f: impl FnMut(usize),
+) {
+    let y: i32 = draw_call.y as i32;
+    if y < 0 || y >= rows as i32 {
+        return;
+    }
+
+    let row_start_index: usize = y as usize * cols as usize;
+    for_each_placed_char(
+        &draw_call.rich_text.text,
+        draw_call.x,
+        cols,
+        |col, _, _, width| {
+            for offset in 0..width {
+                f(row_start_index + col + offset);
+            }
+        },
+    );
+}
+
+/// Shifts the queued draw calls of every layer with a camera onto the screen by the layer's camera,
+/// dropping the calls which end up entirely outside of it.
+///
+/// Layers without a camera offset are left as they are, their calls are clipped when composing.
+pub(crate) fn move_into_view(layers: &mut [Layer], cols: u16, rows: u16) {
+    for layer in layers.iter_mut().filter(|layer| layer.camera != (0, 0)) {
+        let camera: (i16, i16) = layer.camera;
+        // Shifting in place keeps the queue's allocation for the next frame
+        layer
+            .draw_calls
+            .retain_mut(|draw_call| through_camera(draw_call, camera, cols, rows));
+    }
+}
+
+/// Shifts a draw call from the coordinates of its layer onto the screen,
+/// returning whether any of it is still within the screen.
+#[inline]
+fn through_camera(draw_call: &mut DrawCall, camera: (i16, i16), cols: u16, rows: u16) -> bool {
+    let x: i32 = draw_call.x as i32 + camera.0 as i32;
+    let y: i32 = draw_call.y as i32 + camera.1 as i32;
+    // A text is never wider than its length in bytes
+    let max_width: i32 = draw_call.rich_text.text.len() as i32;
+    if y < 0 || y >= rows as i32 || x >= cols as i32 || x.saturating_add(max_width) <= 0 {
+        return false;
+    }
+    let Ok(x) = i16::try_from(x) else {
+        return false;
+    };
+
+    draw_call.x = x;
+    draw_call.y = y as i16;
+    true
+}
+
+/// Composes the layers onto a blank frame, consuming their draw calls.
+///
+/// The draw calls are expected to be shifted by their layer's camera already, see [`move_into_view`].
 pub(crate) fn compose_frame_buffer(
     mut buffer: FrameMut<'_>,
-    draw_queue: impl Iterator<Item = DrawCall>,
+    layers: &mut [Layer],
     cols: u16,
     rows: u16,
     default_blending_color: Color,
 ) {
-    let (cols, rows) = (cols as i16, rows as i16);
+    for cell_index in 0..cols as usize * rows as usize {
+        buffer[cell_index] = Cell::EMPTY;
+    }
+    // Only allocated once a frame actually uses a blended layer
+    let mut blend_plane: Vec<Cell> = Vec::new();
 
-    for draw_call in draw_queue {
-        let mut x: i16 = draw_call.x;
-        let y: i16 = draw_call.y;
+    for layer_index in layer_composition_order(layers) {
+        let layer: &mut Layer = &mut layers[layer_index];
+        // Hidden layers still take draw calls, which are simply dropped
+        if !layer.is_visible {
+            layer.draw_calls.clear();
+            continue;
+        }
+
+        let opacity: u8 = layer.opacity;
+        let octad_color_mode: OctadColorMode = layer.octad_color_mode;
+        compose_layer(
+            &mut buffer,
+            &mut blend_plane,
+            layer.blend_mode,
+            layer.draw_calls.drain(..).map(|draw_call| {
+                with_octad_color_mode(apply_opacity(draw_call, opacity), octad_color_mode)
+            }),
+            cols,
+            rows,
+            default_blending_color,
+        );
+        if let Some(effect) = layer.effect {
+            apply_cell_effect(&mut buffer, cols, rows, effect, default_blending_color);
+        }
+    }
+}
+
+/// Composes the draw calls queued so far on the layers up to and including `last_layer_index`,
+/// without consuming them, onto a blank frame.
+///
+/// Gives a look at what the frame would look like if it ended right now, for drawing functions which depend on it.
+pub(crate) fn compose_queued_layers(
+    layers: &[Layer],
+    last_layer_index: usize,
+    cols: u16,
+    rows: u16,
+    default_blending_color: Color,
+) -> Vec<Cell> {
+    let composition_order: Vec<usize> = layer_composition_order(layers);
+    let end: usize = composition_order
+        .iter()
+        .position(|&layer_index| layer_index == last_layer_index)
+        .map_or(composition_order.len(), |position| position + 1);
+    compose_queued(
+        layers,
+        &composition_order[..end],
+        cols,
+        rows,
+        default_blending_color,
+    )
+}
 
-        // --- Skipping out of bounds draw calls ---
-        let is_oob_left: bool = x < 0;
-        let is_oob_top: bool = y < 0;
-        let is_oob_right: bool = x >= cols;
-        let is_oob_bottom: bool = y >= rows;
+/// Like [`compose_queued_layers`], but only composes the layers beneath `layer_index`, leaving the layer itself out.
+pub(crate) fn compose_queued_layers_beneath(
+    layers: &[Layer],
+    layer_index: usize,
+    cols: u16,
+    rows: u16,
+    default_blending_color: Color,
+) -> Vec<Cell> {
+    let composition_order: Vec<usize> = layer_composition_order(layers);
+    let end: usize = composition_order
+        .iter()
+        .position(|&index| index == layer_index)
+        .unwrap_or(composition_order.len());
+    compose_queued(
+        layers,
+        &composition_order[..end],
+        cols,
+        rows,
+        default_blending_color,
+    )
+}
 
-        if is_oob_right || is_oob_top || is_oob_bottom {
+/// Composes the draw calls of a single layer onto empty cells, ignoring its visibility and the layers beneath.
+///
+/// Only the `cols` x `rows` cells starting at `origin` are composed, in the coordinates the draw calls were made in,
+/// so the camera of the layer doesn't apply.
+pub(crate) fn compose_layer_area(
+    layer: &Layer,
+    origin: (i32, i32),
+    cols: u16,
+    rows: u16,
+    default_blending_color: Color,
+) -> Vec<Cell> {
+    let mut buffer: Vec<Cell> = vec![Cell::EMPTY; cols as usize * rows as usize];
+    let mut octad_dots: OctadDots = OctadDots::new();
+    for draw_call in &layer.draw_calls {
+        let row: i32 = draw_call.y as i32 - origin.1;
+        if row < 0 || row >= rows as i32 {
             continue;
         }
+        // Calls starting further left than that end long before reaching the area
+        let Ok(x) = i16::try_from(draw_call.x as i32 - origin.0) else {
+            continue;
+        };
+        let draw_call = DrawCall {
+            rich_text: draw_call.rich_text.clone(),
+            x,
+            y: row as i16,
+        };
+        compose_draw_call(
+            &mut buffer,
+            &mut octad_dots,
+            draw_call,
+            cols,
+            rows,
+            default_blending_color,
+        );
+    }
+    buffer
+}
+
+fn compose_queued(
+    layers: &[Layer],
+    composition_order: &[usize],
+    cols: u16,
+    rows: u16,
+    default_blending_color: Color,
+) -> Vec<Cell> {
+    let mut buffer: Vec<Cell> = vec![Cell::EMPTY; cols as usize * rows as usize];
+    let mut blend_plane: Vec<Cell> = Vec::new();
+
+    for &layer_index in composition_order {
+        let layer: &Layer = &layers[layer_index];
+        if layer.is_visible {
+            compose_layer(
+                &mut buffer,
+                &mut blend_plane,
+                layer.blend_mode,
+                layer
+                    .draw_calls
+                    .iter()
+                    .filter_map(|draw_call| {
+                        let mut draw_call: DrawCall = draw_call.clone();
+                        through_camera(&mut draw_call, layer.camera, cols, rows)
+                            .then_some(draw_call)
+                    })
+                    .map(|draw_call| {
+                        with_octad_color_mode(
+                            apply_opacity(draw_call, layer.opacity),
+                            layer.octad_color_mode,
+                        )
+                    }),
+                cols,
+                rows,
+                default_blending_color,
+            );
+            if let Some(effect) = layer.effect {
+                apply_cell_effect(&mut buffer, cols, rows, effect, default_blending_color);
+            }
+        }
+    }
+    buffer
+}
+
+/// Applies a [`ColorEffect`] to the colors of every composed cell.
+///
+/// Cells without a background color get the default blending color with the effect applied instead,
+/// as that's what they're displayed with.
+pub(crate) fn apply_cell_effect(
+    buffer: &mut impl IndexMut<usize, Output = Cell>,
+    cols: u16,
+    rows: u16,
+    effect: ColorEffect,
+    default_blending_color: Color,
+) {
+    for cell_index in 0..cols as usize * rows as usize {
+        let cell: &mut Cell = &mut buffer[cell_index];
+        if !cell.attributes.contains(Attributes::NO_FG_COLOR) {
+            cell.fg = apply_color_effect(cell.fg, effect);
+        }
+        if cell.attributes.contains(Attributes::NO_BG_COLOR) {
+            cell.bg = default_blending_color;
+            cell.attributes.remove(Attributes::NO_BG_COLOR);
+        }
+        cell.bg = apply_color_effect(cell.bg, effect);
+    }
+}
 
-        let mut chars: Chars<'_> = draw_call.rich_text.text.chars();
+/// Dithers the backgrounds of the composed cells for terminals with [`ColorDepth::Ansi256`],
+/// replacing them with the palette colors they're drawn with.
+///
+/// The error of quantizing a background is carried over to the next cell of the row, breaking up the banding
+/// of smooth gradients. Only opaque backgrounds of [`CellFormat::Standard`] cells are dithered,
+/// as dithered braille and block characters read as noise. No randomness is involved,
+/// so identical frames stay identical and don't show up in the diff.
+pub(crate) fn dither_backgrounds(
+    buffer: &mut impl IndexMut<usize, Output = Cell>,
+    cols: u16,
+    rows: u16,
+) {
+    for row in 0..rows as usize {
+        let mut error: [i16; 3] = [0; 3];
 
-        // --- Cropping the out of bounds left side chars ---
-        if is_oob_left {
-            for _ in 0..(-x) {
-                chars.next();
+        for col in 0..cols as usize {
+            let cell: &mut Cell = &mut buffer[row * cols as usize + col];
+            if cell.format != CellFormat::Standard
+                || cell.attributes.contains(Attributes::NO_BG_COLOR)
+            {
+                error = [0; 3];
+                continue;
             }
-            x = 0;
+
+            let (r, g, b): (u8, u8, u8) = cell.bg.rgb();
+            let target: [u8; 3] = [
+                (r as i16 + error[0]).clamp(0, 255) as u8,
+                (g as i16 + error[1]).clamp(0, 255) as u8,
+                (b as i16 + error[2]).clamp(0, 255) as u8,
+            ];
+            let quantized: (u8, u8, u8) = ansi256_rgb(nearest_ansi256(Color::new(
+                target[0], target[1], target[2], 255,
+            )));
+
+            error = [
+                target[0] as i16 - quantized.0 as i16,
+                target[1] as i16 - quantized.1 as i16,
+                target[2] as i16 - quantized.2 as i16,
+            ];
+            cell.bg = Color::new(quantized.0, quantized.1, quantized.2, cell.bg.a());
         }
+    }
+}
+
+/// The indices of the layers in the order they're composed in, from the bottom up.
+///
+/// Layers are composed by their index, as changed by [`crate::layer::set_layer_index`],
+/// and by the index they were created with when those are equal.
+pub(crate) fn layer_composition_order(layers: &[Layer]) -> Vec<usize> {
+    let mut composition_order: Vec<usize> = (0..layers.len()).collect();
+    composition_order.sort_by_key(|&layer_index| {
+        (
+            layers[layer_index].order.unwrap_or(layer_index),
+            layer_index,
+        )
+    });
+    composition_order
+}
+
+fn compose_layer(
+    buffer: &mut impl IndexMut<usize, Output = Cell>,
+    blend_plane: &mut Vec<Cell>,
+    blend_mode: LayerBlendMode,
+    draw_calls: impl Iterator<Item = DrawCall>,
+    cols: u16,
+    rows: u16,
+    default_blending_color: Color,
+) {
+    let mut octad_dots: OctadDots = OctadDots::new();
+    if blend_mode == LayerBlendMode::Normal {
+        for draw_call in draw_calls {
+            compose_draw_call(
+                buffer,
+                &mut octad_dots,
+                draw_call,
+                cols,
+                rows,
+                default_blending_color,
+            );
+        }
+        return;
+    }
+
+    blend_plane.clear();
+    blend_plane.resize(cols as usize * rows as usize, TRANSPARENT_CELL);
+    for draw_call in draw_calls {
+        compose_draw_call(
+            blend_plane,
+            &mut octad_dots,
+            draw_call,
+            cols,
+            rows,
+            default_blending_color,
+        );
+    }
 
-        let row_start_index: usize = (y as usize) * (cols as usize);
-        let remaining_cols: usize = (cols - x).max(0) as usize;
+    for (cell_index, &layer_cell) in blend_plane.iter().enumerate() {
+        buffer[cell_index] = blend_cell(
+            buffer[cell_index],
+            layer_cell,
+            blend_mode,
+            default_blending_color,
+        );
+    }
+}
+
+/// A cell which leaves anything it's composed onto unchanged.
+const TRANSPARENT_CELL: Cell = Cell {
+    ch: ' ',
+    fg: Color::CLEAR,
+    bg: Color::CLEAR,
+    attributes: Attributes::empty(),
+    format: CellFormat::Standard,
+    format_data: 0,
+    link: None,
+};
 
-        for (x_offset, ch) in chars.take(remaining_cols).enumerate() {
-            let cell_index: usize = row_start_index + x as usize + x_offset;
-            let old_cell: Cell = buffer[cell_index];
+fn compose_draw_call(
+    buffer: &mut impl IndexMut<usize, Output = Cell>,
+    octad_dots: &mut OctadDots,
+    draw_call: DrawCall,
+    cols: u16,
+    rows: u16,
+    default_blending_color: Color,
+) {
+    let y: i32 = draw_call.y as i32;
+    if y < 0 || y >= rows as i32 {
+        return;
+    }
+
+    let row_start_index: usize = y as usize * cols as usize;
+    let rich_text: &RichText = &draw_call.rich_text;
+
+    for_each_placed_char(
+        &rich_text.text,
+        draw_call.x,
+        cols,
+        |col, ch, cluster_data, width| {
             let new_cell: Cell = Cell {
                 ch,
-                fg: draw_call.rich_text.fg,
-                bg: draw_call.rich_text.bg,
-                attributes: draw_call.rich_text.attributes,
-                format: draw_call.rich_text.cell_format,
+                fg: rich_text.fg,
+                bg: rich_text.bg,
+                attributes: rich_text.attributes,
+                format: rich_text.cell_format,
+                format_data: match rich_text.cell_format {
+                    CellFormat::Standard => cluster_data,
+                    _ => rich_text.format_data,
+                },
+                link: rich_text.link,
             };
+            let old_cell: Cell = buffer[row_start_index + col];
+            if new_cell.format == CellFormat::Octad
+                && rich_text.octad_color_mode != OctadColorMode::LastWins
+            {
+                let composed: Cell = compose_tracked_octad(
+                    octad_dots,
+                    row_start_index + col,
+                    old_cell,
+                    new_cell,
+                    rich_text,
+                    default_blending_color,
+                );
+                write_cell(buffer, row_start_index, col, cols, composed);
+                return;
+            }
+            if rich_text.erases_sub_cells {
+                write_cell(
+                    buffer,
+                    row_start_index,
+                    col,
+                    cols,
+                    compose_sub_cell_erase(old_cell, new_cell),
+                );
+                return;
+            }
+            let composed: Cell = compose_cell(old_cell, new_cell, default_blending_color);
+            let composed: Cell = write_cell(buffer, row_start_index, col, cols, composed);
+
+            // The trailing half only exists while the wide char itself made it into the cell
+            if width == 2 && composed.ch == ch {
+                let continuation: Cell = Cell {
+                    ch: Cell::WIDE_CONTINUATION,
+                    ..composed
+                };
+                write_cell(buffer, row_start_index, col + 1, cols, continuation);
+            }
+        },
+    );
+}
+
+/// Multiplies the opacity of a layer into the alpha of the colors of a draw call on it.
+#[inline]
+fn apply_opacity(mut draw_call: DrawCall, opacity: u8) -> DrawCall {
+    if opacity == u8::MAX {
+        return draw_call;
+    }
+
+    let scale_alpha = |color: Color| -> Color {
+        let alpha: u16 = (color.a() as u16 * opacity as u16 + 127) / 255;
+        color.with_alpha(alpha as u8)
+    };
+    draw_call.rich_text.fg = scale_alpha(draw_call.rich_text.fg);
+    draw_call.rich_text.bg = scale_alpha(draw_call.rich_text.bg);
+    draw_call
+}
+
+#[inline]
+fn with_octad_color_mode(mut draw_call: DrawCall, mode: OctadColorMode) -> DrawCall {
+    draw_call.rich_text.octad_color_mode = mode;
+    draw_call
+}
+
+/// Calls `f` with the column, cell char, `format_data` of `Standard` cells and width
+/// of every grapheme cluster of `text` that fits on screen when drawn at `x`, see [`for_each_grapheme`].
+///
+/// Zero-width clusters are skipped, and wide clusters that would only partially fit are dropped.
+#[inline]
+fn for_each_placed_char(text: &str, x: i16, cols: u16, mut f: impl FnMut(usize, char, u8, usize)) {
+    let mut col: i32 = x as i32;
+
+    for_each_grapheme(text, |ch, cluster_data, width| {
+        let width: i32 = width as i32;
+        if width > 0 && col >= 0 && col + width <= cols as i32 {
+            f(col as usize, ch, cluster_data, width as usize);
+        }
+        col += width;
+    });
+}
+
+/// Writes a cell, turning the other half of any wide char it overwrites into a space,
+/// so no half-glyphs are left behind.
+#[inline]
+fn write_cell(
+    buffer: &mut impl IndexMut<usize, Output = Cell>,
+    row_start_index: usize,
+    col: usize,
+    cols: u16,
+    cell: Cell,
+) -> Cell {
+    debug_assert!(
+        col < cols as usize,
+        "Cell column {col} falls outside of {cols} columns"
+    );
+    let cell_index: usize = row_start_index + col;
+    let old: Cell = buffer[cell_index];
+
+    if (old.ch, old.format_data) != (cell.ch, cell.format_data) {
+        if old.ch == Cell::WIDE_CONTINUATION && col > 0 {
+            let leading: &mut Cell = &mut buffer[cell_index - 1];
+            if grapheme_width(leading) == 2 {
+                leading.ch = ' ';
+                leading.format_data = 0;
+            }
+        } else if grapheme_width(&old) == 2 && col + 1 < cols as usize {
+            let trailing: &mut Cell = &mut buffer[cell_index + 1];
+            if trailing.ch == Cell::WIDE_CONTINUATION {
+                trailing.ch = ' ';
+                trailing.format_data = 0;
+            }
+        }
+    }
+
+    buffer[cell_index] = cell;
+    cell
+}
+
+/// Merges a cell of a blended layer onto the cell beneath it, see [`LayerBlendMode`].
+fn blend_cell(
+    old: Cell,
+    layer_cell: Cell,
+    mode: LayerBlendMode,
+    default_blending_color: Color,
+) -> Cell {
+    let mut result: Cell = old;
+
+    if !layer_cell.attributes.contains(Attributes::NO_BG_COLOR) && layer_cell.bg.a() > 0 {
+        let old_bg: Color = if old.attributes.contains(Attributes::NO_BG_COLOR) {
+            default_blending_color
+        } else {
+            old.bg
+        };
+        result.bg = blend_colors(old_bg, layer_cell.bg, mode);
+        result.attributes.remove(Attributes::NO_BG_COLOR);
+
+        if !old.attributes.contains(Attributes::NO_FG_COLOR) {
+            result.fg = blend_colors(old.fg, layer_cell.bg, mode);
+        }
+    }
+
+    // Characters of the blended layer itself are drawn on top as usual, without their bg
+    if layer_cell.ch != ' ' {
+        let char_cell: Cell = Cell {
+            bg: Color::CLEAR,
+            attributes: layer_cell.attributes.difference(Attributes::NO_BG_COLOR),
+            ..layer_cell
+        };
+        result = compose_cell(result, char_cell, default_blending_color);
+    }
+
+    result
+}
+
+/// Blends `top` onto `bottom` using a [`LayerBlendMode`], with the alpha of `top` as the strength of the effect.
+///
+/// The alpha of `bottom` is kept as-is.
+#[inline]
+fn blend_colors(bottom: Color, top: Color, mode: LayerBlendMode) -> Color {
+    #[inline]
+    fn blend_channel(b: u8, t: u8, mode: LayerBlendMode, strength: u32) -> u8 {
+        let (b32, t32): (u32, u32) = (b as u32, t as u32);
+        let blended: u32 = match mode {
+            LayerBlendMode::Normal => t32,
+            LayerBlendMode::Multiply => (b32 * t32 + 127) / 255,
+            LayerBlendMode::Screen => 255 - ((255 - b32) * (255 - t32) + 127) / 255,
+            LayerBlendMode::Overlay if b32 < 128 => (2 * b32 * t32 + 127) / 255,
+            LayerBlendMode::Overlay => 255 - (2 * (255 - b32) * (255 - t32) + 127) / 255,
+        };
+        // Mixing between the original and the blended channel by the strength
+        ((b32 * (255 - strength) + blended * strength + 127) / 255) as u8
+    }
+
+    let strength: u32 = top.a() as u32;
+    Color::new(
+        blend_channel(bottom.r(), top.r(), mode, strength),
+        blend_channel(bottom.g(), top.g(), mode, strength),
+        blend_channel(bottom.b(), top.b(), mode, strength),
+        bottom.a(),
+    )
+}
+
+/// The most quantized colors [`TerminalColors`] keeps around, eg. smooth gradients could otherwise grow the cache without bound.
+const MAX_QUANTIZED_COLORS: usize = 4096;
+
+/// Maps colors to what the terminal is able to display, caching the quantized colors
+/// since frames tend to reuse the same handful of colors.
+pub(crate) struct TerminalColors {
+    pub(crate) depth: ColorDepth,
+    /// Whether backgrounds are dithered with [`dither_backgrounds`] when the depth is [`ColorDepth::Ansi256`].
+    pub(crate) dithering: bool,
+    quantized: HashMap<u32, u8>,
+}
 
-            buffer[cell_index] = compose_cell(old_cell, new_cell, default_blending_color);
+impl TerminalColors {
+    pub fn new(depth: ColorDepth) -> Self {
+        Self {
+            depth,
+            dithering: false,
+            quantized: HashMap::new(),
         }
     }
 }
 
-pub(crate) fn build_crossterm_content_style(cell: &Cell) -> crossterm::style::ContentStyle {
+pub(crate) fn set_color_depth(terminal_colors: &mut TerminalColors, depth: ColorDepth) {
+    if terminal_colors.depth != depth {
+        terminal_colors.depth = depth;
+        terminal_colors.quantized.clear();
+    }
+}
+
+fn to_crossterm_color(terminal_colors: &mut TerminalColors, color: Color) -> ctstyle::Color {
+    let quantize: fn(Color) -> u8 = match terminal_colors.depth {
+        ColorDepth::TrueColor => {
+            return ctstyle::Color::Rgb {
+                r: color.r(),
+                g: color.g(),
+                b: color.b(),
+            };
+        }
+        ColorDepth::Ansi256 => nearest_ansi256,
+        ColorDepth::Ansi16 => nearest_ansi16,
+    };
+
+    let rgb_key: u32 = color.0 & 0xFFFF_FF00;
+    // Starting over is cheap, as the colors still in use get cached again within a frame
+    if terminal_colors.quantized.len() >= MAX_QUANTIZED_COLORS
+        && !terminal_colors.quantized.contains_key(&rgb_key)
+    {
+        terminal_colors.quantized.clear();
+    }
+    let index: u8 = *terminal_colors
+        .quantized
+        .entry(rgb_key)
+        .or_insert_with(|| quantize(color));
+    ctstyle::Color::AnsiValue(index)
+}
+
+pub(crate) fn build_crossterm_content_style(
+    cell: &Cell,
+    terminal_colors: &mut TerminalColors,
+) -> crossterm::style::ContentStyle {
     use crossterm::style as ctstyle;
 
     let fg_color: Option<ctstyle::Color> = if cell.attributes.contains(Attributes::NO_FG_COLOR) {
         None
     } else {
-        Some(ctstyle::Color::Rgb {
-            r: cell.fg.r(),
-            g: cell.fg.g(),
-            b: cell.fg.b(),
-        })
+        Some(to_crossterm_color(terminal_colors, cell.fg))
     };
 
     let bg_color: Option<ctstyle::Color> = if cell.attributes.contains(Attributes::NO_BG_COLOR) {
         None
     } else {
-        Some(ctstyle::Color::Rgb {
-            r: cell.bg.r(),
-            g: cell.bg.g(),
-            b: cell.bg.b(),
-        })
+        Some(to_crossterm_color(terminal_colors, cell.bg))
     };
 
     let attributes = [
@@ -224,41 +919,130 @@ pub(crate) fn build_crossterm_content_style(cell: &Cell) -> crossterm::style::Co
     }
 }
 
+/// Writes the changed cells to the terminal.
+///
+/// Diff products come in row-major order, so consecutive cells on the same row are batched into runs:
+/// the cursor is only moved when a cell doesn't directly follow the previous one,
+/// and the style is only re-emitted when it differs from the previous cell.
+///
+/// Continuation cells of wide chars are skipped, as the leading cell already covers them.
+///
+/// Rows are offset by `origin_row`, which is non-zero when rendering inline.
+///
+/// Nothing is flushed, so the caller can wrap the whole frame in synchronized update sequences (`CSI ? 2026 h/l`).
+///
+/// When `hyperlinks` is set, runs of cells with the same link are wrapped in a single pair of OSC 8 sequences.
+/// The link is closed again before returning, so nothing printed afterwards ends up linked.
 pub(crate) fn draw_to_terminal<'a>(
-    stdout: &mut Stdout,
+    writer: &mut impl Write,
     diff_products: impl Iterator<Item = DiffProduct<'a>>,
+    terminal_colors: &mut TerminalColors,
+    origin_row: u16,
+    hyperlinks: bool,
 ) -> io::Result<()> {
+    let mut run: String = String::new();
+    // Where the terminal cursor ends up after printing the current run
+    let mut run_end: Option<(u16, u16)> = None;
+    let mut last_style: Option<ctstyle::ContentStyle> = None;
+    let mut open_link: Option<LinkId> = None;
+
     for diff_product in diff_products {
         let x: u16 = diff_product.x;
         let y: u16 = diff_product.y;
         let cell: &Cell = diff_product.cell;
 
-        let style: ctstyle::ContentStyle = build_crossterm_content_style(cell);
-        queue!(
-            stdout,
-            ctcursor::MoveTo(x, y),
-            ctstyle::SetAttribute(ctstyle::Attribute::Reset),
-            ctstyle::SetStyle(style),
-            ctstyle::Print(cell.ch),
-        )?;
+        // Drawn as part of the leading cell
+        if cell.ch == Cell::WIDE_CONTINUATION {
+            continue;
+        }
+
+        let style: ctstyle::ContentStyle = build_crossterm_content_style(cell, terminal_colors);
+        let link: Option<LinkId> = cell.link.filter(|_| hyperlinks);
+        let is_continuing_run: bool =
+            run_end == Some((x, y)) && last_style == Some(style) && open_link == link;
+
+        if !is_continuing_run {
+            if !run.is_empty() {
+                queue!(writer, ctstyle::Print(&run))?;
+                run.clear();
+            }
+            if run_end != Some((x, y)) {
+                queue!(writer, ctcursor::MoveTo(x, y + origin_row))?;
+            }
+            if open_link != link {
+                write_link(writer, link)?;
+                open_link = link;
+            }
+            if last_style != Some(style) {
+                queue!(
+                    writer,
+                    ctstyle::SetAttribute(ctstyle::Attribute::Reset),
+                    ctstyle::SetStyle(style),
+                )?;
+                last_style = Some(style);
+            }
+        }
+
+        push_grapheme(&mut run, cell);
+        run_end = Some((x + grapheme_width(cell) as u16, y));
     }
 
-    stdout.flush()?;
+    if !run.is_empty() {
+        queue!(writer, ctstyle::Print(&run))?;
+    }
+    if open_link.is_some() {
+        write_link(writer, None)?;
+    }
     Ok(())
 }
 
+/// Resets the style and clears the rows the frame is drawn to, ahead of a full repaint.
+pub(crate) fn clear_for_repaint(writer: &mut impl Write, origin_row: u16) -> io::Result<()> {
+    queue!(
+        writer,
+        ctstyle::SetAttribute(ctstyle::Attribute::Reset),
+        ctcursor::MoveTo(0, origin_row),
+        ctterminal::Clear(ctterminal::ClearType::FromCursorDown),
+    )
+}
+
+/// Starts an OSC 8 hyperlink, which closes the previous one, or closes it when `link` is `None`.
+#[inline]
+fn write_link(writer: &mut impl Write, link: Option<LinkId>) -> io::Result<()> {
+    let url: Option<Arc<str>> = link.map(LinkId::url);
+    write!(
+        writer,
+        "\x1b]8;;{}\x1b\\",
+        url.as_deref().unwrap_or_default()
+    )
+}
+
+/// Composes a `new` cell on top of an `old` one.
+///
+/// Guaranteed properties, compared by what ends up displayed in the terminal:
+/// - **Identity**: composing a space with a fully transparent `fg` and `bg` and no `NO_{FG/BG}_COLOR` attributes
+///   leaves any cell unchanged.
+/// - **Associativity**: for `Standard` format cells whose colors are either fully opaque or fully transparent,
+///   composing `a`, then `b`, then `c` gives the same result as composing `a` with the pre-composed `b` + `c`.
+///   Checked exhaustively over such cells in this module's tests.
+///
+/// An invisible character (a space, or one with a fully transparent `fg`) over an opaque or `NO_BG_COLOR` bg
+/// erases the character underneath, rather than being displayed in the `fg` of the cell below.
+///
+/// Translucent colors and the sub-cell formats (`Twoxel`, `Octad`, `Blocktad`, `Sextant`, `Quadrant`) are order-dependent by design,
+/// as blending and sub-cell merging depend on what's underneath.
 #[inline]
 fn compose_cell(old: Cell, new: Cell, default_blending_color: Color) -> Cell {
     let both_ch_equal: bool = old.ch == new.ch;
 
     // Cell format related
     let new_twoxel: bool = new.format == CellFormat::Twoxel;
-    let new_octad: bool = new.format == CellFormat::Octad;
-    let new_blocktad: bool = new.format == CellFormat::Blocktad;
-
     let old_twoxel: bool = old.format == CellFormat::Twoxel;
-    let old_octad: bool = old.format == CellFormat::Octad;
-    let old_blocktad: bool = old.format == CellFormat::Blocktad;
+    let is_sub_cell_merge: bool = old.format == new.format
+        && matches!(
+            new.format,
+            CellFormat::Octad | CellFormat::Blocktad | CellFormat::Sextant | CellFormat::Quadrant
+        );
 
     // Foreground related
     let new_fg_no_color: bool = new.attributes.contains(Attributes::NO_FG_COLOR);
@@ -280,104 +1064,146 @@ fn compose_cell(old: Cell, new: Cell, default_blending_color: Color) -> Cell {
     let old_bg_no_color: bool = old.attributes.contains(Attributes::NO_BG_COLOR);
     let old_bg_opaque: bool = old.bg.a() == 255;
 
-    let (ch, format, mut attributes, fg, no_fg_color, bg, no_bg_color) = if new_twoxel {
-        let (ch, format, attributes) = if old_twoxel && !new_fg_no_color {
-            (old.ch, old.format, old.attributes)
-        } else {
-            (new.ch, new.format, new.attributes)
-        };
-
-        let (fg, no_fg_color) = if old_twoxel && both_ch_equal {
-            (blend_source_over(old.fg, new.fg), false)
-        } else if old_twoxel {
-            (old.fg, false)
-        } else if !old_bg_no_color {
-            (blend_source_over(old.bg, new.fg), false)
-        } else if new_fg_invisible {
-            (default_blending_color, true)
-        } else {
-            (blend_source_over(default_blending_color, new.fg), false)
-        };
+    let (ch, format, format_data, mut attributes, link, fg, no_fg_color, bg, no_bg_color) =
+        if new_twoxel {
+            let (ch, format, attributes, link) = if old_twoxel && !new_fg_no_color {
+                (old.ch, old.format, old.attributes, old.link)
+            } else {
+                (new.ch, new.format, new.attributes, new.link)
+            };
 
-        let (bg, no_bg_color) = if old_twoxel && both_ch_equal {
-            (old.bg, false)
-        } else if old_twoxel && old_bg_no_color {
-            if new_fg_invisible {
+            let (fg, no_fg_color) = if old_twoxel && both_ch_equal {
+                (blend_source_over(old.fg, new.fg), false)
+            } else if old_twoxel {
+                (old.fg, false)
+            } else if !old_bg_no_color {
+                (blend_source_over(old.bg, new.fg), false)
+            } else if new_fg_invisible {
                 (default_blending_color, true)
             } else {
                 (blend_source_over(default_blending_color, new.fg), false)
-            }
-        } else if old_twoxel {
-            (blend_source_over(old.bg, new.fg), false)
-        } else if old_bg_no_color {
-            (old.bg, true)
-        } else {
-            (old.bg, false)
-        };
+            };
 
-        (ch, format, attributes, fg, no_fg_color, bg, no_bg_color)
-    } else {
-        // This branch handles the following cell formats: [Standard, Octad, Blocktad]
-        let (ch, format, attributes) = if new_fg_no_color && new_bg_opaque && !old_ch_invisible {
-            (new.ch, new.format, new.attributes)
-        } else if new_blocktad && old_blocktad {
-            (merge_blocktad(old.ch, new.ch), new.format, new.attributes)
-        } else if new_octad && old_octad {
-            (merge_octad(old.ch, new.ch), new.format, new.attributes)
-        } else if new_ch_invisible && !new_bg_no_color {
-            (old.ch, old.format, old.attributes)
+            let (bg, no_bg_color) = if old_twoxel && both_ch_equal {
+                (old.bg, false)
+            } else if old_twoxel && old_bg_no_color {
+                if new_fg_invisible {
+                    (default_blending_color, true)
+                } else {
+                    (blend_source_over(default_blending_color, new.fg), false)
+                }
+            } else if old_twoxel {
+                (blend_source_over(old.bg, new.fg), false)
+            } else if old_bg_no_color {
+                (old.bg, true)
+            } else {
+                (old.bg, false)
+            };
+
+            (
+                ch,
+                format,
+                0,
+                attributes,
+                link,
+                fg,
+                no_fg_color,
+                bg,
+                no_bg_color,
+            )
         } else {
-            (new.ch, new.format, new.attributes)
-        };
+            // This branch handles the following cell formats: [Standard, Octad, Blocktad, Sextant, Quadrant]
+            let (ch, format, format_data, attributes, link) =
+                if new_ch_invisible && (new_bg_opaque || new_bg_no_color) {
+                    // An opaque or erased bg fully covers whatever character was underneath
+                    (' ', new.format, 0, new.attributes, new.link)
+                } else if is_sub_cell_merge {
+                    let format_data: u8 = old.format_data | new.format_data;
+                    let ch: char = sub_cell_char(new.format, format_data);
+                    (
+                        ch,
+                        new.format,
+                        format_data,
+                        new.attributes,
+                        new.link.or(old.link),
+                    )
+                } else if new_ch_invisible && !new_bg_no_color {
+                    // The char underneath shows through along with its link, but linked spaces
+                    // over empty cells keep their link, so the link isn't split up at every word
+                    let link: Option<LinkId> = if old.ch == ' ' {
+                        new.link.or(old.link)
+                    } else {
+                        old.link
+                    };
+                    (old.ch, old.format, old.format_data, old.attributes, link)
+                } else {
+                    (
+                        new.ch,
+                        new.format,
+                        new.format_data,
+                        new.attributes,
+                        new.link,
+                    )
+                };
 
-        let (fg, no_fg_color) = if new_ch_invisible && new_bg_opaque {
-            (Color::CLEAR, true)
-        } else if new_ch_invisible {
-            if new_bg_invisible && old_bg_no_color {
-                (old.fg, false)
-            } else if new_bg_translucent {
-                (blend_source_over(old.fg, new.bg), false)
-            } else {
-                (old.fg, old_fg_no_color)
-            }
-        } else if new_ch_translucent {
-            let bottom_color = if !old_ch_invisible {
-                old.fg
-            } else if old_bg_no_color && new_bg_invisible {
-                default_blending_color
-            } else if old_bg_no_color && new_bg_translucent {
-                blend_source_over(default_blending_color, new.bg)
-            } else if new_bg_opaque {
-                new.bg
-            } else if old_bg_opaque && new_bg_translucent {
-                blend_source_over(old.bg, new.bg)
-            } else if old_bg_opaque {
-                old.bg
+            let (fg, no_fg_color) = if new_ch_invisible && (new_bg_opaque || new_bg_no_color) {
+                (Color::CLEAR, true)
+            } else if new_ch_invisible {
+                if new_bg_invisible {
+                    (old.fg, old_fg_no_color)
+                } else if new_bg_translucent {
+                    (blend_source_over(old.fg, new.bg), false)
+                } else {
+                    (old.fg, old_fg_no_color)
+                }
+            } else if new_ch_translucent {
+                let bottom_color = if !old_ch_invisible {
+                    old.fg
+                } else if old_bg_no_color && new_bg_invisible {
+                    default_blending_color
+                } else if old_bg_no_color && new_bg_translucent {
+                    blend_source_over(default_blending_color, new.bg)
+                } else if new_bg_opaque {
+                    new.bg
+                } else if old_bg_opaque && new_bg_translucent {
+                    blend_source_over(old.bg, new.bg)
+                } else if old_bg_opaque {
+                    old.bg
+                } else {
+                    Color::CLEAR
+                };
+                (blend_source_over(bottom_color, new.fg), new_fg_no_color)
             } else {
-                Color::CLEAR
+                (new.fg, new_fg_no_color)
             };
-            (blend_source_over(bottom_color, new.fg), new_fg_no_color)
-        } else {
-            (new.fg, new_fg_no_color)
-        };
 
-        let (bg, no_bg_color) = if new_bg_no_color || (old_bg_no_color && new_bg_invisible) {
-            (Color::CLEAR, true)
-        } else if new_bg_invisible {
-            (old.bg, false)
-        } else if new_bg_translucent {
-            let bottom_color = if old_bg_no_color {
-                default_blending_color
+            let (bg, no_bg_color) = if new_bg_no_color || (old_bg_no_color && new_bg_invisible) {
+                (Color::CLEAR, true)
+            } else if new_bg_invisible {
+                (old.bg, false)
+            } else if new_bg_translucent {
+                let bottom_color = if old_bg_no_color {
+                    default_blending_color
+                } else {
+                    old.bg
+                };
+                (blend_source_over(bottom_color, new.bg), false)
             } else {
-                old.bg
+                (new.bg, false)
             };
-            (blend_source_over(bottom_color, new.bg), false)
-        } else {
-            (new.bg, false)
-        };
 
-        (ch, format, attributes, fg, no_fg_color, bg, no_bg_color)
-    };
+            (
+                ch,
+                format,
+                format_data,
+                attributes,
+                link,
+                fg,
+                no_fg_color,
+                bg,
+                no_bg_color,
+            )
+        };
 
     // Independent NO_{FG/BG}_COLOR patched into attributes
     attributes = (attributes & !(Attributes::NO_FG_COLOR | Attributes::NO_BG_COLOR))
@@ -398,28 +1224,837 @@ fn compose_cell(old: Cell, new: Cell, default_blending_color: Color) -> Cell {
         bg,
         attributes,
         format,
+        format_data,
+        link,
     }
 }
 
-#[inline]
-fn merge_octad(a: char, b: char) -> char {
-    let mask_a = (a as u32) - 0x2800;
-    let mask_b = (b as u32) - 0x2800;
-    std::char::from_u32(0x2800 + (mask_a | mask_b)).unwrap()
+/// The octad cells of a layer being composed in an [`OctadColorMode`] other than `LastWins`, by their cell index.
+type OctadDots = HashMap<usize, TrackedOctad>;
+
+/// An octad cell along with the color every one of its dots was drawn with.
+#[derive(Clone, Copy)]
+struct TrackedOctad {
+    /// The cell as `LastWins` composes it, holding every dot.
+    merged: Cell,
+    dot_colors: [Color; 8],
+    /// When each dot was drawn, counting up with every draw call on the cell.
+    dot_draws: [u32; 8],
+    draws: u32,
+    /// The cell written to the buffer. Once the cell holds anything else, it was drawn over and the dots are forgotten.
+    shown: Cell,
+}
+
+/// Composes an octad draw call onto an `old` cell in the [`OctadColorMode`] of the draw call,
+/// keeping track of the color of every dot in `octad_dots`.
+///
+/// Dots which were in the cell before the layer started tracking it count towards the color they're displayed in.
+fn compose_tracked_octad(
+    octad_dots: &mut OctadDots,
+    cell_index: usize,
+    old: Cell,
+    new: Cell,
+    rich_text: &RichText,
+    default_blending_color: Color,
+) -> Cell {
+    let mut tracked: TrackedOctad = match octad_dots.get(&cell_index) {
+        Some(tracked) if tracked.shown == old => *tracked,
+        _ => TrackedOctad {
+            merged: old,
+            dot_colors: [old.fg; 8],
+            dot_draws: [0; 8],
+            draws: 0,
+            shown: old,
+        },
+    };
+
+    let merged: Cell = if rich_text.erases_sub_cells {
+        compose_sub_cell_erase(tracked.merged, new)
+    } else {
+        compose_cell(tracked.merged, new, default_blending_color)
+    };
+    if merged.format != CellFormat::Octad || merged.attributes.contains(Attributes::NO_FG_COLOR) {
+        octad_dots.remove(&cell_index);
+        return merged;
+    }
+
+    tracked.merged = merged;
+    if !rich_text.erases_sub_cells {
+        tracked.draws += 1;
+        for dot in (0..8).filter(|dot| new.format_data & (1 << dot) != 0) {
+            tracked.dot_colors[dot] = merged.fg;
+            tracked.dot_draws[dot] = tracked.draws;
+        }
+    }
+    tracked.shown =
+        resolve_octad_colors(&tracked, rich_text.octad_color_mode, default_blending_color);
+    octad_dots.insert(cell_index, tracked);
+    tracked.shown
+}
+
+/// The cell a [`TrackedOctad`] is displayed as, with its colors picked by an [`OctadColorMode`].
+fn resolve_octad_colors(
+    tracked: &TrackedOctad,
+    mode: OctadColorMode,
+    default_blending_color: Color,
+) -> Cell {
+    let merged: Cell = tracked.merged;
+    // The distinct colors of the dots, with their amount of dots and when they were last drawn
+    let mut tallies: [(Color, u32, u32); 8] = [(Color::CLEAR, 0, 0); 8];
+    let mut tally_count: usize = 0;
+    for dot in (0..8).filter(|dot| merged.format_data & (1 << dot) != 0) {
+        let (color, drawn_at): (Color, u32) = (tracked.dot_colors[dot], tracked.dot_draws[dot]);
+        match tallies[..tally_count]
+            .iter_mut()
+            .find(|(tally_color, _, _)| *tally_color == color)
+        {
+            Some(tally) => {
+                tally.1 += 1;
+                tally.2 = tally.2.max(drawn_at);
+            }
+            None => {
+                tallies[tally_count] = (color, 1, drawn_at);
+                tally_count += 1;
+            }
+        }
+    }
+    let tallies: &mut [(Color, u32, u32)] = &mut tallies[..tally_count];
+
+    match mode {
+        OctadColorMode::LastWins => merged,
+        OctadColorMode::Dominant => {
+            // Most dots first, the last drawn color on a tie
+            tallies.sort_by_key(|&(_, dots, drawn_at)| std::cmp::Reverse((dots, drawn_at)));
+            let Some(&(dominant, _, _)) = tallies.first() else {
+                return merged;
+            };
+            let format_data: u8 = (0..8)
+                .filter(|&dot| {
+                    merged.format_data & (1 << dot) != 0 && tracked.dot_colors[dot] == dominant
+                })
+                .fold(0, |mask, dot| mask | (1 << dot));
+            let mut cell: Cell = Cell {
+                ch: sub_cell_char(CellFormat::Octad, format_data),
+                fg: dominant,
+                format_data,
+                ..merged
+            };
+            // The runner-up takes over the bg, its dots being the ones turned off
+            if let Some(&(runner_up, _, _)) = tallies.get(1) {
+                let bottom_color: Color = if merged.attributes.contains(Attributes::NO_BG_COLOR) {
+                    default_blending_color
+                } else {
+                    merged.bg
+                };
+                cell.bg = if runner_up.a() == 255 {
+                    runner_up
+                } else {
+                    blend_source_over(bottom_color, runner_up)
+                };
+                cell.attributes.remove(Attributes::NO_BG_COLOR);
+            }
+            cell
+        }
+        OctadColorMode::Average => {
+            let dot_count: u32 = merged.format_data.count_ones();
+            let channel = |channel_of: fn(&Color) -> u8| -> u8 {
+                let sum: u32 = tallies
+                    .iter()
+                    .map(|(color, dots, _)| channel_of(color) as u32 * dots)
+                    .sum();
+                ((sum + dot_count / 2) / dot_count) as u8
+            };
+            Cell {
+                fg: Color::new(
+                    channel(Color::r),
+                    channel(Color::g),
+                    channel(Color::b),
+                    channel(Color::a),
+                ),
+                ..merged
+            }
+        }
+    }
 }
 
+/// Clears the sub-cells of an `erase` cell from an `old` octad, blocktad, sextant or quadrant cell.
+///
+/// Once no sub-cells are left, the cell reverts to having no character and no `fg` color, while keeping its `bg`.
 #[inline]
-fn merge_blocktad(a: char, b: char) -> char {
-    let mask_a = BLOCKTAD_CHAR_LUT
-        .iter()
-        .position(|&c| c == a)
-        .expect("char not in BLOCKTAD LUT") as u8;
-    let mask_b = BLOCKTAD_CHAR_LUT
-        .iter()
-        .position(|&c| c == b)
-        .expect("char not in BLOCKTAD LUT") as u8;
+fn compose_sub_cell_erase(old: Cell, erase: Cell) -> Cell {
+    let is_matching_erase: bool = old.format == erase.format
+        && matches!(
+            old.format,
+            CellFormat::Octad | CellFormat::Blocktad | CellFormat::Sextant | CellFormat::Quadrant
+        );
+    if !is_matching_erase {
+        return old;
+    }
 
-    let merged_mask = mask_a | mask_b;
+    let format_data: u8 = old.format_data & !erase.format_data;
+    if format_data != 0 {
+        return Cell {
+            ch: sub_cell_char(old.format, format_data),
+            format_data,
+            ..old
+        };
+    }
+    Cell {
+        ch: ' ',
+        fg: Color::CLEAR,
+        attributes: old.attributes | Attributes::NO_FG_COLOR,
+        format: CellFormat::Standard,
+        format_data: 0,
+        link: None,
+        ..old
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{
+        DiffProduct, MAX_QUANTIZED_COLORS, TerminalColors, blend_colors, compose_cell,
+        draw_to_terminal, move_into_view, to_crossterm_color,
+    };
+    use crate::{
+        cell::{Cell, CellFormat},
+        color::{Color, ColorDepth, lerp, nearest_ansi256},
+        draw::{
+            draw_octad, draw_rect, draw_text, draw_twoxel, erase_octad, octad_dot_offset,
+            sub_cell_char,
+        },
+        engine::{Engine, culled_draw_calls, end_frame, init, start_frame},
+        layer::{
+            Layer, LayerBlendMode, LayerIndex, OctadColorMode, create_layer, set_layer_blend_mode,
+            set_layer_camera, set_layer_index, set_layer_opacity, set_layer_visible,
+            set_octad_color_mode,
+        },
+        link::{LinkId, intern_link},
+        rich_text::{Attributes, RichText},
+        screenshot::{ScreenshotFormat, screenshot},
+        test_support::composed_cells,
+    };
+    use crossterm::style as ctstyle;
+
+    const BLENDING_COLOR: Color = Color::TEAL;
 
-    BLOCKTAD_CHAR_LUT[merged_mask as usize]
+    /// Every `Standard` cell made of a few chars and opaque, fully transparent or missing colors.
+    fn opaque_or_transparent_cells() -> Vec<Cell> {
+        let colors: [(Color, bool); 6] = [
+            (Color::RED, false),
+            (Color::BLUE, false),
+            (Color::CLEAR, false),
+            (Color::RED.with_alpha(0), false),
+            (Color::CLEAR, true),
+            (Color::RED, true),
+        ];
+
+        let mut cells: Vec<Cell> = Vec::new();
+        for ch in [' ', 'a', 'b'] {
+            for (fg, no_fg_color) in colors {
+                for (bg, no_bg_color) in colors {
+                    let mut attributes: Attributes = Attributes::empty();
+                    attributes.set(Attributes::NO_FG_COLOR, no_fg_color);
+                    attributes.set(Attributes::NO_BG_COLOR, no_bg_color);
+                    cells.push(Cell {
+                        ch,
+                        fg,
+                        bg,
+                        attributes,
+                        ..Cell::EMPTY
+                    });
+                }
+            }
+        }
+        cells
+    }
+
+    /// What a cell looks like in the terminal: the colors of missing channels,
+    /// and the fg of blank chars don't show up.
+    fn displayed(mut cell: Cell) -> Cell {
+        if cell.ch == ' ' {
+            cell.fg = Color::CLEAR;
+            cell.attributes.insert(Attributes::NO_FG_COLOR);
+        }
+        if cell.attributes.contains(Attributes::NO_FG_COLOR) {
+            cell.fg = Color::CLEAR;
+        }
+        if cell.attributes.contains(Attributes::NO_BG_COLOR) {
+            cell.bg = Color::CLEAR;
+        }
+        cell
+    }
+
+    #[test]
+    fn composing_opaque_and_transparent_cells_is_associative() {
+        let cells: Vec<Cell> = opaque_or_transparent_cells();
+        let compose = |old: Cell, new: Cell| compose_cell(old, new, BLENDING_COLOR);
+
+        for &a in &cells {
+            for &b in &cells {
+                let ab: Cell = compose(a, b);
+                for &c in &cells {
+                    assert_eq!(
+                        displayed(compose(ab, c)),
+                        displayed(compose(a, compose(b, c))),
+                        "(a + b) + c != a + (b + c) for a: {a:?}, b: {b:?}, c: {c:?}",
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn composing_an_identity_cell_changes_nothing() {
+        let identity: Cell = Cell {
+            attributes: Attributes::empty(),
+            ..Cell::EMPTY
+        };
+        for cell in opaque_or_transparent_cells() {
+            assert_eq!(
+                displayed(compose_cell(cell, identity, BLENDING_COLOR)),
+                displayed(cell)
+            );
+        }
+    }
+
+    #[test]
+    fn blend_modes_match_their_formulas() {
+        let bottom: Color = Color::new(200, 100, 50, 255);
+        let top: Color = Color::new(128, 255, 0, 255);
+
+        let blended = |mode: LayerBlendMode| blend_colors(bottom, top, mode);
+        assert_eq!(blended(LayerBlendMode::Normal), top);
+        assert_eq!(
+            blended(LayerBlendMode::Multiply),
+            Color::new(100, 100, 0, 255)
+        );
+        assert_eq!(
+            blended(LayerBlendMode::Screen),
+            Color::new(228, 255, 50, 255)
+        );
+        // Darker channels are multiplied, lighter ones screened
+        assert_eq!(
+            blended(LayerBlendMode::Overlay),
+            Color::new(200, 200, 0, 255)
+        );
+    }
+
+    #[test]
+    fn blend_strength_follows_the_top_alpha() {
+        let bottom: Color = Color::new(200, 100, 50, 255);
+        let top: Color = Color::new(128, 255, 0, 128);
+
+        assert_eq!(
+            blend_colors(bottom, top, LayerBlendMode::Multiply),
+            Color::new(150, 100, 25, 255)
+        );
+        assert_eq!(
+            blend_colors(bottom, top.with_alpha(0), LayerBlendMode::Screen),
+            bottom
+        );
+    }
+
+    #[test]
+    fn multiply_layer_darkens_the_layers_beneath() {
+        let mut engine: Engine = Engine::offscreen(2, 1);
+        let base = create_layer(&mut engine, 0);
+        let lighting = create_layer(&mut engine, 1);
+        set_layer_blend_mode(&mut engine, lighting, LayerBlendMode::Multiply);
+        init(&mut engine).unwrap();
+
+        start_frame(&mut engine);
+        draw_rect(
+            &mut engine,
+            base,
+            (0, 0),
+            (2, 1),
+            Color::new(200, 100, 50, 255),
+        );
+        draw_rect(
+            &mut engine,
+            lighting,
+            (0, 0),
+            (1, 1),
+            Color::new(128, 255, 0, 255),
+        );
+        end_frame(&mut engine).unwrap();
+
+        let frame = engine.frame.current();
+        assert_eq!(frame[0].bg, Color::new(100, 100, 0, 255));
+        assert_eq!(frame[1].bg, Color::new(200, 100, 50, 255));
+    }
+
+    /// The bg colors of a red rect on a bottom layer overlapped by a blue rect on a top layer.
+    fn two_layer_bgs(configure: impl FnOnce(&mut Engine, LayerIndex, LayerIndex)) -> [Color; 2] {
+        let mut engine: Engine = Engine::offscreen(2, 1);
+        let bottom = create_layer(&mut engine, 0);
+        let top = create_layer(&mut engine, 1);
+        configure(&mut engine, bottom, top);
+        init(&mut engine).unwrap();
+
+        start_frame(&mut engine);
+        draw_rect(&mut engine, bottom, (0, 0), (2, 1), Color::RED);
+        draw_rect(&mut engine, top, (0, 0), (1, 1), Color::BLUE);
+        end_frame(&mut engine).unwrap();
+
+        let frame = engine.frame.current();
+        [frame[0].bg, frame[1].bg]
+    }
+
+    #[test]
+    fn hidden_layers_are_skipped_and_shown_again() {
+        assert_eq!(two_layer_bgs(|_, _, _| {}), [Color::BLUE, Color::RED]);
+        assert_eq!(
+            two_layer_bgs(|engine, _, top| set_layer_visible(engine, top, false)),
+            [Color::RED, Color::RED]
+        );
+        assert_eq!(
+            two_layer_bgs(|engine, _, top| {
+                set_layer_visible(engine, top, false);
+                set_layer_visible(engine, top, true);
+            }),
+            [Color::BLUE, Color::RED]
+        );
+    }
+
+    #[test]
+    fn layer_opacity_fades_its_draw_calls() {
+        let mut engine: Engine = Engine::offscreen(1, 1);
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+        start_frame(&mut engine);
+        draw_rect(&mut engine, layer, (0, 0), (1, 1), Color::RED);
+        draw_rect(
+            &mut engine,
+            layer,
+            (0, 0),
+            (1, 1),
+            Color::BLUE.with_alpha(128),
+        );
+        end_frame(&mut engine).unwrap();
+        let half_blue_over_red: Color = engine.frame.current()[0].bg;
+
+        assert_eq!(
+            two_layer_bgs(|engine, _, top| set_layer_opacity(engine, top, 128)),
+            [half_blue_over_red, Color::RED]
+        );
+        assert_eq!(
+            two_layer_bgs(|engine, _, top| set_layer_opacity(engine, top, 0)),
+            [Color::RED, Color::RED]
+        );
+    }
+
+    #[test]
+    fn reordered_layers_compose_in_their_new_order() {
+        assert_eq!(
+            two_layer_bgs(|engine, bottom, _| set_layer_index(engine, bottom, 2)),
+            [Color::RED, Color::RED]
+        );
+    }
+
+    /// The cells which aren't blank, composed from a 20x20 world layer drawn with a camera offset of `(-90, -90)`.
+    fn cells_through_camera(draw: impl FnOnce(&mut Engine, LayerIndex)) -> Vec<(usize, Cell)> {
+        let mut engine: Engine = Engine::offscreen(20, 20);
+        let world = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+
+        start_frame(&mut engine);
+        set_layer_camera(&mut engine, world, (-90, -90));
+        draw(&mut engine, world);
+        end_frame(&mut engine).unwrap();
+
+        let frame = engine.frame.current();
+        (0..20 * 20)
+            .map(|cell_index| (cell_index, frame[cell_index]))
+            .filter(|&(_, cell)| cell != Cell::EMPTY)
+            .collect()
+    }
+
+    #[test]
+    fn layer_camera_shifts_world_positions_onto_the_screen() {
+        let cells = cells_through_camera(|engine, world| {
+            draw_twoxel(engine, world, 100.0, 100.0, Color::GREEN);
+        });
+        assert_eq!(cells.len(), 1);
+        let (cell_index, cell) = cells[0];
+        assert_eq!((cell_index, cell.ch), (10 * 20 + 10, '▀'));
+    }
+
+    #[test]
+    fn off_screen_world_positions_compose_no_cells() {
+        let cells = cells_through_camera(|engine, world| {
+            draw_twoxel(engine, world, 0.0, 0.0, Color::GREEN);
+            draw_text(engine, world, 200, 100, "far away");
+            draw_text(engine, world, 80, 100, "left");
+            draw_octad(engine, world, 100.0, 200.0, Color::GREEN);
+        });
+        assert!(cells.is_empty());
+    }
+
+    #[test]
+    fn layer_camera_applies_when_composing() {
+        let cells = cells_through_camera(|engine, world| {
+            set_layer_camera(engine, world, (0, 0));
+            draw_twoxel(engine, world, 100.0, 100.0, Color::GREEN);
+            draw_text(engine, world, 5, 5, "x");
+            // Moving the camera after drawing moves everything drawn so far
+            set_layer_camera(engine, world, (-90, -90));
+        });
+        assert_eq!(cells.len(), 1);
+        assert_eq!(cells[0].0, 10 * 20 + 10);
+    }
+
+    #[test]
+    fn over_budget_frames_keep_the_highest_priority_and_earliest_draw_calls() {
+        let mut engine: Engine = Engine::offscreen(6, 3).cell_budget(Some(5));
+        let bottom = create_layer(&mut engine, 0);
+        let top = create_layer(&mut engine, 1);
+        init(&mut engine).unwrap();
+
+        // Stays the same between frames, ties between equal priorities included
+        for _ in 0..2 {
+            start_frame(&mut engine);
+            draw_text(
+                &mut engine,
+                bottom,
+                0,
+                0,
+                RichText::new("aa").with_priority(100),
+            );
+            draw_text(
+                &mut engine,
+                bottom,
+                0,
+                1,
+                RichText::new("b").with_priority(50),
+            );
+            draw_text(
+                &mut engine,
+                bottom,
+                2,
+                1,
+                RichText::new("c").with_priority(50),
+            );
+            draw_text(&mut engine, top, 4, 1, RichText::new("d").with_priority(50));
+            draw_text(
+                &mut engine,
+                top,
+                0,
+                2,
+                RichText::new("ee").with_priority(200),
+            );
+            end_frame(&mut engine).unwrap();
+
+            // 7 cells over a budget of 5, the two most recently queued calls of the lowest priority go first
+            let text: String = composed_cells(&engine).iter().map(|cell| cell.ch).collect();
+            assert_eq!(text, "aa    b     ee    ");
+            assert_eq!(culled_draw_calls(&engine), 2);
+        }
+    }
+
+    #[test]
+    fn moving_into_view_shifts_only_layers_with_a_camera_in_place() {
+        let mut engine: Engine = Engine::offscreen(20, 20);
+        let world = create_layer(&mut engine, 0);
+        let ui = create_layer(&mut engine, 1);
+        init(&mut engine).unwrap();
+
+        start_frame(&mut engine);
+        set_layer_camera(&mut engine, world, (-90, -90));
+        for x in [80, 100, 200] {
+            draw_text(&mut engine, world, x, 100, "x");
+            draw_text(&mut engine, ui, x - 90, 10, "x");
+        }
+        let layers: &mut Vec<Layer> = &mut engine.frame.layered_draw_queue;
+        let capacity: usize = layers[world.0].draw_calls.capacity();
+        move_into_view(layers, 20, 20);
+
+        let positions = |layer: LayerIndex| -> Vec<(i16, i16)> {
+            engine.frame.layered_draw_queue[layer.0]
+                .draw_calls
+                .iter()
+                .map(|draw_call| (draw_call.x, draw_call.y))
+                .collect()
+        };
+        assert_eq!(positions(world), [(10, 10)]);
+        assert_eq!(
+            engine.frame.layered_draw_queue[world.0]
+                .draw_calls
+                .capacity(),
+            capacity
+        );
+        // Without a camera the calls are left for composing to clip
+        assert_eq!(positions(ui), [(-10, 10), (10, 10), (110, 10)]);
+    }
+
+    /// The cell composed from single dots drawn one after the other into a cell, on a layer with the given `mode`.
+    ///
+    /// Dots are numbered down the left column and then down the right one, `None` erases the dot.
+    fn octad_cell(mode: OctadColorMode, dots: &[(usize, Option<Color>)]) -> Cell {
+        let mut engine: Engine = Engine::offscreen(1, 1);
+        let layer = create_layer(&mut engine, 0);
+        set_octad_color_mode(&mut engine, layer, mode);
+        init(&mut engine).unwrap();
+
+        start_frame(&mut engine);
+        for &(dot, color) in dots {
+            let (x, y): (f32, f32) = ((dot / 4) as f32 * 0.5, (dot % 4) as f32 * 0.25);
+            match color {
+                Some(color) => draw_octad(&mut engine, layer, x, y, color),
+                None => erase_octad(&mut engine, layer, x, y),
+            }
+        }
+        end_frame(&mut engine).unwrap();
+        engine.frame.current()[0]
+    }
+
+    /// The `format_data` of an octad cell with the given dots set, numbered like in [`octad_cell`].
+    fn octad_mask(dots: &[usize]) -> u8 {
+        dots.iter().fold(0, |mask, &dot| {
+            mask | 1 << octad_dot_offset(dot / 4, dot % 4)
+        })
+    }
+
+    #[test]
+    fn dominant_octads_count_every_dot_drawn() {
+        let (red, blue, green) = (Some(Color::RED), Some(Color::BLUE), Some(Color::GREEN));
+        let colors = |cell: Cell| (cell.fg, cell.bg, cell.format_data);
+
+        // Two series plotted a dot at a time, in either order
+        for dots in [
+            [(0, red), (1, red), (2, blue), (3, blue), (4, blue)],
+            [(2, blue), (3, blue), (4, blue), (0, red), (1, red)],
+            [(0, red), (2, blue), (1, red), (3, blue), (4, blue)],
+        ] {
+            let cell: Cell = octad_cell(OctadColorMode::Dominant, &dots);
+            assert_eq!(
+                colors(cell),
+                (Color::BLUE, Color::RED, octad_mask(&[2, 3, 4]))
+            );
+            assert_eq!(cell.ch, sub_cell_char(CellFormat::Octad, cell.format_data));
+        }
+
+        // Ties go to the last drawn color
+        let cell: Cell = octad_cell(OctadColorMode::Dominant, &[(0, red), (1, blue)]);
+        assert_eq!(colors(cell), (Color::BLUE, Color::RED, octad_mask(&[1])));
+
+        // Dots of any third color are turned off too
+        let cell: Cell = octad_cell(
+            OctadColorMode::Dominant,
+            &[
+                (0, red),
+                (1, red),
+                (2, red),
+                (3, green),
+                (4, green),
+                (5, blue),
+            ],
+        );
+        assert_eq!(
+            colors(cell),
+            (Color::RED, Color::GREEN, octad_mask(&[0, 1, 2]))
+        );
+
+        // A redrawn dot only counts towards its new color
+        let cell: Cell = octad_cell(
+            OctadColorMode::Dominant,
+            &[(0, red), (1, red), (0, blue), (2, blue)],
+        );
+        assert_eq!(colors(cell), (Color::BLUE, Color::RED, octad_mask(&[0, 2])));
+
+        // With a single color left, the cell keeps its own bg
+        let cell: Cell = octad_cell(OctadColorMode::Dominant, &[(0, red), (1, red)]);
+        assert_eq!(
+            (cell.fg, cell.format_data),
+            (Color::RED, octad_mask(&[0, 1]))
+        );
+        assert!(cell.attributes.contains(Attributes::NO_BG_COLOR));
+
+        let cell: Cell = octad_cell(
+            OctadColorMode::Dominant,
+            &[(0, blue), (1, red), (2, red), (0, None)],
+        );
+        assert_eq!(
+            (cell.fg, cell.format_data),
+            (Color::RED, octad_mask(&[1, 2]))
+        );
+        assert!(cell.attributes.contains(Attributes::NO_BG_COLOR));
+    }
+
+    #[test]
+    fn average_octads_weigh_every_dot_drawn() {
+        let (red, blue) = (Some(Color::RED), Some(Color::BLUE));
+        let dots = [(0, red), (1, red), (2, blue), (3, blue), (4, blue)];
+
+        let cell: Cell = octad_cell(OctadColorMode::Average, &dots);
+        assert_eq!(cell.fg, Color::new(102, 0, 153, 255));
+        assert_eq!(cell.format_data, octad_mask(&[0, 1, 2, 3, 4]));
+        assert!(cell.attributes.contains(Attributes::NO_BG_COLOR));
+
+        // Unlike the default, which takes the last drawn color
+        let cell: Cell = octad_cell(OctadColorMode::LastWins, &dots);
+        assert_eq!(cell.fg, Color::BLUE);
+        assert_eq!(cell.format_data, octad_mask(&[0, 1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn quantized_color_cache_stays_bounded() {
+        let mut terminal_colors: TerminalColors = TerminalColors::new(ColorDepth::Ansi256);
+        for i in 0..(MAX_QUANTIZED_COLORS as u32 * 3) {
+            let color: Color = Color::new((i >> 16) as u8, (i >> 8) as u8, i as u8, 255);
+            assert_eq!(
+                to_crossterm_color(&mut terminal_colors, color),
+                ctstyle::Color::AnsiValue(nearest_ansi256(color))
+            );
+            assert!(terminal_colors.quantized.len() <= MAX_QUANTIZED_COLORS);
+        }
+    }
+
+    /// Renders a horizontal gradient strip, returning the drawn frame and the distinct palette indices it uses.
+    fn render_gradient_strip(dithering: bool) -> (String, HashSet<u8>) {
+        let mut engine: Engine = Engine::offscreen(64, 1)
+            .color_depth(ColorDepth::Ansi256)
+            .dithering(dithering);
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+
+        start_frame(&mut engine);
+        let (from, to): (Color, Color) =
+            (Color::new(20, 30, 60, 255), Color::new(70, 90, 150, 255));
+        for x in 0..64 {
+            draw_rect(
+                &mut engine,
+                layer,
+                (x, 0),
+                (1, 1),
+                lerp(from, to, x as f32 / 63.0),
+            );
+        }
+        end_frame(&mut engine).unwrap();
+
+        let output: String = screenshot(&engine, ScreenshotFormat::Ansi);
+        let indices: HashSet<u8> = output
+            .split("\x1b[48;5;")
+            .skip(1)
+            .map(|rest| rest[..rest.find('m').unwrap()].parse().unwrap())
+            .collect();
+        (output, indices)
+    }
+
+    #[test]
+    fn dithering_breaks_up_gradient_bands() {
+        let (_, banded) = render_gradient_strip(false);
+        let (dithered, dithered_indices) = render_gradient_strip(true);
+
+        assert!(dithered_indices.len() > banded.len());
+        assert_eq!(render_gradient_strip(true).0, dithered);
+    }
+
+    #[test]
+    fn linked_spaces_only_link_the_chars_they_draw() {
+        let link: Option<LinkId> = intern_link("https://example.com/space").ok();
+        let linked_space: Cell = Cell {
+            bg: Color::RED.with_alpha(128),
+            attributes: Attributes::empty(),
+            link,
+            ..Cell::EMPTY
+        };
+        let plain: Cell = Cell {
+            ch: 'x',
+            fg: Color::WHITE,
+            bg: Color::BLUE,
+            attributes: Attributes::empty(),
+            ..Cell::EMPTY
+        };
+
+        let over_char: Cell = compose_cell(plain, linked_space, BLENDING_COLOR);
+        assert_eq!((over_char.ch, over_char.link), ('x', None));
+
+        let over_space: Cell =
+            compose_cell(Cell { ch: ' ', ..plain }, linked_space, BLENDING_COLOR);
+        assert_eq!((over_space.ch, over_space.link), (' ', link));
+    }
+
+    #[test]
+    fn same_style_runs_emit_a_single_style_sequence() {
+        let styled = |ch: char, fg: Color| Cell {
+            ch,
+            fg,
+            bg: Color::BLUE,
+            attributes: Attributes::BOLD,
+            ..Cell::EMPTY
+        };
+        // A run of 20 cells, one more after a gap, and a cell of another style
+        let mut cells: Vec<(u16, Cell)> = (0..20).map(|x| (x, styled('a', Color::RED))).collect();
+        cells.push((25, styled('b', Color::RED)));
+        cells.push((26, styled('c', Color::GREEN)));
+
+        let diff_products = cells
+            .iter()
+            .map(|(x, cell)| DiffProduct { cell, x: *x, y: 0 });
+        let mut writer: Vec<u8> = Vec::new();
+        let mut terminal_colors: TerminalColors = TerminalColors::new(ColorDepth::TrueColor);
+        draw_to_terminal(&mut writer, diff_products, &mut terminal_colors, 0, true).unwrap();
+        let output: String = String::from_utf8(writer).unwrap();
+
+        // The style is only set again once it changes, not after moving the cursor
+        let red_style: &str = "\x1b[0m\x1b[48;2;0;0;255m\x1b[38;2;255;0;0m\x1b[1m";
+        let green_style: &str = "\x1b[0m\x1b[48;2;0;0;255m\x1b[38;2;0;255;0m\x1b[1m";
+        assert_eq!(
+            output,
+            format!(
+                "\x1b[1;1H{red_style}{}\x1b[1;26Hb{green_style}c",
+                "a".repeat(20)
+            )
+        );
+    }
+
+    #[test]
+    fn linked_runs_are_framed_by_a_single_osc8_pair() {
+        let link: Option<LinkId> = intern_link("https://example.com/\x1b]docs").ok();
+        let cell = |ch: char, link: Option<LinkId>| Cell {
+            ch,
+            attributes: Attributes::NO_FG_COLOR | Attributes::NO_BG_COLOR,
+            link,
+            ..Cell::EMPTY
+        };
+        let cells: [Cell; 5] = [
+            cell('a', link),
+            cell(' ', link),
+            cell('b', link),
+            cell('c', None),
+            cell('d', link),
+        ];
+
+        let output = |hyperlinks: bool| -> String {
+            let diff_products = cells.iter().enumerate().map(|(x, cell)| DiffProduct {
+                cell,
+                x: x as u16 + 1,
+                y: 2,
+            });
+            let mut writer: Vec<u8> = Vec::new();
+            let mut terminal_colors: TerminalColors = TerminalColors::new(ColorDepth::TrueColor);
+            draw_to_terminal(
+                &mut writer,
+                diff_products,
+                &mut terminal_colors,
+                0,
+                hyperlinks,
+            )
+            .unwrap();
+            String::from_utf8(writer).unwrap()
+        };
+
+        // Control chars are dropped from the url, so it can't end the sequence early
+        let open: &str = "\x1b]8;;https://example.com/]docs\x1b\\";
+        let close: &str = "\x1b]8;;\x1b\\";
+        assert_eq!(
+            output(true),
+            format!("\x1b[3;2H{open}\x1b[0ma b{close}c{open}d{close}")
+        );
+        assert_eq!(output(false), "\x1b[3;2H\x1b[0ma bcd");
+    }
 }
@@ -0,0 +1,234 @@
+//! Rasterizing frames into RGBA pixel buffers, eg. for LED matrices, SPI displays or emulator windows.
+//!
+//! [`render_to_framebuffer`] maps every cell of an [offscreen](Engine::offscreen) engine to a rectangle of pixels.
+//! Sub-cell characters (octads, blocktads, sextants, quadrants and twoxels) are drawn dot for dot, printable ASCII is drawn
+//! with the built-in mini font, and any other character becomes a solid block of its foreground color.
+//!
+//! ## Notes
+//! The engine itself still depends on `std` and crossterm, only the output stage is independent of the terminal.
+//! Driving a display from a `no_std` target isn't supported, an offscreen engine has to run on a `std` host
+//! which sends the pixels over to the display.
+
+use crate::{
+    cell::{Cell, CellFormat},
+    color::Color,
+    draw::octad_dot_offset,
+    engine::Engine,
+    font::{MINI_FONT, MINI_GLYPH_HEIGHT, MiniGlyph},
+    rich_text::Attributes,
+};
+
+const BYTES_PER_PIXEL: usize = 4;
+
+/// Draws the frame last composed by the engine into `pixels`, tightly packed RGBA rows,
+/// with every cell taking up `cell_width` x `cell_height` pixels.
+///
+/// Cells without a background color use the default blending color of the engine,
+/// and cells without a foreground color use white or black, whichever is more readable on the background.
+///
+/// # Panics
+/// If `pixels` is smaller than the whole frame, `engine.width * cell_width * engine.height * cell_height * 4` bytes.
+///
+/// # Example
+/// ```rust
+/// # use germterm::{color::Color, draw::draw_octad, engine::{Engine, end_frame, init, start_frame}};
+/// # use germterm::{framebuffer::render_to_framebuffer, layer::create_layer};
+/// // A 16x16 LED matrix, with every cell mapped to 2x4 LEDs
+/// let mut engine = Engine::offscreen(8, 4);
+/// let layer = create_layer(&mut engine, 0);
+/// init(&mut engine).unwrap();
+///
+/// start_frame(&mut engine);
+/// draw_octad(&mut engine, layer, 0.0, 0.0, Color::RED);
+/// end_frame(&mut engine).unwrap();
+///
+/// let mut pixels = [0u8; 16 * 16 * 4];
+/// render_to_framebuffer(&engine, &mut pixels, 2, 4);
+///
+/// assert_eq!(pixels[0..4], [255, 0, 0, 255]);
+/// assert_eq!(pixels[4..8], [0, 0, 0, 255]);
+/// ```
+pub fn render_to_framebuffer(
+    engine: &Engine,
+    pixels: &mut [u8],
+    cell_width: usize,
+    cell_height: usize,
+) {
+    let width: usize = engine.frame.width as usize;
+    let height: usize = engine.frame.height as usize;
+    let stride: usize = width * cell_width * BYTES_PER_PIXEL;
+    assert!(
+        pixels.len() >= stride * height * cell_height,
+        "Framebuffer of {} bytes is too small for {}x{} cells of {cell_width}x{cell_height} pixels.",
+        pixels.len(),
+        width,
+        height,
+    );
+
+    let frame = if engine.offscreen {
+        engine.frame.current()
+    } else {
+        engine.frame.old()
+    };
+    let mut target: Framebuffer = Framebuffer {
+        pixels,
+        stride,
+        cell_width,
+        cell_height,
+    };
+
+    for row in 0..height {
+        for col in 0..width {
+            let cell: &Cell = &frame[row * width + col];
+            let bg: Color = if cell.attributes.contains(Attributes::NO_BG_COLOR) {
+                engine.default_blending_color
+            } else {
+                cell.bg
+            };
+            let fg: Color = if cell.attributes.contains(Attributes::NO_FG_COLOR) {
+                readable_on(bg)
+            } else {
+                cell.fg
+            };
+
+            target.fill_cell(col, row, bg);
+            draw_cell(&mut target, col, row, cell, fg);
+        }
+    }
+}
+
+struct Framebuffer<'a> {
+    pixels: &'a mut [u8],
+    stride: usize,
+    cell_width: usize,
+    cell_height: usize,
+}
+
+impl Framebuffer<'_> {
+    #[inline]
+    fn fill_cell(&mut self, col: usize, row: usize, color: Color) {
+        self.fill_sub_cell(col, row, (0, 0), (1, 1), color);
+    }
+
+    /// Fills the sub-cell at `(sub_x, sub_y)` of a cell split into `cols` x `rows` equally sized parts.
+    fn fill_sub_cell(
+        &mut self,
+        col: usize,
+        row: usize,
+        (sub_x, sub_y): (usize, usize),
+        (cols, rows): (usize, usize),
+        color: Color,
+    ) {
+        let x0: usize = col * self.cell_width + sub_x * self.cell_width / cols;
+        let x1: usize = col * self.cell_width + (sub_x + 1) * self.cell_width / cols;
+        let y0: usize = row * self.cell_height + sub_y * self.cell_height / rows;
+        let y1: usize = row * self.cell_height + (sub_y + 1) * self.cell_height / rows;
+
+        for y in y0..y1 {
+            for x in x0..x1 {
+                self.set_pixel(x, y, color);
+            }
+        }
+    }
+
+    #[inline]
+    fn set_pixel(&mut self, x: usize, y: usize, color: Color) {
+        let (r, g, b): (u8, u8, u8) = color.rgb();
+        let offset: usize = y * self.stride + x * BYTES_PER_PIXEL;
+        self.pixels[offset..offset + BYTES_PER_PIXEL].copy_from_slice(&[r, g, b, 255]);
+    }
+}
+
+fn draw_cell(target: &mut Framebuffer, col: usize, row: usize, cell: &Cell, fg: Color) {
+    if cell.attributes.contains(Attributes::HIDDEN) {
+        return;
+    }
+
+    match (cell.format, cell.ch) {
+        (_, ' ' | Cell::WIDE_CONTINUATION) => {}
+        (CellFormat::Octad, _) => for_each_sub_cell(4, |sub_x, sub_y| {
+            if cell.format_data & (1 << octad_dot_offset(sub_x, sub_y)) != 0 {
+                target.fill_sub_cell(col, row, (sub_x, sub_y), (2, 4), fg);
+            }
+        }),
+        (CellFormat::Blocktad | CellFormat::Sextant | CellFormat::Quadrant, _) => {
+            let rows: usize = match cell.format {
+                CellFormat::Quadrant => 2,
+                CellFormat::Sextant => 3,
+                _ => 4,
+            };
+            for_each_sub_cell(rows, |sub_x, sub_y| {
+                if cell.format_data & (1 << (sub_y * 2 + sub_x)) != 0 {
+                    target.fill_sub_cell(col, row, (sub_x, sub_y), (2, rows), fg);
+                }
+            });
+        }
+        (_, '▀') => target.fill_sub_cell(col, row, (0, 0), (1, 2), fg),
+        (_, '▄') => target.fill_sub_cell(col, row, (0, 1), (1, 2), fg),
+        (_, ch @ ' '..='~') => {
+            draw_mini_glyph(target, col, row, &MINI_FONT[ch as usize - 0x20], fg)
+        }
+        _ => target.fill_cell(col, row, fg),
+    }
+
+    if cell.attributes.contains(Attributes::UNDERLINED) && target.cell_height > 1 {
+        let y: usize = row * target.cell_height + target.cell_height - 1;
+        for x in col * target.cell_width..(col + 1) * target.cell_width {
+            target.set_pixel(x, y, fg);
+        }
+    }
+}
+
+/// Calls `f` with every sub-cell position of a cell split into 2 columns and `rows` rows.
+#[inline]
+fn for_each_sub_cell(rows: usize, mut f: impl FnMut(usize, usize)) {
+    for sub_y in 0..rows {
+        for sub_x in 0..2 {
+            f(sub_x, sub_y);
+        }
+    }
+}
+
+/// Draws a glyph scaled up by the largest whole factor that still fits the cell, centered in the cell.
+/// Glyph pixels which don't fit small cells are cut off.
+fn draw_mini_glyph(target: &mut Framebuffer, col: usize, row: usize, glyph: &MiniGlyph, fg: Color) {
+    let glyph_width: usize = glyph.width as usize;
+    let glyph_height: usize = MINI_GLYPH_HEIGHT as usize;
+    let scale: usize = (target.cell_width / (glyph_width + 1))
+        .min(target.cell_height / (glyph_height + 1))
+        .max(1);
+    let offset_x: usize = target.cell_width.saturating_sub(glyph_width * scale) / 2;
+    let offset_y: usize = target.cell_height.saturating_sub(glyph_height * scale) / 2;
+
+    for (glyph_y, bits) in glyph.rows.iter().enumerate() {
+        for glyph_x in 0..glyph_width {
+            if bits & (1 << (glyph_width - 1 - glyph_x)) == 0 {
+                continue;
+            }
+            for y in 0..scale {
+                for x in 0..scale {
+                    let cell_x: usize = offset_x + glyph_x * scale + x;
+                    let cell_y: usize = offset_y + glyph_y * scale + y;
+                    if cell_x < target.cell_width && cell_y < target.cell_height {
+                        target.set_pixel(
+                            col * target.cell_width + cell_x,
+                            row * target.cell_height + cell_y,
+                            fg,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// White or black, whichever is more readable on `bg`.
+fn readable_on(bg: Color) -> Color {
+    let (r, g, b): (u8, u8, u8) = bg.rgb();
+    let luma: u32 = (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000;
+    if luma < 128 {
+        Color::WHITE
+    } else {
+        Color::BLACK
+    }
+}
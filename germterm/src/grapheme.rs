@@ -0,0 +1,270 @@
+//! Grapheme cluster segmentation and interning.
+//!
+//! Text is split into clusters of chars which terminals render as a single glyph, like a letter followed by
+//! combining marks (`"e\u{301}"`), emoji with modifiers or variation selectors, ZWJ sequences and flags.
+//! The segmentation is a simplified take on the Unicode extended grapheme cluster rules, covering these cases.
+//!
+//! To keep [`Cell`] small and `Copy`, clusters made of more than one char are interned,
+//! and stored in the cell as a char from the Supplementary Private Use Area-B which maps back to the full cluster.
+//! Such cells are marked with [`INTERNED_CLUSTER`] in their `format_data`, so chars from the private use area
+//! drawn as-is are never mistaken for clusters. Single char clusters are stored as-is.
+
+use std::{
+    collections::HashMap,
+    ops::Range,
+    sync::{LazyLock, Mutex},
+};
+
+use crate::{
+    cell::{Cell, CellFormat},
+    width::char_width,
+};
+
+/// The `format_data` of [`CellFormat::Standard`] cells whose char stands in for an interned cluster.
+pub(crate) const INTERNED_CLUSTER: u8 = 1;
+
+/// The first char handed out to interned clusters.
+const FIRST_INTERNED: u32 = 0x10_0000;
+/// The last char handed out to interned clusters, once exhausted clusters fall back to their first char.
+const LAST_INTERNED: u32 = 0x10_FFFD;
+
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+const EMOJI_PRESENTATION_SELECTOR: char = '\u{FE0F}';
+
+/// Ranges of chars which extend the cluster before them instead of starting a new one.
+#[rustfmt::skip]
+static EXTEND_RANGES: [(u32, u32); 9] = [
+    (0x0300, 0x036F), (0x0483, 0x0489), (0x200C, 0x200D), (0x20D0, 0x20FF), (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F), (0x1F3FB, 0x1F3FF), (0xE0020, 0xE007F), (0xE0100, 0xE01EF),
+];
+
+struct Interner {
+    clusters: Vec<(&'static str, usize)>,
+    lookup: HashMap<&'static str, char>,
+}
+
+// Interned clusters are leaked, as they have to outlive every cell referring to them.
+// The amount of distinct clusters is bounded by the size of the private use area.
+static INTERNER: LazyLock<Mutex<Interner>> = LazyLock::new(|| {
+    Mutex::new(Interner {
+        clusters: Vec::new(),
+        lookup: HashMap::new(),
+    })
+});
+
+/// Calls `f` with the cell char, the `format_data` marking it for `Standard` cells and the display width
+/// of every grapheme cluster of `text`.
+#[inline]
+pub(crate) fn for_each_grapheme(text: &str, mut f: impl FnMut(char, u8, usize)) {
+    for_each_grapheme_range(text, |range| {
+        let cluster: &str = &text[range];
+        let first: char = cluster.chars().next().unwrap_or(' ');
+
+        if cluster.len() == first.len_utf8() {
+            f(first, 0, char_width(first));
+        } else {
+            let width: usize = cluster_width(cluster, first);
+            match intern(cluster, width) {
+                Some(ch) => f(ch, INTERNED_CLUSTER, width),
+                None => f(first, 0, width),
+            }
+        }
+    });
+}
+
+/// Calls `f` with the byte range of every grapheme cluster of `text`.
+#[inline]
+pub(crate) fn for_each_grapheme_range(text: &str, mut f: impl FnMut(Range<usize>)) {
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((start, first)) = chars.next() {
+        let mut end: usize = start + first.len_utf8();
+        let mut prev: char = first;
+        let mut regional_indicator_count: usize = is_regional_indicator(first) as usize;
+
+        while let Some(&(index, next)) = chars.peek() {
+            if !continues_cluster(prev, next, regional_indicator_count) {
+                break;
+            }
+            regional_indicator_count += is_regional_indicator(next) as usize;
+            end = index + next.len_utf8();
+            prev = next;
+            chars.next();
+        }
+
+        f(start..end);
+    }
+}
+
+/// The display width of a grapheme cluster of text, as split by [`for_each_grapheme_range`].
+#[inline]
+pub(crate) fn text_grapheme_width(cluster: &str) -> usize {
+    let first: char = cluster.chars().next().unwrap_or(' ');
+    if cluster.len() == first.len_utf8() {
+        char_width(first)
+    } else {
+        cluster_width(cluster, first)
+    }
+}
+
+/// The display width of the char of a cell, taking interned clusters into account.
+#[inline]
+pub(crate) fn grapheme_width(cell: &Cell) -> usize {
+    match resolve(cell) {
+        Some((_, width)) => width,
+        None => char_width(cell.ch),
+    }
+}
+
+/// Pushes the full cluster the char of a cell stands for.
+#[inline]
+pub(crate) fn push_grapheme(string: &mut String, cell: &Cell) {
+    match resolve(cell) {
+        Some((cluster, _)) => string.push_str(cluster),
+        None => string.push(cell.ch),
+    }
+}
+
+#[inline]
+fn resolve(cell: &Cell) -> Option<(&'static str, usize)> {
+    if cell.format != CellFormat::Standard || cell.format_data != INTERNED_CLUSTER {
+        return None;
+    }
+    let code: u32 = (cell.ch as u32).checked_sub(FIRST_INTERNED)?;
+
+    let interner = INTERNER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    interner.clusters.get(code as usize).copied()
+}
+
+/// The char standing in for `cluster`, or `None` once the private use area is exhausted.
+fn intern(cluster: &str, width: usize) -> Option<char> {
+    let mut interner = INTERNER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(&ch) = interner.lookup.get(cluster) {
+        return Some(ch);
+    }
+
+    let code: u32 = FIRST_INTERNED + interner.clusters.len() as u32;
+    let ch: char = char::from_u32(code).filter(|_| code <= LAST_INTERNED)?;
+
+    let cluster: &'static str = Box::leak(cluster.into());
+    interner.clusters.push((cluster, width));
+    interner.lookup.insert(cluster, ch);
+    Some(ch)
+}
+
+#[inline]
+fn continues_cluster(prev: char, next: char, regional_indicator_count: usize) -> bool {
+    if next.is_ascii() || prev.is_control() {
+        return false;
+    }
+    if prev == ZERO_WIDTH_JOINER || is_extend(next) {
+        return true;
+    }
+    // Flags are pairs of regional indicators
+    is_regional_indicator(next) && regional_indicator_count % 2 == 1
+}
+
+/// Clusters take up the width of their first char, except for emoji presentation sequences and flags,
+/// which terminals render as wide emoji.
+fn cluster_width(cluster: &str, first: char) -> usize {
+    let width: usize = char_width(first);
+    if width == 1 && (is_regional_indicator(first) || cluster.contains(EMOJI_PRESENTATION_SELECTOR))
+    {
+        return 2;
+    }
+    width
+}
+
+#[inline]
+fn is_extend(ch: char) -> bool {
+    let code: u32 = ch as u32;
+    EXTEND_RANGES
+        .iter()
+        .any(|&(start, end)| (start..=end).contains(&code))
+}
+
+#[inline]
+fn is_regional_indicator(ch: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&ch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        FIRST_INTERNED, INTERNED_CLUSTER, for_each_grapheme, grapheme_width, push_grapheme,
+    };
+    use crate::{
+        cell::Cell,
+        draw::draw_text,
+        engine::{Engine, end_frame, init, start_frame},
+        layer::create_layer,
+        screenshot::{ScreenshotFormat, screenshot},
+    };
+
+    /// The cells `text` is stored as, leaving out the wide char continuations.
+    fn cells(text: &str) -> Vec<Cell> {
+        let mut cells: Vec<Cell> = Vec::new();
+        for_each_grapheme(text, |ch, format_data, _| {
+            cells.push(Cell {
+                ch,
+                format_data,
+                ..Cell::EMPTY
+            });
+        });
+        cells
+    }
+
+    fn displayed(cell: &Cell) -> String {
+        let mut string: String = String::new();
+        push_grapheme(&mut string, cell);
+        string
+    }
+
+    #[test]
+    fn interned_clusters_resolve_to_the_full_cluster() {
+        let family: &str = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let [cell] = cells(family)[..] else {
+            panic!("a ZWJ sequence is a single cluster");
+        };
+
+        assert_eq!(cell.format_data, INTERNED_CLUSTER);
+        assert_eq!(displayed(&cell), family);
+        assert_eq!(grapheme_width(&cell), 2);
+        assert_eq!(cells(family), [cell]);
+    }
+
+    #[test]
+    fn private_use_chars_are_never_mistaken_for_clusters() {
+        // Makes sure there is an interned cluster the first private use char could be mistaken for
+        cells("e\u{301}");
+        let private_use: String = char::from_u32(FIRST_INTERNED).unwrap().to_string();
+        let [cell] = cells(&private_use)[..] else {
+            panic!("a single char is a single cluster");
+        };
+
+        assert_eq!(cell.format_data, 0);
+        assert_eq!(displayed(&cell), private_use);
+        assert_eq!(grapheme_width(&cell), 1);
+    }
+
+    #[test]
+    fn drawn_clusters_and_private_use_chars_show_up_as_themselves() {
+        let text: String = format!("e\u{301}{}", char::from_u32(FIRST_INTERNED).unwrap());
+        let mut engine: Engine = Engine::offscreen(4, 1);
+        let layer = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+
+        start_frame(&mut engine);
+        draw_text(&mut engine, layer, 0, 0, text.as_str());
+        end_frame(&mut engine).unwrap();
+
+        assert_eq!(
+            screenshot(&engine, ScreenshotFormat::PlainText),
+            format!("{text}\n")
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! Hexagonal grid coordinates and their mapping onto terminal cells.
+//!
+//! [`HexPosition`] uses axial coordinates (`q`, `r`), with the third cube coordinate `s` derived as `-q - r`.
+//! Distances, neighbors, rings and lines all operate purely on these coordinates.
+//!
+//! [`HexLayout`] maps hexes to the cell coordinate space used by the drawing functions and back,
+//! the latter being useful for mouse hit-testing using [`HexLayout::hex_at`].
+//! Hexes can be drawn using [`crate::draw::draw_hex_filled`] and [`crate::draw::draw_hex_outline`].
+//!
+//! The algorithms follow the excellent [Red Blob Games guide](https://www.redblobgames.com/grids/hexagons/).
+
+use std::f32::consts::PI;
+
+const SQRT_3: f32 = 1.732_050_8;
+
+/// A hex in axial coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct HexPosition {
+    pub q: i32,
+    pub r: i32,
+}
+
+/// The six neighbor offsets, counter-clockwise starting from the `+q` direction.
+pub const HEX_DIRECTIONS: [HexPosition; 6] = [
+    HexPosition::new(1, 0),
+    HexPosition::new(1, -1),
+    HexPosition::new(0, -1),
+    HexPosition::new(-1, 0),
+    HexPosition::new(-1, 1),
+    HexPosition::new(0, 1),
+];
+
+impl HexPosition {
+    pub const fn new(q: i32, r: i32) -> Self {
+        Self { q, r }
+    }
+
+    /// The third cube coordinate, `q + r + s` is always `0`.
+    pub const fn s(&self) -> i32 {
+        -self.q - self.r
+    }
+
+    /// The neighbor in one of the six [`HEX_DIRECTIONS`], `direction` wraps around.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::hex::HexPosition;
+    /// assert_eq!(HexPosition::new(2, 3).neighbor(0), HexPosition::new(3, 3));
+    /// assert_eq!(HexPosition::new(2, 3).neighbor(8), HexPosition::new(2, 2));
+    /// ```
+    pub fn neighbor(&self, direction: usize) -> Self {
+        let offset: HexPosition = HEX_DIRECTIONS[direction % 6];
+        Self::new(self.q + offset.q, self.r + offset.r)
+    }
+
+    pub fn neighbors(&self) -> [Self; 6] {
+        std::array::from_fn(|direction| self.neighbor(direction))
+    }
+
+    /// The amount of steps between two hexes.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::hex::HexPosition;
+    /// assert_eq!(HexPosition::new(0, 0).distance(HexPosition::new(0, 0)), 0);
+    /// assert_eq!(HexPosition::new(0, 0).distance(HexPosition::new(3, -1)), 3);
+    /// assert_eq!(HexPosition::new(-2, 4).distance(HexPosition::new(1, -3)), 7);
+    /// ```
+    pub fn distance(&self, other: HexPosition) -> u32 {
+        let dq: i32 = self.q - other.q;
+        let dr: i32 = self.r - other.r;
+        let ds: i32 = self.s() - other.s();
+        dq.unsigned_abs()
+            .max(dr.unsigned_abs())
+            .max(ds.unsigned_abs())
+    }
+
+    /// All hexes exactly `radius` steps away, in counter-clockwise order.
+    ///
+    /// A `radius` of `0` returns the hex itself.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::hex::HexPosition;
+    /// let center = HexPosition::new(1, 2);
+    /// assert_eq!(center.ring(0), vec![center]);
+    ///
+    /// let ring = center.ring(3);
+    /// assert_eq!(ring.len(), 18);
+    /// assert!(ring.iter().all(|hex| hex.distance(center) == 3));
+    /// ```
+    pub fn ring(&self, radius: u32) -> Vec<HexPosition> {
+        if radius == 0 {
+            return vec![*self];
+        }
+
+        let mut ring: Vec<HexPosition> = Vec::with_capacity(6 * radius as usize);
+        let start: HexPosition = HEX_DIRECTIONS[4];
+        let mut hex: HexPosition = Self::new(
+            self.q + start.q * radius as i32,
+            self.r + start.r * radius as i32,
+        );
+
+        for direction in 0..6 {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex = hex.neighbor(direction);
+            }
+        }
+        ring
+    }
+
+    /// All hexes on the straight line between two hexes, including both ends.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::hex::HexPosition;
+    /// let line = HexPosition::new(0, 0).line_to(HexPosition::new(3, -1));
+    /// assert_eq!(
+    ///     line,
+    ///     vec![
+    ///         HexPosition::new(0, 0),
+    ///         HexPosition::new(1, 0),
+    ///         HexPosition::new(2, -1),
+    ///         HexPosition::new(3, -1),
+    ///     ]
+    /// );
+    /// ```
+    pub fn line_to(&self, other: HexPosition) -> Vec<HexPosition> {
+        let steps: u32 = self.distance(other);
+        if steps == 0 {
+            return vec![*self];
+        }
+
+        // Nudging the endpoints, so the samples never land exactly on an edge between two hexes
+        let a: (f32, f32) = (self.q as f32 + 1e-3, self.r as f32 + 1e-3);
+        let b: (f32, f32) = (other.q as f32 + 1e-3, other.r as f32 + 1e-3);
+
+        (0..=steps)
+            .map(|step| {
+                let t: f32 = step as f32 / steps as f32;
+                round_hex(a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum HexOrientation {
+    /// Hexes have a pointed top, rows of hexes are offset horizontally by half a hex.
+    ///
+    /// This renders best in terminals, as the offset rows line up with the cell grid.
+    #[default]
+    PointyTop,
+    /// Hexes have a flat top, columns of hexes are offset vertically by half a hex.
+    FlatTop,
+}
+
+/// Maps hexes onto the cell coordinate space.
+///
+/// `cell_width` and `cell_height` define the spacing between hex centers, measured in cells:
+/// - [`HexOrientation::PointyTop`]: `cell_width` is the distance between neighbors in the same row
+///   and `cell_height` is the distance between rows.
+/// - [`HexOrientation::FlatTop`]: `cell_width` is the distance between columns
+///   and `cell_height` is the distance between neighbors in the same column.
+///
+/// `origin` is the center of the hex at `(0, 0)`.
+///
+/// The default layout is pointy-top with `4.0` by `2.0` cell spacing, which compensates for the cell aspect ratio.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HexLayout {
+    pub orientation: HexOrientation,
+    pub cell_width: f32,
+    pub cell_height: f32,
+    pub origin: (f32, f32),
+}
+
+impl Default for HexLayout {
+    fn default() -> Self {
+        Self {
+            orientation: HexOrientation::PointyTop,
+            cell_width: 4.0,
+            cell_height: 2.0,
+            origin: (2.0, 1.0),
+        }
+    }
+}
+
+impl HexLayout {
+    /// The center of a hex in cell coordinates.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::hex::{HexLayout, HexPosition};
+    /// let layout = HexLayout::default();
+    /// assert_eq!(layout.center(HexPosition::new(0, 0)), (2.0, 1.0));
+    /// assert_eq!(layout.center(HexPosition::new(1, 0)), (6.0, 1.0));
+    /// assert_eq!(layout.center(HexPosition::new(0, 1)), (4.0, 3.0));
+    /// ```
+    pub fn center(&self, hex: HexPosition) -> (f32, f32) {
+        let (q, r): (f32, f32) = (hex.q as f32, hex.r as f32);
+        let (x, y): (f32, f32) = match self.orientation {
+            HexOrientation::PointyTop => (self.cell_width * (q + r * 0.5), self.cell_height * r),
+            HexOrientation::FlatTop => (self.cell_width * q, self.cell_height * (r + q * 0.5)),
+        };
+        (self.origin.0 + x, self.origin.1 + y)
+    }
+
+    /// The hex containing a position in cell coordinates.
+    ///
+    /// Positions on an edge between hexes always resolve to the same hex,
+    /// making this suitable for hit-testing the mouse position, eg. at the center of the hovered cell.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::hex::{HexLayout, HexOrientation, HexPosition};
+    /// let layout = HexLayout::default();
+    /// assert_eq!(layout.hex_at((6.5, 1.5)), HexPosition::new(1, 0));
+    ///
+    /// let flat = HexLayout { orientation: HexOrientation::FlatTop, cell_width: 3.0, cell_height: 2.0, origin: (10.0, 10.0) };
+    /// for q in -3..=3 {
+    ///     for r in -3..=3 {
+    ///         let hex = HexPosition::new(q, r);
+    ///         assert_eq!(layout.hex_at(layout.center(hex)), hex);
+    ///         assert_eq!(flat.hex_at(flat.center(hex)), hex);
+    ///     }
+    /// }
+    /// ```
+    pub fn hex_at(&self, pos: (f32, f32)) -> HexPosition {
+        let x: f32 = (pos.0 - self.origin.0) / self.cell_width;
+        let y: f32 = (pos.1 - self.origin.1) / self.cell_height;
+
+        match self.orientation {
+            HexOrientation::PointyTop => round_hex(x - y * 0.5, y),
+            HexOrientation::FlatTop => round_hex(x, y - x * 0.5),
+        }
+    }
+
+    /// The six corners of a hex in cell coordinates, in clockwise order.
+    pub fn corners(&self, hex: HexPosition) -> [(f32, f32); 6] {
+        let center: (f32, f32) = self.center(hex);
+        // Radii of a regular hexagon stretched to the layout spacing
+        let (radius_x, radius_y, start_angle): (f32, f32, f32) = match self.orientation {
+            HexOrientation::PointyTop => {
+                (self.cell_width / SQRT_3, self.cell_height / 1.5, -PI / 6.0)
+            }
+            HexOrientation::FlatTop => (self.cell_width / 1.5, self.cell_height / SQRT_3, 0.0),
+        };
+
+        std::array::from_fn(|i| {
+            let angle: f32 = start_angle + i as f32 * PI / 3.0;
+            (
+                center.0 + radius_x * angle.cos(),
+                center.1 + radius_y * angle.sin(),
+            )
+        })
+    }
+}
+
+/// Rounds fractional axial coordinates to the nearest hex, by rounding in cube space
+/// and recomputing the coordinate with the largest rounding error.
+fn round_hex(q: f32, r: f32) -> HexPosition {
+    let s: f32 = -q - r;
+    let mut rounded_q: f32 = q.round();
+    let mut rounded_r: f32 = r.round();
+    let rounded_s: f32 = s.round();
+
+    let q_diff: f32 = (rounded_q - q).abs();
+    let r_diff: f32 = (rounded_r - r).abs();
+    let s_diff: f32 = (rounded_s - s).abs();
+
+    if q_diff > r_diff && q_diff > s_diff {
+        rounded_q = -rounded_r - rounded_s;
+    } else if r_diff > s_diff {
+        rounded_r = -rounded_q - rounded_s;
+    }
+
+    HexPosition::new(rounded_q as i32, rounded_r as i32)
+}
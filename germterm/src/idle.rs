@@ -0,0 +1,301 @@
+//! Power-saving render throttling while nothing happens on screen.
+//!
+//! Once an [`IdlePolicy`] is set using [`set_idle_policy`], the engine goes idle when no input arrived
+//! and every frame came out identical to the previous one for [`IdlePolicy::after`].
+//! While idle, [`crate::engine::start_frame`] waits for the much lower [`IdlePolicy::fps`] instead of the regular FPS limit.
+//!
+//! The engine wakes back up to the full frame rate as soon as:
+//! - an input event arrives, which cuts the idle wait short so the event is handled in the very next frame,
+//! - or a frame differs from the previous one.
+//!
+//! Animations driven by `game_time` look choppy while idle, use [`is_idle`] to pause them deliberately.
+//!
+//! ## Notes
+//! Offscreen engines never diff their frames, so they never go idle.
+
+use std::time::{Duration, Instant};
+
+use crate::{engine::Engine, input::InputSource};
+
+/// The lowest frame rate while idle, lower (or non-finite) [`IdlePolicy::fps`] values are raised to it.
+pub const MIN_IDLE_FPS: f32 = 0.1;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IdlePolicy {
+    /// How long nothing has to happen before the engine goes idle.
+    pub after: Duration,
+    /// The frame rate while idle, may be below `1.0` but never below [`MIN_IDLE_FPS`].
+    pub fps: f32,
+}
+
+impl Default for IdlePolicy {
+    fn default() -> Self {
+        Self {
+            after: Duration::from_secs(2),
+            fps: 2.0,
+        }
+    }
+}
+
+pub(crate) struct IdleState {
+    policy: Option<IdlePolicy>,
+    last_activity: Instant,
+    last_frame_start: Instant,
+    is_idle: bool,
+    is_input_pending: bool,
+    frame_was_identical: bool,
+}
+
+impl IdleState {
+    pub fn new() -> Self {
+        Self {
+            policy: None,
+            last_activity: Instant::now(),
+            last_frame_start: Instant::now(),
+            is_idle: false,
+            is_input_pending: false,
+            frame_was_identical: false,
+        }
+    }
+}
+
+/// Sets the policy for throttling the frame rate while idle, `None` disables throttling.
+///
+/// Disabled by default.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{engine::Engine, idle::{IdlePolicy, set_idle_policy}};
+/// # use std::time::Duration;
+/// let mut engine = Engine::new(40, 20).limit_fps(60);
+/// set_idle_policy(
+///     &mut engine,
+///     Some(IdlePolicy {
+///         after: Duration::from_secs(5),
+///         fps: 2.0,
+///     }),
+/// );
+/// ```
+pub fn set_idle_policy(engine: &mut Engine, policy: Option<IdlePolicy>) {
+    engine.idle.policy = policy;
+    engine.idle.is_idle = false;
+    engine.idle.last_activity = Instant::now();
+}
+
+/// Whether the frame rate is currently throttled by the [`IdlePolicy`].
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{engine::Engine, idle::is_idle};
+/// let engine = Engine::new(40, 20);
+/// let spinner_speed: f32 = if is_idle(&engine) { 0.0 } else { 8.0 };
+/// ```
+pub fn is_idle(engine: &Engine) -> bool {
+    engine.idle.is_idle
+}
+
+/// Whether the last frame drawn to the terminal was identical to the one before it.
+///
+/// Always `false` for offscreen engines.
+pub fn frame_was_identical(engine: &Engine) -> bool {
+    engine.idle.frame_was_identical
+}
+
+pub(crate) fn reset_idle_state(idle_state: &mut IdleState) {
+    let now: Instant = Instant::now();
+    idle_state.last_activity = now;
+    idle_state.last_frame_start = now;
+    idle_state.is_idle = false;
+    idle_state.is_input_pending = false;
+    idle_state.frame_was_identical = false;
+}
+
+/// While idle, waits until the next idle frame is due or an input event arrives.
+///
+/// Events are only peeked at, so they are still returned by the input polling functions in the frame that follows.
+pub(crate) fn wait_while_idle(
+    idle_state: &mut IdleState,
+    input_source: InputSource,
+    is_input_queued: bool,
+) {
+    let Some(policy) = idle_state.policy.filter(|_| idle_state.is_idle) else {
+        return;
+    };
+    if is_input_queued {
+        idle_state.is_idle = false;
+        return;
+    }
+
+    let next_idle_frame: Instant = idle_state
+        .last_frame_start
+        .checked_add(idle_frametime(policy))
+        .unwrap_or_else(Instant::now);
+
+    loop {
+        let remaining: Duration = next_idle_frame.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+
+        match input_source.poll(remaining) {
+            Ok(true) => {
+                idle_state.is_idle = false;
+                break;
+            }
+            Ok(false) => {}
+            // Without an input source there's nothing to wake up for, so the rest of the wait is slept away
+            Err(_) => {
+                std::thread::sleep(remaining);
+                break;
+            }
+        }
+    }
+}
+
+/// The time between frames while idle.
+fn idle_frametime(policy: IdlePolicy) -> Duration {
+    // `max` also replaces NaN
+    Duration::from_secs_f32(1.0 / policy.fps.max(MIN_IDLE_FPS))
+}
+
+/// Marks the start of a frame, right after waiting for the FPS limiter.
+pub(crate) fn start_idle_frame(
+    idle_state: &mut IdleState,
+    input_source: InputSource,
+    is_input_queued: bool,
+) {
+    idle_state.last_frame_start = Instant::now();
+    if idle_state.policy.is_some() {
+        idle_state.is_input_pending =
+            is_input_queued || input_source.poll(Duration::ZERO).unwrap_or(false);
+    }
+}
+
+/// Advances the idle state at the end of a frame, given whether it came out identical to the previous one.
+pub(crate) fn end_idle_frame(idle_state: &mut IdleState, frame_was_identical: bool) {
+    idle_state.frame_was_identical = frame_was_identical;
+    let had_activity: bool = idle_state.is_input_pending || !frame_was_identical;
+    update_idle_state(idle_state, had_activity, Instant::now());
+}
+
+pub(crate) fn update_idle_state(idle_state: &mut IdleState, had_activity: bool, now: Instant) {
+    if had_activity {
+        idle_state.last_activity = now;
+        idle_state.is_idle = false;
+    } else if let Some(policy) = idle_state.policy {
+        idle_state.is_idle =
+            now.saturating_duration_since(idle_state.last_activity) >= policy.after;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{
+        IdlePolicy, IdleState, MIN_IDLE_FPS, idle_frametime, is_idle, set_idle_policy,
+        update_idle_state,
+    };
+    use crate::{
+        crossterm::event::{Event, KeyCode, KeyEvent},
+        engine::{Engine, end_frame, init, start_frame},
+        input::{poll_tracked_input, push_input},
+        test_support::SharedOutput,
+    };
+
+    const POLICY: IdlePolicy = IdlePolicy {
+        after: Duration::from_secs(2),
+        fps: 2.0,
+    };
+
+    fn idle_state(policy: Option<IdlePolicy>, start: Instant) -> IdleState {
+        IdleState {
+            policy,
+            last_activity: start,
+            ..IdleState::new()
+        }
+    }
+
+    #[test]
+    fn goes_idle_once_nothing_happened_for_a_while() {
+        let start: Instant = Instant::now();
+        let mut state: IdleState = idle_state(Some(POLICY), start);
+
+        update_idle_state(&mut state, false, start + Duration::from_secs(1));
+        assert!(!state.is_idle);
+        update_idle_state(&mut state, false, start + Duration::from_secs(2));
+        assert!(state.is_idle);
+    }
+
+    #[test]
+    fn activity_wakes_up_and_restarts_the_countdown() {
+        let start: Instant = Instant::now();
+        let mut state: IdleState = idle_state(Some(POLICY), start);
+        update_idle_state(&mut state, false, start + Duration::from_secs(3));
+        assert!(state.is_idle);
+
+        update_idle_state(&mut state, true, start + Duration::from_secs(4));
+        assert!(!state.is_idle);
+        update_idle_state(&mut state, false, start + Duration::from_secs(5));
+        assert!(!state.is_idle);
+        update_idle_state(&mut state, false, start + Duration::from_secs(6));
+        assert!(state.is_idle);
+    }
+
+    #[test]
+    fn never_goes_idle_without_a_policy() {
+        let start: Instant = Instant::now();
+        let mut state: IdleState = idle_state(None, start);
+        update_idle_state(&mut state, false, start + Duration::from_secs(60));
+        assert!(!state.is_idle);
+    }
+
+    #[test]
+    fn idle_frametime_is_bounded_for_invalid_fps() {
+        assert_eq!(idle_frametime(POLICY), Duration::from_millis(500));
+
+        let longest: Duration = Duration::from_secs_f32(1.0 / MIN_IDLE_FPS);
+        for fps in [0.0, -1.0, f32::NAN, f32::NEG_INFINITY, 0.001] {
+            assert_eq!(idle_frametime(IdlePolicy { fps, ..POLICY }), longest);
+        }
+        assert_eq!(
+            idle_frametime(IdlePolicy {
+                fps: f32::INFINITY,
+                ..POLICY
+            }),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn pushed_input_wakes_remote_engines_up() {
+        let mut engine: Engine = Engine::remote(4, 1, SharedOutput::default()).limit_fps(0);
+        init(&mut engine).unwrap();
+        set_idle_policy(
+            &mut engine,
+            Some(IdlePolicy {
+                after: Duration::ZERO,
+                fps: MIN_IDLE_FPS,
+            }),
+        );
+        // Stopping as soon as it's idle, as the frame after would be throttled
+        for _ in 0..3 {
+            start_frame(&mut engine);
+            end_frame(&mut engine).unwrap();
+            if is_idle(&engine) {
+                break;
+            }
+        }
+        assert!(is_idle(&engine));
+
+        // Waiting for the next idle frame would take 10 seconds
+        let started_at: Instant = Instant::now();
+        push_input(&mut engine, Event::Key(KeyEvent::from(KeyCode::Enter)));
+        start_frame(&mut engine);
+        assert!(!is_idle(&engine));
+        assert!(started_at.elapsed() < Duration::from_secs(1));
+        assert_eq!(poll_tracked_input(&mut engine).count(), 1);
+        end_frame(&mut engine).unwrap();
+        assert!(!is_idle(&engine));
+    }
+}
@@ -0,0 +1,529 @@
+//! Rendering into a region of the normal screen, instead of the alternate screen.
+//!
+//! An engine built with [`crate::engine::Engine::inline`] reserves as many rows as the engine is tall,
+//! starting at the cursor's line, and renders into them without entering the alternate screen.
+//! The user's scrollback stays intact, which makes it a good fit for eg. live progress displays inside of regular CLIs.
+//!
+//! Log lines can be printed above the region using [`print_above_inline`] and [`print_lines_above_inline`].
+//!
+//! When the terminal is resized, the region is located again starting at the cursor's new line and fully repainted.
+//!
+//! ## Leaving the region
+//! The region is drawn over whatever shell output was on its rows, which can't be read back from the terminal.
+//! Instead, the rows drawn to are tracked for the whole session, and on [`crate::engine::exit_cleanup`]
+//! exactly those rows are cleared and the cursor is moved below the region.
+//! Tools which want their final frame to persist, like a table of results, can keep it with [`InlineMode::leave_last_frame`].
+//!
+//! ## Scroll region
+//! With [`InlineMode::scroll_region`] set, the region is pinned to the bottom of the terminal instead,
+//! and the rows above it are set up as the terminal's scroll region (`DECSTBM`).
+//! Printed lines then scroll into the scrollback like regular output, so native scrolling and selection keep working,
+//! while the region stays in place and doesn't have to be repainted. This suits eg. chat or log viewers
+//! with a status or input bar at the bottom.
+//!
+//! ## Notes
+//! Mouse capture interferes with scrolling through the scrollback, so it's usually best turned off for inline engines.
+
+use std::io::{self, Write};
+
+use crossterm::{cursor, execute, queue, style, terminal};
+
+use crate::{
+    cell::Cell,
+    engine::Engine,
+    frame::{TerminalColors, build_crossterm_content_style},
+    rich_text::{Attributes, RichText},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct InlineMode {
+    /// Whether the final frame is left visible on [`crate::engine::exit_cleanup`],
+    /// instead of clearing the rows drawn to, see the [module docs](self#leaving-the-region).
+    pub leave_last_frame: bool,
+    /// Whether the region is pinned to the bottom of the terminal, with the rows above it as the scroll region,
+    /// see the [module docs](self#scroll-region).
+    pub scroll_region: bool,
+}
+
+impl InlineMode {
+    /// Sets [`InlineMode::leave_last_frame`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::inline::InlineMode;
+    /// let mode = InlineMode::default().leave_last_frame(true);
+    /// assert!(mode.leave_last_frame && !mode.scroll_region);
+    /// ```
+    pub fn leave_last_frame(mut self, value: bool) -> Self {
+        self.leave_last_frame = value;
+        self
+    }
+
+    /// Sets [`InlineMode::scroll_region`].
+    pub fn scroll_region(mut self, value: bool) -> Self {
+        self.scroll_region = value;
+        self
+    }
+}
+
+pub(crate) struct InlineViewport {
+    pub mode: InlineMode,
+    pub origin_row: u16,
+    /// The row of the region the cursor was left at after the last frame.
+    parked_row: u16,
+    terminal_size: (u16, u16),
+    /// Whether each row of the region was drawn to at any point of the session.
+    touched_rows: Vec<bool>,
+}
+
+impl InlineViewport {
+    pub fn new(mode: InlineMode) -> Self {
+        Self {
+            mode,
+            origin_row: 0,
+            parked_row: 0,
+            terminal_size: (0, 0),
+            touched_rows: Vec::new(),
+        }
+    }
+}
+
+/// Prints `text` above the inline region in the terminal's default colors, see [`print_lines_above_inline`].
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{engine::{Engine, init}, inline::{InlineMode, print_above_inline}};
+/// let mut engine = Engine::new(40, 1)
+///     .inline(Some(InlineMode::default()))
+///     .enable_mouse_capture(false);
+/// init(&mut engine);
+///
+/// print_above_inline(&mut engine, "Downloaded assets.tar.gz");
+/// ```
+pub fn print_above_inline(engine: &mut Engine, text: &str) -> io::Result<()> {
+    print_lines_above_inline(
+        engine,
+        text.lines().map(|line| {
+            RichText::new(line).with_attributes(Attributes::NO_FG_COLOR | Attributes::NO_BG_COLOR)
+        }),
+    )
+}
+
+/// Prints styled lines above the inline region.
+///
+/// Without [`InlineMode::scroll_region`] the region is pushed down, and fully repainted by the next [`crate::engine::end_frame`].
+/// With it, the rows above the region scroll up to make room for the lines, leaving the region untouched.
+///
+/// Lines longer than the terminal is wide are wrapped by the terminal itself.
+/// Colors are printed as they are, without blending, fully transparent ones leave the terminal's default colors.
+/// Does nothing for engines which aren't inline.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{color::Color, engine::{Engine, init}, rich_text::RichText};
+/// # use germterm::inline::{InlineMode, print_lines_above_inline};
+/// let mut engine = Engine::new(40, 1)
+///     .inline(Some(InlineMode {
+///         scroll_region: true,
+///         ..Default::default()
+///     }))
+///     .enable_mouse_capture(false);
+/// init(&mut engine);
+///
+/// print_lines_above_inline(
+///     &mut engine,
+///     [
+///         RichText::new("<alice> hi!").with_fg(Color::CYAN),
+///         RichText::new("* bob joined").with_fg(Color::DARK_GRAY),
+///     ],
+/// );
+/// ```
+pub fn print_lines_above_inline(
+    engine: &mut Engine,
+    lines: impl IntoIterator<Item = impl Into<RichText>>,
+) -> io::Result<()> {
+    let height: u16 = engine.frame.height;
+    let Some(viewport) = engine.inline.as_mut().filter(|_| !engine.offscreen) else {
+        return Ok(());
+    };
+
+    if viewport.mode.scroll_region && viewport.origin_row > 0 {
+        return print_into_scroll_region(
+            &mut engine.output,
+            viewport,
+            lines,
+            &mut engine.terminal_colors,
+        );
+    }
+
+    queue!(
+        engine.output,
+        cursor::MoveTo(0, viewport.origin_row),
+        style::ResetColor,
+        terminal::Clear(terminal::ClearType::FromCursorDown),
+    )?;
+    for line in lines {
+        print_line(
+            &mut engine.output,
+            &line.into(),
+            &mut engine.terminal_colors,
+        )?;
+        // Raw mode doesn't translate line feeds, so each line has to return the carriage by itself
+        queue!(engine.output, style::Print("\r\n"))?;
+    }
+    reserve_inline_region(&mut engine.output, viewport, height)?;
+    engine.frame.reset_frames();
+    Ok(())
+}
+
+/// Prints lines at the bottom of the scroll region above a pinned region, scrolling the rows above up for each one.
+pub(crate) fn print_into_scroll_region(
+    writer: &mut impl Write,
+    viewport: &InlineViewport,
+    lines: impl IntoIterator<Item = impl Into<RichText>>,
+    terminal_colors: &mut TerminalColors,
+) -> io::Result<()> {
+    // A line feed on the bottom row of the scroll region scrolls it, while the rows below stay put
+    queue!(
+        writer,
+        cursor::MoveTo(0, viewport.origin_row.saturating_sub(1))
+    )?;
+    for line in lines {
+        queue!(writer, style::Print("\r\n"))?;
+        print_line(writer, &line.into(), terminal_colors)?;
+    }
+    writer.flush()
+}
+
+/// Prints a single line styled like a cell with the colors and attributes of `line`, resetting the style afterwards.
+fn print_line(
+    writer: &mut impl Write,
+    line: &RichText,
+    terminal_colors: &mut TerminalColors,
+) -> io::Result<()> {
+    let mut attributes: Attributes = line.attributes;
+    attributes.set(
+        Attributes::NO_FG_COLOR,
+        attributes.contains(Attributes::NO_FG_COLOR) || line.fg.a() == 0,
+    );
+    attributes.set(
+        Attributes::NO_BG_COLOR,
+        attributes.contains(Attributes::NO_BG_COLOR) || line.bg.a() == 0,
+    );
+    let cell: Cell = Cell {
+        fg: line.fg,
+        bg: line.bg,
+        attributes,
+        ..Cell::EMPTY
+    };
+
+    queue!(
+        writer,
+        style::SetAttribute(style::Attribute::Reset),
+        style::SetStyle(build_crossterm_content_style(&cell, terminal_colors)),
+        style::Print(line.text.as_str()),
+        style::SetAttribute(style::Attribute::Reset),
+    )
+}
+
+/// Reserves `height` rows starting at the cursor's line, scrolling the terminal if there isn't enough room below,
+/// and clears them. Pinned regions are reserved at the bottom of the terminal instead, see [`pin_inline_region`].
+pub(crate) fn reserve_inline_region(
+    writer: &mut impl Write,
+    viewport: &mut InlineViewport,
+    height: u16,
+) -> io::Result<()> {
+    if viewport.mode.scroll_region {
+        return pin_inline_region(writer, viewport, height, terminal::size()?);
+    }
+
+    queue!(writer, cursor::MoveToColumn(0))?;
+    for _ in 1..height {
+        queue!(writer, style::Print("\n"))?;
+    }
+    writer.flush()?;
+
+    let (_, cursor_row): (u16, u16) = cursor::position()?;
+    viewport.origin_row = cursor_row.saturating_sub(height.saturating_sub(1));
+    viewport.parked_row = 0;
+    viewport.terminal_size = terminal::size()?;
+    execute!(
+        writer,
+        cursor::MoveTo(0, viewport.origin_row),
+        terminal::Clear(terminal::ClearType::FromCursorDown),
+    )
+}
+
+/// Reserves the bottom `height` rows of a terminal of `terminal_size`, and confines scrolling to the rows above them.
+///
+/// The whole screen is scrolled up to make room, pushing its top rows into the scrollback.
+pub(crate) fn pin_inline_region(
+    writer: &mut impl Write,
+    viewport: &mut InlineViewport,
+    height: u16,
+    terminal_size: (u16, u16),
+) -> io::Result<()> {
+    let rows: u16 = terminal_size.1;
+    viewport.origin_row = rows.saturating_sub(height);
+    viewport.parked_row = 0;
+    viewport.terminal_size = terminal_size;
+
+    // The previous scroll region has to go first, or it would be the only part scrolled
+    reset_scroll_region(writer)?;
+    queue!(writer, cursor::MoveTo(0, rows.saturating_sub(1)))?;
+    for _ in 0..height {
+        queue!(writer, style::Print("\n"))?;
+    }
+    // Without any rows above the region there's nothing to scroll
+    if viewport.origin_row > 0 {
+        write!(writer, "\x1b[1;{}r", viewport.origin_row)?;
+    }
+    execute!(
+        writer,
+        cursor::MoveTo(0, viewport.origin_row),
+        terminal::Clear(terminal::ClearType::FromCursorDown),
+    )
+}
+
+/// Lets the whole screen scroll again (`DECSTBM` without margins), which also moves the cursor to the top-left.
+fn reset_scroll_region(writer: &mut impl Write) -> io::Result<()> {
+    write!(writer, "\x1b[r")
+}
+
+/// Reserves the region again if the terminal was resized since, returning whether it was.
+///
+/// The cursor stays within the region between frames, so it follows the region
+/// when the terminal reflows its contents, and is moved relatively back to the start of the region.
+pub(crate) fn refresh_inline_region(
+    writer: &mut impl Write,
+    viewport: &mut InlineViewport,
+    height: u16,
+) -> io::Result<bool> {
+    if terminal::size()? == viewport.terminal_size {
+        return Ok(false);
+    }
+
+    // Pinned regions are found by their absolute position, no matter where the cursor went
+    if viewport.parked_row > 0 && !viewport.mode.scroll_region {
+        queue!(writer, cursor::MoveToPreviousLine(viewport.parked_row))?;
+    }
+    reserve_inline_region(writer, viewport, height)?;
+    Ok(true)
+}
+
+/// Parks the cursor at the start of the region, unless it was placed by [`crate::cursor::set_cursor`],
+/// given the row it was placed at.
+pub(crate) fn park_inline_cursor(
+    writer: &mut impl Write,
+    viewport: &mut InlineViewport,
+    cursor_row: Option<u16>,
+) -> io::Result<()> {
+    match cursor_row {
+        Some(row) => {
+            viewport.parked_row = row;
+            Ok(())
+        }
+        None => {
+            viewport.parked_row = 0;
+            execute!(writer, cursor::MoveTo(0, viewport.origin_row))
+        }
+    }
+}
+
+/// Records `row` of the region as drawn to, so it's cleared by [`leave_inline_region`].
+pub(crate) fn touch_inline_row(viewport: &mut InlineViewport, row: u16) {
+    let row: usize = row as usize;
+    if row >= viewport.touched_rows.len() {
+        viewport.touched_rows.resize(row + 1, false);
+    }
+    viewport.touched_rows[row] = true;
+}
+
+/// Clears every row of the region drawn to during the session, unless [`InlineMode::leave_last_frame`] is set,
+/// and moves the cursor below the region.
+pub(crate) fn leave_inline_region(
+    writer: &mut impl Write,
+    viewport: &InlineViewport,
+    height: u16,
+) -> io::Result<()> {
+    queue!(writer, style::SetAttribute(style::Attribute::Reset))?;
+    if viewport.mode.scroll_region {
+        reset_scroll_region(writer)?;
+    }
+    if !viewport.mode.leave_last_frame {
+        let touched_rows = (0..height).filter(|&row| {
+            viewport
+                .touched_rows
+                .get(row as usize)
+                .is_some_and(|&is_touched| is_touched)
+        });
+        for row in touched_rows {
+            queue!(
+                writer,
+                cursor::MoveTo(0, viewport.origin_row.saturating_add(row)),
+                terminal::Clear(terminal::ClearType::CurrentLine),
+            )?;
+        }
+    }
+
+    let last_row: u16 = viewport.origin_row.saturating_add(height.saturating_sub(1));
+    execute!(writer, cursor::MoveTo(0, last_row), style::Print("\r\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        InlineMode, InlineViewport, leave_inline_region, pin_inline_region,
+        print_into_scroll_region, touch_inline_row,
+    };
+    use crate::{
+        cell::Cell,
+        color::{Color, ColorDepth},
+        frame::{DiffProduct, FramePair, TerminalColors, draw_to_terminal},
+        rich_text::RichText,
+    };
+
+    const TERMINAL_SIZE: (u16, u16) = (20, 10);
+    const HEIGHT: u16 = 3;
+
+    fn pinned_viewport() -> (InlineViewport, String) {
+        let mut viewport: InlineViewport = InlineViewport::new(InlineMode {
+            scroll_region: true,
+            ..Default::default()
+        });
+        let mut output: Vec<u8> = Vec::new();
+        pin_inline_region(&mut output, &mut viewport, HEIGHT, TERMINAL_SIZE).unwrap();
+        (viewport, String::from_utf8(output).unwrap())
+    }
+
+    /// The 1-based rows of every absolute cursor move in `output`.
+    fn cursor_rows(output: &str) -> Vec<u16> {
+        output
+            .split("\x1b[")
+            .skip(1)
+            .filter_map(|sequence| {
+                let end: usize = sequence.find(|ch: char| !ch.is_ascii_digit() && ch != ';')?;
+                let (row, _) = sequence[..end].split_once(';')?;
+                (sequence[end..].starts_with('H')).then(|| row.parse().unwrap())
+            })
+            .collect()
+    }
+
+    /// Draws frames with an `x` at each of the given cell indices into a region at row 4,
+    /// tracking the rows drawn to, and returns the output of leaving the region afterwards.
+    fn leave_after_session(mode: InlineMode, frames: &[&[usize]]) -> String {
+        let mut viewport: InlineViewport = InlineViewport::new(mode);
+        viewport.origin_row = 4;
+        let mut frames_pair: FramePair = FramePair::new(TERMINAL_SIZE.0, HEIGHT);
+        let mut terminal_colors: TerminalColors = TerminalColors::new(ColorDepth::TrueColor);
+        for frame in frames {
+            let mut current = frames_pair.current_mut();
+            for index in 0..(TERMINAL_SIZE.0 * HEIGHT) as usize {
+                current[index] = Cell::EMPTY;
+            }
+            for &index in *frame {
+                current[index] = Cell {
+                    ch: 'x',
+                    fg: Color::WHITE,
+                    ..Cell::EMPTY
+                };
+            }
+            let diff: Vec<DiffProduct> = frames_pair.diff().collect();
+            for product in &diff {
+                touch_inline_row(&mut viewport, product.y);
+            }
+            draw_to_terminal(
+                &mut Vec::new(),
+                diff.into_iter(),
+                &mut terminal_colors,
+                viewport.origin_row,
+                false,
+            )
+            .unwrap();
+            frames_pair.swap_frames();
+        }
+
+        let mut output: Vec<u8> = Vec::new();
+        leave_inline_region(&mut output, &viewport, HEIGHT).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn leaving_clears_every_row_drawn_to_during_the_session() {
+        // The last row is only drawn to by the first frame, the middle one never
+        let frames: [&[usize]; 3] = [&[45], &[45], &[45, 5]];
+
+        assert_eq!(
+            leave_after_session(InlineMode::default(), &frames),
+            "\x1b[0m\x1b[5;1H\x1b[2K\x1b[7;1H\x1b[2K\x1b[7;1H\r\n"
+        );
+        assert_eq!(
+            leave_after_session(InlineMode::default().leave_last_frame(true), &frames),
+            "\x1b[0m\x1b[7;1H\r\n"
+        );
+    }
+
+    #[test]
+    fn pinned_region_sets_up_the_scroll_region_above_it() {
+        let (viewport, output) = pinned_viewport();
+
+        assert_eq!(viewport.origin_row, 7);
+        // Scrolls the whole screen to make room, then confines scrolling to the 7 rows above
+        let scroll_region_start: usize = output.find("\x1b[1;7r").unwrap();
+        assert!(output.find("\x1b[r").unwrap() < scroll_region_start);
+        assert_eq!(output[..scroll_region_start].matches('\n').count(), 3);
+        assert_eq!(cursor_rows(&output[scroll_region_start..]), [8]);
+    }
+
+    #[test]
+    fn printed_lines_scroll_the_rows_above_the_region() {
+        let (viewport, _) = pinned_viewport();
+        let mut terminal_colors: TerminalColors = TerminalColors::new(ColorDepth::TrueColor);
+        let mut output: Vec<u8> = Vec::new();
+        print_into_scroll_region(
+            &mut output,
+            &viewport,
+            [
+                RichText::new("first"),
+                RichText::new("second").with_fg(Color::new(255, 0, 0, 255)),
+            ],
+            &mut terminal_colors,
+        )
+        .unwrap();
+        let output: String = String::from_utf8(output).unwrap();
+
+        // Only ever on the bottom row of the scroll region, where line feeds scroll it
+        assert_eq!(cursor_rows(&output), [7]);
+        let first: usize = output.find("first").unwrap();
+        let second: usize = output.find("second").unwrap();
+        assert!(output[..first].ends_with('m') && output[..first].contains("\r\n"));
+        assert!(output[first..second].contains("\r\n"));
+        assert!(output[first..second].contains("\x1b[38;2;255;0;0m"));
+    }
+
+    #[test]
+    fn frame_diffs_stay_within_the_pinned_region() {
+        let (viewport, _) = pinned_viewport();
+        let mut frames: FramePair = FramePair::new(TERMINAL_SIZE.0, HEIGHT);
+        for index in [0, 25, 59] {
+            frames.current_mut()[index] = Cell {
+                ch: 'x',
+                fg: Color::WHITE,
+                ..Cell::EMPTY
+            };
+        }
+        let mut terminal_colors: TerminalColors = TerminalColors::new(ColorDepth::TrueColor);
+        let mut output: Vec<u8> = Vec::new();
+        draw_to_terminal(
+            &mut output,
+            frames.diff(),
+            &mut terminal_colors,
+            viewport.origin_row,
+            false,
+        )
+        .unwrap();
+
+        let rows: Vec<u16> = cursor_rows(&String::from_utf8(output).unwrap());
+        assert_eq!(rows, [8, 9, 10]);
+    }
+}
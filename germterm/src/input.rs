@@ -1,7 +1,123 @@
 //! Input handling.
+//!
+//! ## Timing
+//!
+//! [`poll_input`] drains events once per frame, so all events end up quantized to the frame interval.
+//! Timing sensitive applications (eg. rhythm games) can opt into [`InputSampling::HighResolution`]
+//! using [`crate::engine::Engine::input_sampling`] and read events through [`poll_timed_input`],
+//! which carry the instant they were received at.
+//!
+//! ## Key state
+//!
+//! Events drained through [`poll_tracked_input`] or [`poll_timed_input`] also update the key state of the engine,
+//! which can be queried with [`is_key_held`], [`is_key_just_pressed`] and [`is_key_just_released`].
+//!
+//! Most terminals never report key releases. Keys are then considered held from the press
+//! until no further presses or repeats arrived within the repeat timeout,
+//! configured with [`crate::engine::Engine::key_repeat_timeout`].
+//! Terminals that do report releases release keys immediately instead,
+//! which can be requested using [`crate::engine::Engine::enable_keyboard_enhancement`].
+//! Held keys are released when the terminal loses focus, as their releases would never arrive.
+//!
+//! ## Mouse
+//!
+//! Mouse capture is enabled by [`crate::engine::init`] unless turned off with
+//! [`crate::engine::Engine::enable_mouse_capture`].
+//! Mouse events can be translated into the drawing coordinate space with [`mouse_input`].
+//!
+//! Terminals only report mouse positions at cell granularity,
+//! so sub-cell positions always point at the top-left sub-cell of the hovered cell.
+//!
+//! ## Paste and focus
+//!
+//! Bracketed paste and focus reporting are enabled by [`crate::engine::init`] unless turned off with
+//! [`crate::engine::Engine::enable_bracketed_paste`] and [`crate::engine::Engine::enable_focus_events`].
+//! Pasted text then arrives as a single [`Event::Paste`] instead of a key event per char, so it can't trigger keybindings,
+//! and [`Event::FocusGained`] and [`Event::FocusLost`] allow eg. pausing while the terminal is in the background.
+//!
+//! ## Drag and drop
+//!
+//! [`DragState`] turns mouse events into drags using [`update_drag`],
+//! and [`drop_target`] computes where a dragged panel would be inserted among the others.
+//! A dimmed copy of the dragged panel can be drawn at the cursor using [`crate::draw::draw_drag_ghost`].
+//! Reordering the actual panels is left to the application.
 
-use crossterm::event::{self, Event};
-use std::time::Duration;
+use crate::{
+    engine::Engine,
+    replay::{InputPlayback, InputRecording, next_played_back_event, record_event},
+};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+/// Where the input events of an engine come from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum InputSource {
+    /// The local terminal, read through crossterm.
+    Terminal,
+    /// Events handed over with [`push_input`], as the local terminal isn't the one being drawn to.
+    Pushed,
+}
+
+impl InputSource {
+    /// Waits up to `timeout` for an event to become available, without reading it.
+    ///
+    /// Pushed events only ever arrive between frames, so there's nothing to wait on and the timeout is slept away.
+    pub(crate) fn poll(self, timeout: Duration) -> io::Result<bool> {
+        match self {
+            InputSource::Terminal => event::poll(timeout),
+            InputSource::Pushed => {
+                sleep(timeout);
+                Ok(false)
+            }
+        }
+    }
+}
+
+/// How often the terminal gets sampled for input events.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InputSampling {
+    /// Events are only read when drained, usually once per frame.
+    #[default]
+    PerFrame,
+    /// Events are also read while the FPS limiter waits for the next frame,
+    /// waking up at least every `poll_interval` and immediately when an event arrives.
+    ///
+    /// The queued events are only returned by [`poll_timed_input`], [`poll_input`] does not see them.
+    /// At most [`MAX_QUEUED_EVENTS`] are kept between drains, with the oldest events being dropped first.
+    HighResolution { poll_interval: Duration },
+}
+
+/// The most events [`InputSampling::HighResolution`] queues up before dropping the oldest ones,
+/// so the queue can't grow forever when it's never drained.
+pub const MAX_QUEUED_EVENTS: usize = 1024;
+
+/// An input event along with the instant it was read from the terminal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimedEvent {
+    pub event: Event,
+    pub timestamp: Instant,
+}
+
+/// A mouse event translated into the drawing coordinate space.
+///
+/// `x` and `y` refer to terminal columns and rows, just like the drawing API.
+///
+/// `kind` carries button presses, releases, drags, movement and scroll wheel events.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MouseInput {
+    pub kind: MouseEventKind,
+    pub x: i16,
+    pub y: i16,
+    pub modifiers: KeyModifiers,
+}
 
 /// Polls the terminal for input events and drains all available events.
 ///
@@ -30,3 +146,784 @@ pub fn poll_input() -> impl Iterator<Item = Event> {
         }
     })
 }
+
+/// Drains all input events along with the instant each of them was read at.
+///
+/// Events queued up by [`InputSampling::HighResolution`] since the last call are returned first,
+/// followed by any events currently available, in the order they were received.
+/// While an input recording is played back, the recorded events are returned instead, see [`crate::replay`].
+/// With [`InputSampling::PerFrame`] this behaves like [`poll_input`], timestamping events as they're drained.
+///
+/// [Remote](Engine::remote) engines never read the local terminal, and only return the events given to [`push_input`].
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{engine::Engine, input::{InputSampling, poll_timed_input}};
+/// # use std::time::{Duration, Instant};
+/// let mut engine = Engine::new(40, 20).input_sampling(InputSampling::HighResolution {
+///     poll_interval: Duration::from_millis(1),
+/// });
+/// let song_start = Instant::now();
+///
+/// for timed_event in poll_timed_input(&mut engine) {
+///     let song_time = timed_event.timestamp.duration_since(song_start);
+///     println!("{:?} at {:?}", timed_event.event, song_time);
+/// }
+/// ```
+pub fn poll_timed_input(engine: &mut Engine) -> impl Iterator<Item = TimedEvent> + '_ {
+    let input_source: InputSource = engine.input_source;
+    let key_state: &mut KeyState = &mut engine.key_state;
+    let input_recording: &mut Option<InputRecording> = &mut engine.input_recording;
+    let input_playback: &mut Option<InputPlayback> = &mut engine.input_playback;
+
+    // The terminal is left alone while a recording is being played back
+    let live_events = (input_playback.is_none() && input_source == InputSource::Terminal)
+        .then(poll_input)
+        .into_iter()
+        .flatten();
+    let played_back_events =
+        std::iter::from_fn(move || next_played_back_event(input_playback.as_mut()?));
+
+    engine
+        .input_queue
+        .drain(..)
+        .chain(
+            played_back_events
+                .chain(live_events)
+                .map(|event| TimedEvent {
+                    event,
+                    timestamp: Instant::now(),
+                }),
+        )
+        .inspect(move |timed_event| {
+            update_key_state(key_state, &timed_event.event, timed_event.timestamp);
+            if let Some(input_recording) = input_recording {
+                record_event(input_recording, &timed_event.event);
+            }
+        })
+}
+
+/// Same as [`poll_input`], but also updates the key state queried by [`is_key_held`] and friends.
+///
+/// Events are still passed through as-is, eg. for text input.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{crossterm::event::KeyCode, engine::Engine};
+/// # use germterm::input::{is_key_held, is_key_just_pressed, poll_tracked_input};
+/// let mut engine = Engine::new(40, 20);
+/// let mut typed: String = String::new();
+///
+/// for event in poll_tracked_input(&mut engine) {
+///     if let Some(key_event) = event.as_key_press_event()
+///         && let KeyCode::Char(ch) = key_event.code
+///     {
+///         typed.push(ch);
+///     }
+/// }
+///
+/// let jump: bool = is_key_just_pressed(&engine, KeyCode::Char(' '));
+/// let walk_right: bool = is_key_held(&engine, KeyCode::Right);
+/// ```
+pub fn poll_tracked_input(engine: &mut Engine) -> impl Iterator<Item = Event> + '_ {
+    poll_timed_input(engine).map(|timed_event| timed_event.event)
+}
+
+/// Queues up an input event read from the connection of a [remote](Engine::remote) engine.
+///
+/// Pushed events are returned by [`poll_timed_input`] and [`poll_tracked_input`] in the order they were pushed,
+/// just like events read from the local terminal, and wake the engine up when it's [idle](crate::idle).
+///
+/// # Example
+/// ```rust
+/// # use germterm::{crossterm::event::{Event, KeyCode, KeyEvent}, engine::Engine};
+/// # use germterm::input::{is_key_held, poll_tracked_input, push_input};
+/// let mut engine = Engine::remote(40, 20, Vec::new());
+/// // Decoded from the bytes the client sent
+/// push_input(&mut engine, Event::Key(KeyEvent::from(KeyCode::Left)));
+///
+/// assert_eq!(poll_tracked_input(&mut engine).count(), 1);
+/// assert!(is_key_held(&engine, KeyCode::Left));
+/// ```
+pub fn push_input(engine: &mut Engine, event: Event) {
+    let timed_event = TimedEvent {
+        event,
+        timestamp: Instant::now(),
+    };
+    queue_event(&mut engine.input_queue, timed_event);
+}
+
+/// Spends up to `idle_time` waiting for input events, queueing them up as they arrive.
+///
+/// Used in place of sleeping while the FPS limiter waits for the next frame.
+pub(crate) fn sample_input_while_idle(
+    input_queue: &mut VecDeque<TimedEvent>,
+    input_source: InputSource,
+    idle_time: Duration,
+    poll_interval: Duration,
+) {
+    let timeout: Duration = idle_time.min(poll_interval);
+    sample_event(
+        input_queue,
+        timeout,
+        |timeout| input_source.poll(timeout),
+        event::read,
+        Instant::now,
+    );
+}
+
+/// Waits up to `timeout` for an event using `poll`, queueing it up along with the instant `now` returns once it's read.
+fn sample_event(
+    input_queue: &mut VecDeque<TimedEvent>,
+    timeout: Duration,
+    poll: impl FnOnce(Duration) -> io::Result<bool>,
+    read: impl FnOnce() -> io::Result<Event>,
+    now: impl FnOnce() -> Instant,
+) {
+    match poll(timeout) {
+        Ok(true) => {
+            if let Ok(event) = read() {
+                queue_event(
+                    input_queue,
+                    TimedEvent {
+                        event,
+                        timestamp: now(),
+                    },
+                );
+            }
+        }
+        Ok(false) => {}
+        // Falling back to sleeping, so a broken input source doesn't turn the wait into a busy loop
+        Err(_) => sleep(timeout),
+    }
+}
+
+/// Queues up an event, dropping the oldest one when the queue is full.
+#[inline]
+fn queue_event(input_queue: &mut VecDeque<TimedEvent>, timed_event: TimedEvent) {
+    if input_queue.len() >= MAX_QUEUED_EVENTS {
+        input_queue.pop_front();
+    }
+    input_queue.push_back(timed_event);
+}
+
+/// Translates an [`Event`] into a [`MouseInput`].
+///
+/// Returns `None` for non-mouse events.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{crossterm::event::{MouseButton, MouseEventKind}, input::{mouse_input, poll_input}};
+/// for event in poll_input() {
+///     if let Some(mouse) = mouse_input(&event) {
+///         if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
+///             println!("Clicked cell ({}, {})", mouse.x, mouse.y);
+///         }
+///     }
+/// }
+/// ```
+pub fn mouse_input(event: &Event) -> Option<MouseInput> {
+    match event {
+        Event::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers,
+        }) => Some(MouseInput {
+            kind: *kind,
+            x: *column as i16,
+            y: *row as i16,
+            modifiers: *modifiers,
+        }),
+        _ => None,
+    }
+}
+
+/// Returns the mouse position on the twoxel grid, which has 2 rows per terminal row.
+///
+/// To draw at the returned position with [`crate::draw::draw_twoxel`], divide `y` by `2`.
+///
+/// # Example
+/// ```rust
+/// # use germterm::{crossterm::event::{KeyModifiers, MouseEventKind}, input::{MouseInput, mouse_twoxel_position}};
+/// let mouse = MouseInput { kind: MouseEventKind::Moved, x: 3, y: 4, modifiers: KeyModifiers::NONE };
+/// assert_eq!(mouse_twoxel_position(&mouse), (3, 8));
+/// ```
+pub fn mouse_twoxel_position(mouse: &MouseInput) -> (i16, i16) {
+    (mouse.x, mouse.y.saturating_mul(2))
+}
+
+/// Returns the mouse position on the quadrant grid, which has 2 columns and 2 rows per terminal cell.
+///
+/// To draw at the returned position with [`crate::draw::draw_quadrant`], divide `x` and `y` by `2`.
+///
+/// # Example
+/// ```rust
+/// # use germterm::{crossterm::event::{KeyModifiers, MouseEventKind}, input::{MouseInput, mouse_quadrant_position}};
+/// let mouse = MouseInput { kind: MouseEventKind::Moved, x: 3, y: 4, modifiers: KeyModifiers::NONE };
+/// assert_eq!(mouse_quadrant_position(&mouse), (6, 8));
+/// ```
+pub fn mouse_quadrant_position(mouse: &MouseInput) -> (i16, i16) {
+    (mouse.x.saturating_mul(2), mouse.y.saturating_mul(2))
+}
+
+/// Returns the mouse position on the octad grid, which has 2 columns and 4 rows per terminal cell.
+///
+/// To draw at the returned position with [`crate::draw::draw_octad`], divide `x` by `2` and `y` by `4`.
+///
+/// # Example
+/// ```rust
+/// # use germterm::{crossterm::event::{KeyModifiers, MouseEventKind}, input::{MouseInput, mouse_octad_position}};
+/// let mouse = MouseInput { kind: MouseEventKind::Moved, x: 3, y: 4, modifiers: KeyModifiers::NONE };
+/// assert_eq!(mouse_octad_position(&mouse), (6, 16));
+/// ```
+pub fn mouse_octad_position(mouse: &MouseInput) -> (i16, i16) {
+    (mouse.x.saturating_mul(2), mouse.y.saturating_mul(4))
+}
+
+/// The phase of a mouse drag reported by [`update_drag`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DragPhase {
+    /// The mouse moved for the first time while the button was held down.
+    Began,
+    Ongoing,
+    /// The button was released, completing the drag.
+    Ended,
+    /// The drag was aborted with `Esc`, the button release that follows is ignored.
+    Cancelled,
+}
+
+/// A snapshot of a mouse drag in terminal cells.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Drag {
+    pub phase: DragPhase,
+    /// Where the button was pressed.
+    pub start: (i16, i16),
+    pub current: (i16, i16),
+    /// What was under the start point, as resolved by the hit-test passed to [`update_drag`].
+    pub id: Option<usize>,
+}
+
+/// Tracks mouse drags of a single button across input events, see [`update_drag`].
+#[derive(Clone, Debug)]
+pub struct DragState {
+    button: MouseButton,
+    pressed: Option<((i16, i16), Option<usize>)>,
+    active: Option<Drag>,
+}
+
+impl DragState {
+    pub fn new(button: MouseButton) -> Self {
+        Self {
+            button,
+            pressed: None,
+            active: None,
+        }
+    }
+}
+
+/// Feeds an input event into a [`DragState`], returning the drag it affected.
+///
+/// A drag only begins once the mouse moves with the button held down, so plain clicks never turn into drags.
+/// `hit_test` resolves the cell the button was pressed on into an id, eg. the index of the panel under the cursor.
+///
+/// # Example
+/// ```rust
+/// # use germterm::crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+/// # use germterm::input::{DragPhase, DragState, update_drag};
+/// let mouse = |kind, column, row| Event::Mouse(MouseEvent { kind, column, row, modifiers: KeyModifiers::NONE });
+/// let panel_at = |_x: i16, y: i16| Some(y as usize / 4);
+/// let mut drag_state = DragState::new(MouseButton::Left);
+///
+/// assert_eq!(update_drag(&mut drag_state, &mouse(MouseEventKind::Down(MouseButton::Left), 3, 5), panel_at), None);
+/// let drag = update_drag(&mut drag_state, &mouse(MouseEventKind::Drag(MouseButton::Left), 3, 9), panel_at).unwrap();
+/// assert_eq!((drag.phase, drag.start, drag.current, drag.id), (DragPhase::Began, (3, 5), (3, 9), Some(1)));
+///
+/// let escape = Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+/// assert_eq!(update_drag(&mut drag_state, &escape, panel_at).unwrap().phase, DragPhase::Cancelled);
+/// assert_eq!(update_drag(&mut drag_state, &mouse(MouseEventKind::Up(MouseButton::Left), 3, 9), panel_at), None);
+/// ```
+pub fn update_drag(
+    drag_state: &mut DragState,
+    event: &Event,
+    hit_test: impl FnOnce(i16, i16) -> Option<usize>,
+) -> Option<Drag> {
+    if let Event::Key(key_event) = event {
+        if key_event.code == KeyCode::Esc && key_event.kind != KeyEventKind::Release {
+            drag_state.pressed = None;
+            let mut drag: Drag = drag_state.active.take()?;
+            drag.phase = DragPhase::Cancelled;
+            return Some(drag);
+        }
+        return None;
+    }
+
+    let mouse: MouseInput = mouse_input(event)?;
+    let pos: (i16, i16) = (mouse.x, mouse.y);
+
+    match mouse.kind {
+        MouseEventKind::Down(button) if button == drag_state.button => {
+            drag_state.pressed = Some((pos, hit_test(pos.0, pos.1)));
+            drag_state.active = None;
+            None
+        }
+        MouseEventKind::Drag(button) if button == drag_state.button => {
+            if let Some(drag) = &mut drag_state.active {
+                drag.phase = DragPhase::Ongoing;
+                drag.current = pos;
+                return Some(*drag);
+            }
+
+            let (start, id): ((i16, i16), Option<usize>) = drag_state.pressed?;
+            let drag: Drag = Drag {
+                phase: DragPhase::Began,
+                start,
+                current: pos,
+                id,
+            };
+            drag_state.active = Some(drag);
+            Some(drag)
+        }
+        MouseEventKind::Up(button) if button == drag_state.button => {
+            drag_state.pressed = None;
+            let mut drag: Drag = drag_state.active.take()?;
+            drag.phase = DragPhase::Ended;
+            drag.current = pos;
+            Some(drag)
+        }
+        _ => None,
+    }
+}
+
+/// The drag currently in progress, if any.
+pub fn active_drag(drag_state: &DragState) -> Option<Drag> {
+    drag_state.active
+}
+
+/// The direction panels are stacked in for [`drop_target`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DropAxis {
+    /// Panels are stacked top to bottom.
+    Vertical,
+    /// Panels are laid out left to right.
+    Horizontal,
+}
+
+/// Where a dragged panel would be inserted, see [`drop_target`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DropTarget {
+    /// The index the dragged panel would be inserted at, in `0..=panels.len()`.
+    pub index: usize,
+    /// A one cell thick `(x, y, width, height)` line marking the insertion point, for visual feedback.
+    pub highlight: (i16, i16, u16, u16),
+}
+
+/// Computes where a panel dragged to `pos` would be inserted among `panels`.
+///
+/// `panels` are `(x, y, width, height)` rects in their current order along `axis`.
+/// The insertion index is the amount of panels whose center lies before `pos`,
+/// so the index changes as soon as the cursor crosses the middle of a panel.
+///
+/// Returns `None` if there are no panels.
+///
+/// # Example
+/// ```rust
+/// # use germterm::input::{DropAxis, drop_target};
+/// let panels = [(0, 0, 20, 4), (0, 4, 20, 4), (0, 8, 20, 4)];
+///
+/// assert_eq!(drop_target(&panels, DropAxis::Vertical, (5, 1)).unwrap().index, 0);
+/// assert_eq!(drop_target(&panels, DropAxis::Vertical, (5, 2)).unwrap().index, 1);
+/// assert_eq!(drop_target(&panels, DropAxis::Vertical, (5, 7)).unwrap().index, 2);
+/// assert_eq!(drop_target(&panels, DropAxis::Vertical, (5, 30)).unwrap().index, 3);
+///
+/// // Between the first two panels, the highlight is the top row of the second one
+/// assert_eq!(drop_target(&panels, DropAxis::Vertical, (5, 3)).unwrap().highlight, (0, 4, 20, 1));
+/// // Past the end, it's the bottom row of the last one
+/// assert_eq!(drop_target(&panels, DropAxis::Vertical, (5, 30)).unwrap().highlight, (0, 11, 20, 1));
+/// ```
+pub fn drop_target(
+    panels: &[(i16, i16, u16, u16)],
+    axis: DropAxis,
+    pos: (i16, i16),
+) -> Option<DropTarget> {
+    let last_panel: (i16, i16, u16, u16) = *panels.last()?;
+
+    // Comparing cell centers against panel centers, both doubled to stay in integers
+    let index: usize = panels
+        .iter()
+        .filter(|&&(x, y, width, height)| match axis {
+            DropAxis::Vertical => pos.1 as i32 * 2 + 1 > y as i32 * 2 + height as i32,
+            DropAxis::Horizontal => pos.0 as i32 * 2 + 1 > x as i32 * 2 + width as i32,
+        })
+        .count();
+
+    let highlight: (i16, i16, u16, u16) = match panels.get(index) {
+        Some(&(x, y, width, height)) => match axis {
+            DropAxis::Vertical => (x, y, width, 1.min(height)),
+            DropAxis::Horizontal => (x, y, 1.min(width), height),
+        },
+        None => {
+            let (x, y, width, height): (i16, i16, u16, u16) = last_panel;
+            match axis {
+                DropAxis::Vertical => (
+                    x,
+                    y.saturating_add(height.saturating_sub(1) as i16),
+                    width,
+                    1.min(height),
+                ),
+                DropAxis::Horizontal => (
+                    x.saturating_add(width.saturating_sub(1) as i16),
+                    y,
+                    1.min(width),
+                    height,
+                ),
+            }
+        }
+    };
+
+    Some(DropTarget { index, highlight })
+}
+
+/// Per-key hold state, updated from key events and advanced once per frame.
+pub(crate) struct KeyState {
+    /// Held keys along with when they stop being held, unless refreshed by a press or repeat.
+    held: HashMap<KeyCode, Instant>,
+    just_pressed: Vec<KeyCode>,
+    just_released: Vec<KeyCode>,
+    modifiers: KeyModifiers,
+    pub(crate) repeat_timeout: Duration,
+    /// Set once the terminal reported a key release, after which holds no longer time out.
+    pub(crate) reports_releases: bool,
+}
+
+impl KeyState {
+    pub fn new(repeat_timeout: Duration) -> Self {
+        Self {
+            held: HashMap::new(),
+            just_pressed: Vec::new(),
+            just_released: Vec::new(),
+            modifiers: KeyModifiers::NONE,
+            repeat_timeout,
+            reports_releases: false,
+        }
+    }
+}
+
+/// Whether a key is currently held down.
+pub fn is_key_held(engine: &Engine, code: KeyCode) -> bool {
+    engine.key_state.held.contains_key(&code)
+}
+
+/// Whether a key got pressed this frame.
+///
+/// Key repeats don't count. Without key release reporting, repeats look just like presses,
+/// so quickly pressing the same key again within the repeat timeout doesn't count either.
+pub fn is_key_just_pressed(engine: &Engine, code: KeyCode) -> bool {
+    engine.key_state.just_pressed.contains(&code)
+}
+
+/// Whether a key got released this frame, or its hold timed out at the end of the previous frame.
+pub fn is_key_just_released(engine: &Engine, code: KeyCode) -> bool {
+    engine.key_state.just_released.contains(&code)
+}
+
+/// The modifiers of the most recent key event while any key is held, otherwise [`KeyModifiers::NONE`].
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{crossterm::event::{KeyCode, KeyModifiers}, engine::Engine};
+/// # use germterm::input::{is_key_held, key_modifiers};
+/// let engine = Engine::new(40, 20);
+/// let sprint: bool = is_key_held(&engine, KeyCode::Char('W'))
+///     || key_modifiers(&engine).contains(KeyModifiers::SHIFT);
+/// ```
+pub fn key_modifiers(engine: &Engine) -> KeyModifiers {
+    if engine.key_state.held.is_empty() {
+        KeyModifiers::NONE
+    } else {
+        engine.key_state.modifiers
+    }
+}
+
+pub(crate) fn update_key_state(key_state: &mut KeyState, event: &Event, now: Instant) {
+    if let Event::FocusLost = event {
+        key_state
+            .just_released
+            .extend(key_state.held.drain().map(|(code, _)| code));
+        key_state.modifiers = KeyModifiers::NONE;
+        return;
+    }
+
+    let Event::Key(KeyEvent {
+        code,
+        modifiers,
+        kind,
+        ..
+    }) = event
+    else {
+        return;
+    };
+    key_state.modifiers = *modifiers;
+
+    match kind {
+        KeyEventKind::Press | KeyEventKind::Repeat => {
+            let release_at: Instant = now.checked_add(key_state.repeat_timeout).unwrap_or(now);
+            let was_held: bool = key_state.held.insert(*code, release_at).is_some();
+            if !was_held && !key_state.just_pressed.contains(code) {
+                key_state.just_pressed.push(*code);
+            }
+        }
+        KeyEventKind::Release => {
+            key_state.reports_releases = true;
+            if key_state.held.remove(code).is_some() {
+                key_state.just_released.push(*code);
+            }
+        }
+    }
+}
+
+/// Clears the per-frame state and releases keys whose hold timed out, called at the end of every frame.
+pub(crate) fn advance_key_state(key_state: &mut KeyState, now: Instant) {
+    key_state.just_pressed.clear();
+    key_state.just_released.clear();
+
+    if key_state.reports_releases {
+        return;
+    }
+
+    let just_released: &mut Vec<KeyCode> = &mut key_state.just_released;
+    key_state.held.retain(|code, release_at| {
+        let is_held: bool = *release_at > now;
+        if !is_held {
+            just_released.push(*code);
+        }
+        is_held
+    });
+}
+
+pub(crate) fn reset_key_state(key_state: &mut KeyState) {
+    key_state.held.clear();
+    key_state.just_pressed.clear();
+    key_state.just_released.clear();
+    key_state.modifiers = KeyModifiers::NONE;
+    key_state.reports_releases = false;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// A terminal with events arriving at scripted instants, on a clock which only moves forward while polled.
+    struct ScriptedTerminal {
+        clock: Instant,
+        arrivals: VecDeque<(Instant, Event)>,
+    }
+
+    impl ScriptedTerminal {
+        fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+            match self.arrivals.front() {
+                Some(&(arrival, _)) if arrival <= self.clock + timeout => {
+                    self.clock = self.clock.max(arrival);
+                    Ok(true)
+                }
+                _ => {
+                    self.clock += timeout;
+                    Ok(false)
+                }
+            }
+        }
+
+        fn read(&mut self) -> io::Result<Event> {
+            let (_, event) = self.arrivals.pop_front().unwrap();
+            Ok(event)
+        }
+    }
+
+    /// Samples the scripted terminal like the FPS limiter does while waiting for a frame ending at `frame_end`.
+    fn sample_frame(
+        input_queue: &mut VecDeque<TimedEvent>,
+        terminal: &RefCell<ScriptedTerminal>,
+        frame_end: Instant,
+        poll_interval: Duration,
+    ) {
+        loop {
+            let clock: Instant = terminal.borrow().clock;
+            if clock >= frame_end {
+                break;
+            }
+            sample_event(
+                input_queue,
+                frame_end.duration_since(clock).min(poll_interval),
+                |timeout| terminal.borrow_mut().poll(timeout),
+                || terminal.borrow_mut().read(),
+                || terminal.borrow().clock,
+            );
+        }
+    }
+
+    #[test]
+    fn high_resolution_sampling_timestamps_every_event_within_the_poll_interval() {
+        let start: Instant = Instant::now();
+        let ms = |millis: u64| start + Duration::from_millis(millis);
+        let frame_end: Instant = ms(16);
+        let poll_interval: Duration = Duration::from_millis(1);
+
+        // A few spread out key presses, and a burst of resizes arriving all at once
+        let mut arrivals: Vec<(Instant, Event)> = [2, 7, 13]
+            .into_iter()
+            .map(|millis| (ms(millis), Event::Key(KeyEvent::from(KeyCode::Char('a')))))
+            .collect();
+        arrivals.splice(
+            2..2,
+            (0..50).map(|i| (ms(9) + Duration::from_micros(300), Event::Resize(i, 0))),
+        );
+        let terminal: RefCell<ScriptedTerminal> = RefCell::new(ScriptedTerminal {
+            clock: start,
+            arrivals: arrivals.iter().cloned().collect(),
+        });
+
+        let mut engine: Engine = Engine::remote(1, 1, Vec::new());
+        sample_frame(&mut engine.input_queue, &terminal, frame_end, poll_interval);
+        let sampled: Vec<TimedEvent> = poll_timed_input(&mut engine).collect();
+
+        // Nothing lost or reordered, which per-frame sampling would have drained at `frame_end` instead
+        let events: Vec<&Event> = sampled.iter().map(|timed| &timed.event).collect();
+        let expected_events: Vec<&Event> = arrivals.iter().map(|(_, event)| event).collect();
+        assert_eq!(events, expected_events);
+        for (timed_event, &(arrival, _)) in sampled.iter().zip(&arrivals) {
+            let error: Duration = timed_event.timestamp.duration_since(arrival);
+            assert!(error < poll_interval, "{error:?} late");
+        }
+        assert!(sampled.is_sorted_by_key(|timed_event| timed_event.timestamp));
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    /// Pushes `events` into a remote engine and feeds the drained ones into a left button [`DragState`],
+    /// hit-testing panels 4 rows tall. Returns the drag reported for each event, and the one active afterwards.
+    fn drags(events: &[Event]) -> (Vec<Option<Drag>>, Option<Drag>) {
+        let mut engine: Engine = Engine::remote(20, 12, Vec::new());
+        for event in events {
+            push_input(&mut engine, event.clone());
+        }
+
+        let mut drag_state: DragState = DragState::new(MouseButton::Left);
+        let reported: Vec<Option<Drag>> = poll_tracked_input(&mut engine)
+            .map(|event| update_drag(&mut drag_state, &event, |_, y| Some(y as usize / 4)))
+            .collect();
+        (reported, active_drag(&drag_state))
+    }
+
+    const DOWN: MouseEventKind = MouseEventKind::Down(MouseButton::Left);
+    const DRAG: MouseEventKind = MouseEventKind::Drag(MouseButton::Left);
+    const UP: MouseEventKind = MouseEventKind::Up(MouseButton::Left);
+
+    #[test]
+    fn drags_begin_once_moved_and_end_on_release() {
+        let (reported, active) = drags(&[mouse(DOWN, 3, 5), mouse(DRAG, 3, 9)]);
+        let began: Drag = Drag {
+            phase: DragPhase::Began,
+            start: (3, 5),
+            current: (3, 9),
+            id: Some(1),
+        };
+        assert_eq!(reported, [None, Some(began)]);
+        assert_eq!(active, Some(began));
+
+        let (reported, active) = drags(&[
+            mouse(DOWN, 3, 5),
+            mouse(DRAG, 3, 9),
+            mouse(MouseEventKind::Drag(MouseButton::Right), 3, 10),
+            mouse(DRAG, 4, 10),
+            mouse(UP, 4, 11),
+        ]);
+        let phases: Vec<Option<(DragPhase, (i16, i16))>> = reported
+            .iter()
+            .map(|drag| drag.map(|drag| (drag.phase, drag.current)))
+            .collect();
+        assert_eq!(
+            phases,
+            [
+                None,
+                Some((DragPhase::Began, (3, 9))),
+                None,
+                Some((DragPhase::Ongoing, (4, 10))),
+                Some((DragPhase::Ended, (4, 11))),
+            ]
+        );
+        assert_eq!(active, None);
+
+        // Plain clicks never turn into drags
+        let (reported, active) = drags(&[mouse(DOWN, 3, 5), mouse(UP, 3, 5)]);
+        assert_eq!((reported, active), (vec![None, None], None));
+    }
+
+    #[test]
+    fn escape_cancels_the_drag_and_its_release() {
+        let escape: Event = Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        let (reported, active) = drags(&[
+            mouse(DOWN, 3, 5),
+            mouse(DRAG, 3, 9),
+            escape.clone(),
+            mouse(DRAG, 3, 10),
+            mouse(UP, 3, 10),
+            escape,
+        ]);
+
+        let cancelled: Drag = Drag {
+            phase: DragPhase::Cancelled,
+            start: (3, 5),
+            current: (3, 9),
+            id: Some(1),
+        };
+        assert_eq!(reported[2], Some(cancelled));
+        // Neither moving on nor releasing the button picks the drag back up
+        assert_eq!(reported[3..], [None, None, None]);
+        assert_eq!(active, None);
+    }
+
+    #[test]
+    fn completed_drags_drop_at_the_insertion_index() {
+        let panels: [(i16, i16, u16, u16); 3] = [(0, 0, 20, 4), (0, 4, 20, 4), (0, 8, 20, 4)];
+        let drop_index = |row: u16| {
+            let (reported, _) = drags(&[mouse(DOWN, 5, 1), mouse(DRAG, 5, 2), mouse(UP, 5, row)]);
+            let drag: Drag = reported[2].unwrap();
+            assert_eq!((drag.phase, drag.id), (DragPhase::Ended, Some(0)));
+            drop_target(&panels, DropAxis::Vertical, drag.current)
+                .unwrap()
+                .index
+        };
+
+        // The index changes once the cursor crosses the middle of a panel
+        assert_eq!(drop_index(0), 0);
+        assert_eq!(drop_index(6), 2);
+        assert_eq!(drop_index(9), 2);
+        assert_eq!(drop_index(10), 3);
+        assert_eq!(drop_index(11), 3);
+    }
+
+    #[test]
+    fn undrained_event_queue_is_capped() {
+        let mut input_queue: VecDeque<TimedEvent> = VecDeque::new();
+        for i in 0..MAX_QUEUED_EVENTS + 10 {
+            queue_event(
+                &mut input_queue,
+                TimedEvent {
+                    event: Event::Resize(i as u16, 0),
+                    timestamp: Instant::now(),
+                },
+            );
+        }
+
+        assert_eq!(input_queue.len(), MAX_QUEUED_EVENTS);
+        assert_eq!(input_queue[0].event, Event::Resize(10, 0));
+    }
+}
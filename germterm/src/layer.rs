@@ -1,18 +1,244 @@
-use crate::{engine::Engine, frame::DrawCall};
+use crate::{color::ColorEffect, engine::Engine, frame::DrawCall};
 
 pub fn create_layer(engine: &mut Engine, index: usize) -> LayerIndex {
     engine.max_layer_index = engine.max_layer_index.max(index);
     LayerIndex(index)
 }
 
+/// Sets how a layer is merged onto the layers beneath it, [`LayerBlendMode::Normal`] by default.
+///
+/// Useful for eg. a lighting layer, where light and shadow colors are drawn with [`crate::draw::draw_rect`]
+/// and multiplied onto the scene instead of covering it.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{engine::Engine, layer::{LayerBlendMode, create_layer, set_layer_blend_mode}};
+/// let mut engine = Engine::new(40, 20);
+/// let world_layer = create_layer(&mut engine, 0);
+/// let lighting_layer = create_layer(&mut engine, 1);
+/// set_layer_blend_mode(&mut engine, lighting_layer, LayerBlendMode::Multiply);
+/// ```
+pub fn set_layer_blend_mode(engine: &mut Engine, layer_index: LayerIndex, mode: LayerBlendMode) {
+    layer_mut(engine, layer_index).blend_mode = mode;
+}
+
+/// Sets whether a layer is composed, visible by default.
+///
+/// Hidden layers still accept draw calls, which are dropped at the end of the frame,
+/// so drawing code doesn't need to check for visibility. Useful for toggling eg. debug overlays.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{engine::Engine, layer::{create_layer, set_layer_visible}};
+/// let mut engine = Engine::new(40, 20);
+/// let debug_layer = create_layer(&mut engine, 5);
+/// set_layer_visible(&mut engine, debug_layer, false);
+/// ```
+pub fn set_layer_visible(engine: &mut Engine, layer_index: LayerIndex, visible: bool) {
+    layer_mut(engine, layer_index).is_visible = visible;
+}
+
+/// Sets the opacity of a layer, `255` (fully opaque) by default.
+///
+/// The opacity is multiplied into the alpha of the `fg` and `bg` colors of every draw call on the layer,
+/// before they're composed onto the layers beneath. Draw calls on the same layer are composed onto each other
+/// with the reduced alpha too, so overlapping translucent draws show through one another.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{engine::Engine, layer::{create_layer, set_layer_opacity}};
+/// let mut engine = Engine::new(40, 20);
+/// let menu_layer = create_layer(&mut engine, 2);
+///
+/// // Fading the menu in over half a second
+/// let fade_in: f32 = (engine.game_time / 0.5).min(1.0);
+/// set_layer_opacity(&mut engine, menu_layer, (fade_in * 255.0) as u8);
+/// ```
+pub fn set_layer_opacity(engine: &mut Engine, layer_index: LayerIndex, opacity: u8) {
+    layer_mut(engine, layer_index).opacity = opacity;
+}
+
+/// Moves a layer to another index in the composition order, the index it was created with by default.
+///
+/// The handle stays valid, as only the order changes. Layers ending up with the same index
+/// are composed in the order of the indices they were created with.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{engine::Engine, layer::{create_layer, set_layer_index}};
+/// let mut engine = Engine::new(40, 20);
+/// let left_window = create_layer(&mut engine, 1);
+/// let right_window = create_layer(&mut engine, 2);
+///
+/// // Brings the left window to the front
+/// set_layer_index(&mut engine, left_window, 3);
+/// ```
+pub fn set_layer_index(engine: &mut Engine, layer_index: LayerIndex, new_index: usize) {
+    layer_mut(engine, layer_index).order = Some(new_index);
+}
+
+/// Sets the camera offset of a layer, `(0, 0)` by default.
+///
+/// Everything drawn on the layer is shifted by `offset` cells, so drawing can happen in world coordinates
+/// while the camera scrolls through the world. A call drawn at world `(x, y)` lands on screen at `(x + offset.0, y + offset.1)`.
+/// The offset is applied when the frame is composed, and draw calls landing entirely outside of the screen
+/// are dropped right before that, so off-screen parts of a world are never composed.
+///
+/// The offset is whole cells, shared by cells and sub-cell primitives alike, so all of them scroll in lockstep.
+/// Camera positions tracked with `f32` should be snapped using `floor`, the same way sub-cell positions are snapped to cells,
+/// so the world doesn't jitter against things positioned relative to the camera.
+///
+/// As the offset in effect at the end of the frame applies to everything drawn on the layer during it, the camera can
+/// be moved after drawing. Shapes like rects, lines and ellipses are clipped to the area visible through the camera
+/// as they're drawn though, so the camera should be set before drawing those.
+/// The offset also applies to the positions read by [`crate::draw::get_composed_cell`] and the flood fills.
+/// Areas read from the screen itself, like the region of [`crate::draw::draw_drag_ghost`], are unaffected.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_twoxel, engine::Engine, layer::{create_layer, set_layer_camera}, color::Color};
+/// let mut engine = Engine::new(40, 20);
+/// let world_layer = create_layer(&mut engine, 0);
+///
+/// let player: (f32, f32) = (100.0, 100.0);
+/// let camera: (i16, i16) = ((player.0 - 20.0).floor() as i16, (player.1 - 10.0).floor() as i16);
+/// set_layer_camera(&mut engine, world_layer, (-camera.0, -camera.1));
+///
+/// // Lands in the middle of the screen
+/// draw_twoxel(&mut engine, world_layer, player.0, player.1, Color::GREEN);
+/// ```
+pub fn set_layer_camera(engine: &mut Engine, layer_index: LayerIndex, offset: (i16, i16)) {
+    layer_mut(engine, layer_index).camera = offset;
+}
+
+/// Sets a [`ColorEffect`] applied to everything composed up to and including the layer, `None` by default.
+///
+/// The effect runs on the composed cells right after the layer is composed, so layers above stay unaffected.
+/// Useful for eg. dimming the game behind a pause menu drawn on a higher layer.
+/// Cells without a background color get the default blending color with the effect applied as their background.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{color::ColorEffect, engine::Engine, layer::{create_layer, set_layer_effect}};
+/// let mut engine = Engine::new(40, 20);
+/// let game_layer = create_layer(&mut engine, 0);
+/// let menu_layer = create_layer(&mut engine, 1);
+///
+/// let is_paused: bool = true;
+/// set_layer_effect(&mut engine, game_layer, is_paused.then_some(ColorEffect::Dim(0.4)));
+/// ```
+pub fn set_layer_effect(engine: &mut Engine, layer_index: LayerIndex, effect: Option<ColorEffect>) {
+    layer_mut(engine, layer_index).effect = effect;
+}
+
+/// Sets how the colors of octads drawn on a layer are merged with octads already in the cell,
+/// [`OctadColorMode::LastWins`] by default.
+///
+/// Useful for eg. plotting several data series into the same chart, where the series would otherwise
+/// keep taking over each other's cells.
+///
+/// # Example
+/// ```rust
+/// # use germterm::{color::Color, draw::{draw_octad, get_composed_octad}, engine::Engine};
+/// # use germterm::layer::{OctadColorMode, create_layer, set_octad_color_mode};
+/// let mut engine = Engine::offscreen(40, 20);
+/// let chart_layer = create_layer(&mut engine, 0);
+/// set_octad_color_mode(&mut engine, chart_layer, OctadColorMode::Dominant);
+///
+/// // Three red dots outnumber the blue one, which is turned off and shown as the bg instead
+/// draw_octad(&mut engine, chart_layer, 0.0, 0.0, Color::RED);
+/// draw_octad(&mut engine, chart_layer, 0.5, 0.0, Color::BLUE);
+/// draw_octad(&mut engine, chart_layer, 0.0, 0.25, Color::RED);
+/// draw_octad(&mut engine, chart_layer, 0.0, 0.5, Color::RED);
+/// assert!(get_composed_octad(&engine, chart_layer, 0.0, 0.0) == Some(Color::RED));
+/// assert!(get_composed_octad(&engine, chart_layer, 0.5, 0.0) == None);
+///
+/// set_octad_color_mode(&mut engine, chart_layer, OctadColorMode::Average);
+/// assert!(get_composed_octad(&engine, chart_layer, 0.0, 0.0) == Some(Color::new(191, 0, 64, 255)));
+/// ```
+pub fn set_octad_color_mode(engine: &mut Engine, layer_index: LayerIndex, mode: OctadColorMode) {
+    layer_mut(engine, layer_index).octad_color_mode = mode;
+}
+
+pub(crate) fn layer_camera(engine: &Engine, layer_index: LayerIndex) -> (i16, i16) {
+    engine
+        .frame
+        .layered_draw_queue
+        .get(layer_index.0)
+        .map_or((0, 0), |layer| layer.camera)
+}
+
+fn layer_mut(engine: &mut Engine, layer_index: LayerIndex) -> &mut Layer {
+    let layers: &mut Vec<Layer> = &mut engine.frame.layered_draw_queue;
+    if layers.len() <= layer_index.0 {
+        layers.resize_with(layer_index.0 + 1, Layer::new);
+    }
+    &mut layers[layer_index.0]
+}
+
+/// How the cells of a layer are merged onto the accumulated result of the layers beneath it.
+///
+/// For every mode other than [`LayerBlendMode::Normal`], the layer is first composed on its own.
+/// The bg color of each of its cells is then blended onto both the fg and bg colors beneath,
+/// using its alpha as the strength of the effect. Characters and formats beneath are left intact,
+/// unless the layer has a character of its own in that cell, which is drawn on top as usual.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum LayerBlendMode {
+    /// Regular alpha compositing.
+    #[default]
+    Normal,
+    /// Darkens, white leaves colors unchanged. Useful for shadows and night tints.
+    Multiply,
+    /// Brightens, black leaves colors unchanged. Useful for lights and glows.
+    Screen,
+    /// Multiplies dark colors and screens bright ones, increasing contrast.
+    Overlay,
+}
+
+/// How the colors of a cell are picked when octads of different colors merge in it,
+/// see [`set_octad_color_mode`].
+///
+/// Every dot drawn on the layer counts towards the color it was drawn with, for as long as the layer keeps drawing octads
+/// into the cell. Dots which were already in the cell, eg. from the layers beneath, count towards the color they're displayed in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum OctadColorMode {
+    /// The merged cluster takes the color of the last drawn octads.
+    #[default]
+    LastWins,
+    /// The color with the most dots in the cell becomes the `fg`, the last drawn one on a tie.
+    /// The color with the second most dots becomes the `bg`, with its dots and those of any other color turned off.
+    Dominant,
+    /// The merged cluster takes the average of the colors of all its dots.
+    Average,
+}
+
 #[derive(Copy, Clone)]
 pub struct LayerIndex(pub(crate) usize);
 
-pub struct Layer(pub(crate) Vec<DrawCall>);
+pub struct Layer {
+    pub(crate) draw_calls: Vec<DrawCall>,
+    pub(crate) blend_mode: LayerBlendMode,
+    pub(crate) is_visible: bool,
+    pub(crate) opacity: u8,
+    /// The index the layer is composed at, when changed from the index it was created with.
+    pub(crate) order: Option<usize>,
+    pub(crate) camera: (i16, i16),
+    pub(crate) effect: Option<ColorEffect>,
+    pub(crate) octad_color_mode: OctadColorMode,
+}
 
 impl Layer {
     pub const fn new() -> Self {
-        Layer(Vec::new())
+        Layer {
+            draw_calls: Vec::new(),
+            blend_mode: LayerBlendMode::Normal,
+            is_visible: true,
+            opacity: u8::MAX,
+            order: None,
+            camera: (0, 0),
+            effect: None,
+            octad_color_mode: OctadColorMode::LastWins,
+        }
     }
 }
 
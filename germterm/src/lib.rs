@@ -2,14 +2,38 @@
 
 pub use crossterm;
 
+pub mod adaptive_quality;
+pub mod anim;
+pub mod asset;
+pub mod bell;
+pub mod cast;
 pub mod cell;
 pub mod color;
+pub mod cursor;
 pub mod draw;
+pub mod effects;
 pub mod engine;
+pub mod floating_text;
+mod font;
 pub mod fps_counter;
 pub mod fps_limiter;
 pub mod frame;
+pub mod framebuffer;
+mod grapheme;
+pub mod hex;
+pub mod idle;
+pub mod inline;
 pub mod input;
 pub mod layer;
+pub mod link;
+pub mod noise;
 pub mod particle;
+pub mod render_target;
+pub mod replay;
 pub mod rich_text;
+pub mod screenshot;
+pub mod sprite;
+#[cfg(test)]
+mod test_support;
+pub mod theme;
+mod width;
@@ -0,0 +1,84 @@
+//! Clickable hyperlinks.
+//!
+//! Text drawn with [`crate::rich_text::RichText::with_link`] is written to the terminal wrapped in OSC 8 sequences,
+//! which modern terminals turn into clickable links. Terminals without support simply show the plain text.
+//!
+//! To keep [`Cell`](crate::cell::Cell) small and `Copy`, link urls are interned,
+//! and cells only store the [`LinkId`] which maps back to the full url.
+//!
+//! Interned urls stay around for the rest of the program, as any cell may still refer to them.
+//! Up to [`MAX_LINKS`] distinct urls can be interned, past that [`RichText::with_link`](crate::rich_text::RichText::with_link)
+//! returns a [`LinkLimitError`].
+
+use std::{
+    collections::HashMap,
+    fmt,
+    num::NonZeroU16,
+    sync::{Arc, LazyLock, Mutex},
+};
+
+/// The amount of distinct urls that can be interned, bounded by the size of [`LinkId`].
+pub const MAX_LINKS: usize = u16::MAX as usize;
+
+/// An interned hyperlink url, see the [module docs](self).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LinkId(NonZeroU16);
+
+impl LinkId {
+    /// The url the link points to.
+    pub fn url(self) -> Arc<str> {
+        let interner = INTERNER
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        Arc::clone(&interner.urls[self.0.get() as usize - 1])
+    }
+}
+
+/// Returned when a new url is linked after [`MAX_LINKS`] distinct urls have already been interned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LinkLimitError;
+
+impl fmt::Display for LinkLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "more than {MAX_LINKS} distinct link urls were interned")
+    }
+}
+
+impl std::error::Error for LinkLimitError {}
+
+struct Interner {
+    urls: Vec<Arc<str>>,
+    lookup: HashMap<Arc<str>, LinkId>,
+}
+
+static INTERNER: LazyLock<Mutex<Interner>> = LazyLock::new(|| {
+    Mutex::new(Interner {
+        urls: Vec::new(),
+        lookup: HashMap::new(),
+    })
+});
+
+/// Interns a url, returning the id it was already interned with if there is one.
+///
+/// Control characters are dropped from the url, as they would break out of the OSC 8 sequence.
+pub(crate) fn intern_link(url: &str) -> Result<LinkId, LinkLimitError> {
+    let url: String = url.chars().filter(|ch| !ch.is_control()).collect();
+
+    let mut interner = INTERNER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(&link) = interner.lookup.get(url.as_str()) {
+        return Ok(link);
+    }
+
+    let id: NonZeroU16 = u16::try_from(interner.urls.len() + 1)
+        .ok()
+        .and_then(NonZeroU16::new)
+        .ok_or(LinkLimitError)?;
+    let link: LinkId = LinkId(id);
+
+    let url: Arc<str> = Arc::from(url);
+    interner.urls.push(Arc::clone(&url));
+    interner.lookup.insert(url, link);
+    Ok(link)
+}
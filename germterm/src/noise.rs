@@ -0,0 +1,162 @@
+//! Deterministic hashing and noise functions for procedural effects.
+//!
+//! All functions in this module are pure and only use `core` arithmetic,
+//! so the same inputs produce the same outputs on every platform.
+//!
+//! The outputs are considered part of the public API and will not change between versions,
+//! anything derived from them (eg. a generated level or a replay) can be safely reproduced from a seed.
+//!
+//! ## Functions
+//!
+//! - [`hash2`] hashes a 2D integer position into a pseudo-random `u32`.
+//! - [`value_noise_2d`] smoothly interpolates hashed values on an integer lattice.
+//! - [`fbm`] sums multiple octaves of [`value_noise_2d`] for more natural looking detail.
+//! - [`perlin1`] is 1D gradient noise, useful for animating wobbles and flickers.
+
+/// Hashes a 2D integer position and a seed into a pseudo-random `u32`.
+///
+/// The algorithm is fixed and will not change between versions:
+/// 1. The inputs are combined as `x * 0x27d4eb2d ^ y * 0x165667b1 ^ seed * 0x9e3779b9 ^ 0x2545f491` (wrapping, on `u32`).
+/// 2. The result is passed through the `lowbias32` integer finalizer by Chris Wellons:
+///    `h ^= h >> 16; h *= 0x7feb352d; h ^= h >> 15; h *= 0x846ca68b; h ^= h >> 16`.
+///
+/// # Example
+/// ```rust
+/// # use germterm::noise::hash2;
+/// assert_eq!(hash2(0, 0, 0), 2409258929);
+/// assert_eq!(hash2(1, 2, 3), 73958399);
+/// assert_eq!(hash2(-7, 12, 42), 3284919244);
+/// ```
+pub fn hash2(x: i32, y: i32, seed: u32) -> u32 {
+    let mut h: u32 = (x as u32).wrapping_mul(0x27d4eb2d)
+        ^ (y as u32).wrapping_mul(0x165667b1)
+        ^ seed.wrapping_mul(0x9e3779b9)
+        ^ 0x2545f491;
+
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x7feb352d);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x846ca68b);
+    h ^= h >> 16;
+    h
+}
+
+/// 2D value noise in the range `0.0..1.0`.
+///
+/// Random values are assigned to every integer lattice point using [`hash2`]
+/// and smoothly interpolated in between, meaning the noise has features roughly `1.0` units apart.
+/// Scale the inputs to change the feature size.
+///
+/// # Example
+/// ```rust
+/// # use germterm::noise::value_noise_2d;
+/// assert_eq!(value_noise_2d(3.25, 1.5, 7), 0.5997991);
+/// assert_eq!(value_noise_2d(-0.75, 2.0, 0), 0.2157519);
+/// ```
+pub fn value_noise_2d(x: f32, y: f32, seed: u32) -> f32 {
+    let cell_x: i32 = floor_to_i32(x);
+    let cell_y: i32 = floor_to_i32(y);
+    let tx: f32 = smoothstep(x - cell_x as f32);
+    let ty: f32 = smoothstep(y - cell_y as f32);
+
+    let top_left: f32 = lattice_value(cell_x, cell_y, seed);
+    let top_right: f32 = lattice_value(cell_x.wrapping_add(1), cell_y, seed);
+    let bottom_left: f32 = lattice_value(cell_x, cell_y.wrapping_add(1), seed);
+    let bottom_right: f32 = lattice_value(cell_x.wrapping_add(1), cell_y.wrapping_add(1), seed);
+
+    let top: f32 = lerp(top_left, top_right, tx);
+    let bottom: f32 = lerp(bottom_left, bottom_right, tx);
+    lerp(top, bottom, ty)
+}
+
+/// Fractal Brownian motion built from `octaves` layers of [`value_noise_2d`], in the range `0.0..1.0`.
+///
+/// Each octave multiplies the frequency by `lacunarity` and the amplitude by `gain`.
+/// Common values are a `lacunarity` of `2.0` and a `gain` of `0.5`.
+///
+/// Returns `0.0` when `octaves` is `0`.
+///
+/// # Example
+/// ```rust
+/// # use germterm::noise::fbm;
+/// assert_eq!(fbm(0.5, 0.4, 5, 2.0, 0.5, 1234), 0.34782407);
+/// ```
+pub fn fbm(x: f32, y: f32, octaves: u32, lacunarity: f32, gain: f32, seed: u32) -> f32 {
+    let mut sum: f32 = 0.0;
+    let mut amplitude_sum: f32 = 0.0;
+    let mut amplitude: f32 = 1.0;
+    let mut frequency: f32 = 1.0;
+
+    for octave in 0..octaves {
+        // Offsetting the seed per octave, so the octaves don't line up at the origin
+        let octave_seed: u32 = seed.wrapping_add(octave.wrapping_mul(0x632be5ab));
+        sum += value_noise_2d(x * frequency, y * frequency, octave_seed) * amplitude;
+        amplitude_sum += amplitude;
+        amplitude *= gain;
+        frequency *= lacunarity;
+    }
+
+    if amplitude_sum > 0.0 {
+        sum / amplitude_sum
+    } else {
+        0.0
+    }
+}
+
+/// 1D gradient (Perlin) noise in the range `-1.0..=1.0`.
+///
+/// The noise is `0.0` at every integer, with a random slope there,
+/// making it well suited for organic looking wobbles when sampled over time.
+///
+/// # Example
+/// ```rust
+/// # use germterm::noise::perlin1;
+/// assert_eq!(perlin1(3.0, 99), 0.0);
+/// assert_eq!(perlin1(3.4, 99), -0.25430012);
+/// assert_eq!(perlin1(-1.25, 0), -0.29648656);
+/// ```
+pub fn perlin1(x: f32, seed: u32) -> f32 {
+    let cell: i32 = floor_to_i32(x);
+    let t: f32 = x - cell as f32;
+
+    let gradient_a: f32 = lattice_value(cell, 0, seed) * 2.0 - 1.0;
+    let gradient_b: f32 = lattice_value(cell.wrapping_add(1), 0, seed) * 2.0 - 1.0;
+
+    let a: f32 = gradient_a * t;
+    let b: f32 = gradient_b * (t - 1.0);
+
+    // Scaling into -1.0..=1.0, the unscaled extremes are ±0.5
+    (lerp(a, b, quintic_fade(t)) * 2.0).clamp(-1.0, 1.0)
+}
+
+/// Maps a lattice point to a value in `0.0..1.0`, using 24 bits so the conversion to `f32` is exact.
+#[inline]
+fn lattice_value(x: i32, y: i32, seed: u32) -> f32 {
+    (hash2(x, y, seed) >> 8) as f32 / (1u32 << 24) as f32
+}
+
+/// `f32::floor` isn't available in `core`, so flooring is done through integer truncation.
+#[inline]
+fn floor_to_i32(value: f32) -> i32 {
+    let truncated: i32 = value as i32;
+    if (truncated as f32) > value {
+        truncated - 1
+    } else {
+        truncated
+    }
+}
+
+#[inline]
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+#[inline]
+fn smoothstep(t: f32) -> f32 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+#[inline]
+fn quintic_fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
@@ -1,35 +1,148 @@
 //! Octad-based particle system.
 //!
-//! This module provides a way of spawning particles using the [`spawn_particles`] function.
-//! Particles are automatically updated and drawn at the end of the frame.
+//! This module provides a way of spawning particles using the [`spawn_particles`] function,
+//! or continuously over time using emitters created with [`create_emitter`].
+//! Particles and emitters are automatically updated, and particles drawn, at the end of the frame.
 //!
 //! The particles and their behaviors can be customized using [`ParticleSpec`] and [`ParticleEmitter`].
 //! The system uses approximated velocity, gravity and drag calculations.
 //!
+//! When [`crate::adaptive_quality`] is enabled, spawned particle counts are scaled down under sustained frame overruns.
+//!
 //! ## Notes
 //! Particles are always drawn at the end of the frame. This means they'll always be drawn last on the specified layer.
 //! If you wish to spawn particles underneath other drawn elements, you can create a new layer with a lower index and draw to it.
 
 use std::{f32::consts::PI, ops::RangeInclusive};
 
-use rand::{Rng, rngs::ThreadRng};
+use rand::{
+    Rng, SeedableRng,
+    rngs::{StdRng, ThreadRng},
+};
 
 use crate::{
+    adaptive_quality::{scale_particle_count, scale_particle_lifetime},
     color::{Color, ColorGradient, sample_gradient},
-    draw::draw_octad,
+    draw::draw_octad_with_priority,
     engine::Engine,
     layer::LayerIndex,
 };
 
+/// The shape particles are emitted in.
+///
+/// More shapes may be added in the future, so matches on it need a wildcard arm.
+#[non_exhaustive]
 pub enum ParticleEmitterShape {
+    /// Particles are emitted from a single point in all directions.
     Circle,
+    /// Particles are emitted from a single point within a cone centered around `direction_deg`.
     Cone { direction_deg: f32, width_deg: f32 },
+    /// Particles are emitted from random points along a line `length` cells long, centered at the spawn position
+    /// and rotated by `angle_deg`. Every particle moves perpendicular to the line (rotated by `angle_deg + 90`).
+    ///
+    /// A horizontal line (`angle_deg: 0.0`) emits particles downwards, which is useful for effects like waterfalls or rain.
+    Line { angle_deg: f32, length: f32 },
 }
 
+/// What happens to a particle once it leaves the screen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParticleBounds {
+    /// Particles keep moving off-screen until their lifetime runs out.
+    None,
+    /// Particles are despawned as soon as they leave the screen.
+    Kill,
+    /// Particles bounce off the screen edges, keeping `restitution` of their velocity.
+    Bounce { restitution: f32 },
+    /// Particles leaving the screen reappear on the opposite edge.
+    Wrap,
+}
+
+/// How the color of a particle is picked every frame.
+///
+/// Defaults to [`ParticleColor::Solid`] white in [`ParticleSpec::default`].
 #[derive(Clone)]
+#[non_exhaustive]
 pub enum ParticleColor {
     Solid(Color),
+    /// Sampled by the fraction of the lifetime that has passed.
     Gradient(ColorGradient),
+    /// Sampled by the current speed divided by `max_speed`, in cells per second like [`ParticleSpec::speed`].
+    /// Speeds of `max_speed` and up sample the end of the gradient, which a `max_speed` of `0.0` or less always does.
+    ///
+    /// Useful for eg. embers that glow while moving fast and fade as they slow down.
+    BySpeed(ColorGradient, f32),
+    /// Picked by a function called for every particle on every frame.
+    ///
+    /// The most expensive of the variants, keep the function cheap when spawning lots of particles.
+    Custom(fn(&ParticleSample) -> Color),
+}
+
+/// The state of a particle a [`ParticleColor`] is sampled with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParticleSample {
+    /// The fraction of the lifetime that has passed, from `0.0` to `1.0`.
+    pub age_fraction: f32,
+    /// In cells per second.
+    pub speed: f32,
+    pub pos: (f32, f32),
+    pub velocity: (f32, f32),
+}
+
+/// Samples the color of a particle in the given state.
+///
+/// # Example
+/// ```rust
+/// # use germterm::{color::{Color, ColorGradient, GradientStop}, engine::Engine, layer::create_layer};
+/// # use germterm::particle::{
+/// #     ParticleColor, ParticleEmitter, ParticleSpec, for_each_particle_mut, sample_particle_color,
+/// #     spawn_particles_with_rng,
+/// # };
+/// # use rand::{SeedableRng, rngs::StdRng};
+/// let mut engine = Engine::offscreen(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// let mut rng = StdRng::seed_from_u64(7);
+///
+/// let embers = ParticleColor::BySpeed(
+///     ColorGradient::new(vec![
+///         GradientStop::new(0.0, Color::BLACK),
+///         GradientStop::new(1.0, Color::RED),
+///     ]),
+///     20.0,
+/// );
+/// let emitter = ParticleEmitter { count: 1, ..Default::default() };
+/// for speed in [5.0, 40.0] {
+///     let spec = ParticleSpec { color: embers.clone(), speed: speed..=speed, ..Default::default() };
+///     spawn_particles_with_rng(&mut engine, layer, 20.0, 10.0, &spec, &emitter, &mut rng);
+/// }
+///
+/// let mut colors = Vec::new();
+/// for_each_particle_mut(&mut engine, layer, |particle| {
+///     colors.push(sample_particle_color(particle.color(), &particle.sample()));
+/// });
+///
+/// let (slow, fast) = (colors[0], colors[1]);
+/// assert_eq!(fast, Color::RED);
+/// assert!(slow.r() > 0 && slow.r() < 128);
+/// ```
+pub fn sample_particle_color(color: &ParticleColor, sample: &ParticleSample) -> Color {
+    match color {
+        ParticleColor::Solid(color) => *color,
+        ParticleColor::Gradient(color_gradient) => {
+            sample_gradient(color_gradient, sample.age_fraction)
+        }
+        ParticleColor::BySpeed(color_gradient, max_speed) => {
+            let t: f32 = sample.speed / max_speed;
+            let t: f32 = if *max_speed <= 0.0 || max_speed.is_nan() {
+                1.0
+            } else if t.is_nan() {
+                0.0
+            } else {
+                t.clamp(0.0, 1.0)
+            };
+            sample_gradient(color_gradient, t)
+        }
+        ParticleColor::Custom(f) => f(sample),
+    }
 }
 
 pub(crate) struct ParticleState {
@@ -37,6 +150,10 @@ pub(crate) struct ParticleState {
     velocity: (f32, f32),
     color: ParticleColor,
     gravity_scale: f32,
+    drag: f32,
+    constant_force: (f32, f32),
+    bounds_behavior: ParticleBounds,
+    priority: u8,
     spawn_timestamp: f32,
     death_timestamp: f32,
     layer_index: LayerIndex,
@@ -48,8 +165,24 @@ pub struct ParticleSpec {
     pub speed: RangeInclusive<f32>,
     pub lifetime_sec: f32,
     pub gravity_scale: f32,
+    /// Additional velocity damping per second, applied on top of the base drag all particles have.
+    pub drag: f32,
+    /// A constant acceleration applied every frame alongside gravity, eg. wind.
+    pub constant_force: (f32, f32),
+    /// Defaults to [`ParticleBounds::None`].
+    ///
+    /// Using [`ParticleBounds::Kill`] is a cheap way of avoiding wasted update time
+    /// on particles that are no longer visible.
+    pub bounds_behavior: ParticleBounds,
+    /// Priority of the drawn particles, see [`crate::rich_text::RichText::with_priority`].
+    ///
+    /// Defaults to [`PARTICLE_PRIORITY`], making particles the first to go when the engine is over its cell budget.
+    pub priority: u8,
 }
 
+/// The default [`ParticleSpec::priority`], lower than [`crate::rich_text::RichText::DEFAULT_PRIORITY`].
+pub const PARTICLE_PRIORITY: u8 = 64;
+
 impl Default for ParticleSpec {
     fn default() -> Self {
         Self {
@@ -57,6 +190,10 @@ impl Default for ParticleSpec {
             speed: 15.0..=30.0,
             lifetime_sec: 3.0,
             gravity_scale: 1.0,
+            drag: 0.0,
+            constant_force: (0.0, 0.0),
+            bounds_behavior: ParticleBounds::None,
+            priority: PARTICLE_PRIORITY,
         }
     }
 }
@@ -98,53 +235,470 @@ pub fn spawn_particles(
     emitter: &ParticleEmitter,
 ) {
     let mut rng: ThreadRng = rand::rng();
+    spawn_particles_with_rng(engine, layer_index, x, y, spec, emitter, &mut rng);
+}
 
-    match emitter.shape {
-        ParticleEmitterShape::Circle => {
-            for _ in 0..emitter.count {
-                let angle: f32 = rng.random_range(0.0..=2.0 * PI);
-                let speed: f32 = rng.random_range(spec.speed.clone());
-
-                let velocity_x: f32 = speed * angle.cos();
-                let velocity_y: f32 = speed * angle.sin();
-
-                engine.particle_state.push(ParticleState {
-                    pos: (x, y),
-                    velocity: (velocity_x, velocity_y),
-                    color: spec.color.clone(),
-                    gravity_scale: spec.gravity_scale,
-                    spawn_timestamp: engine.game_time,
-                    death_timestamp: engine.game_time + spec.lifetime_sec,
-                    layer_index,
-                })
-            }
-        }
-        ParticleEmitterShape::Cone {
-            direction_deg,
-            width_deg,
-        } => {
-            for _ in 0..emitter.count {
+/// Same as [`spawn_particles`], but samples all randomness from the provided `rng`.
+///
+/// Useful when particle effects need to be reproducible, for example by using a seeded RNG.
+///
+/// # Examples
+/// ```rust,no_run
+/// # use germterm::{layer::create_layer, engine::Engine, particle::{spawn_particles_with_rng, ParticleSpec, ParticleEmitter}};
+/// # use rand::{SeedableRng, rngs::StdRng};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// let mut rng = StdRng::seed_from_u64(42);
+/// let spec = ParticleSpec::default();
+/// let emitter = ParticleEmitter::default();
+/// spawn_particles_with_rng(&mut engine, layer, 20.0, 10.0, &spec, &emitter, &mut rng);
+/// ```
+pub fn spawn_particles_with_rng(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    x: f32,
+    y: f32,
+    spec: &ParticleSpec,
+    emitter: &ParticleEmitter,
+    rng: &mut impl Rng,
+) {
+    // y:x aspect ratio, matching the one used when updating particles
+    let aspect_ratio: f32 = 1.0 / 2.0;
+    let count: usize = scale_particle_count(engine, emitter.count);
+    let lifetime_sec: f32 = scale_particle_lifetime(engine, spec.lifetime_sec);
+
+    for _ in 0..count {
+        let (pos, angle): ((f32, f32), f32) = match emitter.shape {
+            ParticleEmitterShape::Circle => ((x, y), rng.random_range(0.0..=2.0 * PI)),
+            ParticleEmitterShape::Cone {
+                direction_deg,
+                width_deg,
+            } => {
                 let half_angle_rad: f32 = (width_deg / 2.0).to_radians();
                 let direction_rad: f32 = direction_deg.to_radians();
 
                 let random_angle_offset: f32 = rng.random_range(-half_angle_rad..half_angle_rad);
-                let particle_angle: f32 = direction_rad + random_angle_offset;
-
-                let speed: f32 = rng.random_range(spec.speed.clone());
-                let velocity_x: f32 = speed * particle_angle.cos();
-                let velocity_y: f32 = speed * particle_angle.sin();
-
-                engine.particle_state.push(ParticleState {
-                    pos: (x, y),
-                    velocity: (velocity_x, velocity_y),
-                    color: spec.color.clone(),
-                    gravity_scale: spec.gravity_scale,
-                    spawn_timestamp: engine.game_time,
-                    death_timestamp: engine.game_time + spec.lifetime_sec,
-                    layer_index,
-                })
+                ((x, y), direction_rad + random_angle_offset)
+            }
+            ParticleEmitterShape::Line { angle_deg, length } => {
+                let angle_rad: f32 = angle_deg.to_radians();
+                let offset: f32 = rng.random_range(-0.5..=0.5) * length;
+
+                let pos: (f32, f32) = (
+                    x + offset * angle_rad.cos(),
+                    y + offset * angle_rad.sin() * aspect_ratio,
+                );
+                (pos, angle_rad + PI / 2.0)
             }
+        };
+
+        let speed: f32 = rng.random_range(spec.speed.clone());
+        let velocity_x: f32 = speed * angle.cos();
+        let velocity_y: f32 = speed * angle.sin();
+
+        engine.particle_state.push(ParticleState {
+            pos,
+            velocity: (velocity_x, velocity_y),
+            color: spec.color.clone(),
+            gravity_scale: spec.gravity_scale,
+            drag: spec.drag,
+            constant_force: spec.constant_force,
+            bounds_behavior: spec.bounds_behavior,
+            priority: spec.priority,
+            spawn_timestamp: engine.game_time,
+            death_timestamp: engine.game_time + lifetime_sec,
+            layer_index,
+        })
+    }
+}
+
+/// A handle to an emitter created with [`create_emitter`].
+///
+/// Handles of destroyed emitters never point to another emitter, even once its slot is reused,
+/// functions given such a handle do nothing and return `false`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EmitterHandle {
+    index: u32,
+    generation: u32,
+}
+
+/// The highest [`EmitterConfig::rate_per_sec`], higher rates are lowered to it and negative or NaN ones raised to `0.0`.
+///
+/// Also bounds the particles an emitter spawns in a single frame, besides its burst, eg. after a huge delta time.
+pub const MAX_EMITTER_RATE: f32 = 100_000.0;
+
+/// How an emitter created with [`create_emitter`] spawns particles over time.
+pub struct EmitterConfig {
+    pub shape: ParticleEmitterShape,
+    /// Particles spawned per second, with fractions carried over to later frames
+    /// so low rates and high frame rates still add up to the right count.
+    ///
+    /// Limited to `0.0..=`[`MAX_EMITTER_RATE`].
+    pub rate_per_sec: f32,
+    /// Particles spawned all at once on the first update of the emitter, on top of the rate.
+    pub burst: Option<usize>,
+    /// Seconds after which the emitter destroys itself, or `None` to emit until [`destroy_emitter`] is called.
+    ///
+    /// Time spent paused doesn't count towards the duration.
+    pub duration: Option<f32>,
+    /// Seeds the randomness of the spawned particles, so they come out the same on every run, eg. when replaying input.
+    /// `None` picks a random seed.
+    pub seed: Option<u64>,
+}
+
+impl Default for EmitterConfig {
+    fn default() -> Self {
+        Self {
+            shape: ParticleEmitterShape::Circle,
+            rate_per_sec: 30.0,
+            burst: None,
+            duration: None,
+            seed: None,
+        }
+    }
+}
+
+pub(crate) struct EmitterSlot {
+    generation: u32,
+    emitter: Option<EmitterState>,
+}
+
+struct EmitterState {
+    layer_index: LayerIndex,
+    pos: (f32, f32),
+    spec: ParticleSpec,
+    emitter: ParticleEmitter,
+    rate_per_sec: f32,
+    burst: Option<usize>,
+    duration: Option<f32>,
+    elapsed: f32,
+    /// Fraction of a particle carried over from previous frames.
+    accumulated: f32,
+    is_paused: bool,
+    rng: StdRng,
+}
+
+/// Creates an emitter spawning particles at `pos` every frame, until it's destroyed.
+///
+/// Emitters are updated at the end of every frame, right before the particles, spawning `rate_per_sec * delta_time`
+/// particles with fractions carried over to later frames.
+///
+/// # Example
+/// ```rust
+/// # use germterm::{engine::{Engine, end_frame, init, start_frame}, layer::create_layer};
+/// # use germterm::particle::{EmitterConfig, ParticleSpec, create_emitter, particle_count};
+/// let mut engine = Engine::offscreen(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// init(&mut engine).unwrap();
+///
+/// let config = EmitterConfig { rate_per_sec: 10.0, burst: Some(4), ..Default::default() };
+/// create_emitter(&mut engine, layer, (20.0, 10.0), ParticleSpec::default(), config);
+///
+/// let mut counts = Vec::new();
+/// for _ in 0..4 {
+///     start_frame(&mut engine);
+///     engine.delta_time = 0.25;
+///     end_frame(&mut engine).unwrap();
+///     counts.push(particle_count(&engine));
+/// }
+///
+/// // 2.5 particles per frame, plus the burst on the first one
+/// assert_eq!(counts, [6, 9, 11, 14]);
+/// ```
+pub fn create_emitter(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    pos: (f32, f32),
+    spec: ParticleSpec,
+    config: EmitterConfig,
+) -> EmitterHandle {
+    let emitter: EmitterState = EmitterState {
+        layer_index,
+        pos,
+        spec,
+        emitter: ParticleEmitter {
+            shape: config.shape,
+            count: 0,
+        },
+        rate_per_sec: if config.rate_per_sec.is_nan() {
+            0.0
+        } else {
+            config.rate_per_sec.clamp(0.0, MAX_EMITTER_RATE)
+        },
+        burst: config.burst,
+        duration: config.duration,
+        elapsed: 0.0,
+        accumulated: 0.0,
+        is_paused: false,
+        rng: match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_rng(&mut rand::rng()),
+        },
+    };
+
+    let slots: &mut Vec<EmitterSlot> = &mut engine.particle_emitters;
+    let index: usize = match slots.iter().position(|slot| slot.emitter.is_none()) {
+        Some(index) => index,
+        None => {
+            slots.push(EmitterSlot {
+                generation: 0,
+                emitter: None,
+            });
+            slots.len() - 1
+        }
+    };
+    slots[index].emitter = Some(emitter);
+
+    EmitterHandle {
+        index: index as u32,
+        generation: slots[index].generation,
+    }
+}
+
+/// Moves the emitter, eg. to make it follow a game object. Returns `false` if the emitter no longer exists.
+pub fn set_emitter_position(engine: &mut Engine, handle: EmitterHandle, pos: (f32, f32)) -> bool {
+    emitter_mut(engine, handle)
+        .map(|emitter| emitter.pos = pos)
+        .is_some()
+}
+
+/// Stops the emitter from spawning particles until [`resume_emitter`] is called.
+/// Returns `false` if the emitter no longer exists.
+///
+/// Particles the emitter already spawned keep moving.
+pub fn pause_emitter(engine: &mut Engine, handle: EmitterHandle) -> bool {
+    emitter_mut(engine, handle)
+        .map(|emitter| emitter.is_paused = true)
+        .is_some()
+}
+
+/// Resumes an emitter paused with [`pause_emitter`]. Returns `false` if the emitter no longer exists.
+pub fn resume_emitter(engine: &mut Engine, handle: EmitterHandle) -> bool {
+    emitter_mut(engine, handle)
+        .map(|emitter| emitter.is_paused = false)
+        .is_some()
+}
+
+/// Destroys the emitter, leaving the particles it already spawned alive.
+/// Returns `false` if the emitter no longer exists, eg. because its duration ran out.
+///
+/// # Example
+/// ```rust
+/// # use germterm::{engine::Engine, layer::create_layer};
+/// # use germterm::particle::{
+/// #     EmitterConfig, ParticleSpec, create_emitter, destroy_emitter, is_emitter_alive, set_emitter_position,
+/// # };
+/// let mut engine = Engine::offscreen(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// let torch = create_emitter(&mut engine, layer, (5.0, 5.0), ParticleSpec::default(), EmitterConfig::default());
+/// assert!(destroy_emitter(&mut engine, torch));
+///
+/// // The slot of the torch is reused, but its handle stays dead
+/// let campfire = create_emitter(&mut engine, layer, (9.0, 5.0), ParticleSpec::default(), EmitterConfig::default());
+/// assert!(!set_emitter_position(&mut engine, torch, (0.0, 0.0)));
+/// assert!(!destroy_emitter(&mut engine, torch));
+/// assert!(is_emitter_alive(&engine, campfire));
+/// ```
+pub fn destroy_emitter(engine: &mut Engine, handle: EmitterHandle) -> bool {
+    let Some(slot) = emitter_slot_mut(&mut engine.particle_emitters, handle) else {
+        return false;
+    };
+    free_emitter_slot(slot);
+    true
+}
+
+/// Whether the emitter exists, being neither destroyed nor out of its duration.
+///
+/// # Example
+/// ```rust
+/// # use germterm::{engine::{Engine, end_frame, init, start_frame}, layer::create_layer};
+/// # use germterm::particle::{EmitterConfig, ParticleSpec, create_emitter, is_emitter_alive, particle_count};
+/// let mut engine = Engine::offscreen(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+/// init(&mut engine).unwrap();
+///
+/// let config = EmitterConfig { rate_per_sec: 10.0, duration: Some(0.6), ..Default::default() };
+/// let sparks = create_emitter(&mut engine, layer, (20.0, 10.0), ParticleSpec::default(), config);
+///
+/// for _ in 0..5 {
+///     start_frame(&mut engine);
+///     engine.delta_time = 0.25;
+///     end_frame(&mut engine).unwrap();
+/// }
+///
+/// // Only the first 0.1 seconds of the third frame were still within the duration
+/// assert_eq!(particle_count(&engine), 6);
+/// assert!(!is_emitter_alive(&engine, sparks));
+/// ```
+pub fn is_emitter_alive(engine: &Engine, handle: EmitterHandle) -> bool {
+    engine
+        .particle_emitters
+        .get(handle.index as usize)
+        .is_some_and(|slot| slot.generation == handle.generation && slot.emitter.is_some())
+}
+
+#[inline]
+fn emitter_mut(engine: &mut Engine, handle: EmitterHandle) -> Option<&mut EmitterState> {
+    emitter_slot_mut(&mut engine.particle_emitters, handle)?
+        .emitter
+        .as_mut()
+}
+
+#[inline]
+fn emitter_slot_mut(slots: &mut [EmitterSlot], handle: EmitterHandle) -> Option<&mut EmitterSlot> {
+    slots
+        .get_mut(handle.index as usize)
+        .filter(|slot| slot.generation == handle.generation && slot.emitter.is_some())
+}
+
+/// Empties the slot, invalidating every handle pointing to it.
+#[inline]
+fn free_emitter_slot(slot: &mut EmitterSlot) {
+    slot.emitter = None;
+    slot.generation = slot.generation.wrapping_add(1);
+}
+
+pub(crate) fn update_emitters(engine: &mut Engine) {
+    if engine.particle_emitters.is_empty() {
+        return;
+    }
+
+    // Taken out for the duration of the update, as spawning particles borrows the whole engine
+    let mut slots: Vec<EmitterSlot> = std::mem::take(&mut engine.particle_emitters);
+
+    for slot in &mut slots {
+        let Some(state) = slot.emitter.as_mut().filter(|state| !state.is_paused) else {
+            continue;
+        };
+
+        // The last frame of an emitter only emits for the part of the frame still within its duration
+        let emitting_time: f32 = match state.duration {
+            Some(duration) => engine.delta_time.min(duration - state.elapsed).max(0.0),
+            None => engine.delta_time,
+        };
+        state.elapsed += engine.delta_time;
+        let due: f32 = state.rate_per_sec * emitting_time;
+        if !due.is_nan() {
+            state.accumulated = (state.accumulated + due).min(MAX_EMITTER_RATE);
+        }
+
+        let whole_particles: f32 = state.accumulated.floor();
+        state.accumulated -= whole_particles;
+        state.emitter.count = whole_particles as usize + state.burst.take().unwrap_or(0);
+
+        if state.emitter.count > 0 {
+            let (x, y): (f32, f32) = state.pos;
+            spawn_particles_with_rng(
+                engine,
+                state.layer_index,
+                x,
+                y,
+                &state.spec,
+                &state.emitter,
+                &mut state.rng,
+            );
         }
+
+        if state
+            .duration
+            .is_some_and(|duration| state.elapsed >= duration)
+        {
+            free_emitter_slot(slot);
+        }
+    }
+
+    engine.particle_emitters = slots;
+}
+
+/// Mutable access to a single live particle, handed out by [`for_each_particle_mut`].
+///
+/// Positions use the same floating point coordinate space as [`crate::draw::draw_octad`].
+/// Velocities are in cells per second, with the vertical component being halved when moving the particle
+/// to account for the 1:2 width to height ratio of terminal cells.
+pub struct ParticleMut<'a> {
+    state: &'a mut ParticleState,
+    game_time: f32,
+}
+
+impl ParticleMut<'_> {
+    #[inline]
+    pub fn pos(&self) -> (f32, f32) {
+        self.state.pos
+    }
+
+    #[inline]
+    pub fn set_pos(&mut self, pos: (f32, f32)) {
+        self.state.pos = pos;
+    }
+
+    #[inline]
+    pub fn velocity(&self) -> (f32, f32) {
+        self.state.velocity
+    }
+
+    #[inline]
+    pub fn set_velocity(&mut self, velocity: (f32, f32)) {
+        self.state.velocity = velocity;
+    }
+
+    /// Seconds left until the particle despawns.
+    #[inline]
+    pub fn remaining_lifetime(&self) -> f32 {
+        (self.state.death_timestamp - self.game_time).max(0.0)
+    }
+
+    #[inline]
+    pub fn color(&self) -> &ParticleColor {
+        &self.state.color
+    }
+
+    /// The current state of the particle, as passed to [`ParticleColor::Custom`].
+    #[inline]
+    pub fn sample(&self) -> ParticleSample {
+        particle_sample(self.state, self.game_time)
+    }
+
+    /// Despawns the particle during the next particle update.
+    #[inline]
+    pub fn kill(&mut self) {
+        self.state.death_timestamp = self.game_time;
+    }
+}
+
+/// Calls `f` for every live particle on the specified layer, allowing for custom per-frame behaviors like attractors.
+///
+/// Call this between [`crate::engine::start_frame`] and [`crate::engine::end_frame`],
+/// the changes are picked up by the particle update at the end of the frame.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{layer::create_layer, engine::Engine, particle::for_each_particle_mut};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// // Pulling all particles towards the center of the screen
+/// let delta_time = engine.delta_time;
+/// for_each_particle_mut(&mut engine, layer, |particle| {
+///     let (x, y) = particle.pos();
+///     let (vx, vy) = particle.velocity();
+///     particle.set_velocity((vx + (20.0 - x) * delta_time, vy + (10.0 - y) * delta_time));
+/// });
+/// ```
+pub fn for_each_particle_mut(
+    engine: &mut Engine,
+    layer_index: LayerIndex,
+    mut f: impl FnMut(&mut ParticleMut),
+) {
+    let game_time: f32 = engine.game_time;
+
+    for state in engine
+        .particle_state
+        .iter_mut()
+        .filter(|state| state.layer_index.0 == layer_index.0)
+    {
+        f(&mut ParticleMut { state, game_time });
     }
 }
 
@@ -156,15 +710,15 @@ pub fn particle_count(engine: &Engine) -> usize {
 
 pub(crate) fn update_and_draw_particles(engine: &mut Engine) {
     let gravity: f32 = 200.0;
-    let drag: f32 = 3.0;
-    let drag_decay: f32 = 1.0 / (1.0 + drag * engine.delta_time);
+    let base_drag: f32 = 3.0;
     // y:x aspect ratio to account for terminal cells not being perfect squares
     // and not making the end result look stretched out vertically
     let aspect_ratio: f32 = 1.0 / 2.0;
+    let screen_size: (f32, f32) = (engine.frame.width as f32, engine.frame.height as f32);
 
     let mut i: usize = 0;
     while i < engine.particle_state.len() {
-        let (layer_index, x, y, color) = {
+        let (layer_index, x, y, color, priority) = {
             let state: &mut ParticleState = &mut engine.particle_state[i];
 
             if engine.game_time >= state.death_timestamp {
@@ -172,28 +726,273 @@ pub(crate) fn update_and_draw_particles(engine: &mut Engine) {
                 continue;
             }
 
-            let t: f32 = ((engine.game_time - state.spawn_timestamp)
-                / (state.death_timestamp - state.spawn_timestamp))
-                .clamp(0.0, 1.0);
-
             let color: Color = match &state.color {
                 ParticleColor::Solid(color) => *color,
-                ParticleColor::Gradient(color_gradient) => sample_gradient(color_gradient, t),
+                color => sample_particle_color(color, &particle_sample(state, engine.game_time)),
             };
 
+            state.velocity.0 += state.constant_force.0 * engine.delta_time;
             state.velocity.1 += gravity * state.gravity_scale * engine.delta_time;
+            state.velocity.1 += state.constant_force.1 * engine.delta_time;
 
+            let drag_decay: f32 = 1.0 / (1.0 + (base_drag + state.drag) * engine.delta_time);
             state.velocity.0 *= drag_decay;
             state.velocity.1 *= drag_decay;
 
             state.pos.0 += state.velocity.0 * engine.delta_time;
             state.pos.1 += state.velocity.1 * engine.delta_time * aspect_ratio;
 
-            (state.layer_index, state.pos.0, state.pos.1, color)
+            let is_outside: bool = state.pos.0 < 0.0
+                || state.pos.1 < 0.0
+                || state.pos.0 >= screen_size.0
+                || state.pos.1 >= screen_size.1;
+
+            if is_outside {
+                match state.bounds_behavior {
+                    ParticleBounds::None => {}
+                    ParticleBounds::Kill => {
+                        engine.particle_state.swap_remove(i);
+                        continue;
+                    }
+                    ParticleBounds::Bounce { restitution } => {
+                        bounce_off_edges(
+                            &mut state.pos.0,
+                            &mut state.velocity.0,
+                            screen_size.0,
+                            restitution,
+                        );
+                        bounce_off_edges(
+                            &mut state.pos.1,
+                            &mut state.velocity.1,
+                            screen_size.1,
+                            restitution,
+                        );
+                    }
+                    ParticleBounds::Wrap => {
+                        wrap_around_edges(&mut state.pos.0, screen_size.0);
+                        wrap_around_edges(&mut state.pos.1, screen_size.1);
+                    }
+                }
+            }
+
+            (
+                state.layer_index,
+                state.pos.0,
+                state.pos.1,
+                color,
+                state.priority,
+            )
         };
 
-        draw_octad(engine, layer_index, x, y, color);
+        draw_octad_with_priority(engine, layer_index, x, y, color, priority);
 
         i += 1;
     }
 }
+
+#[inline]
+fn particle_sample(state: &ParticleState, game_time: f32) -> ParticleSample {
+    let age_fraction: f32 = ((game_time - state.spawn_timestamp)
+        / (state.death_timestamp - state.spawn_timestamp))
+        .clamp(0.0, 1.0);
+
+    ParticleSample {
+        age_fraction,
+        speed: state.velocity.0.hypot(state.velocity.1),
+        pos: state.pos,
+        velocity: state.velocity,
+    }
+}
+
+/// Moves a particle that crossed either edge of an axis to the opposite edge.
+///
+/// Zero-sized axes have nothing to wrap around, the particle is left where it is.
+#[inline]
+fn wrap_around_edges(pos: &mut f32, size: f32) {
+    if size <= 0.0 {
+        return;
+    }
+
+    *pos = pos.rem_euclid(size);
+    // Tiny negative positions round up to exactly `size`
+    if *pos >= size {
+        *pos = 0.0;
+    }
+}
+
+/// Flips the velocity of a particle that crossed either edge of an axis and clamps it back inside.
+#[inline]
+fn bounce_off_edges(pos: &mut f32, velocity: &mut f32, size: f32, restitution: f32) {
+    // Keeps the particle within the last octad before the edge
+    let edge_inset: f32 = 0.01;
+
+    if *pos < 0.0 {
+        *pos = 0.0;
+        *velocity = velocity.abs() * restitution;
+    } else if *pos >= size {
+        *pos = (size - edge_inset).max(0.0);
+        *velocity = -velocity.abs() * restitution;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        color::GradientStop,
+        engine::{end_frame, init, start_frame},
+        layer::create_layer,
+    };
+
+    #[test]
+    fn wrapping_on_a_zero_sized_screen_keeps_positions_finite() {
+        let mut engine: Engine = Engine::offscreen(0, 0);
+        let layer: LayerIndex = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+
+        let spec: ParticleSpec = ParticleSpec {
+            bounds_behavior: ParticleBounds::Wrap,
+            ..Default::default()
+        };
+        spawn_particles(
+            &mut engine,
+            layer,
+            0.0,
+            0.0,
+            &spec,
+            &ParticleEmitter::default(),
+        );
+
+        start_frame(&mut engine);
+        end_frame(&mut engine).unwrap();
+
+        let mut count: usize = 0;
+        for_each_particle_mut(&mut engine, layer, |particle| {
+            let (x, y) = particle.pos();
+            assert!(x.is_finite() && y.is_finite());
+            count += 1;
+        });
+        assert_eq!(count, ParticleEmitter::default().count);
+    }
+
+    #[test]
+    fn fixed_timesteps_integrate_speed_forces_and_drag() {
+        let mut engine: Engine = Engine::offscreen(80, 40);
+        let layer: LayerIndex = create_layer(&mut engine, 0);
+        init(&mut engine).unwrap();
+
+        // A single particle heading right at exactly 10 cols per second
+        let spec: ParticleSpec = ParticleSpec {
+            speed: 10.0..=10.0,
+            lifetime_sec: 10.0,
+            gravity_scale: 0.0,
+            drag: 1.0,
+            constant_force: (5.0, -2.0),
+            ..Default::default()
+        };
+        let emitter: ParticleEmitter = ParticleEmitter {
+            shape: ParticleEmitterShape::Line {
+                angle_deg: -90.0,
+                length: 0.0,
+            },
+            count: 1,
+        };
+        spawn_particles(&mut engine, layer, 20.0, 10.0, &spec, &emitter);
+
+        // 4 steps of a quarter second, each halving the velocity after the forces were applied
+        for _ in 0..4 {
+            start_frame(&mut engine);
+            engine.delta_time = 0.25;
+            end_frame(&mut engine).unwrap();
+        }
+        assert_eq!(engine.game_time, 1.0);
+
+        let mut states: Vec<((f32, f32), (f32, f32))> = Vec::new();
+        for_each_particle_mut(&mut engine, layer, |particle| {
+            states.push((particle.pos(), particle.velocity()));
+        });
+        assert_eq!(states, [((23.300_781, 9.808_594), (1.796_875, -0.468_75))]);
+    }
+
+    #[test]
+    fn speed_colors_stay_within_the_gradient() {
+        let gradient: ColorGradient = ColorGradient::new(vec![
+            GradientStop::new(0.0, Color::BLACK),
+            GradientStop::new(1.0, Color::RED),
+        ]);
+        let color = |max_speed: f32, speed: f32| {
+            let sample = ParticleSample {
+                age_fraction: 0.0,
+                speed,
+                pos: (0.0, 0.0),
+                velocity: (speed, 0.0),
+            };
+            sample_particle_color(
+                &ParticleColor::BySpeed(gradient.clone(), max_speed),
+                &sample,
+            )
+        };
+
+        for max_speed in [0.0, -1.0, f32::NAN] {
+            assert_eq!(color(max_speed, 5.0), Color::RED);
+        }
+        assert_eq!(color(10.0, 0.0), Color::BLACK);
+        assert_eq!(color(10.0, 50.0), Color::RED);
+        assert_eq!(color(10.0, f32::INFINITY), Color::RED);
+        assert_eq!(color(10.0, f32::NAN), Color::BLACK);
+    }
+
+    #[test]
+    fn seeded_emitters_spawn_the_same_particles() {
+        let spawn = |seed: u64| {
+            let mut engine: Engine = Engine::offscreen(40, 20);
+            let layer: LayerIndex = create_layer(&mut engine, 0);
+            let config: EmitterConfig = EmitterConfig {
+                burst: Some(10),
+                seed: Some(seed),
+                ..Default::default()
+            };
+            create_emitter(
+                &mut engine,
+                layer,
+                (20.0, 10.0),
+                ParticleSpec::default(),
+                config,
+            );
+            update_emitters(&mut engine);
+
+            let mut velocities: Vec<(f32, f32)> = Vec::new();
+            for_each_particle_mut(&mut engine, layer, |particle| {
+                velocities.push(particle.velocity())
+            });
+            velocities
+        };
+
+        assert_eq!(spawn(7), spawn(7));
+        assert_ne!(spawn(7), spawn(8));
+    }
+
+    #[test]
+    fn emitter_rates_are_bounded() {
+        let mut engine: Engine = Engine::offscreen(40, 20);
+        let layer: LayerIndex = create_layer(&mut engine, 0);
+        for rate_per_sec in [f32::INFINITY, f32::NAN, -1.0] {
+            let config: EmitterConfig = EmitterConfig {
+                rate_per_sec,
+                ..Default::default()
+            };
+            create_emitter(
+                &mut engine,
+                layer,
+                (20.0, 10.0),
+                ParticleSpec::default(),
+                config,
+            );
+        }
+
+        engine.delta_time = f32::INFINITY;
+        update_emitters(&mut engine);
+
+        assert_eq!(particle_count(&engine), MAX_EMITTER_RATE as usize);
+    }
+}
@@ -0,0 +1,85 @@
+//! Reusable pre-composed buffers for contents which rarely change.
+//!
+//! A [`RenderTarget`] is drawn to with the regular drawing functions and composed once,
+//! after which it can be stamped onto a layer every frame with [`crate::draw::draw_target`].
+//! This skips queueing and composing the original draw calls every frame, which adds up for
+//! eg. static backgrounds made of thousands of octads.
+//!
+//! Targets are never redrawn on their own, [`redraw_render_target`] has to be called again whenever the contents change.
+
+use crate::{
+    cell::Cell,
+    engine::Engine,
+    frame::compose_queued_layers,
+    layer::{Layer, LayerIndex, create_layer},
+};
+
+pub struct RenderTarget {
+    engine: Engine,
+    layer_index: LayerIndex,
+    pub(crate) cells: Vec<Cell>,
+}
+
+impl RenderTarget {
+    /// Creates an empty target of the given size, in cells.
+    pub fn new(width: u16, height: u16) -> Self {
+        let mut engine: Engine = Engine::offscreen(width, height);
+        let layer_index: LayerIndex = create_layer(&mut engine, 0);
+        engine.frame.layered_draw_queue.resize_with(1, Layer::new);
+
+        Self {
+            engine,
+            layer_index,
+            cells: vec![Cell::EMPTY; width as usize * height as usize],
+        }
+    }
+
+    pub fn width(&self) -> u16 {
+        self.engine.frame.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.engine.frame.height
+    }
+}
+
+/// Replaces the contents of a target with everything drawn by `draw`.
+///
+/// `draw` is handed an offscreen engine the size of the target and the layer to draw onto.
+/// Translucent colors are blended like on an offscreen engine, so use [`crate::engine::override_default_blending_color`]
+/// on the handed engine to match the engine the target is drawn to.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::{draw_octad, draw_target}, layer::create_layer, engine::Engine, color::Color};
+/// # use germterm::render_target::{RenderTarget, redraw_render_target};
+/// let mut engine = Engine::new(80, 40);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// let mut map = RenderTarget::new(80, 40);
+/// redraw_render_target(&mut map, |map_engine, map_layer| {
+///     for i in 0..2000 {
+///         let x: f32 = (i % 160) as f32 * 0.5;
+///         let y: f32 = (i / 160) as f32 * 0.25;
+///         draw_octad(map_engine, map_layer, x, y, Color::DARK_GREEN);
+///     }
+/// });
+///
+/// // Every frame
+/// draw_target(&mut engine, layer, (0, 0), &map);
+/// ```
+pub fn redraw_render_target(target: &mut RenderTarget, draw: impl FnOnce(&mut Engine, LayerIndex)) {
+    let engine: &mut Engine = &mut target.engine;
+    draw(engine, target.layer_index);
+
+    target.cells = compose_queued_layers(
+        &engine.frame.layered_draw_queue,
+        target.layer_index.0,
+        engine.frame.width,
+        engine.frame.height,
+        engine.default_blending_color,
+    );
+    engine.frame.layered_draw_queue[target.layer_index.0]
+        .draw_calls
+        .clear();
+}
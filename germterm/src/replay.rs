@@ -0,0 +1,194 @@
+//! Input recording and playback for reproducible sessions.
+//!
+//! [`start_input_recording`] records every event drained through [`crate::input::poll_timed_input`]
+//! or [`crate::input::poll_tracked_input`], along with the delta time of every frame.
+//! Playing the recording back with [`play_input_recording`] feeds the same events to the same frames
+//! instead of reading the terminal, and replaces the measured delta times with the recorded ones,
+//! so anything driven by input and `delta_time` or `game_time` plays out identically.
+//!
+//! Recordings can be serialized with any `serde` format by enabling the `serde` feature.
+//!
+//! ## Notes
+//! Events read through [`crate::input::poll_input`] don't go through the engine, so they are neither recorded nor played back.
+//! Randomness has to be seeded for the playback to match, eg. through [`crate::noise`] or a seeded RNG.
+
+use crossterm::event::Event;
+
+use crate::engine::Engine;
+
+/// The input events and frame timings of a recorded session.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputRecording {
+    /// The delta time of every recorded frame, in order.
+    pub delta_times: Vec<f32>,
+    /// Every drained event, along with the amount of frames started before it was drained.
+    pub events: Vec<(u32, Event)>,
+}
+
+pub(crate) struct InputPlayback {
+    recording: InputRecording,
+    frame: u32,
+    next_event_index: usize,
+}
+
+/// Starts recording input, discarding any recording in progress.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{engine::{Engine, end_frame, start_frame}, input::poll_tracked_input};
+/// # use germterm::replay::{play_input_recording, start_input_recording, stop_input_recording};
+/// let mut engine = Engine::new(40, 20);
+/// start_input_recording(&mut engine);
+///
+/// for _ in 0..600 {
+///     start_frame(&mut engine);
+///     for event in poll_tracked_input(&mut engine) {
+///         // ...
+///     }
+///     end_frame(&mut engine);
+/// }
+///
+/// let recording = stop_input_recording(&mut engine).unwrap();
+/// play_input_recording(&mut engine, recording);
+/// ```
+pub fn start_input_recording(engine: &mut Engine) {
+    engine.input_recording = Some(InputRecording::default());
+}
+
+/// Stops recording input, returning the recording if one was in progress.
+pub fn stop_input_recording(engine: &mut Engine) -> Option<InputRecording> {
+    engine.input_recording.take()
+}
+
+/// Starts playing back a recording, beginning with the next started frame.
+///
+/// The terminal is ignored while playing back and reading it resumes once the recording runs out.
+pub fn play_input_recording(engine: &mut Engine, recording: InputRecording) {
+    engine.input_playback = Some(InputPlayback {
+        recording,
+        frame: 0,
+        next_event_index: 0,
+    });
+}
+
+/// Whether a recording is currently being played back.
+pub fn is_playing_input_recording(engine: &Engine) -> bool {
+    engine.input_playback.is_some()
+}
+
+pub(crate) fn record_frame(recording: &mut InputRecording, delta_time: f32) {
+    recording.delta_times.push(delta_time);
+}
+
+pub(crate) fn record_event(recording: &mut InputRecording, event: &Event) {
+    let frame: u32 = recording.delta_times.len() as u32;
+    recording.events.push((frame, event.clone()));
+}
+
+/// Advances the playback by one frame, returning the recorded delta time,
+/// or `None` once the recording ran out.
+pub(crate) fn next_played_back_frame(playback: &mut InputPlayback) -> Option<f32> {
+    let delta_time: f32 = *playback
+        .recording
+        .delta_times
+        .get(playback.frame as usize)?;
+    playback.frame += 1;
+    Some(delta_time)
+}
+
+/// The next event recorded for the current frame or any earlier one.
+pub(crate) fn next_played_back_event(playback: &mut InputPlayback) -> Option<Event> {
+    let (frame, event): &(u32, Event) = playback.recording.events.get(playback.next_event_index)?;
+    if *frame > playback.frame {
+        return None;
+    }
+
+    playback.next_event_index += 1;
+    Some(event.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use crossterm::event::{Event, KeyCode, KeyEvent};
+
+    use super::{
+        InputRecording, is_playing_input_recording, play_input_recording, start_input_recording,
+        stop_input_recording,
+    };
+    use crate::{
+        color::Color,
+        draw::draw_rect,
+        engine::{Engine, end_frame, init, start_frame},
+        input::{TimedEvent, poll_tracked_input},
+        layer::create_layer,
+        screenshot::{ScreenshotFormat, screenshot},
+    };
+
+    const FRAME_COUNT: usize = 30;
+
+    /// Runs a tiny game where a block drifts right over time and jumps on key presses,
+    /// returning a screenshot of every frame along with the final game time.
+    ///
+    /// `live_events` are queued up as if they arrived from the terminal at the given frames.
+    fn run_session(engine: &mut Engine, live_events: &[(usize, Event)]) -> (Vec<String>, f32) {
+        let layer = create_layer(engine, 0);
+        let mut x: f32 = 0.0;
+        let mut screenshots: Vec<String> = Vec::new();
+
+        for frame in 0..FRAME_COUNT {
+            for (_, event) in live_events.iter().filter(|(at, _)| *at == frame) {
+                engine.input_queue.push_back(TimedEvent {
+                    event: event.clone(),
+                    timestamp: Instant::now(),
+                });
+            }
+
+            start_frame(engine);
+            for event in poll_tracked_input(engine).collect::<Vec<Event>>() {
+                if let Event::Key(_) = event {
+                    x += 3.0;
+                }
+            }
+            x += engine.delta_time * 40.0;
+            draw_rect(engine, layer, (x as i16, 0), (1, 1), Color::RED);
+            end_frame(engine).unwrap();
+            screenshots.push(screenshot(engine, ScreenshotFormat::Ansi));
+        }
+        (screenshots, engine.game_time)
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::from(code))
+    }
+
+    #[test]
+    fn played_back_sessions_match_the_recorded_one() {
+        let mut engine: Engine = Engine::offscreen(64, 1).limit_fps(120);
+        init(&mut engine).unwrap();
+        start_input_recording(&mut engine);
+        let live_events: [(usize, Event); 3] = [
+            (2, key(KeyCode::Right)),
+            (2, key(KeyCode::Right)),
+            (17, key(KeyCode::Char('d'))),
+        ];
+        let recorded = run_session(&mut engine, &live_events);
+        let recording: InputRecording = stop_input_recording(&mut engine).unwrap();
+        assert_eq!(recording.delta_times.len(), FRAME_COUNT);
+        assert_eq!(recording.events.len(), 3);
+
+        for _ in 0..2 {
+            let mut engine: Engine = Engine::offscreen(64, 1).limit_fps(0);
+            init(&mut engine).unwrap();
+            play_input_recording(&mut engine, recording.clone());
+
+            assert_eq!(run_session(&mut engine, &[]), recorded);
+            // Ran out exactly at the end of the recording
+            assert!(is_playing_input_recording(&engine));
+            start_frame(&mut engine);
+            assert!(!is_playing_input_recording(&engine));
+        }
+    }
+}
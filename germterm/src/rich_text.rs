@@ -1,12 +1,34 @@
 //! Stylized text.
+//!
+//! ## Markup
+//!
+//! Styled text coming from data, like chat logs or localized strings, can be written using an inline markup
+//! parsed by [`parse_markup`] into [`RichText`] spans, which are drawn with [`crate::draw::draw_spans`]:
+//! - `[bold red]` opens a tag, taking a space separated list of attribute names and colors.
+//!   A color sets the foreground, and a color following `on` sets the background, eg. `[black on #ffcc00]`.
+//! - `[/]` closes the most recently opened tag, so does `[/bold red]` if it matches the opened tag.
+//!   Tags nest, and text after an inner tag is closed is styled by the outer one again.
+//! - `[[` is a literal `[`.
+//!
+//! Colors are either hex codes (`#RRGGBB` or `#RRGGBBAA`) or the lowercase names of the [`Color`] constants,
+//! like `red` or `dark_gray`. The attributes are `bold`, `italic`, `underlined` and `hidden`.
+//!
+//! ```text
+//! [bold red]error:[/] disk [italic]full[/], [[retry] or [underlined]cancel[/]
+//! ```
 
-use crate::{cell::CellFormat, color::Color};
+use crate::{
+    cell::CellFormat,
+    color::{Color, parse_color_value},
+    layer::OctadColorMode,
+    link::{LinkId, LinkLimitError, intern_link},
+};
 use bitflags::bitflags;
-use std::sync::Arc;
+use std::{fmt, sync::Arc};
 
 bitflags! {
     /// Attributes that can be applied to drawn text.
-    #[derive(Clone, Copy, PartialEq, Eq)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
     pub struct Attributes: u8 {
         const BOLD          = 0b_00000001;
         const ITALIC        = 0b_00000010;
@@ -17,6 +39,63 @@ bitflags! {
     }
 }
 
+/// The names attributes are serialized and written in markup with.
+const ATTRIBUTE_NAMES: [(&str, Attributes); 6] = [
+    ("bold", Attributes::BOLD),
+    ("italic", Attributes::ITALIC),
+    ("underlined", Attributes::UNDERLINED),
+    ("hidden", Attributes::HIDDEN),
+    ("no_fg_color", Attributes::NO_FG_COLOR),
+    ("no_bg_color", Attributes::NO_BG_COLOR),
+];
+
+/// Looks up the attribute written as `name`.
+fn attribute_named(name: &str) -> Option<Attributes> {
+    ATTRIBUTE_NAMES
+        .iter()
+        .find(|(known, _)| *known == name)
+        .map(|&(_, attribute)| attribute)
+}
+
+/// Written as a list of lowercase flag names, eg. `["bold", "italic"]`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Attributes {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let names = ATTRIBUTE_NAMES
+            .iter()
+            .filter(|(_, attribute)| self.contains(*attribute))
+            .map(|(name, _)| name);
+        serializer.collect_seq(names)
+    }
+}
+
+/// Read from a list of lowercase flag names, unknown names are an error.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Attributes {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        const NAMES: [&str; ATTRIBUTE_NAMES.len()] = {
+            let mut names: [&str; ATTRIBUTE_NAMES.len()] = [""; ATTRIBUTE_NAMES.len()];
+            let mut index: usize = 0;
+            while index < names.len() {
+                names[index] = ATTRIBUTE_NAMES[index].0;
+                index += 1;
+            }
+            names
+        };
+
+        let names: Vec<String> = serde::Deserialize::deserialize(deserializer)?;
+
+        let mut attributes: Attributes = Attributes::empty();
+        for name in names {
+            let Some(attribute) = attribute_named(&name) else {
+                return Err(serde::de::Error::unknown_variant(&name, &NAMES));
+            };
+            attributes |= attribute;
+        }
+        Ok(attributes)
+    }
+}
+
 /// Stylized text representation.
 ///
 /// Bundles together text, foreground color, background color and attributes.
@@ -31,10 +110,24 @@ pub struct RichText {
     pub fg: Color,
     pub bg: Color,
     pub attributes: Attributes,
+    /// Used to decide which draw calls get culled first when the engine is over its cell budget,
+    /// see [`crate::engine::Engine::cell_budget`].
+    pub priority: u8,
+    /// The hyperlink the text is drawn as, see [`RichText::with_link`].
+    pub link: Option<LinkId>,
     pub(crate) cell_format: CellFormat,
+    /// The sub-cell mask of the drawn cells, see [`crate::cell::Cell::format_data`].
+    pub(crate) format_data: u8,
+    /// Set from the layer when composing, see [`crate::layer::set_octad_color_mode`].
+    pub(crate) octad_color_mode: OctadColorMode,
+    /// Clears the sub-cells of `format_data` from the cell underneath instead of adding them.
+    pub(crate) erases_sub_cells: bool,
 }
 
 impl RichText {
+    /// The priority draw calls have unless specified otherwise.
+    pub const DEFAULT_PRIORITY: u8 = 128;
+
     /// Creates a new `RichText` with default styling.
     ///
     /// To customize the style, use the following builder methods:
@@ -50,7 +143,12 @@ impl RichText {
             fg: Color::WHITE,
             bg: Color::CLEAR,
             attributes: Attributes::empty(),
+            priority: Self::DEFAULT_PRIORITY,
+            link: None,
             cell_format: CellFormat::Standard,
+            format_data: 0,
+            octad_color_mode: OctadColorMode::LastWins,
+            erases_sub_cells: false,
         }
     }
 
@@ -72,11 +170,54 @@ impl RichText {
         self
     }
 
+    /// Higher priority draw calls are kept over lower priority ones when the engine is over its cell budget.
+    ///
+    /// Defaults to [`RichText::DEFAULT_PRIORITY`].
+    #[inline]
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Makes the text a clickable hyperlink pointing to `url`, on terminals supporting OSC 8 hyperlinks.
+    ///
+    /// Terminals without support show the text as-is. The url is interned, see [`crate::link`],
+    /// failing once the limit of distinct urls is reached.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use germterm::{draw::draw_text, layer::create_layer, engine::Engine, rich_text::RichText};
+    /// # fn main() -> Result<(), germterm::link::LinkLimitError> {
+    /// let mut engine = Engine::new(40, 20);
+    /// let layer = create_layer(&mut engine, 0);
+    /// let text = RichText::new("germterm").with_link("https://github.com/thehuglet/germterm")?;
+    /// draw_text(&mut engine, layer, 2, 1, text);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with_link(mut self, url: &str) -> Result<Self, LinkLimitError> {
+        self.link = Some(intern_link(url)?);
+        Ok(self)
+    }
+
     #[inline]
     pub(crate) fn with_cell_format(mut self, format: CellFormat) -> Self {
         self.cell_format = format;
         self
     }
+
+    #[inline]
+    pub(crate) fn with_format_data(mut self, format_data: u8) -> Self {
+        self.format_data = format_data;
+        self
+    }
+
+    #[inline]
+    pub(crate) fn erasing_sub_cells(mut self) -> Self {
+        self.erases_sub_cells = true;
+        self
+    }
 }
 
 impl From<String> for RichText {
@@ -92,3 +233,285 @@ impl<'a> From<&'a str> for RichText {
         RichText::new(s)
     }
 }
+
+/// The reason markup failed to parse.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MarkupErrorKind {
+    /// A `[` without a closing `]`.
+    UnclosedTag,
+    /// A closing tag without any open tag, or one not matching the open tag.
+    UnmatchedClose,
+    /// A tag still open at the end of the text.
+    UnclosedSpan,
+    /// A word in a tag which is neither an attribute nor a color.
+    UnknownStyle(String),
+}
+
+/// A markup parsing error, pointing at the tag which caused it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MarkupError {
+    pub kind: MarkupErrorKind,
+    /// The byte offset of the tag's opening bracket.
+    pub offset: usize,
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let offset: usize = self.offset;
+        match &self.kind {
+            MarkupErrorKind::UnclosedTag => write!(f, "unclosed tag at byte {offset}"),
+            MarkupErrorKind::UnmatchedClose => {
+                write!(f, "no matching tag to close at byte {offset}")
+            }
+            MarkupErrorKind::UnclosedSpan => {
+                write!(f, "tag opened at byte {offset} is never closed")
+            }
+            MarkupErrorKind::UnknownStyle(style) => {
+                write!(f, "unknown style `{style}` at byte {offset}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MarkupError {}
+
+/// An open markup tag, along with the style of the text inside of it.
+struct MarkupTag<'a> {
+    content: &'a str,
+    offset: usize,
+    fg: Color,
+    bg: Color,
+    attributes: Attributes,
+}
+
+/// Parses text with inline style markup into spans, see the [module docs](self) for the syntax.
+///
+/// Text outside of any tag has the default [`RichText`] style. Spans never are empty,
+/// and neighboring text with the same style is merged into a single span.
+///
+/// # Example
+/// ```rust
+/// # use germterm::{color::Color, rich_text::{Attributes, MarkupErrorKind, parse_markup}};
+/// let spans = parse_markup("[bold red]error:[/] disk full").unwrap();
+/// assert_eq!(spans.len(), 2);
+/// assert_eq!(spans[0].text.as_str(), "error:");
+/// assert_eq!(spans[0].fg, Color::RED);
+/// assert!(spans[0].attributes == Attributes::BOLD);
+/// assert_eq!(spans[1].text.as_str(), " disk full");
+///
+/// // Nested tags inherit the outer style, adjacent tags and `[[` escapes
+/// let spans = parse_markup("[red]a[bold]b[/]c[/][blue]d[/][[e").unwrap();
+/// let texts: Vec<&str> = spans.iter().map(|span| span.text.as_str()).collect();
+/// assert_eq!(texts, ["a", "b", "c", "d", "[e"]);
+/// assert_eq!(spans[1].fg, Color::RED);
+/// assert!(spans[1].attributes == Attributes::BOLD);
+/// assert!(spans[2].attributes.is_empty());
+/// assert_eq!(spans[3].fg, Color::BLUE);
+///
+/// // Errors point at the opening bracket of the offending tag
+/// let error = parse_markup("ok [blink]").err().unwrap();
+/// assert_eq!(error.offset, 3);
+/// assert_eq!(error.kind, MarkupErrorKind::UnknownStyle("blink".to_string()));
+///
+/// assert_eq!(parse_markup("[red]a[/blue]").err().unwrap().offset, 6);
+/// assert_eq!(parse_markup("a[/]").err().unwrap().kind, MarkupErrorKind::UnmatchedClose);
+/// assert_eq!(parse_markup("ab[red]c").err().unwrap().kind, MarkupErrorKind::UnclosedSpan);
+/// assert_eq!(parse_markup("ab[red").err().unwrap().offset, 2);
+/// ```
+pub fn parse_markup(markup: &str) -> Result<Vec<RichText>, MarkupError> {
+    parse_markup_with(markup, false)
+}
+
+/// Like [`parse_markup`], but never fails.
+///
+/// Tags which would fail to parse are kept in the text as they are written,
+/// and tags still open at the end of the text are closed.
+///
+/// # Example
+/// ```rust
+/// # use germterm::rich_text::parse_markup_lossy;
+/// let spans = parse_markup_lossy("[blink]hi[/]");
+/// assert_eq!(spans[0].text.as_str(), "[blink]hi[/]");
+/// ```
+pub fn parse_markup_lossy(markup: &str) -> Vec<RichText> {
+    match parse_markup_with(markup, true) {
+        Ok(spans) => spans,
+        Err(_) => unreachable!("lossy markup parsing never fails"),
+    }
+}
+
+fn parse_markup_with(markup: &str, lossy: bool) -> Result<Vec<RichText>, MarkupError> {
+    let default: RichText = RichText::new("");
+    let mut tags: Vec<MarkupTag> = Vec::new();
+    let mut spans: Vec<RichText> = Vec::new();
+    let mut text: String = String::new();
+    let mut rest_start: usize = 0;
+
+    while let Some(bracket) = markup[rest_start..].find('[').map(|i| rest_start + i) {
+        text.push_str(&markup[rest_start..bracket]);
+
+        if markup[bracket + 1..].starts_with('[') {
+            text.push('[');
+            rest_start = bracket + 2;
+            continue;
+        }
+
+        let error = |kind: MarkupErrorKind| MarkupError {
+            kind,
+            offset: bracket,
+        };
+
+        let Some(close) = markup[bracket..].find(']').map(|i| bracket + i) else {
+            if lossy {
+                text.push('[');
+                rest_start = bracket + 1;
+                continue;
+            }
+            return Err(error(MarkupErrorKind::UnclosedTag));
+        };
+        let content: &str = &markup[bracket + 1..close];
+        let tag_text: &str = &markup[bracket..=close];
+        rest_start = close + 1;
+
+        let (fg, bg, attributes) = match tags.last() {
+            Some(tag) => (tag.fg, tag.bg, tag.attributes),
+            None => (default.fg, default.bg, default.attributes),
+        };
+
+        let parsed: Result<Option<MarkupTag>, MarkupErrorKind> = match content.strip_prefix('/') {
+            Some(closed) => {
+                let closed: &str = closed.trim();
+                match tags.last() {
+                    Some(tag) if closed.is_empty() || closed == tag.content.trim() => Ok(None),
+                    _ => Err(MarkupErrorKind::UnmatchedClose),
+                }
+            }
+            None => parse_markup_style(content, fg, bg, attributes).map(|(fg, bg, attributes)| {
+                Some(MarkupTag {
+                    content,
+                    offset: bracket,
+                    fg,
+                    bg,
+                    attributes,
+                })
+            }),
+        };
+
+        match parsed {
+            Ok(tag) => {
+                push_markup_span(&mut spans, &mut text, fg, bg, attributes);
+                match tag {
+                    Some(tag) => tags.push(tag),
+                    None => {
+                        tags.pop();
+                    }
+                }
+            }
+            Err(_) if lossy => text.push_str(tag_text),
+            Err(kind) => return Err(error(kind)),
+        }
+    }
+    text.push_str(&markup[rest_start..]);
+
+    if !lossy && let Some(tag) = tags.first() {
+        return Err(MarkupError {
+            kind: MarkupErrorKind::UnclosedSpan,
+            offset: tag.offset,
+        });
+    }
+    let (fg, bg, attributes) = match tags.last() {
+        Some(tag) => (tag.fg, tag.bg, tag.attributes),
+        None => (default.fg, default.bg, default.attributes),
+    };
+    push_markup_span(&mut spans, &mut text, fg, bg, attributes);
+
+    Ok(spans)
+}
+
+/// Applies the space separated styles of a tag on top of the style of the tag it's nested in.
+fn parse_markup_style(
+    content: &str,
+    mut fg: Color,
+    mut bg: Color,
+    mut attributes: Attributes,
+) -> Result<(Color, Color, Attributes), MarkupErrorKind> {
+    let mut words = content.split_whitespace();
+    if content.trim().is_empty() {
+        return Err(MarkupErrorKind::UnknownStyle(content.to_string()));
+    }
+
+    while let Some(word) = words.next() {
+        if word == "on" {
+            let color: &str = words.next().unwrap_or_default();
+            bg = parse_color_value(color)
+                .ok_or_else(|| MarkupErrorKind::UnknownStyle(format!("on {color}")))?;
+        } else if let Some(attribute) = attribute_named(word) {
+            attributes |= attribute;
+        } else if let Some(color) = parse_color_value(word) {
+            fg = color;
+        } else {
+            return Err(MarkupErrorKind::UnknownStyle(word.to_string()));
+        }
+    }
+    Ok((fg, bg, attributes))
+}
+
+/// Turns the pending text into a span, merging it into the previous span if they're styled the same.
+fn push_markup_span(
+    spans: &mut Vec<RichText>,
+    text: &mut String,
+    fg: Color,
+    bg: Color,
+    attributes: Attributes,
+) {
+    if text.is_empty() {
+        return;
+    }
+
+    if let Some(last) = spans.last_mut()
+        && (last.fg, last.bg, last.attributes) == (fg, bg, attributes)
+    {
+        Arc::make_mut(&mut last.text).push_str(text);
+    } else {
+        let span: RichText = RichText::new(text.as_str())
+            .with_fg(fg)
+            .with_bg(bg)
+            .with_attributes(attributes);
+        spans.push(span);
+    }
+    text.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_attribute_has_a_name() {
+        let named: Attributes = ATTRIBUTE_NAMES
+            .iter()
+            .fold(Attributes::empty(), |all, &(_, attribute)| all | attribute);
+        assert_eq!(named, Attributes::all());
+        assert_eq!(attribute_named("underlined"), Some(Attributes::UNDERLINED));
+        assert_eq!(attribute_named("blink"), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn attributes_round_trip_through_serde() {
+        use crate::test_support::serde_round_trip;
+
+        for attributes in [
+            Attributes::empty(),
+            Attributes::BOLD | Attributes::HIDDEN,
+            Attributes::all(),
+        ] {
+            assert_eq!(serde_round_trip(&attributes), (attributes, attributes));
+        }
+        assert_eq!(
+            serde_json::to_string(&(Attributes::ITALIC | Attributes::NO_BG_COLOR)).unwrap(),
+            r#"["italic","no_bg_color"]"#
+        );
+        assert!(serde_json::from_str::<Attributes>(r#"["blink"]"#).is_err());
+    }
+}
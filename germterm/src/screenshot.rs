@@ -0,0 +1,274 @@
+//! Exporting the last drawn frame, eg. for capturing what an app looks like for documentation.
+//!
+//! Colors are exported the same way the renderer draws them, including the quantization to the color depth
+//! of the engine. Cells without a foreground or background color use the default colors of the output instead.
+
+use crossterm::{Command, style as ctstyle};
+use std::{fmt::Write, sync::Arc};
+
+use crate::{
+    cell::Cell,
+    color::{Color, ansi256_rgb},
+    engine::Engine,
+    frame::{TerminalColors, build_crossterm_content_style},
+    grapheme::{grapheme_width, push_grapheme},
+    link::LinkId,
+    rich_text::Attributes,
+};
+
+/// The format of a [`screenshot`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScreenshotFormat {
+    /// Only the characters, with trailing spaces trimmed from every row.
+    PlainText,
+    /// Characters along with the escape sequences styling them, which can be printed with eg. `cat`.
+    Ansi,
+    /// A standalone HTML page, with a `<span>` with inline styles for every run of equally styled cells.
+    Html,
+}
+
+/// Exports the frame last drawn to the terminal, with a trailing newline after every row.
+///
+/// Can be called at any point of the update loop. Offscreen engines export their last composed frame instead.
+/// Hidden text is exported as spaces in plain text.
+///
+/// # Example
+/// ```rust
+/// # use germterm::{color::{Color, ColorDepth}, draw::draw_text, engine::{Engine, end_frame, init, start_frame}};
+/// # use germterm::{layer::create_layer, rich_text::RichText, screenshot::{ScreenshotFormat, screenshot}};
+/// let mut engine = Engine::offscreen(6, 2).color_depth(ColorDepth::TrueColor);
+/// let layer = create_layer(&mut engine, 0);
+/// init(&mut engine).unwrap();
+///
+/// start_frame(&mut engine);
+/// draw_text(&mut engine, layer, 1, 0, RichText::new("hi").with_fg(Color::RED).with_bg(Color::BLACK));
+/// end_frame(&mut engine).unwrap();
+///
+/// assert_eq!(screenshot(&engine, ScreenshotFormat::PlainText), " hi\n\n");
+/// assert_eq!(
+///     screenshot(&engine, ScreenshotFormat::Ansi),
+///     " \x1b[0m\x1b[48;2;0;0;0m\x1b[38;2;255;0;0mhi\x1b[0m   \x1b[0m\n      \x1b[0m\n",
+/// );
+/// assert!(
+///     screenshot(&engine, ScreenshotFormat::Html)
+///         .contains(" <span style=\"color: #ff0000; background-color: #000000\">hi</span>   \n"),
+/// );
+/// ```
+pub fn screenshot(engine: &Engine, format: ScreenshotFormat) -> String {
+    let frame = if engine.offscreen {
+        engine.frame.current()
+    } else {
+        engine.frame.old()
+    };
+    let width: usize = engine.frame.width as usize;
+    let height: usize = engine.frame.height as usize;
+    let mut terminal_colors: TerminalColors = TerminalColors::new(engine.terminal_colors.depth);
+
+    let mut output: String = String::new();
+    if format == ScreenshotFormat::Html {
+        write_html_header(&mut output, engine.default_blending_color);
+    }
+
+    for row in 0..height {
+        let cells = (0..width)
+            .map(|col| frame[row * width + col])
+            .filter(|cell| cell.ch != Cell::WIDE_CONTINUATION);
+
+        match format {
+            ScreenshotFormat::PlainText => write_plain_text_row(&mut output, cells),
+            ScreenshotFormat::Ansi => {
+                write_ansi_row(&mut output, cells, &mut terminal_colors, engine.hyperlinks)
+            }
+            ScreenshotFormat::Html => write_html_row(&mut output, cells, &mut terminal_colors),
+        }
+        output.push('\n');
+    }
+
+    if format == ScreenshotFormat::Html {
+        output.push_str("</pre>\n</body>\n</html>\n");
+    }
+    output
+}
+
+fn write_plain_text_row(output: &mut String, cells: impl Iterator<Item = Cell>) {
+    let row_start: usize = output.len();
+    for cell in cells {
+        push_visible_grapheme(output, &cell);
+    }
+
+    let trimmed_len: usize = output[row_start..].trim_end_matches(' ').len();
+    output.truncate(row_start + trimmed_len);
+}
+
+/// Mirrors [`crate::frame::draw_to_terminal`], only re-emitting the style and link when they change.
+///
+/// Every row starts out and ends with the default style, so rows can be printed on their own.
+fn write_ansi_row(
+    output: &mut String,
+    cells: impl Iterator<Item = Cell>,
+    terminal_colors: &mut TerminalColors,
+    hyperlinks: bool,
+) {
+    let mut last_style: ctstyle::ContentStyle = ctstyle::ContentStyle::default();
+    let mut open_link: Option<LinkId> = None;
+
+    for cell in cells {
+        let style: ctstyle::ContentStyle = build_crossterm_content_style(&cell, terminal_colors);
+        let link: Option<LinkId> = cell.link.filter(|_| hyperlinks);
+
+        if open_link != link {
+            write_ansi_link(output, link);
+            open_link = link;
+        }
+        if last_style != style {
+            write_ansi_command(output, ctstyle::SetAttribute(ctstyle::Attribute::Reset));
+            write_ansi_command(output, ctstyle::SetStyle(style));
+            last_style = style;
+        }
+        push_grapheme(output, &cell);
+    }
+
+    if open_link.is_some() {
+        write_ansi_link(output, None);
+    }
+    write_ansi_command(output, ctstyle::SetAttribute(ctstyle::Attribute::Reset));
+}
+
+#[inline]
+fn write_ansi_command(output: &mut String, command: impl Command) {
+    // Writing into a `String` never fails
+    let _ = command.write_ansi(output);
+}
+
+#[inline]
+fn write_ansi_link(output: &mut String, link: Option<LinkId>) {
+    let url: Option<Arc<str>> = link.map(LinkId::url);
+    let _ = write!(
+        output,
+        "\x1b]8;;{}\x1b\\",
+        url.as_deref().unwrap_or_default()
+    );
+}
+
+/// The page background is the default blending color, with black or white as the default text color,
+/// depending on which one is more readable on it.
+fn write_html_header(output: &mut String, background: Color) {
+    let (r, g, b): (u8, u8, u8) = background.rgb();
+    let luma: u32 = (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000;
+    let foreground: &str = if luma < 128 { "#ffffff" } else { "#000000" };
+
+    output.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n</head>\n<body>\n");
+    let _ = writeln!(
+        output,
+        "<pre style=\"font-family: monospace; line-height: 1.2; color: {foreground}; background-color: #{r:02x}{g:02x}{b:02x}\">",
+    );
+}
+
+fn write_html_row(
+    output: &mut String,
+    cells: impl Iterator<Item = Cell>,
+    terminal_colors: &mut TerminalColors,
+) {
+    let mut run: String = String::new();
+    let mut run_style: Option<(String, Option<LinkId>)> = None;
+
+    for cell in cells {
+        let style: ctstyle::ContentStyle = build_crossterm_content_style(&cell, terminal_colors);
+        let cell_style: (String, Option<LinkId>) = (html_style(&style), cell.link);
+
+        if run_style.as_ref() != Some(&cell_style) {
+            if let Some((css, link)) = &run_style {
+                write_html_run(output, &run, css, *link);
+            }
+            run.clear();
+            run_style = Some(cell_style);
+        }
+        push_html_escaped_grapheme(&mut run, &cell);
+    }
+
+    if let Some((css, link)) = &run_style {
+        write_html_run(output, &run, css, *link);
+    }
+}
+
+fn write_html_run(output: &mut String, run: &str, css: &str, link: Option<LinkId>) {
+    let tag: &str = if link.is_some() { "a" } else { "span" };
+    if css.is_empty() && link.is_none() {
+        output.push_str(run);
+        return;
+    }
+
+    let _ = write!(output, "<{tag}");
+    if let Some(link) = link {
+        output.push_str(" href=\"");
+        push_html_escaped(output, &link.url());
+        output.push('"');
+    }
+    if !css.is_empty() {
+        let _ = write!(output, " style=\"{css}\"");
+    }
+    let _ = write!(output, ">{run}</{tag}>");
+}
+
+/// The inline CSS of a run of cells, empty for cells using the default colors without attributes.
+fn html_style(style: &ctstyle::ContentStyle) -> String {
+    let mut declarations: Vec<String> = Vec::new();
+    if let Some(color) = style.foreground_color {
+        declarations.push(format!("color: {}", css_color(color)));
+    }
+    if let Some(color) = style.background_color {
+        declarations.push(format!("background-color: {}", css_color(color)));
+    }
+
+    let attributes = [
+        (ctstyle::Attribute::Bold, "font-weight: bold"),
+        (ctstyle::Attribute::Italic, "font-style: italic"),
+        (ctstyle::Attribute::Underlined, "text-decoration: underline"),
+        (ctstyle::Attribute::Hidden, "visibility: hidden"),
+    ];
+    for (attribute, declaration) in attributes {
+        if style.attributes.has(attribute) {
+            declarations.push(declaration.to_string());
+        }
+    }
+
+    declarations.join("; ")
+}
+
+fn css_color(color: ctstyle::Color) -> String {
+    let (r, g, b): (u8, u8, u8) = match color {
+        ctstyle::Color::Rgb { r, g, b } => (r, g, b),
+        ctstyle::Color::AnsiValue(index) => ansi256_rgb(index),
+        _ => (0, 0, 0),
+    };
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Pushes the grapheme of a cell, or spaces covering its width if it's hidden.
+#[inline]
+fn push_visible_grapheme(output: &mut String, cell: &Cell) {
+    if cell.attributes.contains(Attributes::HIDDEN) {
+        output.extend(std::iter::repeat_n(' ', grapheme_width(cell)));
+    } else {
+        push_grapheme(output, cell);
+    }
+}
+
+#[inline]
+fn push_html_escaped_grapheme(output: &mut String, cell: &Cell) {
+    let mut grapheme: String = String::new();
+    push_grapheme(&mut grapheme, cell);
+    push_html_escaped(output, &grapheme);
+}
+
+fn push_html_escaped(output: &mut String, text: &str) {
+    for ch in text.chars() {
+        match ch {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            _ => output.push(ch),
+        }
+    }
+}
@@ -0,0 +1,382 @@
+//! RGBA sprites.
+//!
+//! A [`Sprite`] is a 2D grid of [`Color`] pixels, which can be drawn in a single call
+//! using [`crate::draw::draw_sprite`] or [`crate::draw::draw_sprite_scaled`].
+//!
+//! Sprites are drawn using twoxels, mapping 2 vertically stacked pixels to a single cell.
+//!
+//! Decoding image files is left to the user, [`Sprite::from_rgba_bytes`] accepts
+//! raw RGBA data as produced by most image decoding crates.
+//! With the `image` feature enabled, PNG and GIF files can be loaded directly
+//! using [`Sprite::from_image_bytes`] and [`AnimatedSprite::from_gif_bytes`].
+//!
+//! Images are usually a lot larger than a terminal, [`Sprite::resized`] scales them
+//! down to a size fitting the twoxel grid, which is 2 pixels per cell vertically.
+
+#[cfg(feature = "image")]
+use std::{fs::File, io, path::Path};
+
+use crate::color::Color;
+
+/// How [`Sprite::resized`] picks the color of each resized pixel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ResizeFilter {
+    /// Takes the source pixel closest to the center, keeping the hard edges of pixel art.
+    #[default]
+    Nearest,
+    /// Averages every source pixel covered, for smooth results when scaling photos or logos down by a lot.
+    Box,
+}
+
+/// A 2D grid of [`Color`] pixels stored in row-major order.
+#[derive(Clone)]
+pub struct Sprite {
+    width: u16,
+    height: u16,
+    pixels: Vec<Color>,
+}
+
+impl Sprite {
+    /// Creates a new sprite from pixels stored in row-major order.
+    ///
+    /// # Panics
+    /// - If `pixels.len()` is not equal to `width * height`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::{sprite::Sprite, color::Color};
+    /// let sprite = Sprite::new(2, 1, vec![Color::RED, Color::BLUE]);
+    /// assert_eq!(sprite.pixel(1, 0), Some(Color::BLUE));
+    /// ```
+    pub fn new(width: u16, height: u16, pixels: Vec<Color>) -> Self {
+        assert_eq!(
+            pixels.len(),
+            width as usize * height as usize,
+            "Sprite pixel count must match its dimensions"
+        );
+
+        Sprite {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Creates a new sprite filled with a single color.
+    pub fn filled(width: u16, height: u16, color: Color) -> Self {
+        Sprite::new(width, height, vec![color; width as usize * height as usize])
+    }
+
+    /// Creates a new sprite from raw RGBA bytes (4 bytes per pixel) stored in row-major order.
+    ///
+    /// # Panics
+    /// - If `bytes.len()` is not equal to `width * height * 4`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::{sprite::Sprite, color::Color};
+    /// let sprite = Sprite::from_rgba_bytes(1, 2, &[255, 0, 0, 255, 0, 0, 255, 128]);
+    /// assert_eq!(sprite.pixel(0, 0), Some(Color::RED));
+    /// assert_eq!(sprite.pixel(0, 1), Some(Color::BLUE.with_alpha(128)));
+    /// ```
+    pub fn from_rgba_bytes(width: u16, height: u16, bytes: &[u8]) -> Self {
+        assert_eq!(
+            bytes.len(),
+            width as usize * height as usize * 4,
+            "RGBA byte count must match the sprite dimensions"
+        );
+
+        let pixels: Vec<Color> = bytes
+            .chunks_exact(4)
+            .map(|rgba| Color::new(rgba[0], rgba[1], rgba[2], rgba[3]))
+            .collect();
+
+        Sprite::new(width, height, pixels)
+    }
+
+    #[inline]
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// All pixels in row-major order.
+    #[inline]
+    pub fn pixels(&self) -> &[Color] {
+        &self.pixels
+    }
+
+    /// All pixels in row-major order, allowing for in-place modifications.
+    #[inline]
+    pub fn pixels_mut(&mut self) -> &mut [Color] {
+        &mut self.pixels
+    }
+
+    /// Returns the pixel at the given position, or `None` if it's out of bounds.
+    #[inline]
+    pub fn pixel(&self, x: u16, y: u16) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(self.pixels[y as usize * self.width as usize + x as usize])
+    }
+
+    /// Returns a copy of the sprite scaled to the given size.
+    ///
+    /// To fit an image into an area of `cols` x `rows` cells, resize it to `cols` x `rows * 2` pixels.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::{sprite::{ResizeFilter, Sprite}, color::Color};
+    /// let sprite = Sprite::new(2, 1, vec![Color::BLACK, Color::WHITE]);
+    ///
+    /// let averaged = sprite.resized(1, 1, ResizeFilter::Box);
+    /// assert_eq!(averaged.pixel(0, 0), Some(Color::new(128, 128, 128, 255)));
+    /// ```
+    pub fn resized(&self, width: u16, height: u16, filter: ResizeFilter) -> Sprite {
+        if self.width == 0 || self.height == 0 {
+            return Sprite::filled(width, height, Color::CLEAR);
+        }
+
+        // Source pixel range `start..end` covered by the resized pixel at `index`
+        let source_range = |index: u16, size: u16, source_size: u16| -> (usize, usize) {
+            let start: usize = index as usize * source_size as usize / size as usize;
+            let end: usize = (index as usize + 1) * source_size as usize / size as usize;
+            (start, end.max(start + 1))
+        };
+
+        let mut pixels: Vec<Color> = Vec::with_capacity(width as usize * height as usize);
+        for y in 0..height {
+            let rows: (usize, usize) = source_range(y, height, self.height);
+            for x in 0..width {
+                let cols: (usize, usize) = source_range(x, width, self.width);
+                let color: Color = match filter {
+                    ResizeFilter::Nearest => {
+                        self.pixels[(rows.0 + rows.1 - 1) / 2 * self.width as usize
+                            + (cols.0 + cols.1 - 1) / 2]
+                    }
+                    ResizeFilter::Box => self.average(cols, rows),
+                };
+                pixels.push(color);
+            }
+        }
+
+        Sprite::new(width, height, pixels)
+    }
+
+    /// Averages the pixels in the given ranges, weighting colors by their alpha
+    /// so that transparent pixels don't darken the edges.
+    fn average(&self, cols: (usize, usize), rows: (usize, usize)) -> Color {
+        let mut sum: [u64; 4] = [0; 4];
+        for row in rows.0..rows.1 {
+            let row_start: usize = row * self.width as usize;
+            for color in &self.pixels[row_start + cols.0..row_start + cols.1] {
+                let (r, g, b, a) = color.rgba();
+                sum[0] += r as u64 * a as u64;
+                sum[1] += g as u64 * a as u64;
+                sum[2] += b as u64 * a as u64;
+                sum[3] += a as u64;
+            }
+        }
+
+        if sum[3] == 0 {
+            return Color::CLEAR;
+        }
+        let count: u64 = ((cols.1 - cols.0) * (rows.1 - rows.0)) as u64;
+        let channel = |total: u64| ((total + sum[3] / 2) / sum[3]) as u8;
+        Color::new(
+            channel(sum[0]),
+            channel(sum[1]),
+            channel(sum[2]),
+            ((sum[3] + count / 2) / count) as u8,
+        )
+    }
+}
+
+#[cfg(feature = "image")]
+impl Sprite {
+    /// Decodes a PNG or GIF image, keeping only the first frame of animated GIFs.
+    ///
+    /// Requires the `image` feature.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use germterm::sprite::{ResizeFilter, Sprite};
+    /// let logo = Sprite::from_image_bytes(include_bytes!("../../assets/logo-middle.png"))
+    ///     .expect("invalid logo")
+    ///     .resized(16, 16, ResizeFilter::Box);
+    /// ```
+    pub fn from_image_bytes(bytes: &[u8]) -> Result<Self, image::ImageError> {
+        from_rgba_image(image::load_from_memory(bytes)?.into_rgba8())
+    }
+
+    /// Loads a PNG or GIF image from a file, keeping only the first frame of animated GIFs.
+    ///
+    /// Requires the `image` feature.
+    pub fn from_image_path(path: impl AsRef<Path>) -> Result<Self, image::ImageError> {
+        from_rgba_image(image::open(path)?.into_rgba8())
+    }
+}
+
+/// A looping animation made of sprites, each shown for its own duration.
+///
+/// Call [`AnimatedSprite::advance`] once per frame, and draw the current [`AnimatedSprite::sprite`].
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{sprite::{AnimatedSprite, Sprite}, color::Color, engine::Engine};
+/// # use germterm::{draw::draw_sprite, layer::create_layer};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// let mut blink = AnimatedSprite::new(vec![
+///     (Sprite::filled(4, 4, Color::RED), 0.5),
+///     (Sprite::filled(4, 4, Color::CLEAR), 0.25),
+/// ]);
+///
+/// // Every frame
+/// blink.advance(engine.delta_time);
+/// draw_sprite(&mut engine, layer, 2.0, 1.0, blink.sprite());
+/// ```
+#[derive(Clone)]
+pub struct AnimatedSprite {
+    frames: Vec<(Sprite, f32)>,
+    current: usize,
+    elapsed: f32,
+}
+
+impl AnimatedSprite {
+    /// Creates an animation from sprites paired with how long they're shown for, in seconds.
+    ///
+    /// # Panics
+    /// - If `frames` is empty.
+    pub fn new(frames: Vec<(Sprite, f32)>) -> Self {
+        assert!(
+            !frames.is_empty(),
+            "An animated sprite needs at least one frame"
+        );
+
+        AnimatedSprite {
+            frames,
+            current: 0,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Moves the animation forward by `delta_time` seconds, looping back to the first frame after the last one.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::{sprite::{AnimatedSprite, Sprite}, color::Color};
+    /// let mut animation = AnimatedSprite::new(vec![
+    ///     (Sprite::filled(1, 1, Color::RED), 0.5),
+    ///     (Sprite::filled(1, 1, Color::BLUE), 0.25),
+    /// ]);
+    ///
+    /// animation.advance(0.6);
+    /// assert_eq!(animation.frame_index(), 1);
+    /// animation.advance(0.2);
+    /// assert_eq!(animation.frame_index(), 0);
+    /// ```
+    pub fn advance(&mut self, delta_time: f32) {
+        let total: f32 = self
+            .frames
+            .iter()
+            .map(|(_, duration)| duration.max(0.0))
+            .sum();
+        if !delta_time.is_finite() || delta_time <= 0.0 || total <= 0.0 {
+            return;
+        }
+
+        self.elapsed = (self.elapsed + delta_time) % total;
+        while self.elapsed >= self.frames[self.current].1.max(0.0) {
+            self.elapsed -= self.frames[self.current].1.max(0.0);
+            self.current = (self.current + 1) % self.frames.len();
+        }
+    }
+
+    /// The sprite of the current frame.
+    #[inline]
+    pub fn sprite(&self) -> &Sprite {
+        &self.frames[self.current].0
+    }
+
+    #[inline]
+    pub fn frame_index(&self) -> usize {
+        self.current
+    }
+
+    /// All frames along with their durations, in seconds.
+    #[inline]
+    pub fn frames(&self) -> &[(Sprite, f32)] {
+        &self.frames
+    }
+
+    /// Restarts the animation from its first frame.
+    pub fn reset(&mut self) {
+        self.current = 0;
+        self.elapsed = 0.0;
+    }
+}
+
+#[cfg(feature = "image")]
+impl AnimatedSprite {
+    /// Decodes every frame of a GIF, along with their delays.
+    ///
+    /// Frames without a delay are shown for 0.1 seconds, like in most browsers.
+    /// Requires the `image` feature.
+    pub fn from_gif_bytes(bytes: &[u8]) -> Result<Self, image::ImageError> {
+        let decoder = image::codecs::gif::GifDecoder::new(io::Cursor::new(bytes))?;
+        gif_frames(decoder)
+    }
+
+    /// Loads every frame of a GIF file, along with their delays.
+    ///
+    /// Frames without a delay are shown for 0.1 seconds, like in most browsers.
+    /// Requires the `image` feature.
+    pub fn from_gif_path(path: impl AsRef<Path>) -> Result<Self, image::ImageError> {
+        let file: File = File::open(path).map_err(image::ImageError::IoError)?;
+        let decoder = image::codecs::gif::GifDecoder::new(io::BufReader::new(file))?;
+        gif_frames(decoder)
+    }
+}
+
+#[cfg(feature = "image")]
+fn gif_frames<'a>(
+    decoder: impl image::AnimationDecoder<'a>,
+) -> Result<AnimatedSprite, image::ImageError> {
+    const DEFAULT_DELAY: f32 = 0.1;
+
+    let mut frames: Vec<(Sprite, f32)> = Vec::new();
+    for frame in decoder.into_frames() {
+        let frame: image::Frame = frame?;
+        let (numerator, denominator) = frame.delay().numer_denom_ms();
+        let delay: f32 = match numerator {
+            0 => DEFAULT_DELAY,
+            _ => numerator as f32 / denominator.max(1) as f32 / 1000.0,
+        };
+        frames.push((from_rgba_image(frame.into_buffer())?, delay));
+    }
+
+    if frames.is_empty() {
+        return Err(image::ImageError::Decoding(
+            image::error::DecodingError::new(image::ImageFormat::Gif.into(), "GIF has no frames"),
+        ));
+    }
+    Ok(AnimatedSprite::new(frames))
+}
+
+#[cfg(feature = "image")]
+fn from_rgba_image(image: image::RgbaImage) -> Result<Sprite, image::ImageError> {
+    let (Ok(width), Ok(height)) = (u16::try_from(image.width()), u16::try_from(image.height()))
+    else {
+        return Err(image::ImageError::Limits(
+            image::error::LimitError::from_kind(image::error::LimitErrorKind::DimensionError),
+        ));
+    };
+    Ok(Sprite::from_rgba_bytes(width, height, image.as_raw()))
+}
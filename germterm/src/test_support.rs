@@ -0,0 +1,77 @@
+//! Helpers shared by the unit tests.
+
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+use crate::{cell::Cell, engine::Engine};
+
+/// An output for [`crate::engine::Engine::remote`] that can still be read after being handed to the engine.
+#[derive(Clone, Default)]
+pub(crate) struct SharedOutput(Arc<Mutex<Vec<u8>>>);
+
+impl SharedOutput {
+    /// Takes everything written so far.
+    pub fn take(&self) -> String {
+        let bytes: Vec<u8> = std::mem::take(&mut *self.0.lock().unwrap());
+        String::from_utf8(bytes).unwrap()
+    }
+}
+
+impl Write for SharedOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The cells of the last composed frame, the same one [`crate::screenshot::screenshot`] captures.
+pub(crate) fn composed_cells(engine: &Engine) -> Vec<Cell> {
+    let frame = if engine.offscreen {
+        engine.frame.current()
+    } else {
+        engine.frame.old()
+    };
+    let cell_count: usize = engine.frame.width as usize * engine.frame.height as usize;
+    (0..cell_count).map(|index| frame[index]).collect()
+}
+
+/// An FNV-1a hash of the cells of the last composed frame, for pinning down whole frames in a single assert.
+pub(crate) fn frame_hash(engine: &Engine) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for cell in composed_cells(engine) {
+        let words: [u32; 5] = [
+            cell.ch as u32,
+            cell.fg.0,
+            cell.bg.0,
+            cell.attributes.bits() as u32,
+            cell.format_data as u32,
+        ];
+        for byte in words.iter().flat_map(|word| word.to_le_bytes()) {
+            hash = (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+/// Serializes `value` to JSON and to TOML, returning what's read back from each.
+#[cfg(feature = "serde")]
+pub(crate) fn serde_round_trip<T>(value: &T) -> (T, T)
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    // TOML documents are tables, so the value is wrapped in one
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Document<T> {
+        value: T,
+    }
+
+    let json: String = serde_json::to_string(value).unwrap();
+    let toml: String = toml::to_string(&Document { value }).unwrap();
+    let from_toml: Document<T> = toml::from_str(&toml).unwrap();
+    (serde_json::from_str(&json).unwrap(), from_toml.value)
+}
@@ -0,0 +1,172 @@
+//! Named colors, for switching color schemes at runtime.
+//!
+//! A [`Theme`] maps semantic names like `"bg.surface"` or `"accent"` to colors.
+//! The engine holds the current theme, set with [`set_theme`], and [`theme_color`] looks colors up in it.
+//!
+//! As every frame is drawn from scratch, looking colors up while drawing is enough for a theme switch
+//! to show up in the very next frame, without the drawing code having to change.
+//! The exception are contents composed ahead of time, like [`crate::render_target::RenderTarget`]s,
+//! which have to be redrawn after switching.
+//!
+//! ## Built-in themes
+//!
+//! [`Theme::dark`] and [`Theme::light`] define the following names, and can be extended with more:
+//! - `bg`, `bg.surface`, `bg.overlay` - backgrounds, from the furthest back to the closest to the user
+//! - `fg`, `fg.muted` - text
+//! - `border`
+//! - `accent`, `success`, `warning`, `error`
+
+use std::collections::HashMap;
+
+use crate::{color::Color, engine::Engine};
+
+/// A set of named colors.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Theme {
+    colors: HashMap<String, Color>,
+}
+
+pub(crate) struct ThemeState {
+    theme: Theme,
+    missing_colors: Vec<String>,
+}
+
+impl ThemeState {
+    pub fn new() -> Self {
+        Self {
+            theme: Theme::dark(),
+            missing_colors: Vec::new(),
+        }
+    }
+}
+
+impl Theme {
+    /// The color [`theme_color`] falls back to for names missing from the theme, loud enough to stand out.
+    pub const FALLBACK: Color = Color(0xFF_00_FF_FF);
+
+    /// Creates an empty theme.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A dark theme with muted pastel colors, the engine's theme by default.
+    pub fn dark() -> Self {
+        Self::from_hex(&[
+            ("bg", 0x1E1E2E),
+            ("bg.surface", 0x313244),
+            ("bg.overlay", 0x45475A),
+            ("fg", 0xCDD6F4),
+            ("fg.muted", 0xA6ADC8),
+            ("border", 0x585B70),
+            ("accent", 0x89B4FA),
+            ("success", 0xA6E3A1),
+            ("warning", 0xF9E2AF),
+            ("error", 0xF38BA8),
+        ])
+    }
+
+    /// A light counterpart of [`Theme::dark`], defining the same names.
+    pub fn light() -> Self {
+        Self::from_hex(&[
+            ("bg", 0xEFF1F5),
+            ("bg.surface", 0xCCD0DA),
+            ("bg.overlay", 0xBCC0CC),
+            ("fg", 0x4C4F69),
+            ("fg.muted", 0x6C6F85),
+            ("border", 0x9CA0B0),
+            ("accent", 0x1E66F5),
+            ("success", 0x40A02B),
+            ("warning", 0xDF8E1D),
+            ("error", 0xD20F39),
+        ])
+    }
+
+    /// Creates a theme from names paired with opaque `0xRRGGBB` colors.
+    fn from_hex(colors: &[(&str, u32)]) -> Self {
+        let colors = colors
+            .iter()
+            .map(|&(name, rgb)| (name.to_string(), Color((rgb << 8) | 0xFF)));
+
+        Self {
+            colors: colors.collect(),
+        }
+    }
+
+    /// Adds a color to the theme, replacing any color with the same name.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use germterm::{theme::Theme, color::Color};
+    /// let theme = Theme::dark().with_color("player", Color::ORANGE);
+    /// assert_eq!(theme.color("player"), Some(Color::ORANGE));
+    /// ```
+    pub fn with_color(mut self, name: impl Into<String>, color: Color) -> Self {
+        self.set_color(name, color);
+        self
+    }
+
+    /// Adds a color to the theme, replacing any color with the same name.
+    pub fn set_color(&mut self, name: impl Into<String>, color: Color) {
+        self.colors.insert(name.into(), color);
+    }
+
+    /// Returns the color with the given name, or `None` if the theme doesn't define it.
+    pub fn color(&self, name: &str) -> Option<Color> {
+        self.colors.get(name).copied()
+    }
+}
+
+/// Replaces the theme of the engine, taking effect from the next lookup with [`theme_color`] on.
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{engine::Engine, theme::{Theme, set_theme}};
+/// let mut engine = Engine::new(40, 20);
+/// set_theme(&mut engine, Theme::light());
+/// ```
+pub fn set_theme(engine: &mut Engine, theme: Theme) {
+    engine.theme.theme = theme;
+}
+
+/// The current theme of the engine, [`Theme::dark`] by default.
+pub fn current_theme(engine: &Engine) -> &Theme {
+    &engine.theme.theme
+}
+
+/// Looks up a color in the current theme of the engine.
+///
+/// Names missing from the theme resolve to [`Theme::FALLBACK`] instead of panicking mid-frame,
+/// and are recorded once in [`missing_theme_colors`].
+///
+/// # Example
+/// ```rust,no_run
+/// # use germterm::{draw::draw_rect, engine::Engine, layer::create_layer, theme::theme_color};
+/// let mut engine = Engine::new(40, 20);
+/// let layer = create_layer(&mut engine, 0);
+///
+/// let surface = theme_color(&mut engine, "bg.surface");
+/// draw_rect(&mut engine, layer, (2, 1), (20, 8), surface);
+/// ```
+pub fn theme_color(engine: &mut Engine, name: &str) -> Color {
+    let state: &mut ThemeState = &mut engine.theme;
+    if let Some(color) = state.theme.color(name) {
+        return color;
+    }
+
+    if !state.missing_colors.iter().any(|missing| missing == name) {
+        state.missing_colors.push(name.to_string());
+    }
+    Theme::FALLBACK
+}
+
+/// Every name [`theme_color`] failed to find so far, in the order they were first looked up.
+///
+/// Useful for catching typos, as missing names only show up as [`Theme::FALLBACK`] colored cells.
+pub fn missing_theme_colors(engine: &Engine) -> &[String] {
+    &engine.theme.missing_colors
+}
@@ -0,0 +1,18 @@
+//! Display width of characters in terminal columns.
+//!
+//! Widths follow the Unicode East Asian Width property through the `unicode-width` crate,
+//! so wide CJK characters and emoji take up two columns. Ambiguous width characters are treated as narrow.
+
+use unicode_width::UnicodeWidthChar;
+
+/// The amount of terminal columns a character takes up: `0`, `1` or `2`.
+///
+/// Control characters don't take up any columns.
+#[inline]
+pub(crate) fn char_width(ch: char) -> usize {
+    // Fast path for ASCII, which makes up most drawn text
+    if (' '..='~').contains(&ch) {
+        return 1;
+    }
+    ch.width().unwrap_or(0)
+}